@@ -12,6 +12,7 @@ mod token;
 use std::borrow::Cow::{self, Borrowed, Owned};
 use std::fmt::Write;
 use std::process::exit;
+use std::str::FromStr;
 
 use rustyline::error::ReadlineError;
 use rustyline::highlight::{CmdKind, Highlighter};
@@ -31,13 +32,64 @@ struct CasConfig {
 	/// mathematical expression to run through the CAS.
 	expr: Option<String>,
 
+	#[argh(option, default = "Format::Plain")]
+	/// output format of the result: plain, pretty, latex or mathml
+	format: Format,
+
+	#[argh(switch)]
+	/// print the result as LaTeX, like `--format latex`
+	latex: bool,
+
 	#[argh(switch)]
 	/// prevent the CAS from folding the parsed expr
 	raw: bool,
+
+	#[argh(switch)]
+	/// apply trigonometric identities when folding the parsed expr
+	trig: bool,
+}
+
+/// Formats that results can be printed in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Format {
+	Plain,
+	Pretty,
+	Latex,
+	MathMl,
+}
+
+impl Format {
+	/// Formats an expression in this format.
+	fn format(self, expr: &Expr) -> String {
+		match self {
+			Format::Plain => expr.to_string(),
+			Format::Pretty => expr.to_pretty(),
+			Format::Latex => expr.to_latex(),
+			Format::MathMl => expr.to_mathml(),
+		}
+	}
+}
+
+impl FromStr for Format {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"plain" => Ok(Format::Plain),
+			"pretty" => Ok(Format::Pretty),
+			"latex" => Ok(Format::Latex),
+			"mathml" => Ok(Format::MathMl),
+			_ => Err(format!("unknown format '{s}', expected plain, pretty, latex or mathml")),
+		}
+	}
 }
 
 fn main() {
-	let cfg: CasConfig = argh::from_env();
+	let mut cfg: CasConfig = argh::from_env();
+
+	if cfg.latex {
+		cfg.format = Format::Latex;
+	}
 
 	if cfg.expr.is_none() {
 		repl(cfg);
@@ -49,21 +101,20 @@ fn main() {
 
 	let mut ctx = Context::new();
 	let stdlib = StdLib::new();
+
+	if cfg.trig {
+		ctx.passes.push(Expr::trigsimp);
+	}
+
 	let mut ast = Parser::parse_line(&mut ctx, tokens);
 
+	ast = stdlib.evaluate(ast, &mut ctx);
+
 	if !cfg.raw {
 		ast = ast.simplify(&mut ctx).expect("Error while simplifying");
 	}
 
-	ast = if let Expr::Fun(ref name, ref args) = ast
-		&& let Some(f) = stdlib.0.get(name)
-	{
-		(f.execute)(args.to_vec(), &mut ctx)
-	} else {
-		ast
-	};
-
-	println!("{ast}");
+	println!("{}", cfg.format.format(&ast));
 }
 
 #[derive(Helper, Completer, Hinter, Validator)]
@@ -126,6 +177,7 @@ impl Highlighter for HighlightHelper {
 
 fn repl(cfg: CasConfig) {
 	println!("Welcome to abacas v{}\nTo exit, press CTRL+C or CTRL+D", VERSION);
+	println!("To change the output format, enter `format plain|pretty|latex|mathml`");
 
 	let config = Config::builder().build();
 
@@ -138,6 +190,11 @@ fn repl(cfg: CasConfig) {
 
 	let mut ctx = Context::new();
 	let stdlib = StdLib::new();
+	let mut format = cfg.format;
+
+	if cfg.trig {
+		ctx.passes.push(Expr::trigsimp);
+	}
 
 	loop {
 		"\x1b[1m\x1b[32m[In]:\x1b[0m ".clone_into(&mut rl.helper_mut().expect("No helper").colored_prompt);
@@ -150,25 +207,29 @@ fn repl(cfg: CasConfig) {
 					break;
 				}
 
+				// `format <name>` switches the output format for the following results
+				if let Some(name) = line.trim().strip_prefix("format ") {
+					match name.trim().parse::<Format>() {
+						Ok(selected) => format = selected,
+						Err(err) => println!("Error: {err}"),
+					}
+
+					continue;
+				}
+
 				println!("\x1b[1m\x1b[31m[Out]:\x1b[0m ");
 
 				let tokens = Token::lexer(&line).collect::<Result<Vec<Token>, ()>>().unwrap();
 
 				let mut ast = Parser::parse_line(&mut ctx, tokens);
 
+				ast = stdlib.evaluate(ast, &mut ctx);
+
 				if !cfg.raw {
 					ast = ast.simplify(&mut ctx).unwrap();
 				}
 
-				ast = if let Expr::Fun(ref name, ref args) = ast
-					&& let Some(f) = stdlib.0.get(name)
-				{
-					(f.execute)(args.to_vec(), &mut ctx)
-				} else {
-					ast
-				};
-
-				println!("{ast}");
+				println!("{}", format.format(&ast));
 			}
 			Err(ReadlineError::Interrupted) => {
 				println!("CTRL-C");
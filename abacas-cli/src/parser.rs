@@ -1,9 +1,12 @@
 use std::iter::Peekable;
 
+use abacas::assumption::Assumption;
 use abacas::context::Context;
 use abacas::expr::{Expr, Symbol};
+use abacas::matrix::Matrix;
 use abacas::monomial::Monomial;
 use abacas::number::Number;
+use abacas::relation::Relation;
 use rug::ops::Pow;
 
 use crate::token::Token::{self, *};
@@ -12,20 +15,104 @@ pub struct Parser {}
 
 impl Parser {
 	pub fn parse_line(ctx: &mut Context, line: Vec<Token>) -> Expr {
+		if let [Ident(keyword), Ident(name), rest @ ..] = line.as_slice()
+			&& keyword == "assume"
+		{
+			return Self::assume(ctx, name, rest);
+		}
+
 		let mut it = line.into_iter().peekable();
 
 		Self::expr_bp(ctx, 0, &mut it)
 	}
 
+	/// Parses an assumption like `assume x > 0` or `assume n integer`, adding it to the context and returning the
+	/// symbol. Comparisons that do not decide the sign only assume the symbol to be real.
+	fn assume(ctx: &mut Context, name: &str, rest: &[Token]) -> Expr {
+		let sym = Symbol::new(name).expect("Error while parsing symbol");
+
+		let assumption = match rest {
+			[Ident(kind)] => match kind.as_str() {
+				"integer" => Some(Assumption::Integer),
+				"negative" => Some(Assumption::Negative),
+				"nonnegative" => Some(Assumption::Nonnegative),
+				"nonpositive" => Some(Assumption::Nonpositive),
+				"nonzero" => Some(Assumption::Nonzero),
+				"positive" => Some(Assumption::Positive),
+				"real" => Some(Assumption::Real),
+				_ => panic!("Unknown assumption"),
+			},
+			[op @ (Lt | Le | Gt | Ge | Ne), bound @ ..] => {
+				let bound = Self::parse_line(ctx, bound.to_vec())
+					.simplify(ctx)
+					.ok()
+					.and_then(Expr::into_num)
+					.expect("Expected a number to compare with");
+
+				match op {
+					Lt if !bound.is_positive() => Some(Assumption::Negative),
+					Le if bound.is_negative() => Some(Assumption::Negative),
+					Le if bound.is_zero() => Some(Assumption::Nonpositive),
+					Gt if !bound.is_negative() => Some(Assumption::Positive),
+					Ge if bound.is_positive() => Some(Assumption::Positive),
+					Ge if bound.is_zero() => Some(Assumption::Nonnegative),
+					Ne if bound.is_zero() => Some(Assumption::Nonzero),
+					Ne => None,
+					_ => Some(Assumption::Real),
+				}
+			}
+			_ => panic!("Bad assumption"),
+		};
+
+		if let Some(assumption) = assumption
+			&& !ctx.assume(sym.clone(), assumption)
+		{
+			panic!("Assumption contradicts previous assumptions")
+		}
+
+		Expr::Poly(sym, Monomial::linear(Number::one()).into())
+	}
+
+	/// Parses the comma separated entries of a list after its opening bracket, up to and including the closing one.
+	/// Nested lists are the rows of a matrix literal like `[[1, 2], [3, 4]]`, so each is parsed as a matrix with one
+	/// row.
+	fn list<T>(ctx: &mut Context, tokens: &mut Peekable<T>) -> Vec<Expr>
+	where
+		T: Iterator<Item = Token>,
+	{
+		let mut entries = vec![];
+
+		if tokens.next_if_eq(&RBracket).is_some() {
+			return entries;
+		}
+
+		loop {
+			let entry = match tokens.next_if_eq(&LBracket) {
+				Some(_) => Expr::Matrix(Matrix::from_rows(vec![Self::list(ctx, tokens)]).unwrap()),
+				None => Self::expr_bp(ctx, 0, tokens),
+			};
+
+			entries.push(entry);
+
+			match tokens.next() {
+				Some(Comma) => continue,
+				Some(RBracket) => return entries,
+				_ => panic!("Expected ',' or ']'"),
+			}
+		}
+	}
+
 	fn expr_bp<T>(ctx: &mut Context, min_bp: u8, tokens: &mut Peekable<T>) -> Expr
 	where
 		T: Iterator<Item = Token>,
 	{
 		let mut lhs = match tokens.next() {
 			Some(Sub) => -Self::expr_bp(ctx, prefix_bp(Sub), tokens),
+			Some(Not) => Expr::Not(Self::expr_bp(ctx, prefix_bp(Not), tokens).into()),
 			Some(Number(num)) => Expr::Num(num),
+			Some(Ident(name)) if name == "true" || name == "false" => Expr::boolean(name == "true"),
 			Some(Ident(name)) => {
-				if tokens.peek().is_some_and(|x| *x == LParen) {
+				if tokens.next_if_eq(&LParen).is_some() {
 					let mut depth = 0;
 					let mut params = vec![];
 					let mut expression = vec![];
@@ -46,10 +133,14 @@ impl Parser {
 							depth -= 1;
 						}
 
-						if token == LParen {
+						if token == LParen || token == LBracket {
 							depth += 1;
 						}
 
+						if token == RBracket {
+							depth -= 1;
+						}
+
 						if token == Comma && depth == 0 {
 							let mut it = expression.clone().into_iter().peekable();
 							let data = Self::expr_bp(ctx, 0, &mut it);
@@ -85,12 +176,30 @@ impl Parser {
 
 				lhs
 			}
+			Some(LBracket) => {
+				let entries = Self::list(ctx, tokens);
+
+				// A list of rows is a matrix, and a list of other entries a column vector
+				if entries.iter().all(|entry| !entry.is_matrix()) {
+					Expr::Matrix(Matrix::column(entries))
+				} else {
+					let rows = entries
+						.into_iter()
+						.map(|row| match row {
+							Expr::Matrix(row) if row.rows() == 1 => row.into_entries(),
+							_ => panic!("Expected a row like [1, 2]"),
+						})
+						.collect();
+
+					Expr::Matrix(Matrix::from_rows(rows).expect("Rows of a matrix must have the same length"))
+				}
+			}
 			_ => panic!("Bad token"),
 		};
 
 		loop {
 			match tokens.peek() {
-				Some(t @ (Add | Sub | Mul | Div | Rem | Pow | Eq)) => {
+				Some(t @ (Add | Sub | Mul | Div | Rem | Pow | Eq | Lt | Le | Gt | Ge | Ne | EqEq | And | Or)) => {
 					let (l_bp, r_bp) = infix_bp(t.clone());
 
 					if l_bp < min_bp {
@@ -120,11 +229,33 @@ impl Parser {
 						Div => lhs = lhs / rhs,
 						Pow => lhs = lhs.pow(rhs),
 						Rem => todo!(),
+						Lt => lhs = Expr::Rel(Relation::Lt, lhs.into(), rhs.into()),
+						Le => lhs = Expr::Rel(Relation::Le, lhs.into(), rhs.into()),
+						Gt => lhs = Expr::Rel(Relation::Gt, lhs.into(), rhs.into()),
+						Ge => lhs = Expr::Rel(Relation::Ge, lhs.into(), rhs.into()),
+						Ne => lhs = Expr::Rel(Relation::Ne, lhs.into(), rhs.into()),
+						EqEq => lhs = Expr::Rel(Relation::Eq, lhs.into(), rhs.into()),
+						And => lhs = Expr::And(vec![lhs, rhs]),
+						Or => lhs = Expr::Or(vec![lhs, rhs]),
 
 						_ => unreachable!(),
 					}
 				}
-				Some(Number(_) | Ident(_) | LParen) => {
+				Some(t @ (Factorial | DoubleFactorial)) => {
+					if postfix_bp(t.clone()) < min_bp {
+						break;
+					}
+
+					let name = match tokens.next().unwrap() {
+						Factorial => "factorial",
+						DoubleFactorial => "factorial2",
+
+						_ => unreachable!(),
+					};
+
+					lhs = Expr::Fun(Symbol::new(name).unwrap(), vec![lhs]);
+				}
+				Some(Number(_) | Ident(_) | LParen | LBracket) => {
 					let (l_bp, r_bp) = infix_bp(Token::Mul);
 					if l_bp < min_bp {
 						break;
@@ -134,8 +265,8 @@ impl Parser {
 
 					lhs = lhs * rhs;
 				}
-				Some(Comma) => unreachable!(),
-				None | Some(RParen) => break,
+				// A negation cannot follow an operand, so the expression ends before it
+				None | Some(RParen | RBracket | Comma | Not) => break,
 			}
 		}
 
@@ -146,9 +277,12 @@ impl Parser {
 pub fn infix_bp(op: Token) -> (u8, u8) {
 	match op {
 		Eq => (1, 2),
-		Sub | Add => (3, 4),
-		Mul | Div | Rem => (5, 6),
-		Pow => (8, 7),
+		Or => (3, 4),
+		And => (5, 6),
+		Lt | Le | Gt | Ge | Ne | EqEq => (9, 10),
+		Sub | Add => (11, 12),
+		Mul | Div | Rem => (13, 14),
+		Pow => (16, 15),
 
 		_ => unreachable!(),
 	}
@@ -156,7 +290,16 @@ pub fn infix_bp(op: Token) -> (u8, u8) {
 
 pub fn prefix_bp(op: Token) -> u8 {
 	match op {
-		Sub => 7,
+		Not => 7,
+		Sub => 15,
+
+		_ => unreachable!(),
+	}
+}
+
+pub fn postfix_bp(op: Token) -> u8 {
+	match op {
+		Factorial | DoubleFactorial => 17,
 
 		_ => unreachable!(),
 	}
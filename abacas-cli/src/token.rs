@@ -1,5 +1,4 @@
 use std::fmt::Display;
-use std::str::FromStr;
 
 use abacas::number::Number;
 use logos::Logos;
@@ -7,10 +6,13 @@ use logos::Logos;
 #[derive(Logos, Debug, Clone, PartialEq, PartialOrd)]
 #[logos(skip r"[ \t\n\f]+")]
 pub enum Token {
-	#[regex(r"\d+(\.\d+)?", |lex| parse_num(lex.slice()))]
+	#[regex(r"0[xX][0-9a-fA-F](_?[0-9a-fA-F])*", |lex| lex.slice().parse().ok())]
+	#[regex(r"0[oO][0-7](_?[0-7])*", |lex| lex.slice().parse().ok())]
+	#[regex(r"0[bB][01](_?[01])*", |lex| lex.slice().parse().ok())]
+	#[regex(r"\d(_?\d)*(\.(\d(_?\d)*)?(\(\d(_?\d)*\))?)?([eE][+-]?\d(_?\d)*)?", |lex| lex.slice().parse().ok())]
 	Number(Number),
 	// Ident(String),
-	#[regex(r"[a-zA-Z]+", |lex| lex.slice().to_owned())]
+	#[regex(r"[a-zA-Z][a-zA-Z0-9_]*", |lex| lex.slice().to_owned())]
 	Ident(String),
 
 	#[token("=")]
@@ -27,11 +29,37 @@ pub enum Token {
 	Pow,
 	#[token("%")]
 	Rem,
+	#[token("!")]
+	Factorial,
+	#[token("!!")]
+	DoubleFactorial,
+	#[token("<")]
+	Lt,
+	#[token("<=")]
+	Le,
+	#[token(">")]
+	Gt,
+	#[token(">=")]
+	Ge,
+	#[token("!=")]
+	Ne,
+	#[token("==")]
+	EqEq,
+	#[token("and")]
+	And,
+	#[token("or")]
+	Or,
+	#[token("not")]
+	Not,
 
 	#[token("(")]
 	LParen,
 	#[token(")")]
 	RParen,
+	#[token("[")]
+	LBracket,
+	#[token("]")]
+	RBracket,
 	#[token(",")]
 	Comma,
 }
@@ -48,25 +76,22 @@ impl Display for Token {
 			Token::Div => write!(f, "/"),
 			Token::Pow => write!(f, "^"),
 			Token::Rem => write!(f, "%"),
+			Token::Factorial => write!(f, "!"),
+			Token::DoubleFactorial => write!(f, "!!"),
+			Token::Lt => write!(f, "<"),
+			Token::Le => write!(f, "<="),
+			Token::Gt => write!(f, ">"),
+			Token::Ge => write!(f, ">="),
+			Token::Ne => write!(f, "!="),
+			Token::EqEq => write!(f, "=="),
+			Token::And => write!(f, "and"),
+			Token::Or => write!(f, "or"),
+			Token::Not => write!(f, "not"),
 			Token::LParen => write!(f, "("),
 			Token::RParen => write!(f, ")"),
+			Token::LBracket => write!(f, "["),
+			Token::RBracket => write!(f, "]"),
 			Token::Comma => write!(f, ","),
 		}
 	}
 }
-
-fn parse_num(st: &str) -> Number {
-	let (dec, int) = st.split_once(".").unwrap_or((st, ""));
-
-	let formatted = if !int.is_empty() {
-		format!("{} / 1{}", dec.to_owned() + int, "0".repeat(int.len()))
-	} else {
-		dec.to_owned()
-	};
-
-	if let Ok(number) = Number::from_str(&formatted) {
-		number
-	} else {
-		unreachable!()
-	}
-}
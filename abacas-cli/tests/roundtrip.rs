@@ -0,0 +1,238 @@
+//! Property test that the plain output of simplified expressions is read back by the parser and simplified to the
+//! same expression.
+//!
+//! Polynomials with fractional degrees are only generated if the symbols are assumed to be nonnegative, since parsing
+//! only turns fractional powers of symbols into polynomials then.
+
+#[path = "../src/parser.rs"]
+mod parser;
+#[path = "../src/token.rs"]
+mod token;
+
+use abacas::assumption::Assumption;
+use abacas::context::Context;
+use abacas::expr::{Expr, Symbol};
+use abacas::matrix::Matrix;
+use abacas::monomial::Monomial;
+use abacas::number::Number;
+use abacas::polynomial::Polynomial;
+use abacas::relation::Relation;
+use abacas::stdlib::StdLib;
+use logos::Logos;
+
+use crate::parser::Parser;
+use crate::token::Token;
+
+/// The amount of random expressions that are checked.
+const COUNT: usize = 2000;
+
+/// The symbols of the random expressions.
+const SYMBOLS: [&str; 3] = ["x", "y", "e1"];
+
+/// Helper to construct a random nonzero number, which is an integer, a terminating decimal or a fraction.
+fn random_number() -> Number {
+	let numer = Number::from(fastrand::i16(1..=1000)) * [-1, 1][fastrand::usize(..2)];
+
+	match fastrand::u8(..3) {
+		0 => numer,
+		1 => numer / 100,
+		_ => numer / fastrand::u16(1..),
+	}
+}
+
+/// Helper to construct a random symbol, including one that looks like the exponent of a number.
+fn random_symbol() -> Symbol {
+	Symbol::new(SYMBOLS[fastrand::usize(..SYMBOLS.len())]).unwrap()
+}
+
+/// Helper to construct a random polynomial with up to three terms, whose degrees may be negative or fractional.
+fn random_poly(fractional: bool) -> Polynomial {
+	let monomials = (0..fastrand::usize(1..=3)).map(|_| {
+		let degree = match fastrand::u8(..4) {
+			0 => Number::from(fastrand::i8(-3..=-1)),
+			1 if fractional => Number::from(fastrand::i8(-3..=3)) / fastrand::u8(2..=4),
+			_ => Number::from(fastrand::u8(..=4)),
+		};
+
+		Monomial::new(random_number(), degree)
+	});
+
+	Polynomial::new(monomials)
+}
+
+/// Helper to construct a random arithmetic expression with the given depth.
+fn random_expr(depth: u32, fractional: bool) -> Expr {
+	let kind = match depth {
+		0 => fastrand::u8(..2),
+		_ => fastrand::u8(..6),
+	};
+
+	let exprs = |count| (0..count).map(|_| random_expr(depth - 1, fractional)).collect();
+
+	match kind {
+		0 => Expr::Num(random_number()),
+		1 => Expr::Poly(random_symbol(), random_poly(fractional)),
+		2 => Expr::Add(exprs(fastrand::usize(2..=3))),
+		3 => Expr::Mul(exprs(fastrand::usize(2..=3))),
+		4 => {
+			// Exponents are kept small, since integer powers of sums are expanded
+			let exp = match fastrand::u8(..3) {
+				0 => Expr::Num(fastrand::i8(-3..=3).into()),
+				1 => Expr::Num(Number::from(fastrand::i8(-3..=3)) / fastrand::u8(2..=4)),
+				_ => random_expr(depth - 1, fractional),
+			};
+
+			Expr::Pow(random_expr(depth - 1, fractional).into(), exp.into())
+		}
+		// Known functions take one argument, while unknown ones take any amount
+		_ => match fastrand::bool() {
+			true => Expr::Fun(Symbol::new(["sin", "cos"][fastrand::usize(..2)]).unwrap(), exprs(1)),
+			false => Expr::Fun(
+				Symbol::new(["f", "g"][fastrand::usize(..2)]).unwrap(),
+				exprs(fastrand::usize(1..=2)),
+			),
+		},
+	}
+}
+
+/// Helper to construct a random relation between arithmetic expressions.
+fn random_relation(fractional: bool) -> Expr {
+	let rels = [
+		Relation::Eq,
+		Relation::Ge,
+		Relation::Gt,
+		Relation::Le,
+		Relation::Lt,
+		Relation::Ne,
+	];
+	let rel = rels[fastrand::usize(..rels.len())];
+
+	Expr::Rel(
+		rel,
+		random_expr(2, fractional).into(),
+		random_expr(2, fractional).into(),
+	)
+}
+
+/// Helper to construct a random expression of any kind, including logic, matrices and piecewise expressions.
+fn random_statement(fractional: bool) -> Expr {
+	match fastrand::u8(..7) {
+		0 => random_relation(fractional),
+		1 => Expr::And(vec![random_relation(fractional), random_relation(fractional)]),
+		2 => Expr::Or(vec![
+			random_relation(fractional),
+			Expr::Not(random_relation(fractional).into()),
+		]),
+		3 => {
+			let rows = (0..2)
+				.map(|_| (0..2).map(|_| random_expr(1, fractional)).collect())
+				.collect();
+			Expr::Matrix(Matrix::from_rows(rows).unwrap())
+		}
+		4 => Expr::Piecewise(vec![
+			(random_expr(2, fractional), random_relation(fractional)),
+			(random_expr(2, fractional), Expr::boolean(true)),
+		]),
+		_ => random_expr(3, fractional),
+	}
+}
+
+/// Helper to create a context, in which all symbols are assumed to be nonnegative if the degrees may be fractional.
+fn context(fractional: bool) -> Context {
+	let mut ctx = Context::new();
+
+	if fractional {
+		for name in SYMBOLS {
+			assert!(ctx.assume(Symbol::new(name).unwrap(), Assumption::Nonnegative));
+		}
+	}
+
+	ctx
+}
+
+/// Helper to run an expression through the library like the command line does.
+fn evaluate(expr: Expr, fractional: bool) -> Option<Expr> {
+	let ctx = &mut context(fractional);
+	StdLib::new().evaluate(expr, ctx).simplify(ctx).ok()
+}
+
+#[test]
+fn roundtrip() {
+	let mut unsettled = 0;
+
+	for _ in 0..COUNT {
+		let fractional = fastrand::bool();
+
+		// Statements like a division by zero are errors, which have no output to read back
+		let Some(mut expr) = evaluate(random_statement(fractional), fractional) else {
+			continue;
+		};
+
+		// Simplifying again may go further than once, so the expression is simplified until it does not change
+		let mut settled = false;
+
+		for _ in 0..3 {
+			match evaluate(expr.clone(), fractional) {
+				Some(next) if next == expr => {
+					settled = true;
+					break;
+				}
+				Some(next) => expr = next,
+				None => break,
+			}
+		}
+
+		if !settled {
+			unsettled += 1;
+			continue;
+		}
+
+		let src = expr.to_string();
+		let tokens = Token::lexer(&src).collect::<Result<Vec<_>, _>>();
+		let tokens = tokens.unwrap_or_else(|_| panic!("Display output {src} does not lex"));
+
+		// The parser builds the expression from operators, so it is simplified once to compare it structurally
+		let parsed = Parser::parse_line(&mut context(fractional), tokens);
+		assert_eq!(evaluate(parsed, fractional), Some(expr), "{src}");
+	}
+
+	// Only a few expressions may still change after simplifying them several times
+	assert!(
+		unsettled * 100 <= COUNT,
+		"{unsettled} of {COUNT} expressions did not settle"
+	);
+}
+
+#[test]
+fn exponent_symbols() {
+	let e1 = Symbol::new("e1").unwrap();
+	let expr = Expr::Poly(e1, Polynomial::new([Monomial::new(2, 2), Monomial::new(-1, 1)]));
+
+	let src = expr.to_string();
+	assert_eq!(src, "2 * e1^2 - e1");
+
+	let tokens = Token::lexer(&src).collect::<Result<Vec<_>, _>>().unwrap();
+	assert_eq!(
+		evaluate(Parser::parse_line(&mut Context::new(), tokens), false),
+		Some(expr)
+	);
+}
+
+#[test]
+fn fractional_degrees() {
+	let x = Symbol::new("x").unwrap();
+	let poly = Polynomial::new([
+		Monomial::new(3, Number::from(1) / 3),
+		Monomial::new(-2, Number::from(-1) / 2),
+	]);
+	let expr = Expr::Poly(x, poly);
+
+	let src = expr.to_string();
+	assert_eq!(src, "3x^(1/3) - 2x^-0.5");
+
+	let tokens = Token::lexer(&src).collect::<Result<Vec<_>, _>>().unwrap();
+	assert_eq!(
+		evaluate(Parser::parse_line(&mut context(true), tokens), true),
+		Some(expr)
+	);
+}
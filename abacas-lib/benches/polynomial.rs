@@ -35,6 +35,20 @@ fn add(bencher: Bencher) {
 		.bench_values(|(a, b)| a + b);
 }
 
+#[divan::bench]
+fn compose(bencher: Bencher) {
+	bencher
+		.with_inputs(|| (random_poly(20), random_poly(5)))
+		.bench_values(|(a, b)| a.compose(&b));
+}
+
+#[divan::bench]
+fn decompose(bencher: Bencher) {
+	bencher
+		.with_inputs(|| random_poly(10).compose(&random_poly(6)).unwrap())
+		.bench_values(|a| a.decompose());
+}
+
 #[divan::bench]
 fn div(bencher: Bencher) {
 	bencher
@@ -56,6 +70,15 @@ fn gcd_ext(bencher: Bencher) {
 		.bench_values(|(a, b)| a.gcd_ext(b));
 }
 
+#[divan::bench]
+fn mobius_transform(bencher: Bencher) {
+	let (a, b, c, d) = (Number::from(2), Number::from(-3), Number::from(5) / 7, Number::one());
+
+	bencher
+		.with_inputs(|| random_poly(50))
+		.bench_values(|poly| poly.mobius_transform(&a, &b, &c, &d));
+}
+
 #[divan::bench]
 fn mul(bencher: Bencher) {
 	bencher
@@ -63,6 +86,23 @@ fn mul(bencher: Bencher) {
 		.bench_values(|(a, b)| a * &b);
 }
 
+#[divan::bench]
+fn reverse(bencher: Bencher) {
+	bencher.with_inputs(|| random_poly(100)).bench_values(|a| a.reverse());
+}
+
+#[divan::bench]
+fn scale(bencher: Bencher) {
+	let by = Number::from(-5) / 3;
+	bencher.with_inputs(|| random_poly(100)).bench_values(|a| a.scale(&by));
+}
+
+#[divan::bench(args = [1, -1, 7])]
+fn shift(bencher: Bencher, by: i64) {
+	let by = Number::from(by);
+	bencher.with_inputs(|| random_poly(100)).bench_values(|a| a.shift(&by));
+}
+
 #[divan::bench]
 fn sub(bencher: Bencher) {
 	bencher
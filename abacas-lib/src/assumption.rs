@@ -0,0 +1,339 @@
+//! Assumptions about symbols and queries about the values of expressions.
+//!
+//! Every symbol can be assumed to be real, an integer or to have a certain sign, see [`Context::assume`]. Queries like
+//! [`Expr::is_positive`] propagate these assumptions through the expression tree and answer with [`None`] if the
+//! answer is unknown. The sign of an expression is only known if it is known to be real, so for example `x^2` is not
+//! known to be nonnegative without assuming `x` to be real. The constants `pi` and `e` are always positive.
+
+use crate::context::Context;
+use crate::expr::{Expr, Symbol};
+use crate::number::Number;
+
+/// A single assumption about the values of a symbol.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Assumption {
+	/// The symbol is an integer.
+	Integer,
+	/// The symbol is real and less than zero.
+	Negative,
+	/// The symbol is real and greater than or equal to zero.
+	Nonnegative,
+	/// The symbol is real and less than or equal to zero.
+	Nonpositive,
+	/// The symbol is real and not zero.
+	Nonzero,
+	/// The symbol is real and greater than zero.
+	Positive,
+	/// The symbol is real.
+	Real,
+}
+
+/// All assumptions made about a symbol, which are combined with [`Assumptions::with`].
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Assumptions {
+	/// Whether the symbol is an integer.
+	integer: bool,
+	/// The possible signs of the symbol, or [`None`] if it is not known to be real.
+	signs: Option<Signs>,
+}
+
+impl Assumptions {
+	/// Adds an assumption to these assumptions. Returns [`None`] if it contradicts them.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::assumption::{Assumption, Assumptions};
+	///
+	/// let assumptions = Assumptions::default().with(Assumption::Nonnegative).unwrap();
+	///
+	/// assert_eq!(assumptions.with(Assumption::Nonzero), assumptions.with(Assumption::Positive));
+	/// assert_eq!(assumptions.with(Assumption::Negative), None);
+	/// ```
+	pub fn with(self, assumption: Assumption) -> Option<Self> {
+		let (integer, signs) = match assumption {
+			Assumption::Integer => (true, Signs::ALL),
+			Assumption::Negative => (false, Signs::NEGATIVE),
+			Assumption::Nonnegative => (false, Signs::NONNEGATIVE),
+			Assumption::Nonpositive => (false, Signs::NONPOSITIVE),
+			Assumption::Nonzero => (false, Signs::NONZERO),
+			Assumption::Positive => (false, Signs::POSITIVE),
+			Assumption::Real => (false, Signs::ALL),
+		};
+
+		let signs = self.signs.map_or(signs, |previous| previous.intersect(signs));
+
+		(!signs.is_empty()).then_some(Self {
+			integer: self.integer || integer,
+			signs: Some(signs),
+		})
+	}
+}
+
+/// A set of possible signs of a real value.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+struct Signs {
+	/// Whether the value can be negative.
+	negative: bool,
+	/// Whether the value can be zero.
+	zero: bool,
+	/// Whether the value can be positive.
+	positive: bool,
+}
+
+impl Signs {
+	/// Any real value.
+	const ALL: Self = Self::new(true, true, true);
+	/// Real values less than zero.
+	const NEGATIVE: Self = Self::new(true, false, false);
+	/// Real values greater than or equal to zero.
+	const NONNEGATIVE: Self = Self::new(false, true, true);
+	/// Real values less than or equal to zero.
+	const NONPOSITIVE: Self = Self::new(true, true, false);
+	/// Real values other than zero.
+	const NONZERO: Self = Self::new(true, false, true);
+	/// Real values greater than zero.
+	const POSITIVE: Self = Self::new(false, false, true);
+	/// Only zero.
+	const ZERO: Self = Self::new(false, true, false);
+
+	/// Internal method to get the possible signs of a sum of values with these signs.
+	const fn add(self, rhs: Self) -> Self {
+		Self::new(
+			self.negative || rhs.negative,
+			(self.zero && rhs.zero) || (self.negative && rhs.positive) || (self.positive && rhs.negative),
+			self.positive || rhs.positive,
+		)
+	}
+
+	/// Internal method to get the signs contained in both sets.
+	const fn intersect(self, rhs: Self) -> Self {
+		Self::new(
+			self.negative && rhs.negative,
+			self.zero && rhs.zero,
+			self.positive && rhs.positive,
+		)
+	}
+
+	/// Internal method to check whether no sign is possible.
+	const fn is_empty(self) -> bool {
+		!self.negative && !self.zero && !self.positive
+	}
+
+	/// Internal method to get the possible signs of a product of values with these signs.
+	const fn mul(self, rhs: Self) -> Self {
+		Self::new(
+			(self.negative && rhs.positive) || (self.positive && rhs.negative),
+			self.zero || rhs.zero,
+			(self.positive && rhs.positive) || (self.negative && rhs.negative),
+		)
+	}
+
+	/// Internal method to create a set of signs.
+	const fn new(negative: bool, zero: bool, positive: bool) -> Self {
+		Self {
+			negative,
+			zero,
+			positive,
+		}
+	}
+
+	/// Internal method to get the possible signs of a number.
+	fn of(num: &Number) -> Self {
+		Self::new(num.is_negative(), num.is_zero(), num.is_positive())
+	}
+
+	/// Internal method to get the possible signs of a power with these signs as its base and a numeric exponent.
+	/// Returns [`None`] if the power is not known to be real.
+	fn pow(self, exp: &Number) -> Option<Self> {
+		let (numer, denom) = exp.clone().ratio();
+		let is_odd = |num: Number| !(num / 2).is_integer();
+
+		// Even roots of negative values are not real
+		if self.negative && !is_odd(denom) {
+			return None;
+		}
+
+		// Zero can only be raised to positive powers
+		let zero = self.zero && exp.is_positive();
+
+		match (exp.is_zero(), is_odd(numer)) {
+			(true, _) => Some(Self::POSITIVE),
+			(_, true) => Some(Self::new(self.negative, zero, self.positive)),
+			(_, false) => Some(Self::new(false, zero, self.negative || self.positive)),
+		}
+	}
+
+	/// Internal method to get the signs contained in either set.
+	const fn union(self, rhs: Self) -> Self {
+		Self::new(
+			self.negative || rhs.negative,
+			self.zero || rhs.zero,
+			self.positive || rhs.positive,
+		)
+	}
+
+	/// Internal method to compare these signs to an expected set of signs, returning whether they are all contained in
+	/// it or [`None`] if only some of them are.
+	fn within(self, expected: Self) -> Option<bool> {
+		match (self.intersect(expected) == self, self.intersect(expected).is_empty()) {
+			(true, _) => Some(true),
+			(_, true) => Some(false),
+			_ => None,
+		}
+	}
+}
+
+// Operations
+impl Expr {
+	/// Checks whether this expression is an integer. Returns [`None`] if that is unknown.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::assumption::Assumption;
+	/// use abacas::context::Context;
+	/// use abacas::expr::{Expr, Symbol};
+	/// use abacas::number::Number;
+	///
+	/// let mut ctx = Context::new();
+	/// let n = Symbol::new("n").unwrap();
+	/// let expr = Expr::Poly(n.clone(), "x^2 + 3x".parse().unwrap());
+	///
+	/// assert_eq!(expr.is_integer(&ctx), None);
+	///
+	/// ctx.assume(n, Assumption::Integer);
+	/// assert_eq!(expr.is_integer(&ctx), Some(true));
+	/// assert_eq!(Expr::Num(Number::from(1) / 2).is_integer(&ctx), Some(false));
+	/// ```
+	pub fn is_integer(&self, ctx: &Context) -> Option<bool> {
+		match self {
+			Self::Num(num) => Some(num.is_integer()),
+			expr => is_integer(expr, ctx).then_some(true),
+		}
+	}
+
+	/// Checks whether this expression is real and less than zero. Returns [`None`] if that is unknown.
+	pub fn is_negative(&self, ctx: &Context) -> Option<bool> {
+		signs(self, ctx)?.within(Signs::NEGATIVE)
+	}
+
+	/// Checks whether this expression is real and greater than or equal to zero. Returns [`None`] if that is unknown.
+	pub fn is_nonnegative(&self, ctx: &Context) -> Option<bool> {
+		signs(self, ctx)?.within(Signs::NONNEGATIVE)
+	}
+
+	/// Checks whether this expression is real and not zero. Returns [`None`] if that is unknown.
+	pub fn is_nonzero(&self, ctx: &Context) -> Option<bool> {
+		signs(self, ctx)?.within(Signs::NONZERO)
+	}
+
+	/// Checks whether this expression is real and greater than zero. Returns [`None`] if that is unknown.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::assumption::Assumption;
+	/// use abacas::context::Context;
+	/// use abacas::expr::{Expr, Symbol};
+	///
+	/// let mut ctx = Context::new();
+	/// let x = Symbol::new("x").unwrap();
+	/// let expr = Expr::Poly(x.clone(), "x^2 + 1".parse().unwrap());
+	///
+	/// assert_eq!(expr.is_positive(&ctx), None);
+	///
+	/// ctx.assume(x.clone(), Assumption::Real);
+	/// assert_eq!(expr.is_positive(&ctx), Some(true));
+	///
+	/// let expr = Expr::Poly(x, "-2x^3".parse().unwrap());
+	/// assert_eq!(expr.is_positive(&ctx), None);
+	///
+	/// ctx.assume(Symbol::new("x").unwrap(), Assumption::Positive);
+	/// assert_eq!(expr.is_positive(&ctx), Some(false));
+	/// ```
+	pub fn is_positive(&self, ctx: &Context) -> Option<bool> {
+		signs(self, ctx)?.within(Signs::POSITIVE)
+	}
+
+	/// Checks whether this expression is real. Returns [`None`] if that is unknown.
+	pub fn is_real(&self, ctx: &Context) -> Option<bool> {
+		signs(self, ctx).map(|_| true)
+	}
+}
+
+/// Internal helper to check whether an expression is known to be an integer.
+fn is_integer(expr: &Expr, ctx: &Context) -> bool {
+	let is_natural = |num: &Number| num.is_integer() && !num.is_negative();
+
+	match expr {
+		Expr::Add(exprs) | Expr::Mul(exprs) => exprs.iter().all(|expr| is_integer(expr, ctx)),
+		Expr::And(_) | Expr::Fun(_, _) | Expr::Matrix(_) | Expr::Not(_) | Expr::Or(_) | Expr::Rel(_, _, _) => false,
+		Expr::Num(num) => num.is_integer(),
+		Expr::Piecewise(branches) => !branches.is_empty() && branches.iter().all(|(value, _)| is_integer(value, ctx)),
+		Expr::Poly(sym, poly) => {
+			ctx.assumptions.get(sym).is_some_and(|assumptions| assumptions.integer)
+				&& poly
+					.monomials()
+					.all(|mono| mono.coeff.is_integer() && is_natural(&mono.degree))
+		}
+		Expr::Pow(base, exp) => is_integer(base, ctx) && exp.is_num_and(is_natural),
+	}
+}
+
+/// Internal helper to get the possible signs of an expression, or [`None`] if it is not known to be real.
+fn signs(expr: &Expr, ctx: &Context) -> Option<Signs> {
+	match expr {
+		Expr::Add(exprs) => exprs
+			.iter()
+			.try_fold(Signs::ZERO, |acc, expr| Some(acc.add(signs(expr, ctx)?))),
+		Expr::Fun(name, args) => {
+			let [arg] = args.as_slice() else { return None };
+
+			match name.name() {
+				"exp" => signs(arg, ctx).map(|_| Signs::POSITIVE),
+				"cos" | "sin" | "tan" | "atan" => signs(arg, ctx).map(|_| Signs::ALL),
+				"ln" => (arg.is_positive(ctx) == Some(true)).then_some(Signs::ALL),
+				"factorial" => {
+					(arg.is_nonnegative(ctx) == Some(true) && is_integer(arg, ctx)).then_some(Signs::POSITIVE)
+				}
+				_ => None,
+			}
+		}
+		Expr::Mul(exprs) => exprs
+			.iter()
+			.try_fold(Signs::POSITIVE, |acc, expr| Some(acc.mul(signs(expr, ctx)?))),
+		Expr::Num(num) => Some(Signs::of(num)),
+		// Any of the branches may apply
+		Expr::Piecewise(branches) => branches
+			.iter()
+			.map(|(value, _)| signs(value, ctx))
+			.reduce(|acc, signs| Some(acc?.union(signs?)))?,
+		Expr::Poly(sym, poly) => {
+			let var = symbol(sym, ctx)?;
+
+			poly.monomials().try_fold(Signs::ZERO, |acc, mono| {
+				Some(acc.add(Signs::of(&mono.coeff).mul(var.pow(&mono.degree)?)))
+			})
+		}
+		Expr::Pow(base, exp) => {
+			let base = signs(base, ctx)?;
+
+			match &**exp {
+				Expr::Num(exp) => base.pow(exp),
+				// Powers of nonnegative bases keep their sign for every real exponent
+				exp if !base.negative => signs(exp, ctx).map(|_| Signs::new(false, base.zero, base.positive)),
+				_ => None,
+			}
+		}
+		Expr::And(_) | Expr::Matrix(_) | Expr::Not(_) | Expr::Or(_) | Expr::Rel(_, _, _) => None,
+	}
+}
+
+/// Internal helper to get the possible signs of a symbol from the assumptions, where `pi` and `e` are positive.
+fn symbol(sym: &Symbol, ctx: &Context) -> Option<Signs> {
+	match sym.name() {
+		"pi" | "e" => Some(Signs::POSITIVE),
+		_ => ctx.assumptions.get(sym)?.signs,
+	}
+}
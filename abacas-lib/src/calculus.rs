@@ -0,0 +1,879 @@
+//! Differentiation and integration of expressions.
+//!
+//! Antiderivatives are found for polynomials, rational functions using partial fractions, and the elementary functions
+//! of the StdLib applied to linear arguments. Other integrands are tried with substitution and integration by parts,
+//! falling back to an unevaluated `integrate` call if no antiderivative is found.
+
+use std::collections::HashMap;
+
+use rug::ops::Pow;
+
+use crate::context::Context;
+use crate::error::SimplifyError;
+use crate::expr::{Expr, Symbol};
+use crate::interval::Interval;
+use crate::monomial::Monomial;
+use crate::number::Number;
+use crate::polynomial::Polynomial;
+use crate::set::Set;
+use crate::summation::{evaluate, linear, rational, solve_linear};
+
+/// The maximum depth of nested substitutions and integrations by parts.
+const MAX_DEPTH: u32 = 3;
+
+/// The maximum amount of times the range of a definite integral is bisected to show that the integrand is bounded.
+const MAX_BISECTIONS: u32 = 6;
+
+// Operations
+impl Expr {
+	/// Differentiates this expression with respect to a symbol, without simplifying the result. Other symbols are
+	/// treated as constants, and calls to unknown functions of the symbol stay unevaluated as `diff` calls.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::context::Context;
+	/// use abacas::expr::{Expr, Symbol};
+	///
+	/// let x = Symbol::new("x").unwrap();
+	/// let square = Expr::Poly(x.clone(), "x^2".parse().unwrap());
+	/// let expr = Expr::Fun(Symbol::new("sin").unwrap(), vec![square]);
+	///
+	/// let result = expr.derivative(&x).simplify(&mut Context::new()).unwrap();
+	/// assert_eq!(result.to_string(), "cos(x^2) * 2x");
+	/// ```
+	pub fn derivative(&self, var: &Symbol) -> Self {
+		if !self.has_symbol(var) {
+			return Self::zero();
+		}
+
+		match self {
+			Self::Add(terms) => Self::Add(terms.iter().map(|term| term.derivative(var)).collect()),
+			Self::Fun(name, args) => match (name.name(), args.as_slice()) {
+				// The derivative of an antiderivative is the integrand
+				("integrate", [expr, x]) if is_symbol(x, var) => expr.clone(),
+
+				// Chain rule for the elementary functions
+				(name, [arg]) if let Some(outer) = outer_derivative(name, arg) => outer * arg.derivative(var),
+
+				_ => call("diff", vec![self.clone(), symbol(var)]),
+			},
+			Self::Matrix(matrix) => Self::Matrix(matrix.clone().map(|entry| entry.derivative(var))),
+			Self::Mul(factors) => {
+				// Product rule, differentiating one factor at a time
+				let terms = (0..factors.len()).map(|index| {
+					let mut factors = factors.clone();
+					factors[index] = factors[index].derivative(var);
+					Self::Mul(factors)
+				});
+
+				Self::Add(terms.collect())
+			}
+			Self::Num(_) => Self::zero(),
+			// Branches are differentiated separately, ignoring the points where the conditions change
+			Self::Piecewise(branches) => Self::Piecewise(
+				branches
+					.iter()
+					.map(|(value, condition)| (value.derivative(var), condition.clone()))
+					.collect(),
+			),
+			Self::Poly(_, poly) => Self::Poly(var.clone(), poly.derivative()),
+			Self::Pow(base, exp) if !exp.has_symbol(var) => {
+				let lower = base.as_ref().clone().pow(exp.as_ref().clone() - Self::one());
+				exp.as_ref().clone() * lower * base.derivative(var)
+			}
+			Self::Pow(base, exp) if !base.has_symbol(var) => {
+				self.clone() * ln(base.as_ref().clone()) * exp.derivative(var)
+			}
+			Self::Pow(base, exp) => {
+				let inner = exp.derivative(var) * ln(base.as_ref().clone())
+					+ exp.as_ref().clone() * base.derivative(var) / base.as_ref().clone();
+
+				self.clone() * inner
+			}
+			Self::And(_) | Self::Not(_) | Self::Or(_) | Self::Rel(_, _, _) => {
+				call("diff", vec![self.clone(), symbol(var)])
+			}
+		}
+	}
+
+	/// Integrates this expression with respect to a symbol, returning an unevaluated `integrate` call if no
+	/// antiderivative is found. The constant of integration is omitted, and logarithms are taken of the arguments
+	/// themselves rather than their absolute values.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::context::Context;
+	/// use abacas::expr::{Expr, Symbol};
+	///
+	/// let ctx = &mut Context::new();
+	/// let x = Symbol::new("x").unwrap();
+	///
+	/// let expr = Expr::Poly(x.clone(), "3x^2 + x^-1".parse().unwrap());
+	/// assert_eq!(expr.integrate(&x, ctx).unwrap().to_string(), "ln(x) + x^3");
+	///
+	/// let expr = Expr::Pow(Expr::Poly(x.clone(), "x^2 + 1".parse().unwrap()).into(), Expr::neg_one().into());
+	/// assert_eq!(expr.integrate(&x, ctx).unwrap().to_string(), "atan(x)");
+	///
+	/// let expr = Expr::Fun(Symbol::new("gamma").unwrap(), vec![Expr::Poly(x.clone(), "x".parse().unwrap())]);
+	/// assert_eq!(expr.integrate(&x, ctx).unwrap().to_string(), "integrate(gamma(x), x)");
+	/// ```
+	pub fn integrate(&self, var: &Symbol, ctx: &mut Context) -> Result<Self, SimplifyError> {
+		let expr = self.clone().simplify(ctx)?;
+
+		match antiderivative(&expr, var, 0, ctx)? {
+			Some(anti) => anti.simplify(ctx),
+			None => Ok(call("integrate", vec![expr, symbol(var)])),
+		}
+	}
+
+	/// Integrates this expression with respect to a symbol from `lo` to `hi`, returning an unevaluated `integrate`
+	/// call if no antiderivative is found. For numeric bounds, the integrand also has to be bounded in between, which
+	/// is shown with interval arithmetic.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::context::Context;
+	/// use abacas::expr::{Expr, Symbol};
+	/// use abacas::number::Number;
+	///
+	/// let ctx = &mut Context::new();
+	/// let x = Symbol::new("x").unwrap();
+	/// let expr = Expr::Poly(x.clone(), "x^2".parse().unwrap());
+	///
+	/// let result = expr.integrate_definite(&x, &Expr::zero(), &Expr::Num(3.into()), ctx).unwrap();
+	/// assert_eq!(result, Expr::Num(9.into()));
+	///
+	/// // The integrand has a pole at zero
+	/// let expr = Expr::Poly(x.clone(), "x^-2".parse().unwrap());
+	/// let result = expr.integrate_definite(&x, &Expr::neg_one(), &Expr::one(), ctx).unwrap();
+	/// assert_eq!(result.to_string(), "integrate(x^-2, x, -1, 1)");
+	/// ```
+	pub fn integrate_definite(
+		&self,
+		var: &Symbol,
+		lo: &Self,
+		hi: &Self,
+		ctx: &mut Context,
+	) -> Result<Self, SimplifyError> {
+		let (expr, lo, hi) = (
+			self.clone().simplify(ctx)?,
+			lo.clone().simplify(ctx)?,
+			hi.clone().simplify(ctx)?,
+		);
+
+		match definite(&expr, var, &lo, &hi, ctx)? {
+			Some(result) => evaluate(result, ctx),
+			None => Ok(call("integrate", vec![expr, symbol(var), lo, hi])),
+		}
+	}
+}
+
+/// Internal helper to find an antiderivative of a simplified expression, or [`None`] if none is found. The depth
+/// limits how often substitution and integration by parts are nested.
+fn antiderivative(expr: &Expr, var: &Symbol, depth: u32, ctx: &mut Context) -> Result<Option<Expr>, SimplifyError> {
+	if !expr.has_symbol(var) {
+		return Ok(Some(expr.clone() * symbol(var)));
+	}
+
+	match expr {
+		// Polynomials are integrated term by term, including rational and negative degrees
+		Expr::Poly(_, poly) => return Ok(Some(polynomial(poly, var))),
+
+		// Sums are integrated term by term
+		Expr::Add(terms) => {
+			let mut antis = Vec::with_capacity(terms.len());
+
+			for term in terms {
+				let Some(anti) = antiderivative(term, var, depth, ctx)? else {
+					return Ok(None);
+				};
+
+				antis.push(anti);
+			}
+
+			return Ok(Some(Expr::Add(antis)));
+		}
+
+		// Piecewise expressions are integrated branch by branch
+		Expr::Piecewise(branches) => {
+			let mut antis = Vec::with_capacity(branches.len());
+
+			for (value, condition) in branches {
+				let Some(anti) = antiderivative(value, var, depth, ctx)? else {
+					return Ok(None);
+				};
+
+				antis.push((anti, condition.clone()));
+			}
+
+			return Ok(Some(Expr::Piecewise(antis)));
+		}
+
+		// Constant factors are extracted from products
+		Expr::Mul(factors) if factors.iter().any(|factor| !factor.has_symbol(var)) => {
+			let (mut constant, rest): (Vec<_>, Vec<_>) =
+				factors.iter().cloned().partition(|expr| !expr.has_symbol(var));
+
+			let Some(anti) = antiderivative(&Expr::Mul(rest).simplify(ctx)?, var, depth, ctx)? else {
+				return Ok(None);
+			};
+
+			constant.push(anti);
+			return Ok(Some(Expr::Mul(constant)));
+		}
+
+		// Square roots are integrated as powers
+		Expr::Fun(name, args)
+			if name.name() == "sqrt"
+				&& let [arg] = args.as_slice() =>
+		{
+			let power = arg.clone().pow(Expr::Num(Number::from(1) / 2)).simplify(ctx)?;
+			return antiderivative(&power, var, depth, ctx);
+		}
+
+		// Elementary functions of linear arguments `a x + b` are looked up in a table and divided by `a`
+		Expr::Fun(name, args)
+			if let [arg] = args.as_slice()
+				&& let Some((slope, _)) = linear(arg, var)
+				&& let Some(anti) = table(name.name(), arg) =>
+		{
+			return Ok(Some(anti / Expr::Num(slope)));
+		}
+
+		// Exponentials `c^(a x + b)` integrate to `c^(a x + b) / (a ln(c))`
+		Expr::Pow(base, exp)
+			if !base.has_symbol(var)
+				&& let Some((slope, _)) = linear(exp, var) =>
+		{
+			return Ok(Some(expr.clone() / (Expr::Num(slope) * ln(base.as_ref().clone()))));
+		}
+
+		// Powers `(a x + b)^n` integrate to `(a x + b)^(n + 1) / (a (n + 1))`, or a logarithm for `n = -1`
+		Expr::Pow(base, exp)
+			if let Expr::Num(exp) = exp.as_ref()
+				&& let Some((slope, _)) = linear(base, var) =>
+		{
+			let base = base.as_ref().clone();
+			let next = exp.clone() + 1;
+
+			let anti = if next.is_zero() {
+				ln(base)
+			} else {
+				base.pow(Expr::Num(next.clone())) / Expr::Num(next)
+			};
+
+			return Ok(Some(anti / Expr::Num(slope)));
+		}
+
+		_ => {}
+	}
+
+	if let Some((numer, denom)) = rational(expr, var)
+		&& let Some(anti) = rational_function(&numer, &denom, var)
+	{
+		return Ok(Some(anti));
+	}
+
+	if depth >= MAX_DEPTH {
+		return Ok(None);
+	}
+
+	if let Some(anti) = substitution(expr, var, depth, ctx)? {
+		return Ok(Some(anti));
+	}
+
+	parts(expr, var, depth, ctx)
+}
+
+/// Internal helper to check with interval arithmetic that an expression is bounded for all values of `var` in a
+/// range, bisecting the range to reduce overestimation.
+fn bounded(expr: &Expr, var: &Symbol, range: Interval, bisections: u32) -> bool {
+	let vars = HashMap::from([(var.clone(), range.clone())]);
+
+	if expr.eval_interval(&vars, 64).is_some() {
+		return true;
+	}
+
+	let (left, right) = range.bisect();
+	bisections > 0 && bounded(expr, var, left, bisections - 1) && bounded(expr, var, right, bisections - 1)
+}
+
+/// Internal helper to create a function call.
+fn call(name: &str, args: Vec<Expr>) -> Expr {
+	Expr::Fun(Symbol::new(name).unwrap(), args)
+}
+
+/// Internal helper to collect the subexpressions depending on `var` that are worth substituting, innermost first.
+/// Linear subexpressions are skipped, as functions of them are integrated directly.
+fn candidates(expr: &Expr, var: &Symbol, found: &mut Vec<Expr>) {
+	let children: Vec<&Expr> = match expr {
+		Expr::Add(exprs) | Expr::Fun(_, exprs) | Expr::Mul(exprs) => exprs.iter().collect(),
+		Expr::Num(_) | Expr::Poly(_, _) => Vec::new(),
+		Expr::Pow(base, exp) => vec![base, exp],
+		Expr::Matrix(matrix) => matrix.entries().iter().collect(),
+		Expr::And(_) | Expr::Not(_) | Expr::Or(_) | Expr::Piecewise(_) | Expr::Rel(_, _, _) => Vec::new(),
+	};
+
+	for child in &children {
+		candidates(child, var, found);
+	}
+
+	for child in children {
+		if child.has_symbol(var) && linear(child, var).is_none() && !found.contains(child) {
+			found.push(child.clone());
+		}
+	}
+}
+
+/// Internal helper to get the definite integral of a simplified expression before evaluating it, or [`None`] if no
+/// antiderivative is found or the integrand is not bounded between numeric bounds.
+fn definite(expr: &Expr, var: &Symbol, lo: &Expr, hi: &Expr, ctx: &mut Context) -> Result<Option<Expr>, SimplifyError> {
+	match expr {
+		_ if !expr.has_symbol(var) => return Ok(Some(expr.clone() * (hi.clone() - lo.clone()))),
+
+		// Sums are integrated term by term, so every term is checked on its own
+		Expr::Add(terms) => {
+			let mut results = Vec::with_capacity(terms.len());
+
+			for term in terms {
+				let Some(result) = definite(term, var, lo, hi, ctx)? else {
+					return Ok(None);
+				};
+
+				results.push(result);
+			}
+
+			return Ok(Some(Expr::Add(results)));
+		}
+
+		// Constant factors are extracted, so symbolic constants do not prevent the check below
+		Expr::Mul(factors) if factors.iter().any(|factor| !factor.has_symbol(var)) => {
+			let (mut constant, rest): (Vec<_>, Vec<_>) =
+				factors.iter().cloned().partition(|expr| !expr.has_symbol(var));
+
+			let Some(result) = definite(&Expr::Mul(rest).simplify(ctx)?, var, lo, hi, ctx)? else {
+				return Ok(None);
+			};
+
+			constant.push(result);
+			return Ok(Some(Expr::Mul(constant)));
+		}
+
+		// Piecewise expressions are split where their branches apply, which needs numeric bounds
+		Expr::Piecewise(branches) => {
+			return match (lo, hi) {
+				(Expr::Num(lo), Expr::Num(hi)) => piecewise(branches, var, lo, hi, ctx),
+				_ => Ok(None),
+			};
+		}
+
+		_ => {}
+	}
+
+	// The antiderivative only gives the integral if the integrand has no pole between the bounds
+	if let (Expr::Num(lo), Expr::Num(hi)) = (lo, hi) {
+		let range = Interval::new(lo.clone().min(hi.clone()), lo.clone().max(hi.clone()));
+
+		if !bounded(expr, var, range, MAX_BISECTIONS) {
+			return Ok(None);
+		}
+	}
+
+	let Some(anti) = antiderivative(expr, var, 0, ctx)? else {
+		return Ok(None);
+	};
+
+	Ok(Some(anti.substitute(var, hi) - anti.substitute(var, lo)))
+}
+
+/// Internal helper to divide a product by the factors of another expression, cancelling equal factors directly.
+fn divide(expr: &Expr, divisor: &Expr) -> Expr {
+	// Splits an expression into factors, distributing numeric powers over products
+	fn factors(expr: &Expr) -> Vec<Expr> {
+		match expr {
+			Expr::Mul(exprs) => exprs.iter().flat_map(factors).collect(),
+			Expr::Pow(base, exp)
+				if exp.is_num()
+					&& let Expr::Mul(exprs) = base.as_ref() =>
+			{
+				exprs
+					.iter()
+					.flat_map(factors)
+					.map(|factor| factor.pow(exp.as_ref().clone()))
+					.collect()
+			}
+			_ => vec![expr.clone()],
+		}
+	}
+
+	let mut result = factors(expr);
+
+	for factor in factors(divisor) {
+		match result.iter().position(|other| *other == factor) {
+			Some(index) => {
+				result.remove(index);
+			}
+			None => result.push(factor.pow(Expr::neg_one())),
+		}
+	}
+
+	Expr::Mul(result)
+}
+
+/// Internal helper to get the positive divisors of an integer, or [`None`] if it is zero or too large.
+fn divisors(num: &Number) -> Option<Vec<u64>> {
+	let num = u64::from(num.clone().abs().to_u32().filter(|num| *num != 0)?);
+	let small = (1..).take_while(|divisor| divisor * divisor <= num);
+
+	let mut divisors: Vec<_> = small
+		.filter(|divisor| num % divisor == 0)
+		.flat_map(|divisor| [divisor, num / divisor])
+		.collect();
+
+	divisors.sort_unstable();
+	divisors.dedup();
+
+	Some(divisors)
+}
+
+/// Internal helper to find polynomials `p1` and `p2` with `r / (d1 d2) = (p1 / d1)' + p2 / d2`, where `d1` is the
+/// GCD of the denominator and its derivative. Multiplying by the denominator gives the linear system
+/// `r = p1' d2 - p1 h + p2 d1` with `h = d1' d2 / d1` for the coefficients, which is known as Horowitz-Ostrogradsky.
+fn horowitz_ostrogradsky(r: &Polynomial, d1: &Polynomial, d2: &Polynomial) -> Option<(Polynomial, Polynomial)> {
+	let degree = |poly: &Polynomial| poly.degree().and_then(Number::to_u32).unwrap_or_default();
+	let (m, n) = (degree(d1), degree(d2));
+	let h = d1.derivative() * d2 / d1;
+
+	// Every coefficient of `p1` and `p2` contributes one column of a linear system
+	let columns: Vec<_> = (0..m)
+		.map(|j| {
+			let basis = Polynomial::from(Monomial::new(1, j));
+			basis.derivative() * d2 - basis * &h
+		})
+		.chain((0..n).map(|j| Polynomial::from(Monomial::new(1, j)) * d1))
+		.collect();
+
+	let coeff = |poly: &Polynomial, i: u32| poly.get(&i.into()).map(|mono| mono.coeff.clone()).unwrap_or_default();
+
+	let system = (0..m + n)
+		.map(|i| columns.iter().chain([r]).map(|poly| coeff(poly, i)).collect())
+		.collect();
+
+	let solution = solve_linear(system)?;
+	let (p1, p2) = solution.split_at(m as usize);
+	let poly = |coeffs: &[Number]| {
+		let monomials = coeffs.iter().enumerate().filter(|(_, coeff)| !coeff.is_zero());
+		Polynomial::new(monomials.map(|(j, coeff)| Monomial::new(coeff.clone(), j)))
+	};
+
+	Some((poly(p1), poly(p2)))
+}
+
+/// Internal helper to check whether an expression is a logarithm or inverse tangent, or a positive integer power of
+/// one, which become simpler when differentiated.
+fn is_inverse(expr: &Expr) -> bool {
+	match expr {
+		Expr::Fun(name, _) => matches!(name.name(), "ln" | "atan"),
+		Expr::Pow(base, exp) => exp.is_num_and(|exp| exp.is_integer() && exp.is_positive()) && is_inverse(base),
+		_ => false,
+	}
+}
+
+/// Internal helper to check whether an expression is exactly the given symbol.
+fn is_symbol(expr: &Expr, sym: &Symbol) -> bool {
+	expr.is_poly_and(|other, poly| other == sym && *poly == Polynomial::from(Monomial::linear(1)))
+}
+
+/// Internal helper to create the natural logarithm of an expression, which is one for the constant `e`.
+fn ln(expr: Expr) -> Expr {
+	if is_symbol(&expr, &Symbol::new("e").unwrap()) {
+		return Expr::one();
+	}
+
+	call("ln", vec![expr])
+}
+
+/// Internal helper to integrate a proper quotient with a squarefree, monic denominator using partial fractions.
+/// Every rational root `r` contributes `c ln(x - r)` with the residue `c = a(r) / d'(r)`. The remaining part has to
+/// split into quadratic factors with rational coefficients, otherwise [`None`] is returned.
+fn logarithmic(numer: &Polynomial, denom: &Polynomial, var: &Symbol) -> Option<Expr> {
+	let x = Polynomial::from(Monomial::linear(1));
+	let (mut rest, mut linear) = (numer.clone(), Polynomial::from(1));
+	let mut terms = Vec::new();
+
+	for root in denom.rational_roots()? {
+		let factor = x.clone() - root.clone();
+		let cofactor = denom.clone() / &factor;
+		let residue = numer.eval(&root)? / &cofactor.eval(&root)?;
+
+		rest -= cofactor * &residue;
+		linear *= &factor;
+		terms.push(Expr::Num(residue) * ln(Expr::Poly(var.clone(), factor)));
+	}
+
+	// Removing the linear parts leaves a quotient whose denominator has no rational roots
+	let (mut rest, _) = rest.div_rem(&linear)?;
+	let mut remaining = denom.clone() / &linear;
+
+	while remaining.degree().is_some_and(|degree| *degree > 2) {
+		let (factor, cofactor) = quadratic_factor(&remaining)?;
+
+		// Split off `numer / factor` from `rest / (factor cofactor)`, using `s cofactor = 1 (mod factor)`
+		let (s, _, _) = cofactor.clone().gcd_ext(factor.clone());
+		let numer = rest.clone() * &s % &factor;
+
+		rest = (rest - numer.clone() * &cofactor) / &factor;
+		terms.push(quadratic(&numer, &factor, var));
+		remaining = cofactor;
+	}
+
+	match remaining.degree().and_then(Number::to_u32) {
+		Some(0) => {}
+		Some(2) => terms.push(quadratic(&rest, &remaining, var)),
+		_ => return None,
+	}
+
+	Some(Expr::Add(terms))
+}
+
+/// Internal helper to get the outer derivative `f'(u)` of an elementary function `f`, or [`None`] if it is unknown.
+fn outer_derivative(name: &str, arg: &Expr) -> Option<Expr> {
+	let u = arg.clone();
+	let two = Expr::Num(2.into());
+
+	let outer = match name {
+		"exp" => call("exp", vec![u]),
+		"ln" => u.pow(Expr::neg_one()),
+		"sin" => call("cos", vec![u]),
+		"cos" => -call("sin", vec![u]),
+		"tan" => Expr::one() + call("tan", vec![u]).pow(two),
+		"atan" => (Expr::one() + u.pow(two)).pow(Expr::neg_one()),
+		"sqrt" => (two * call("sqrt", vec![u])).pow(Expr::neg_one()),
+		_ => return None,
+	};
+
+	Some(outer)
+}
+
+/// Internal helper to integrate by parts with `∫ u dv = u v - ∫ v du`. The factor `u` is a logarithm or inverse
+/// tangent if there is one, and otherwise a polynomial, whose degree decreases with every step.
+fn parts(expr: &Expr, var: &Symbol, depth: u32, ctx: &mut Context) -> Result<Option<Expr>, SimplifyError> {
+	let mut factors = match expr {
+		Expr::Mul(factors) => factors.clone(),
+		_ => vec![expr.clone()],
+	};
+
+	let polynomial = |factor: &Expr| {
+		factor.is_poly_and(|sym, poly| {
+			sym == var
+				&& poly
+					.monomials()
+					.all(|mono| mono.degree.is_integer() && !mono.degree.is_negative())
+		})
+	};
+
+	// Polynomials always terminate, so they do not count towards the depth
+	let (index, next) = if let Some(index) = factors.iter().position(is_inverse) {
+		(index, depth + 1)
+	} else if factors.len() > 1
+		&& let Some(index) = factors.iter().position(polynomial)
+	{
+		(index, depth)
+	} else {
+		return Ok(None);
+	};
+
+	let u = factors.remove(index);
+	let dv = Expr::Mul(factors).simplify(ctx)?;
+
+	let Some(v) = antiderivative(&dv, var, next, ctx)? else {
+		return Ok(None);
+	};
+
+	let v = v.simplify(ctx)?;
+	let rest = (v.clone() * u.derivative(var)).simplify(ctx)?;
+
+	let Some(anti) = antiderivative(&rest, var, next, ctx)? else {
+		return Ok(None);
+	};
+
+	Ok(Some(u * v - anti))
+}
+
+/// Internal helper to integrate a piecewise expression between numeric bounds, integrating every branch over the
+/// intervals where it is the first one to apply. Returns [`None`] if a condition cannot be solved, a branch cannot be
+/// integrated, or no branch applies somewhere in between.
+fn piecewise(
+	branches: &[(Expr, Expr)],
+	var: &Symbol,
+	lo: &Number,
+	hi: &Number,
+	ctx: &mut Context,
+) -> Result<Option<Expr>, SimplifyError> {
+	// Integrate in ascending order and negate the result if the bounds are swapped
+	let (min, max) = (lo.clone().min(hi.clone()), lo.clone().max(hi.clone()));
+	let mut remaining = Set::interval(Expr::Num(min), Expr::Num(max), true, true);
+	let mut results = Vec::new();
+
+	for (value, condition) in branches {
+		let Some(applies) = condition.solve_inequality(var, ctx)? else {
+			return Ok(None);
+		};
+
+		let part = Set::intersection([remaining.clone(), applies], ctx)?;
+		remaining = part.complement(&remaining, ctx)?;
+
+		// Single points do not contribute to the integral
+		let Some(ranges) = ranges(&part) else {
+			return Ok(None);
+		};
+
+		for (lo, hi) in ranges {
+			let Some(result) = definite(value, var, lo, hi, ctx)? else {
+				return Ok(None);
+			};
+
+			results.push(result);
+		}
+	}
+
+	if !ranges(&remaining).is_some_and(|ranges| ranges.is_empty()) {
+		return Ok(None);
+	}
+
+	match lo > hi {
+		true => Ok(Some(-Expr::Add(results))),
+		false => Ok(Some(Expr::Add(results))),
+	}
+}
+
+/// Internal helper to integrate a polynomial term by term, where the degree `-1` results in a logarithm.
+fn polynomial(poly: &Polynomial, var: &Symbol) -> Expr {
+	let mut result = Polynomial::ZERO;
+	let mut log = None;
+
+	for mono in poly.monomials() {
+		if mono.degree.is_neg_one() {
+			log = Some(mono.coeff.clone());
+			continue;
+		}
+
+		let degree = mono.degree.clone() + 1;
+		result += Monomial::new(mono.coeff.clone() / &degree, degree);
+	}
+
+	let result = Expr::Poly(var.clone(), result);
+
+	match log {
+		Some(coeff) => result + Expr::Num(coeff) * ln(symbol(var)),
+		None => result,
+	}
+}
+
+/// Internal helper to integrate `(p x + q) / (x^2 + b x + c)` for a denominator without rational roots. Completing
+/// the square gives `(x + b/2)^2 + k`, and the numerator splits into `p/2 (2x + b)` and the constant `q - p b/2`.
+fn quadratic(numer: &Polynomial, denom: &Polynomial, var: &Symbol) -> Expr {
+	let coeff = |poly: &Polynomial, degree: u32| {
+		poly.get(&degree.into())
+			.map(|mono| mono.coeff.clone())
+			.unwrap_or_default()
+	};
+
+	let (p, q) = (coeff(numer, 1), coeff(numer, 0));
+	let (b, c) = (coeff(denom, 1), coeff(denom, 0));
+
+	let half = b / 2;
+	let k = c - &half.clone().square();
+	let constant = q - &(p.clone() * &half);
+
+	let shifted = Expr::Poly(var.clone(), Monomial::linear(1) + half);
+
+	// The square root of `|k| = n / d` is taken separately for `n` and `d`, which are often squares themselves
+	let sqrt = |num: Number| Expr::Num(num).pow(Expr::Num(Number::from(1) / 2));
+	let (n, d) = k.clone().abs().ratio();
+	let root = sqrt(n) / sqrt(d);
+
+	// Positive `k` results in an inverse tangent, negative `k` in logarithms of the irrational roots
+	let inner = if k.is_positive() {
+		call("atan", vec![shifted / root.clone()]) / root
+	} else {
+		let scale = Expr::Num(2.into()) * root.clone();
+		(ln(shifted.clone() - root.clone()) - ln(shifted + root)) / scale
+	};
+
+	Expr::Num(p / 2) * ln(Expr::Poly(var.clone(), denom.clone())) + Expr::Num(constant) * inner
+}
+
+/// Internal helper to find a quadratic factor of a polynomial without rational roots, returning the monic factor and
+/// its cofactor. After scaling to integer coefficients, a factor `a x^2 + b x + c` has `a` dividing the leading
+/// coefficient, `c` dividing the constant, and `a + b + c` dividing the value at one, which leaves finitely many
+/// candidates. Returns [`None`] if there is no such factor or the coefficients are too large.
+fn quadratic_factor(poly: &Polynomial) -> Option<(Polynomial, Polynomial)> {
+	let scale = poly
+		.monomials()
+		.fold(Number::one(), |scale, mono| scale.lcm(&mono.coeff.clone().denom()));
+	let scaled = poly.clone() * &scale;
+
+	let leading = divisors(scaled.leading()?)?;
+	let constant = divisors(&scaled.get(&Number::zero())?.coeff)?;
+	let one = divisors(&scaled.eval(&Number::one())?)?;
+
+	let signed = |divisors: &[u64]| {
+		let positive = divisors.iter().map(|&divisor| Number::from(divisor));
+		positive
+			.clone()
+			.chain(positive.map(|divisor| -divisor))
+			.collect::<Vec<_>>()
+	};
+
+	for a in leading.into_iter().map(Number::from) {
+		for c in signed(&constant) {
+			for value in signed(&one) {
+				let b = value - &a - &c;
+				let mut factor = Polynomial::from(Monomial::new(a.clone(), 2))
+					+ Polynomial::from(Monomial::linear(1)) * &b
+					+ c.clone();
+
+				let Some((mut cofactor, remainder)) = poly.clone().div_rem(&factor) else {
+					continue;
+				};
+
+				if remainder.is_zero() {
+					if let Some(leading) = factor.monic_mut() {
+						cofactor *= &leading;
+					}
+
+					return Some((factor, cofactor));
+				}
+			}
+		}
+	}
+
+	None
+}
+
+/// Internal helper to get the endpoints of the intervals with more than one point in a set, or [`None`] if the set is
+/// not a finite set, an interval or a union of those.
+fn ranges(set: &Set) -> Option<Vec<(&Expr, &Expr)>> {
+	match set {
+		Set::Empty | Set::Finite(_) => Some(Vec::new()),
+		Set::Interval { lo, hi, .. } if lo == hi => Some(Vec::new()),
+		Set::Interval { lo, hi, .. } => Some(vec![(lo, hi)]),
+		Set::Union(sets) => Some(sets.iter().map(ranges).collect::<Option<Vec<_>>>()?.concat()),
+		_ => None,
+	}
+}
+
+/// Internal helper to integrate a quotient of polynomials. The polynomial part is integrated directly, a rational
+/// part is split off with Horowitz-Ostrogradsky, and the squarefree rest is integrated with partial fractions.
+fn rational_function(numer: &Polynomial, denom: &Polynomial, var: &Symbol) -> Option<Expr> {
+	let (quotient, mut rest) = numer.clone().div_rem(denom)?;
+	let mut denom = denom.clone();
+	let mut terms = vec![polynomial(&quotient, var)];
+
+	let repeated = denom.clone().gcd(denom.derivative());
+
+	if !rest.is_zero() && !repeated.is_constant() {
+		let squarefree = denom.clone() / &repeated;
+		let (p1, p2) = horowitz_ostrogradsky(&rest, &repeated, &squarefree)?;
+
+		terms.push(Expr::Poly(var.clone(), p1) / Expr::Poly(var.clone(), repeated));
+		(rest, denom) = (p2, squarefree);
+	}
+
+	if !rest.is_zero() {
+		terms.push(logarithmic(&rest, &denom, var)?);
+	}
+
+	Some(Expr::Add(terms))
+}
+
+/// Internal helper to replace every occurrence of a subexpression.
+fn replace(expr: &Expr, target: &Expr, value: &Expr) -> Expr {
+	if expr == target {
+		return value.clone();
+	}
+
+	match expr {
+		Expr::Add(exprs) => Expr::Add(exprs.iter().map(|expr| replace(expr, target, value)).collect()),
+		Expr::And(exprs) => Expr::And(exprs.iter().map(|expr| replace(expr, target, value)).collect()),
+		Expr::Fun(name, args) => Expr::Fun(
+			name.clone(),
+			args.iter().map(|arg| replace(arg, target, value)).collect(),
+		),
+		Expr::Matrix(matrix) => Expr::Matrix(matrix.clone().map(|entry| replace(&entry, target, value))),
+		Expr::Mul(exprs) => Expr::Mul(exprs.iter().map(|expr| replace(expr, target, value)).collect()),
+		Expr::Not(expr) => Expr::Not(replace(expr, target, value).into()),
+		Expr::Num(_) | Expr::Poly(_, _) => expr.clone(),
+		Expr::Or(exprs) => Expr::Or(exprs.iter().map(|expr| replace(expr, target, value)).collect()),
+		Expr::Piecewise(branches) => Expr::Piecewise(
+			branches
+				.iter()
+				.map(|(branch, condition)| (replace(branch, target, value), replace(condition, target, value)))
+				.collect(),
+		),
+		Expr::Pow(base, exp) => replace(base, target, value).pow(replace(exp, target, value)),
+		Expr::Rel(rel, lhs, rhs) => Expr::Rel(
+			*rel,
+			replace(lhs, target, value).into(),
+			replace(rhs, target, value).into(),
+		),
+	}
+}
+
+/// Internal helper to integrate with the substitution `t = u(x)`, which works if dividing the integrand by `u'(x)`
+/// leaves an expression that only depends on `u(x)`.
+fn substitution(expr: &Expr, var: &Symbol, depth: u32, ctx: &mut Context) -> Result<Option<Expr>, SimplifyError> {
+	let sym = Symbol::new(format!("_{depth}")).unwrap();
+
+	let mut inners = Vec::new();
+	candidates(expr, var, &mut inners);
+
+	for inner in inners {
+		let slope = inner.derivative(var).simplify(ctx)?;
+
+		if slope.is_num_and(Number::is_zero) {
+			continue;
+		}
+
+		let rest = replace(&divide(expr, &slope), &inner, &symbol(&sym)).simplify(ctx)?;
+
+		if rest.has_symbol(var) {
+			continue;
+		}
+
+		if let Some(anti) = antiderivative(&rest, &sym, depth + 1, ctx)? {
+			return Ok(Some(anti.substitute(&sym, &inner)));
+		}
+	}
+
+	Ok(None)
+}
+
+/// Internal helper to create an expression of just the given symbol.
+fn symbol(sym: &Symbol) -> Expr {
+	Expr::Poly(sym.clone(), Monomial::linear(1).into())
+}
+
+/// Internal helper to look up the antiderivative `F(u)` of an elementary function `f(u)`.
+fn table(name: &str, arg: &Expr) -> Option<Expr> {
+	let u = arg.clone();
+
+	let anti = match name {
+		"exp" => call("exp", vec![u]),
+		"ln" => u.clone() * call("ln", vec![u.clone()]) - u,
+		"sin" => -call("cos", vec![u]),
+		"cos" => call("sin", vec![u]),
+		"tan" => -call("ln", vec![call("cos", vec![u])]),
+		"atan" => {
+			let square = Expr::one() + u.clone().pow(Expr::Num(2.into()));
+			u.clone() * call("atan", vec![u]) - call("ln", vec![square]) / Expr::Num(2.into())
+		}
+		_ => return None,
+	};
+
+	Some(anti)
+}
@@ -0,0 +1,310 @@
+//! Combinatorial and special integer functions, computed exactly.
+//!
+//! All functions return [`None`] if an argument is outside of their domain, for example a negative or non-integer
+//! number, or an integer too large to be handled.
+
+use rug::Integer;
+
+use crate::number::Number;
+
+/// The largest argument of a factorial, double factorial, Fibonacci or Lucas number, whose results grow linearly in
+/// bits, that is evaluated.
+const MAX_ARGUMENT: u32 = 100_000;
+
+/// The largest index of a Bernoulli number, partition count or Stirling number that is evaluated, since the whole table
+/// up to it is built.
+const MAX_TABLE: u32 = 1000;
+
+/// Returns the Bernoulli number `B(n)`, using the convention `B(1) = -1/2`.
+///
+/// # Examples
+///
+/// ```
+/// use abacas::combinatorics::bernoulli;
+/// use abacas::number::Number;
+///
+/// assert_eq!(bernoulli(&1.into()), Some(Number::from(-1) / 2));
+/// assert_eq!(bernoulli(&12.into()), Some(Number::from(-691) / 2730));
+/// assert_eq!(bernoulli(&13.into()), Some(0.into()));
+/// ```
+pub fn bernoulli(n: &Number) -> Option<Number> {
+	let n = n.to_u32().filter(|&n| n <= MAX_TABLE)?;
+
+	if n > 1 && n % 2 == 1 {
+		return Some(Number::zero());
+	}
+
+	bernoulli_numbers(n).pop()
+}
+
+/// Internal method to calculate all Bernoulli numbers from `B(0)` up to `B(n)`.
+pub(crate) fn bernoulli_numbers(n: u32) -> Vec<Number> {
+	let mut numbers: Vec<Number> = Vec::with_capacity(n as usize + 1);
+
+	// Uses the recurrence `sum(binomial(m + 1, k) * B(k), k = 0..=m) = 0` for every `m > 0`
+	for m in 0..=n {
+		if m == 0 {
+			numbers.push(Number::one());
+			continue;
+		}
+
+		if m > 1 && m % 2 == 1 {
+			numbers.push(Number::zero());
+			continue;
+		}
+
+		let sum = numbers
+			.iter()
+			.enumerate()
+			.filter(|(_, number)| !number.is_zero())
+			.map(|(k, number)| Number::from_integer(Integer::binomial_u(m + 1, k as u32).into()) * number)
+			.fold(Number::zero(), |lhs, rhs| lhs + &rhs);
+
+		numbers.push(-sum / (m + 1));
+	}
+
+	numbers
+}
+
+/// Returns the binomial coefficient `n` choose `k` for any number `n` and non-negative integer `k`.
+///
+/// # Examples
+///
+/// ```
+/// use abacas::combinatorics::binomial;
+/// use abacas::number::Number;
+///
+/// assert_eq!(binomial(&5.into(), &2.into()), Some(10.into()));
+/// assert_eq!(binomial(&(-3).into(), &2.into()), Some(6.into()));
+/// assert_eq!(binomial(&(Number::from(1) / 2), &2.into()), Some(Number::from(-1) / 8));
+/// ```
+pub fn binomial(n: &Number, k: &Number) -> Option<Number> {
+	let k = k.to_u32()?;
+
+	if let Some(n) = n.to_integer() {
+		return Some(Number::from_integer(n.clone().binomial(k)));
+	}
+
+	// For non-integers, use the falling factorial `n * (n - 1) * ... * (n - k + 1) / k!`
+	let denom = factorial(&k.into())?;
+	let falling = (0..k).fold(Number::one(), |acc, i| acc * &(n.clone() - i));
+
+	Some(falling / &denom)
+}
+
+/// Returns the Catalan number `C(n) = binomial(2n, n) / (n + 1)`.
+///
+/// # Examples
+///
+/// ```
+/// use abacas::combinatorics::catalan;
+///
+/// assert_eq!(catalan(&0.into()), Some(1.into()));
+/// assert_eq!(catalan(&10.into()), Some(16796.into()));
+/// ```
+pub fn catalan(n: &Number) -> Option<Number> {
+	let n = n.to_u32()?;
+	let central = Integer::from(Integer::binomial_u(n.checked_mul(2)?, n));
+
+	Some(Number::from_integer(central) / (n + 1))
+}
+
+/// Returns the double factorial `n!! = n * (n - 2) * (n - 4) * ...` of a non-negative integer.
+///
+/// # Examples
+///
+/// ```
+/// use abacas::combinatorics::double_factorial;
+///
+/// assert_eq!(double_factorial(&0.into()), Some(1.into()));
+/// assert_eq!(double_factorial(&9.into()), Some(945.into()));
+/// assert_eq!(double_factorial(&10.into()), Some(3840.into()));
+/// ```
+pub fn double_factorial(n: &Number) -> Option<Number> {
+	Some(Number::from_integer(Integer::factorial_2(argument(n)?).into()))
+}
+
+/// Returns the factorial `n! = n * (n - 1) * ... * 1` of a non-negative integer.
+///
+/// # Examples
+///
+/// ```
+/// use abacas::combinatorics::factorial;
+///
+/// assert_eq!(factorial(&0.into()), Some(1.into()));
+/// assert_eq!(factorial(&10.into()), Some(3628800.into()));
+/// assert_eq!(factorial(&(-1).into()), None);
+/// ```
+pub fn factorial(n: &Number) -> Option<Number> {
+	Some(Number::from_integer(Integer::factorial(argument(n)?).into()))
+}
+
+/// Returns the `n`-th Fibonacci number, starting with `F(0) = 0` and `F(1) = 1`.
+///
+/// # Examples
+///
+/// ```
+/// use abacas::combinatorics::fibonacci;
+///
+/// assert_eq!(fibonacci(&0.into()), Some(0.into()));
+/// assert_eq!(fibonacci(&50.into()), Some(12586269025u64.into()));
+/// ```
+pub fn fibonacci(n: &Number) -> Option<Number> {
+	Some(Number::from_integer(Integer::fibonacci(argument(n)?).into()))
+}
+
+/// Returns the `n`-th Lucas number, starting with `L(0) = 2` and `L(1) = 1`.
+///
+/// # Examples
+///
+/// ```
+/// use abacas::combinatorics::lucas;
+///
+/// assert_eq!(lucas(&0.into()), Some(2.into()));
+/// assert_eq!(lucas(&10.into()), Some(123.into()));
+/// ```
+pub fn lucas(n: &Number) -> Option<Number> {
+	Some(Number::from_integer(Integer::lucas(argument(n)?).into()))
+}
+
+/// Returns the multinomial coefficient `(k1 + k2 + ...)! / (k1! * k2! * ...)`.
+///
+/// # Examples
+///
+/// ```
+/// use abacas::combinatorics::multinomial;
+///
+/// assert_eq!(multinomial(&[2.into(), 3.into(), 4.into()]), Some(1260.into()));
+/// assert_eq!(multinomial(&[]), Some(1.into()));
+/// ```
+pub fn multinomial(ks: &[Number]) -> Option<Number> {
+	let mut total = 0u32;
+	let mut result = Integer::from(1);
+
+	// Build the coefficient as a product of binomials `binomial(k1 + ... + ki, ki)`
+	for k in ks {
+		let k = k.to_u32()?;
+
+		total = total.checked_add(k)?;
+		result *= Integer::from(Integer::binomial_u(total, k));
+	}
+
+	Some(Number::from_integer(result))
+}
+
+/// Returns the number of integer partitions `p(n)` of a non-negative integer.
+///
+/// # Examples
+///
+/// ```
+/// use abacas::combinatorics::partitions;
+///
+/// assert_eq!(partitions(&0.into()), Some(1.into()));
+/// assert_eq!(partitions(&100.into()), Some(190569292.into()));
+/// ```
+pub fn partitions(n: &Number) -> Option<Number> {
+	let n = n.to_u32().filter(|&n| n <= MAX_TABLE)? as usize;
+	let mut table: Vec<Integer> = Vec::with_capacity(n + 1);
+
+	// Uses Euler's pentagonal number theorem for the recurrence
+	for m in 0..=n {
+		if m == 0 {
+			table.push(Integer::from(1));
+			continue;
+		}
+
+		let mut sum = Integer::new();
+
+		for k in 1.. {
+			let first = k * (3 * k - 1) / 2;
+
+			if first > m {
+				break;
+			}
+
+			let second = k * (3 * k + 1) / 2;
+			let mut term = table[m - first].clone();
+
+			if second <= m {
+				term += &table[m - second];
+			}
+
+			if k % 2 == 1 {
+				sum += term;
+			} else {
+				sum -= term;
+			}
+		}
+
+		table.push(sum);
+	}
+
+	table.pop().map(Number::from_integer)
+}
+
+/// Returns the signed Stirling number of the first kind `s(n, k)`.
+///
+/// # Examples
+///
+/// ```
+/// use abacas::combinatorics::stirling1;
+///
+/// assert_eq!(stirling1(&5.into(), &2.into()), Some((-50).into()));
+/// assert_eq!(stirling1(&5.into(), &6.into()), Some(0.into()));
+/// ```
+pub fn stirling1(n: &Number, k: &Number) -> Option<Number> {
+	let (n, k) = (n.to_u32()?, k.to_u32()?);
+
+	// Uses the recurrence `s(m + 1, j) = s(m, j - 1) - m * s(m, j)`
+	stirling(n, k, |m, _| -Integer::from(m))
+}
+
+/// Returns the Stirling number of the second kind `S(n, k)`.
+///
+/// # Examples
+///
+/// ```
+/// use abacas::combinatorics::stirling2;
+///
+/// assert_eq!(stirling2(&5.into(), &2.into()), Some(15.into()));
+/// assert_eq!(stirling2(&0.into(), &0.into()), Some(1.into()));
+/// ```
+pub fn stirling2(n: &Number, k: &Number) -> Option<Number> {
+	let (n, k) = (n.to_u32()?, k.to_u32()?);
+
+	// Uses the recurrence `S(m + 1, j) = S(m, j - 1) + j * S(m, j)`
+	stirling(n, k, |_, j| Integer::from(j))
+}
+
+/// Internal method to calculate Stirling numbers with the recurrence `a(m + 1, j) = a(m, j - 1) + f(m, j) * a(m, j)`.
+fn stirling(n: u32, k: u32, factor: impl Fn(u32, u32) -> Integer) -> Option<Number> {
+	if k > n {
+		return Some(Number::zero());
+	}
+
+	if n > MAX_TABLE {
+		return None;
+	}
+
+	let mut row = vec![Integer::new(); k as usize + 1];
+	row[0] = Integer::from(1);
+
+	for m in 0..n {
+		for j in (0..=k.min(m + 1)).rev() {
+			let mut next = factor(m, j) * &row[j as usize];
+
+			if j > 0 {
+				next += &row[j as usize - 1];
+			}
+
+			row[j as usize] = next;
+		}
+	}
+
+	row.pop().map(Number::from_integer)
+}
+
+/// Internal helper to get the argument of a function whose result grows linearly in bits, if it is small enough.
+fn argument(n: &Number) -> Option<u32> {
+	n.to_u32().filter(|&n| n <= MAX_ARGUMENT)
+}
@@ -2,8 +2,14 @@
 
 use std::collections::HashMap;
 
+use crate::assumption::{Assumption, Assumptions};
+use crate::error::SimplifyError;
 use crate::expr::{Expr, Symbol};
 use crate::function::Function;
+use crate::pattern::RuleSet;
+
+/// An opt-in simplification pass like [`Expr::trigsimp`], which can be enabled on a context.
+pub type Pass = fn(&Expr, &mut Context) -> Result<Expr, SimplifyError>;
 
 /// Context struct owns and manages the core "global" data.
 #[derive(Clone, Debug, Default)]
@@ -12,6 +18,12 @@ pub struct Context {
 	pub variables: HashMap<Symbol, Expr>,
 	/// Functions declared in this context
 	pub functions: HashMap<Symbol, Function>,
+	/// Assumptions made about symbols in this context.
+	pub assumptions: HashMap<Symbol, Assumptions>,
+	/// Passes applied to expressions after simplifying them, in order.
+	pub passes: Vec<Pass>,
+	/// Rule sets applied to expressions after simplifying them and applying the passes, in order.
+	pub rules: Vec<RuleSet>,
 }
 
 impl Context {
@@ -19,4 +31,32 @@ impl Context {
 	pub fn new() -> Self {
 		Self::default()
 	}
+
+	/// Adds an assumption about a symbol, keeping the previous ones. Returns `false` and keeps the previous assumptions
+	/// if the new one contradicts them.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::assumption::Assumption;
+	/// use abacas::context::Context;
+	/// use abacas::expr::Symbol;
+	///
+	/// let mut ctx = Context::new();
+	/// let x = Symbol::new("x").unwrap();
+	///
+	/// assert!(ctx.assume(x.clone(), Assumption::Positive));
+	/// assert!(!ctx.assume(x, Assumption::Nonpositive));
+	/// ```
+	pub fn assume(&mut self, sym: Symbol, assumption: Assumption) -> bool {
+		let assumptions = self.assumptions.get(&sym).copied().unwrap_or_default();
+
+		match assumptions.with(assumption) {
+			Some(assumptions) => {
+				self.assumptions.insert(sym, assumptions);
+				true
+			}
+			None => false,
+		}
+	}
 }
@@ -11,6 +11,14 @@ pub enum ParseError {
 	InvalidNumber(Number),
 	/// The parser encountered an invalid string.
 	InvalidString(String),
+	/// The parser encountered an invalid term of a polynomial at the given byte index.
+	InvalidTerm(String, usize),
+	/// The parser encountered an unexpected character at the given byte index.
+	UnexpectedChar(char, usize),
+	/// The parser reached the end of the input while expecting more at the given byte index.
+	UnexpectedEnd(usize),
+	/// The parser encountered a fraction whose denominator at the given byte index is zero.
+	ZeroDenominator(usize),
 }
 
 impl fmt::Display for ParseError {
@@ -18,6 +26,10 @@ impl fmt::Display for ParseError {
 		match self {
 			Self::InvalidNumber(number) => write!(f, "invalid number: {number}"),
 			Self::InvalidString(string) => write!(f, "invalid string: {string}"),
+			Self::InvalidTerm(term, index) => write!(f, "invalid term '{term}' at index {index}"),
+			Self::UnexpectedChar(char, index) => write!(f, "unexpected character '{char}' at index {index}"),
+			Self::UnexpectedEnd(index) => write!(f, "unexpected end of input at index {index}"),
+			Self::ZeroDenominator(index) => write!(f, "zero denominator at index {index}"),
 		}
 	}
 }
@@ -27,6 +39,8 @@ impl error::Error for ParseError {}
 /// An error that can occur while simplifying an expression.
 #[derive(Debug)]
 pub enum SimplifyError {
+	/// The expression combined matrices, or matrices and scalars, whose dimensions do not fit.
+	DimensionMismatch,
 	/// The expression tried to divide by zero.
 	DivisionByZero,
 }
@@ -34,6 +48,7 @@ pub enum SimplifyError {
 impl fmt::Display for SimplifyError {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 		match self {
+			Self::DimensionMismatch => write!(f, "dimension mismatch"),
 			Self::DivisionByZero => write!(f, "division by zero"),
 		}
 	}
@@ -1,6 +1,7 @@
 //! The expression structure and its related items.
 
 use std::cmp::Ordering;
+use std::collections::HashMap;
 use std::fmt;
 use std::ops::{Add, Div, Mul, Neg, Sub};
 
@@ -9,8 +10,18 @@ use rug::ops::Pow;
 
 use crate::context::Context;
 use crate::error::SimplifyError;
+use crate::interval::Interval;
+use crate::matrix::Matrix;
+use crate::monomial::Monomial;
 use crate::number::Number;
 use crate::polynomial::Polynomial;
+use crate::relation::Relation;
+
+/// The maximum amount of bits of a numeric power that is evaluated, larger powers are kept unevaluated.
+const MAX_POWER_BITS: u64 = 1 << 20;
+
+/// The maximum span between the highest and lowest degree of an integer power of a polynomial that is expanded.
+const MAX_EXPANDED_DEGREE: u32 = 1000;
 
 /// Represents a symbol like `cos` or `pi`.
 #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
@@ -40,24 +51,49 @@ impl fmt::Display for Symbol {
 }
 
 /// Represents a general expression.
+///
+/// The [`Display`](fmt::Display) output uses the input syntax of the command line, so parsing the output of a
+/// simplified expression and simplifying it again gives back the same expression. Powers of symbols with fractional degrees are
+/// only read back as polynomials if the symbols are assumed to be nonnegative.
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub enum Expr {
 	/// Represents the sum of multiple expressions.
 	Add(Vec<Self>),
+	/// Represents the conjunction of multiple relations, which is `true` if empty.
+	And(Vec<Self>),
 	/// Represents a function call.
 	Fun(Symbol, Vec<Self>),
+	/// Represents a matrix of expressions.
+	Matrix(Matrix<Self>),
 	/// Represents the product of multiple expressions.
 	Mul(Vec<Self>),
+	/// Represents the negation of a relation.
+	Not(Box<Self>),
 	/// Represents a constant number.
 	Num(Number),
+	/// Represents the disjunction of multiple relations, which is `false` if empty.
+	Or(Vec<Self>),
+	/// Represents a piecewise expression as pairs of values and conditions, which takes the value of the first branch
+	/// whose condition holds.
+	Piecewise(Vec<(Self, Self)>),
 	/// Represents a polynomial.
 	Poly(Symbol, Polynomial),
 	/// Represents the power of two expressions.
 	Pow(Box<Self>, Box<Self>),
+	/// Represents a comparison of two expressions.
+	Rel(Relation, Box<Self>, Box<Self>),
 }
 
 // Constants
 impl Expr {
+	/// The boolean constant `true` as an empty conjunction, or `false` as an empty disjunction.
+	pub const fn boolean(value: bool) -> Self {
+		match value {
+			true => Self::And(Vec::new()),
+			false => Self::Or(Vec::new()),
+		}
+	}
+
 	/// The number negative one (`-1`).
 	pub fn neg_one() -> Self {
 		Self::Num(Number::neg_one())
@@ -76,6 +112,33 @@ impl Expr {
 
 // Guards
 impl Expr {
+	/// Whether this expression contains the given symbol anywhere.
+	pub fn has_symbol(&self, sym: &Symbol) -> bool {
+		match self {
+			Self::Add(exprs) | Self::And(exprs) | Self::Fun(_, exprs) | Self::Mul(exprs) | Self::Or(exprs) => {
+				exprs.iter().any(|expr| expr.has_symbol(sym))
+			}
+			Self::Matrix(matrix) => matrix.entries().iter().any(|expr| expr.has_symbol(sym)),
+			Self::Not(expr) => expr.has_symbol(sym),
+			Self::Num(_) => false,
+			Self::Piecewise(branches) => branches
+				.iter()
+				.any(|(value, condition)| value.has_symbol(sym) || condition.has_symbol(sym)),
+			Self::Poly(other, _) => other == sym,
+			Self::Pow(lhs, rhs) | Self::Rel(_, lhs, rhs) => lhs.has_symbol(sym) || rhs.has_symbol(sym),
+		}
+	}
+
+	/// Whether this is a relation, a boolean connective or a boolean constant.
+	pub const fn is_logical(&self) -> bool {
+		matches!(self, Self::And(_) | Self::Not(_) | Self::Or(_) | Self::Rel(_, _, _))
+	}
+
+	/// Whether this is a matrix.
+	pub const fn is_matrix(&self) -> bool {
+		matches!(self, Self::Matrix(_))
+	}
+
 	/// Whether this is a constant number.
 	pub const fn is_num(&self) -> bool {
 		matches!(self, Self::Num(_))
@@ -95,10 +158,213 @@ impl Expr {
 	pub fn is_poly_and(&self, predicate: impl FnOnce(&Symbol, &Polynomial) -> bool) -> bool {
 		matches!(self, Self::Poly(sym, poly) if predicate(sym, poly))
 	}
+
+	/// Internal method to check whether this expression needs parentheses as the base of a power, which is the case
+	/// unless it is a single symbol, a non-negative integer, a function call or a matrix.
+	pub(crate) fn needs_base_parens(&self) -> bool {
+		match self {
+			Self::Fun(_, _) | Self::Matrix(_) => false,
+			Self::Num(num) => !num.is_integer() || num.is_negative(),
+			Self::Poly(_, poly) => poly.monomials().as_slice() != [Monomial::linear(1)],
+			_ => true,
+		}
+	}
+
+	/// Internal method to check whether this expression needs parentheses as an exponent, which is the case unless it
+	/// is a number written without a fraction bar or needs none as a base.
+	pub(crate) fn needs_exp_parens(&self) -> bool {
+		match self {
+			Self::Num(num) => num.decimal_places().is_none(),
+			_ => self.needs_base_parens(),
+		}
+	}
+
+	/// Internal method to check whether this expression needs parentheses as part of a larger expression.
+	pub(crate) fn needs_parens(&self) -> bool {
+		match self {
+			// If the expression has more than one term, use parentheses
+			Self::Add(exprs) | Self::Mul(exprs) => exprs.len() > 1,
+			Self::Poly(_, poly) => poly.monomials().len() > 1,
+
+			// Relations and non-constant connectives always use parentheses
+			Self::And(exprs) | Self::Or(exprs) => !exprs.is_empty(),
+			Self::Not(_) | Self::Rel(_, _, _) => true,
+
+			_ => false,
+		}
+	}
 }
 
 // Operations
 impl Expr {
+	/// Returns the value of this expression if it is a boolean constant, see [`Self::boolean`].
+	pub const fn as_bool(&self) -> Option<bool> {
+		match self {
+			Self::And(exprs) if exprs.is_empty() => Some(true),
+			Self::Or(exprs) if exprs.is_empty() => Some(false),
+			_ => None,
+		}
+	}
+
+	/// Applies a rewrite to every subexpression, starting with the innermost ones. Each subexpression is simplified
+	/// before it is rewritten.
+	pub(crate) fn bottom_up(
+		self,
+		ctx: &mut Context,
+		rewrite: &mut impl FnMut(Self, &mut Context) -> Result<Self, SimplifyError>,
+	) -> Result<Self, SimplifyError> {
+		let mut each = |exprs: Vec<Self>, ctx: &mut Context| -> Result<Vec<Self>, SimplifyError> {
+			exprs.into_iter().map(|expr| expr.bottom_up(ctx, rewrite)).collect()
+		};
+
+		let expr = match self {
+			Self::Add(terms) => Self::Add(each(terms, ctx)?),
+			Self::And(exprs) => Self::And(each(exprs, ctx)?),
+			Self::Fun(name, args) => Self::Fun(name, each(args, ctx)?),
+			Self::Matrix(matrix) => {
+				let (rows, cols) = (matrix.rows(), matrix.cols());
+				Self::Matrix(Matrix::new(rows, cols, each(matrix.into_entries(), ctx)?).unwrap())
+			}
+			Self::Mul(factors) => Self::Mul(each(factors, ctx)?),
+			Self::Not(expr) => Self::Not(expr.bottom_up(ctx, rewrite)?.into()),
+			Self::Num(_) | Self::Poly(_, _) => self,
+			Self::Or(exprs) => Self::Or(each(exprs, ctx)?),
+			Self::Piecewise(branches) => {
+				let (values, conditions) = branches.into_iter().unzip();
+				Self::Piecewise(each(values, ctx)?.into_iter().zip(each(conditions, ctx)?).collect())
+			}
+			Self::Pow(base, exp) => {
+				let [base, exp] = each(vec![*base, *exp], ctx)?.try_into().unwrap();
+				Self::Pow(base.into(), exp.into())
+			}
+			Self::Rel(rel, lhs, rhs) => {
+				let [lhs, rhs] = each(vec![*lhs, *rhs], ctx)?.try_into().unwrap();
+				Self::Rel(rel, lhs.into(), rhs.into())
+			}
+		};
+
+		rewrite(expr.simplify_builtin(ctx)?, ctx)
+	}
+
+	/// Evaluates this expression to an interval guaranteed to contain its exact value, using the given precision in
+	/// bits for elementary functions. Symbols are looked up in `vars`, falling back to the constants `pi` and `e`.
+	/// Returns [`None`] if the expression contains unknown symbols or functions, or leaves their domain.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use std::collections::HashMap;
+	///
+	/// use abacas::expr::{Expr, Symbol};
+	/// use abacas::interval::Interval;
+	/// use abacas::number::Number;
+	///
+	/// let pi = Expr::Poly(Symbol::new("pi").unwrap(), "x".parse().unwrap());
+	/// let expr = Expr::Fun(Symbol::new("cos").unwrap(), vec![pi]);
+	///
+	/// let value = expr.eval_interval(&HashMap::new(), 64).unwrap();
+	///
+	/// assert!(value.contains(&(-1).into()));
+	/// assert!(value.width() < Number::from(1) / 1_000_000_000);
+	/// ```
+	pub fn eval_interval(&self, vars: &HashMap<Symbol, Interval>, prec: u32) -> Option<Interval> {
+		match self {
+			Self::Add(exprs) => exprs.iter().try_fold(Interval::point(Number::zero()), |acc, expr| {
+				Some(acc + expr.eval_interval(vars, prec)?)
+			}),
+			Self::Fun(name, args) => {
+				let args: Vec<_> = args
+					.iter()
+					.map(|arg| arg.eval_interval(vars, prec))
+					.collect::<Option<_>>()?;
+
+				match (name.name(), args.as_slice()) {
+					("abs", [x]) => Some(x.abs()),
+					("atan", [x]) => x.atan(prec),
+					("cos", [x]) => x.cos(prec),
+					("exp", [x]) => x.exp(prec),
+					("ln", [x]) => x.ln(prec),
+					("sin", [x]) => x.sin(prec),
+					("sqrt", [x]) => x.sqrt(prec),
+					("tan", [x]) => x.tan(prec),
+					_ => None,
+				}
+			}
+			Self::Mul(exprs) => exprs.iter().try_fold(Interval::point(Number::one()), |acc, expr| {
+				Some(acc * expr.eval_interval(vars, prec)?)
+			}),
+			Self::Num(num) => Some(Interval::point(num.clone())),
+			Self::Poly(sym, poly) => {
+				let x = match (vars.get(sym), sym.name()) {
+					(Some(x), _) => x.clone(),
+					(None, "pi") => Interval::pi(prec),
+					(None, "e") => Interval::e(prec),
+					(None, _) => return None,
+				};
+
+				poly.eval_interval(&x, prec)
+			}
+			Self::Pow(base, exp) => {
+				let base = base.eval_interval(vars, prec)?;
+				let exp = exp.eval_interval(vars, prec)?;
+
+				// Powers with inexact exponents are only defined for positive bases
+				if exp.is_point() {
+					base.pow(exp.lo(), prec)
+				} else {
+					(base.ln(prec)? * exp).exp(prec)
+				}
+			}
+			// Any of the branches may apply
+			Self::Piecewise(branches) => branches
+				.iter()
+				.map(|(value, _)| value.eval_interval(vars, prec))
+				.reduce(|acc, value| Some(acc?.hull(&value?)))?,
+			Self::And(_) | Self::Matrix(_) | Self::Not(_) | Self::Or(_) | Self::Rel(_, _, _) => None,
+		}
+	}
+
+	/// Determines the sign of this expression with interval arithmetic, increasing the precision until the sign is
+	/// certain. Symbols are looked up like in [`Self::eval_interval`]. Returns [`None`] if the expression cannot be
+	/// evaluated or the sign is still unknown at the highest precision, for example because the value is zero.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use std::cmp::Ordering;
+	/// use std::collections::HashMap;
+	///
+	/// use abacas::expr::{Expr, Symbol};
+	/// use abacas::number::Number;
+	///
+	/// // The famous approximation 355/113 is slightly larger than pi
+	/// let pi = Expr::Poly(Symbol::new("pi").unwrap(), "x".parse().unwrap());
+	/// let expr = pi - Expr::Num(Number::from(355) / 113);
+	///
+	/// assert_eq!(expr.eval_sign(&HashMap::new()), Some(Ordering::Less));
+	/// ```
+	pub fn eval_sign(&self, vars: &HashMap<Symbol, Interval>) -> Option<Ordering> {
+		let mut prec = 64;
+
+		while prec <= 4096 {
+			if let Some(sign) = self.eval_interval(vars, prec)?.sign() {
+				return Some(sign);
+			}
+
+			prec *= 2;
+		}
+
+		None
+	}
+
+	/// Returns the inner value if this expression is [`Self::Matrix`], otherwise returns [`None`].
+	pub fn into_matrix(self) -> Option<Matrix<Self>> {
+		match self {
+			Self::Matrix(matrix) => Some(matrix),
+			_ => None,
+		}
+	}
+
 	/// Returns the inner value if this expression is [`Self::Num`], otherwise returns [`None`].
 	pub fn into_num(self) -> Option<Number> {
 		match self {
@@ -115,30 +381,89 @@ impl Expr {
 		}
 	}
 
-	/// Simplifies this expression on a best-effort basis.
-	pub fn simplify(self, ctx: &mut Context) -> Result<Self, SimplifyError> {
+	/// Substitutes every occurrence of a symbol with the given value, without simplifying the result.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::context::Context;
+	/// use abacas::expr::{Expr, Symbol};
+	///
+	/// let x = Symbol::new("x").unwrap();
+	/// let expr = Expr::Poly(x.clone(), "x^2 + 1".parse().unwrap());
+	///
+	/// let result = expr.substitute(&x, &Expr::Num(3.into()));
+	/// assert_eq!(result.simplify(&mut Context::new()).unwrap(), Expr::Num(10.into()));
+	/// ```
+	pub fn substitute(&self, sym: &Symbol, value: &Self) -> Self {
 		match self {
-			Self::Add(exprs) => Self::simplify_add(exprs, ctx),
-			Self::Fun(name, args) => Self::simplify_fun(name, args, ctx),
-			Self::Mul(exprs) => Self::simplify_mul(exprs, ctx),
-			Self::Num(_) => Ok(self),
-			Self::Poly(sym, poly) => Self::simplify_poly(sym, poly, ctx),
-			Self::Pow(base, exp) => Self::simplify_pow(base, exp, ctx),
+			Self::Add(exprs) => Self::Add(exprs.iter().map(|expr| expr.substitute(sym, value)).collect()),
+			Self::And(exprs) => Self::And(exprs.iter().map(|expr| expr.substitute(sym, value)).collect()),
+			Self::Fun(name, args) => Self::Fun(
+				name.clone(),
+				args.iter().map(|arg| arg.substitute(sym, value)).collect(),
+			),
+			Self::Matrix(matrix) => Self::Matrix(matrix.clone().map(|expr| expr.substitute(sym, value))),
+			Self::Mul(exprs) => Self::Mul(exprs.iter().map(|expr| expr.substitute(sym, value)).collect()),
+			Self::Not(expr) => Self::Not(expr.substitute(sym, value).into()),
+			Self::Num(_) => self.clone(),
+			Self::Or(exprs) => Self::Or(exprs.iter().map(|expr| expr.substitute(sym, value)).collect()),
+			Self::Piecewise(branches) => Self::Piecewise(
+				branches
+					.iter()
+					.map(|(branch, condition)| (branch.substitute(sym, value), condition.substitute(sym, value)))
+					.collect(),
+			),
+			Self::Poly(other, poly) if other == sym => {
+				let terms = poly
+					.monomials()
+					.map(|mono| Self::Num(mono.coeff.clone()) * value.clone().pow(Self::Num(mono.degree.clone())));
+
+				Self::Add(terms.collect())
+			}
+			Self::Poly(_, _) => self.clone(),
+			Self::Pow(base, exp) => base.substitute(sym, value).pow(exp.substitute(sym, value)),
+			Self::Rel(rel, lhs, rhs) => Self::Rel(
+				*rel,
+				lhs.substitute(sym, value).into(),
+				rhs.substitute(sym, value).into(),
+			),
 		}
 	}
 
+	/// Simplifies this expression on a best-effort basis, then applies the passes and rule sets enabled on the context.
+	pub fn simplify(self, ctx: &mut Context) -> Result<Self, SimplifyError> {
+		let mut expr = self.simplify_builtin(ctx)?;
+
+		for pass in ctx.passes.clone() {
+			expr = pass(&expr, ctx)?;
+		}
+
+		if ctx.rules.is_empty() {
+			return Ok(expr);
+		}
+
+		let rules = ctx.rules.iter().flat_map(|set| set.rules.iter().cloned()).collect_vec();
+		expr.replace_repeated(&rules, ctx)
+	}
+
 	/// Simplifies a [`Self::Add`] expression.
 	fn simplify_add(mut exprs: Vec<Self>, ctx: &mut Context) -> Result<Self, SimplifyError> {
 		// Simplify all elements individually and flatten inner sums
 		exprs = exprs
 			.into_iter()
-			.map(|expr| match expr.simplify(ctx)? {
+			.map(|expr| match expr.simplify_builtin(ctx)? {
 				Self::Add(exprs) => Ok(exprs),
 				expr => Ok(vec![expr]),
 			})
 			.flatten_ok()
 			.try_collect()?;
 
+		// Matrices are added entry by entry, which needs every term to be a matrix of the same size
+		if exprs.iter().any(Self::is_matrix) {
+			return Self::add_matrices(exprs, ctx);
+		}
+
 		// Add all polynomials into one per symbol
 		let mut polys = exprs
 			.extract_if(.., |expr| expr.is_poly())
@@ -168,7 +493,7 @@ impl Expr {
 		// Convert into iterator of products and chain extracted number and polynomials
 		let iter = counts
 			.into_iter()
-			.map(|(expr, count)| (expr * Self::Num(count.into())).simplify(ctx))
+			.map(|(expr, count)| (expr * Self::Num(count.into())).simplify_builtin(ctx))
 			.chain(num.into_iter().map(|num| Ok(Self::Num(num))))
 			.chain(polys.into_iter().map(|(sym, poly)| Ok(Self::Poly(sym, poly))));
 
@@ -185,10 +510,70 @@ impl Expr {
 		Ok(Self::Add(result))
 	}
 
+	/// Simplifies this expression on a best-effort basis, without applying the rule sets registered on the context.
+	pub(crate) fn simplify_builtin(self, ctx: &mut Context) -> Result<Self, SimplifyError> {
+		match self {
+			Self::Add(exprs) => Self::simplify_add(exprs, ctx),
+			Self::And(exprs) => Self::simplify_connective(exprs, true, ctx),
+			Self::Fun(name, args) => Self::simplify_fun(name, args, ctx),
+			Self::Matrix(matrix) => Ok(Self::Matrix(matrix.simplify(ctx)?)),
+			Self::Mul(exprs) => Self::simplify_mul(exprs, ctx),
+			Self::Not(expr) => Self::simplify_not(*expr, ctx),
+			Self::Num(_) => Ok(self),
+			Self::Or(exprs) => Self::simplify_connective(exprs, false, ctx),
+			Self::Piecewise(branches) => Self::simplify_piecewise(branches, ctx),
+			Self::Poly(sym, poly) => Self::simplify_poly(sym, poly, ctx),
+			Self::Pow(base, exp) => Self::simplify_pow(base, exp, ctx),
+			Self::Rel(rel, lhs, rhs) => Self::simplify_rel(rel, *lhs, *rhs, ctx),
+		}
+	}
+
+	/// Simplifies a [`Self::And`] expression if `conjunction` is true, otherwise a [`Self::Or`] expression.
+	fn simplify_connective(mut exprs: Vec<Self>, conjunction: bool, ctx: &mut Context) -> Result<Self, SimplifyError> {
+		// Simplify all elements individually and flatten inner connectives of the same kind
+		exprs = exprs
+			.into_iter()
+			.map(|expr| match expr.simplify_builtin(ctx)? {
+				Self::And(exprs) if conjunction && !exprs.is_empty() => Ok(exprs),
+				Self::Or(exprs) if !conjunction && !exprs.is_empty() => Ok(exprs),
+				expr => Ok(vec![expr]),
+			})
+			.flatten_ok()
+			.try_collect()?;
+
+		// A single absorbing constant decides the result, while neutral constants are dropped
+		if exprs.iter().any(|expr| expr.as_bool() == Some(!conjunction)) {
+			return Ok(Self::boolean(!conjunction));
+		}
+
+		exprs.retain(|expr| expr.as_bool().is_none());
+
+		// Remove duplicates and sort the remaining elements
+		exprs.sort_by(Self::cmp);
+		exprs.dedup();
+
+		match (exprs.len(), conjunction) {
+			(1, _) => Ok(exprs.pop().unwrap()),
+			(_, true) => Ok(Self::And(exprs)),
+			(_, false) => Ok(Self::Or(exprs)),
+		}
+	}
+
 	/// Simplifies a [`Self::Fun`] expression.
 	fn simplify_fun(name: Symbol, mut args: Vec<Self>, ctx: &mut Context) -> Result<Self, SimplifyError> {
 		// Simplify the inner arguments
-		args = args.into_iter().map(|arg| Self::simplify(arg, ctx)).try_collect()?;
+		args = args
+			.into_iter()
+			.map(|arg| Self::simplify_builtin(arg, ctx))
+			.try_collect()?;
+
+		// Cancel exponentials and logarithms, which are inverse to each other
+		if let [Self::Fun(inner, inner_args)] = args.as_slice()
+			&& let [arg] = inner_args.as_slice()
+			&& matches!((name.name(), inner.name()), ("exp", "ln") | ("ln", "exp"))
+		{
+			return Ok(arg.clone());
+		}
 
 		// Return the result as a new function call
 		Ok(Self::Fun(name, args))
@@ -199,13 +584,26 @@ impl Expr {
 		// Simplify all elements individually and flatten inner products
 		exprs = exprs
 			.into_iter()
-			.map(|expr| match expr.simplify(ctx)? {
+			.map(|expr| match expr.simplify_builtin(ctx)? {
 				Self::Mul(exprs) => Ok(exprs),
 				expr => Ok(vec![expr]),
 			})
 			.flatten_ok()
 			.try_collect()?;
 
+		// Matrices are multiplied in their original order, as their product does not commute
+		let matrices: Vec<_> = exprs
+			.extract_if(.., |expr| expr.is_matrix())
+			.map(|expr| expr.into_matrix().unwrap())
+			.collect();
+
+		if !matrices.is_empty() {
+			return Self::mul_matrices(matrices, exprs, ctx);
+		}
+
+		// Cancel quotients of factorials, for example `n! / (n - 1)! = n`
+		Self::cancel_factorials(&mut exprs, ctx)?;
+
 		// Multiply all polynomials into one per symbol
 		let mut polys = exprs
 			.extract_if(.., |expr| expr.is_poly())
@@ -234,13 +632,33 @@ impl Expr {
 			num.take().into_iter().for_each(|num| *poly *= &num);
 		}
 
-		// For every other expression, count how often it appears
-		let counts = exprs.into_iter().counts();
+		// Group every other expression by its base, adding up the exponents
+		let groups = exprs
+			.into_iter()
+			.map(|expr| match expr {
+				Self::Pow(base, exp) => (*base, *exp),
+				expr => (expr, Self::one()),
+			})
+			.into_group_map();
 
-		// Convert into iterator of powers and chain extracted number and polynomials
-		let iter = counts
+		let powers: Vec<_> = groups
+			.into_iter()
+			.map(|(base, exps)| base.pow(Self::Add(exps)).simplify_builtin(ctx))
+			.try_collect()?;
+
+		// If merged powers became numbers or polynomials, multiply them with the others again
+		if powers.iter().any(|expr| expr.is_num() || expr.is_poly()) {
+			let others = num
+				.into_iter()
+				.map(Self::Num)
+				.chain(polys.into_iter().map(|(sym, poly)| Self::Poly(sym, poly)));
+			return Self::Mul(powers.into_iter().chain(others).collect()).simplify_builtin(ctx);
+		}
+
+		// Chain the powers with the extracted number and polynomials
+		let iter = powers
 			.into_iter()
-			.map(|(expr, count)| expr.pow(Self::Num(count.into())).simplify(ctx))
+			.map(Ok)
 			.chain(num.into_iter().map(|num| Ok(Self::Num(num))))
 			.chain(polys.into_iter().map(|(sym, poly)| Ok(Self::Poly(sym, poly))));
 
@@ -257,6 +675,60 @@ impl Expr {
 		Ok(Self::Mul(result))
 	}
 
+	/// Simplifies a [`Self::Not`] expression.
+	fn simplify_not(expr: Self, ctx: &mut Context) -> Result<Self, SimplifyError> {
+		match expr.simplify_builtin(ctx)? {
+			// Negate relations and apply De Morgan's laws to connectives
+			Self::And(exprs) => {
+				Self::Or(exprs.into_iter().map(|expr| Self::Not(expr.into())).collect()).simplify_builtin(ctx)
+			}
+			Self::Or(exprs) => {
+				Self::And(exprs.into_iter().map(|expr| Self::Not(expr.into())).collect()).simplify_builtin(ctx)
+			}
+			Self::Not(expr) => Ok(*expr),
+			Self::Rel(rel, lhs, rhs) => Ok(Self::Rel(rel.negate(), lhs, rhs)),
+			expr => Ok(Self::Not(expr.into())),
+		}
+	}
+
+	/// Simplifies a [`Self::Piecewise`] expression.
+	fn simplify_piecewise(branches: Vec<(Self, Self)>, ctx: &mut Context) -> Result<Self, SimplifyError> {
+		let mut result: Vec<(Self, Self)> = Vec::new();
+
+		for (value, condition) in branches {
+			let condition = condition.simplify_builtin(ctx)?;
+
+			// Drop branches that never apply
+			if condition.as_bool() == Some(false) {
+				continue;
+			}
+
+			let value = value.simplify_builtin(ctx)?;
+
+			// Merge consecutive branches with the same value
+			match result.last_mut() {
+				Some((last, last_condition)) if *last == value => {
+					*last_condition = Self::Or(vec![last_condition.clone(), condition]).simplify_builtin(ctx)?;
+				}
+				_ => result.push((value, condition)),
+			}
+
+			// Later branches are never reached after one that always applies
+			if result
+				.last()
+				.is_some_and(|(_, condition)| condition.as_bool() == Some(true))
+			{
+				break;
+			}
+		}
+
+		// If the first branch always applies, return its value
+		match result.first() {
+			Some((value, condition)) if condition.as_bool() == Some(true) => Ok(value.clone()),
+			_ => Ok(Self::Piecewise(result)),
+		}
+	}
+
 	/// Simplifies a [`Self::Poly`] expression.
 	fn simplify_poly(sym: Symbol, poly: Polynomial, ctx: &mut Context) -> Result<Self, SimplifyError> {
 		// If the polynomial is constant, return it as a number
@@ -276,8 +748,13 @@ impl Expr {
 	/// Simplifies a [`Self::Pow`] expression.
 	fn simplify_pow(mut base: Box<Self>, mut exp: Box<Self>, ctx: &mut Context) -> Result<Self, SimplifyError> {
 		// First simplify the base and exponent separately
-		*base = base.simplify(ctx)?;
-		*exp = exp.simplify(ctx)?;
+		*base = base.simplify_builtin(ctx)?;
+		*exp = exp.simplify_builtin(ctx)?;
+
+		// Square matrices are raised to integer powers by repeated multiplication, inverting them for negative exponents
+		if base.is_matrix() {
+			return Self::pow_matrix(base.into_matrix().unwrap(), *exp, ctx);
+		}
 
 		// If base is zero and exponent is negative, return zero division error
 		if base.is_num_and(Number::is_zero) && exp.is_num_and(Number::is_negative) {
@@ -294,27 +771,218 @@ impl Expr {
 			return Ok(*base);
 		}
 
+		// If both are numbers and the power is rational and not too large, evaluate it exactly
+		if let (Self::Num(base), Self::Num(exp)) = (&*base, &*exp)
+			&& let Some(power) = exp.clone().numer().abs().to_u32()
+			&& base.bits().saturating_mul(power.into()) <= MAX_POWER_BITS
+			&& let Some(result) = base.checked_pow(exp)
+		{
+			return Ok(Self::Num(result));
+		}
+
+		// If base is a polynomial and the exponent is an integer, expand the power if it stays a polynomial
+		if let (Self::Poly(sym, poly), Self::Num(exp)) = (&*base, &*exp)
+			&& let Some(power) = exp.clone().abs().to_u32()
+		{
+			// Monomials with too large coefficients are kept, like numeric powers
+			if let [mono] = poly.monomials().as_slice()
+				&& mono.coeff.bits().saturating_mul(power.into()) <= MAX_POWER_BITS
+			{
+				return Self::Poly(sym.clone(), mono.clone().pow(exp).into()).simplify_builtin(ctx);
+			}
+
+			// Powers spanning too many degrees are kept, since expanding them would take too long
+			if !exp.is_negative()
+				&& let [first, .., last] = poly.monomials().as_slice()
+				&& (first.degree.clone() - &last.degree) * power <= MAX_EXPANDED_DEGREE
+			{
+				let result = (1..power).fold(poly.clone(), |acc, _| acc * poly);
+				return Self::Poly(sym.clone(), result).simplify_builtin(ctx);
+			}
+		}
+
+		// If the base is a nonnegative monomial in a nonnegative symbol, raise the coefficient and multiply the degree,
+		// like `sqrt(x^2) = x`. The base alone is not enough, since `x^2` is nonnegative for any real `x`
+		if let (Self::Poly(sym, poly), Self::Num(exp)) = (&*base, &*exp)
+			&& let [mono] = poly.monomials().as_slice()
+			&& let Some(power) = exp.clone().numer().abs().to_u32()
+			&& mono.coeff.bits().saturating_mul(power.into()) <= MAX_POWER_BITS
+			&& let Some(coeff) = mono.coeff.checked_pow(exp)
+			&& base.is_nonnegative(ctx) == Some(true)
+			&& Self::Poly(sym.clone(), Monomial::linear(1).into()).is_nonnegative(ctx) == Some(true)
+		{
+			let degree = mono.degree.clone() * exp;
+			return Self::Poly(sym.clone(), Monomial::new(coeff, degree).into()).simplify_builtin(ctx);
+		}
+
+		// If the base is a power itself and the exponent is an integer or the inner base is nonnegative, multiply the
+		// exponents
+		if let Self::Pow(inner, inner_exp) = &*base
+			&& (exp.is_num_and(Number::is_integer) || inner.is_nonnegative(ctx) == Some(true))
+		{
+			return inner
+				.as_ref()
+				.clone()
+				.pow(inner_exp.as_ref().clone() * *exp)
+				.simplify_builtin(ctx);
+		}
+
+		// If the base is a product and the exponent is an integer or all factors are nonnegative, raise each factor to
+		// the power
+		if let Self::Mul(factors) = &*base
+			&& (exp.is_num_and(Number::is_integer)
+				|| factors.iter().all(|factor| factor.is_nonnegative(ctx) == Some(true)))
+		{
+			let factors = factors.iter().map(|factor| factor.clone().pow(exp.as_ref().clone()));
+			return Self::Mul(factors.collect()).simplify_builtin(ctx);
+		}
+
 		// Return the result as a new power
 		Ok(Self::Pow(base, exp))
 	}
 
+	/// Simplifies a [`Self::Rel`] expression, deciding it if the sign of the difference is known.
+	fn simplify_rel(rel: Relation, lhs: Self, rhs: Self, ctx: &mut Context) -> Result<Self, SimplifyError> {
+		// First simplify both sides separately
+		let lhs = lhs.simplify_builtin(ctx)?;
+		let rhs = rhs.simplify_builtin(ctx)?;
+
+		if lhs.is_logical() || rhs.is_logical() {
+			return Ok(Self::Rel(rel, lhs.into(), rhs.into()));
+		}
+
+		// Decide the relation with the sign of the difference, using the assumptions of the context
+		let diff = (lhs.clone() - rhs.clone()).simplify_builtin(ctx)?;
+
+		let result = match rel {
+			Relation::Eq => diff.is_nonzero(ctx).map(|nonzero| !nonzero),
+			Relation::Ge => diff.is_negative(ctx).map(|negative| !negative),
+			Relation::Gt => diff.is_positive(ctx),
+			Relation::Le => diff.is_positive(ctx).map(|positive| !positive),
+			Relation::Lt => diff.is_negative(ctx),
+			Relation::Ne => diff.is_nonzero(ctx),
+		};
+
+		match result {
+			Some(value) => Ok(Self::boolean(value)),
+			None => Ok(Self::Rel(rel, lhs.into(), rhs.into())),
+		}
+	}
+
+	/// Adds matrices entry by entry, failing if any term is not a matrix or the sizes differ.
+	fn add_matrices(exprs: Vec<Self>, ctx: &mut Context) -> Result<Self, SimplifyError> {
+		let mut matrices = exprs.into_iter().map(|expr| expr.into_matrix());
+		let first = matrices.next().flatten().ok_or(SimplifyError::DimensionMismatch)?;
+
+		let sum = matrices.try_fold(first, |acc, matrix| match matrix {
+			Some(matrix) if matrix.rows() == acc.rows() && matrix.cols() == acc.cols() => Ok(acc + matrix),
+			_ => Err(SimplifyError::DimensionMismatch),
+		})?;
+
+		Ok(Self::Matrix(sum.simplify(ctx)?))
+	}
+
+	/// Cancels factorials in a product whose arguments differ by an integer, replacing them with the remaining factors.
+	fn cancel_factorials(exprs: &mut Vec<Self>, ctx: &mut Context) -> Result<(), SimplifyError> {
+		// Returns the argument of a factorial call
+		fn factorial_arg(expr: &Expr) -> Option<&Expr> {
+			match expr {
+				Expr::Fun(name, args) if name.name() == "factorial" && args.len() == 1 => args.first(),
+				_ => None,
+			}
+		}
+
+		// Returns the argument of a factorial call raised to the power of negative one
+		fn inverse_factorial_arg(expr: &Expr) -> Option<&Expr> {
+			match expr {
+				Expr::Pow(base, exp) if exp.is_num_and(Number::is_neg_one) => factorial_arg(base),
+				_ => None,
+			}
+		}
+
+		let mut index = 0;
+
+		while index < exprs.len() {
+			let Some(numer) = factorial_arg(&exprs[index]).cloned() else {
+				index += 1;
+				continue;
+			};
+
+			// Find a factorial in the denominator whose argument differs by a small enough integer
+			let found = exprs.iter().enumerate().find_map(|(other, expr)| {
+				let denom = inverse_factorial_arg(expr)?;
+				let diff = (numer.clone() - denom.clone()).simplify_builtin(ctx).ok()?.into_num()?;
+
+				(diff.is_integer() && diff.clone().abs() <= MAX_EXPANDED_DEGREE).then(|| (other, denom.clone(), diff))
+			});
+
+			let Some((other, denom, diff)) = found else {
+				index += 1;
+				continue;
+			};
+
+			// Remove both factorials, the higher index first so the lower one stays valid
+			exprs.remove(index.max(other));
+			exprs.remove(index.min(other));
+
+			// Build the product `(low + 1) * (low + 2) * ... * high` of the remaining factors
+			let negative = diff.is_negative();
+			let count = diff.abs().to_u32().unwrap();
+			let low = if negative { numer } else { denom };
+
+			let factors: Vec<_> = (1..=count)
+				.map(|offset| (low.clone() + Self::Num(offset.into())).simplify_builtin(ctx))
+				.try_collect()?;
+
+			if negative {
+				exprs.push(Self::Mul(factors).pow(Self::neg_one()).simplify_builtin(ctx)?);
+			} else {
+				exprs.extend(factors);
+			}
+
+			index = 0;
+		}
+
+		Ok(())
+	}
+
 	/// Compares this expression with another for a consistent ordering.
 	fn cmp(&self, other: &Self) -> Ordering {
 		match (self, other) {
-			// If both are sums, compare the vecs
-			(Self::Add(lhs), Self::Add(rhs)) => Self::cmp_vecs(lhs, rhs),
+			// If both are sums or connectives of the same kind, compare the vecs
+			(Self::Add(lhs), Self::Add(rhs)) | (Self::And(lhs), Self::And(rhs)) | (Self::Or(lhs), Self::Or(rhs)) => {
+				Self::cmp_vecs(lhs, rhs)
+			}
 
 			// If both are function calls, compare name first, then arguments
 			(Self::Fun(lhs_name, lhs_args), Self::Fun(rhs_name, rhs_args)) => {
 				lhs_name.cmp(rhs_name).then_with(|| Self::cmp_vecs(lhs_args, rhs_args))
 			}
 
+			// If both are matrices, compare the sizes first, then the entries
+			(Self::Matrix(lhs), Self::Matrix(rhs)) => lhs
+				.rows()
+				.cmp(&rhs.rows())
+				.then_with(|| lhs.cols().cmp(&rhs.cols()))
+				.then_with(|| Self::cmp_vecs(lhs.entries(), rhs.entries())),
+
 			// If both are products, compare the vecs
 			(Self::Mul(lhs), Self::Mul(rhs)) => Self::cmp_vecs(lhs, rhs),
 
+			// If both are negations, compare the inner expressions
+			(Self::Not(lhs), Self::Not(rhs)) => lhs.cmp(rhs),
+
 			// If both are numbers, compare them directly
 			(Self::Num(lhs), Self::Num(rhs)) => lhs.cmp(rhs),
 
+			// If both are piecewise expressions, compare the branches in order
+			(Self::Piecewise(lhs), Self::Piecewise(rhs)) => lhs
+				.iter()
+				.zip(rhs)
+				.map(|((lhs, lhs_cond), (rhs, rhs_cond))| lhs.cmp(rhs).then_with(|| lhs_cond.cmp(rhs_cond)))
+				.find(|ord| ord.is_ne())
+				.unwrap_or_else(|| lhs.len().cmp(&rhs.len())),
+
 			// If both are polynomials, compare symbol first, then monomials
 			(Self::Poly(lhs_sym, lhs_poly), Self::Poly(rhs_sym, rhs_poly)) => {
 				lhs_sym.cmp(rhs_sym).then_with(|| Self::cmp_polys(lhs_poly, rhs_poly))
@@ -325,17 +993,35 @@ impl Expr {
 				lhs_base.cmp(rhs_base).then_with(|| lhs_exp.cmp(rhs_exp))
 			}
 
+			// If both are relations, compare the left sides first, then the right sides and relations
+			(Self::Rel(lhs_rel, lhs_lhs, lhs_rhs), Self::Rel(rhs_rel, rhs_lhs, rhs_rhs)) => lhs_lhs
+				.cmp(rhs_lhs)
+				.then_with(|| lhs_rhs.cmp(rhs_rhs))
+				.then_with(|| lhs_rel.cmp(rhs_rel)),
+
 			// Otherwise, compare the discriminants
 			(Self::Add(_), _) => Ordering::Less,
 			(_, Self::Add(_)) => Ordering::Greater,
+			(Self::And(_), _) => Ordering::Less,
+			(_, Self::And(_)) => Ordering::Greater,
 			(Self::Fun(_, _), _) => Ordering::Less,
 			(_, Self::Fun(_, _)) => Ordering::Greater,
+			(Self::Matrix(_), _) => Ordering::Less,
+			(_, Self::Matrix(_)) => Ordering::Greater,
 			(Self::Mul(_), _) => Ordering::Less,
 			(_, Self::Mul(_)) => Ordering::Greater,
+			(Self::Not(_), _) => Ordering::Less,
+			(_, Self::Not(_)) => Ordering::Greater,
 			(Self::Num(_), _) => Ordering::Less,
 			(_, Self::Num(_)) => Ordering::Greater,
+			(Self::Or(_), _) => Ordering::Less,
+			(_, Self::Or(_)) => Ordering::Greater,
+			(Self::Piecewise(_), _) => Ordering::Less,
+			(_, Self::Piecewise(_)) => Ordering::Greater,
 			(Self::Poly(_, _), _) => Ordering::Less,
 			(_, Self::Poly(_, _)) => Ordering::Greater,
+			(Self::Pow(_, _), _) => Ordering::Less,
+			(_, Self::Pow(_, _)) => Ordering::Greater,
 		}
 	}
 
@@ -357,20 +1043,122 @@ impl Expr {
 			.unwrap_or_else(|| lhs.len().cmp(&rhs.len()))
 	}
 
+	/// Multiplies matrices in order, failing if their sizes do not fit, and multiplies the scalar factors into every
+	/// entry of the product.
+	fn mul_matrices(matrices: Vec<Matrix<Self>>, scalars: Vec<Self>, ctx: &mut Context) -> Result<Self, SimplifyError> {
+		let mut matrices = matrices.into_iter();
+		let first = matrices.next().unwrap();
+
+		let product = matrices.try_fold(first, |acc, matrix| match acc.cols() == matrix.rows() {
+			true => (acc * matrix).simplify(ctx),
+			false => Err(SimplifyError::DimensionMismatch),
+		})?;
+
+		let scalar = Self::Mul(scalars).simplify_builtin(ctx)?;
+
+		if scalar.is_num_and(Number::is_one) {
+			return Ok(Self::Matrix(product));
+		}
+
+		Ok(Self::Matrix(product.map(|entry| scalar.clone() * entry).simplify(ctx)?))
+	}
+
+	/// Raises a matrix to a power, which has to be square if the exponent is an integer. Other exponents are kept.
+	fn pow_matrix(matrix: Matrix<Self>, exp: Self, ctx: &mut Context) -> Result<Self, SimplifyError> {
+		let Some(power) = exp.clone().into_num().filter(Number::is_integer) else {
+			return Ok(Self::Matrix(matrix).pow(exp));
+		};
+
+		if !matrix.is_square() {
+			return Err(SimplifyError::DimensionMismatch);
+		}
+
+		let Some(count) = power.clone().abs().to_u32() else {
+			return Ok(Self::Matrix(matrix).pow(exp));
+		};
+
+		let mut base = match power.is_negative() {
+			true => matrix.inverse(ctx)?.ok_or(SimplifyError::DivisionByZero)?,
+			false => matrix,
+		};
+
+		// Square and multiply, going through the bits of the exponent
+		let mut result = Matrix::identity(base.rows()).map(Self::Num);
+
+		for bit in (0..u32::BITS - count.leading_zeros()).map(|bit| count >> bit & 1 == 1) {
+			if bit {
+				result = (result * base.clone()).simplify(ctx)?;
+			}
+
+			base = (base.clone() * base).simplify(ctx)?;
+		}
+
+		Ok(Self::Matrix(result))
+	}
+
+	/// Internal method to split the factors of a product into a fraction for two-dimensional output. Returns whether
+	/// the numeric coefficient is negative, the factors of the numerator and the factors of the denominator, which has
+	/// the denominator of the coefficient and all factors with negative exponents.
+	pub(crate) fn split_fraction(exprs: &[Self]) -> (bool, Vec<Self>, Vec<Self>) {
+		let mut numer = Vec::new();
+		let mut denom = Vec::new();
+		let mut negative = false;
+
+		// Monomials with negative degrees are split into their coefficient and a power in the denominator
+		let exprs = exprs.iter().flat_map(|expr| match expr {
+			Self::Poly(sym, poly)
+				if let [mono] = poly.monomials().as_slice()
+					&& mono.degree.is_negative() =>
+			{
+				let power = Self::Poly(sym.clone(), Monomial::new(1, -mono.degree.clone()).into());
+				vec![Self::Num(mono.coeff.clone()), power.pow(Self::neg_one())]
+			}
+			_ => vec![expr.clone()],
+		});
+
+		for expr in exprs {
+			match &expr {
+				// The numerator and denominator of a numeric coefficient are split up
+				Self::Num(num) => {
+					negative ^= num.is_negative();
+					let (top, bottom) = num.clone().abs().ratio();
+
+					if !top.is_one() {
+						numer.push(Self::Num(top));
+					}
+
+					if !bottom.is_one() {
+						denom.push(Self::Num(bottom));
+					}
+				}
+
+				// Factors with negative exponents are moved into the denominator
+				Self::Pow(base, exp)
+					if let Self::Num(num) = &**exp
+						&& num.is_negative() =>
+				{
+					match num.is_neg_one() {
+						true => denom.push((**base).clone()),
+						false => denom.push(Self::Pow(base.clone(), Self::Num(-num.clone()).into())),
+					}
+				}
+
+				_ => numer.push(expr),
+			}
+		}
+
+		(negative, numer, denom)
+	}
+
 	/// Formats this expression with parentheses if necessary.
 	fn with_parens(&self) -> impl fmt::Display {
 		struct WithParens<'a>(&'a Expr);
 
 		impl fmt::Display for WithParens<'_> {
 			fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-				match self.0 {
-					// If the expression has more than one term, use parentheses
-					Expr::Add(exprs) if exprs.len() > 1 => write!(f, "({})", self.0),
-					Expr::Mul(exprs) if exprs.len() > 1 => write!(f, "({})", self.0),
-					Expr::Poly(_, poly) if poly.monomials().len() > 1 => write!(f, "({})", self.0),
-
-					// Otherwise, write the expression normally
-					_ => write!(f, "{}", self.0),
+				match self.0.needs_parens() {
+					true => write!(f, "({})", self.0),
+					false => write!(f, "{}", self.0),
 				}
 			}
 		}
@@ -461,11 +1249,59 @@ impl fmt::Display for Expr {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 		match self {
 			Self::Add(exprs) => Self::write_add(f, exprs),
+			Self::And(exprs) if exprs.is_empty() => write!(f, "true"),
+			// Disjunctions bind weaker than conjunctions
+			Self::And(exprs) => write!(
+				f,
+				"{}",
+				exprs
+					.iter()
+					.map(|expr| match expr {
+						Self::Or(_) => expr.with_parens().to_string(),
+						_ => expr.to_string(),
+					})
+					.format(" and ")
+			),
 			Self::Fun(name, args) => write!(f, "{name}({})", args.iter().format(", ")),
+			Self::Matrix(matrix) => write!(f, "{matrix}"),
 			Self::Mul(exprs) => write!(f, "{}", exprs.iter().map(Self::with_parens).format(" * ")),
+			Self::Not(expr) => match **expr {
+				Self::Rel(_, _, _) => write!(f, "not {expr}"),
+				_ => write!(f, "not {}", expr.with_parens()),
+			},
 			Self::Num(num) => write!(f, "{num}"),
+			Self::Or(exprs) if exprs.is_empty() => write!(f, "false"),
+			Self::Or(exprs) => write!(f, "{}", exprs.iter().format(" or ")),
+			Self::Piecewise(branches) => write!(
+				f,
+				"piecewise({})",
+				branches
+					.iter()
+					.format_with(", ", |(value, condition), f| f(&format_args!("{value}, {condition}")))
+			),
 			Self::Poly(sym, poly) => poly.write(f, false, sym.name()),
-			Self::Pow(base, exp) => write!(f, "{}^{}", base.with_parens(), exp.with_parens()),
+			// Powers are right associative and bind tighter than anything else, so only atoms are written bare
+			Self::Pow(base, exp) => {
+				let side = |expr: &Self, parens: bool| match parens {
+					true => format!("({expr})"),
+					false => expr.to_string(),
+				};
+
+				write!(
+					f,
+					"{}^{}",
+					side(base, base.needs_base_parens()),
+					side(exp, exp.needs_exp_parens())
+				)
+			}
+			Self::Rel(rel, lhs, rhs) => {
+				let side = |expr: &Self| match expr.is_logical() {
+					true => expr.with_parens().to_string(),
+					false => expr.to_string(),
+				};
+
+				write!(f, "{} {rel} {}", side(lhs), side(rhs))
+			}
 		}
 	}
 }
@@ -0,0 +1,766 @@
+//! Gröbner bases of polynomial ideals and the solving of polynomial systems.
+//!
+//! Buchberger's algorithm reduces one S-polynomial at a time, skipping pairs with Buchberger's product and chain
+//! criteria. The F4 algorithm instead reduces all pairs of the lowest degree at once: the multiples of the basis
+//! elements needed to reduce them are collected into a Macaulay matrix, which is row reduced exactly. Both end with
+//! the unique reduced basis of the ideal.
+//!
+//! Zero-dimensional systems are solved with a triangular decomposition. Adding the square-free parts of the minimal
+//! polynomials of all variables makes the ideal radical, after which a linear form `u` in the variables is searched
+//! whose minimal polynomial `m` has as many roots as the system has solutions. Every variable is then a polynomial
+//! `g_i` in `u`, which gives triangular systems `m(u) = 0, x_i = g_i(u)`, split further at the rational roots of `m`.
+//! Solutions are read off the real roots of `m`, so only real solutions are found, and every value is given exactly as
+//! a real root of the minimal polynomial of its variable.
+
+use std::collections::{HashMap, HashSet};
+
+use itertools::Itertools;
+use rug::ops::Pow;
+
+use crate::context::Context;
+use crate::error::SimplifyError;
+use crate::expr::{Expr, Symbol};
+use crate::interval::Interval;
+use crate::matrix::Matrix;
+use crate::monomial::Monomial;
+use crate::multivariate::{MonomialOrder, MultiMonomial, MultiPolynomial};
+use crate::number::Number;
+use crate::polynomial::Polynomial;
+use crate::relation::{self, Relation};
+
+/// The algorithm used to compute a Gröbner basis.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Algorithm {
+	/// Buchberger's algorithm, which reduces one S-polynomial at a time.
+	Buchberger,
+	/// The F4 algorithm, which reduces all S-polynomials of the lowest degree together with linear algebra.
+	F4,
+}
+
+/// A reduced Gröbner basis of a polynomial ideal, consisting of monic polynomials sorted by their leading monomials
+/// in ascending order.
+///
+/// # Examples
+///
+/// ```
+/// use abacas::expr::Symbol;
+/// use abacas::groebner::{Algorithm, GroebnerBasis};
+/// use abacas::multivariate::{MonomialOrder, MultiPolynomial};
+///
+/// let vars = [Symbol::new("x").unwrap(), Symbol::new("y").unwrap()];
+/// let x = MultiPolynomial::variable(&vars, MonomialOrder::Lex, 0);
+/// let y = MultiPolynomial::variable(&vars, MonomialOrder::Lex, 1);
+/// let one = MultiPolynomial::constant(&vars, MonomialOrder::Lex, 1);
+///
+/// // The circle `x^2 + y^2 = 1` and the line `x = y`
+/// let circle = x.clone() * &x + &(y.clone() * &y) - &one;
+/// let line = x.clone() - &y;
+///
+/// let basis = GroebnerBasis::new(&[circle, line], Algorithm::Buchberger);
+/// let polys = basis.polynomials().iter().map(ToString::to_string).collect::<Vec<_>>();
+/// assert_eq!(polys, ["y^2 - 0.5", "x - y"]);
+///
+/// assert!(basis.contains(&(x.clone() * &x - &(y.clone() * &y))));
+/// assert!(!basis.contains(&(x - &one)));
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct GroebnerBasis {
+	/// The variables, starting with the largest
+	vars: Vec<Symbol>,
+	/// The order of the monomials
+	order: MonomialOrder,
+	/// The polynomials of the basis
+	polys: Vec<MultiPolynomial>,
+}
+
+/// A triangular system `m(u) = 0, x_1 = g_1(u), ..., x_n = g_n(u)` in a linear form `u = c_1 x_1 + ... + c_n x_n` of
+/// the variables, whose solutions are given by the roots of the square-free polynomial `m`.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct TriangularSet {
+	/// The coefficients `c_i` of the linear form.
+	pub form: Vec<Number>,
+	/// The minimal polynomial `m` of the linear form.
+	pub minimal: Polynomial,
+	/// The value `g_i` of every variable as a polynomial in the linear form, of lower degree than `m`.
+	pub values: Vec<Polynomial>,
+}
+
+/// The quotient ring of a zero-dimensional ideal as a vector space, with the monomials that are not divisible by any
+/// leading monomial of the basis as its basis.
+struct Quotient<'a> {
+	/// The Gröbner basis of the ideal.
+	basis: &'a GroebnerBasis,
+	/// The index of every standard monomial.
+	indices: HashMap<Vec<u32>, usize>,
+}
+
+// Constructors
+impl GroebnerBasis {
+	/// Computes the reduced Gröbner basis of the ideal generated by the given polynomials, in the variables and
+	/// monomial order of the first one. Panics if there are no polynomials or their variables differ.
+	pub fn new(polys: &[MultiPolynomial], algorithm: Algorithm) -> Self {
+		let Some(first) = polys.first() else {
+			panic!("expected at least one polynomial")
+		};
+
+		let (vars, order) = (first.vars().to_vec(), first.order());
+
+		if polys.iter().any(|poly| poly.vars() != vars) {
+			panic!("polynomials must have the same variables")
+		}
+
+		let generators = polys
+			.iter()
+			.filter(|poly| !poly.is_zero())
+			.map(|poly| poly.clone().with_order(order).monic())
+			.collect();
+
+		let polys = match algorithm {
+			Algorithm::Buchberger => buchberger(generators),
+			Algorithm::F4 => f4(generators),
+		};
+
+		Self { vars, order, polys }
+	}
+}
+
+// Guards
+impl GroebnerBasis {
+	/// Whether a polynomial belongs to the ideal, which is the case if it reduces to zero.
+	pub fn contains(&self, poly: &MultiPolynomial) -> bool {
+		self.reduce(poly).is_zero()
+	}
+
+	/// Whether the ideal has finitely many solutions over the complex numbers, which is the case if some power of
+	/// every variable is a leading monomial. This includes the whole ring, which has no solutions.
+	pub fn is_zero_dimensional(&self) -> bool {
+		(0..self.vars.len()).all(|index| {
+			self.leading().any(|mono| {
+				let degrees = mono.degrees.iter().enumerate();
+				degrees
+					.filter(|&(other, _)| other != index)
+					.all(|(_, degree)| *degree == 0)
+			})
+		})
+	}
+}
+
+// Operations
+impl GroebnerBasis {
+	/// Returns the basis elements free of the given number of leading variables, which are a Gröbner basis of the
+	/// elimination ideal. Returns [`None`] if the monomial order does not eliminate these variables.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::expr::Symbol;
+	/// use abacas::groebner::{Algorithm, GroebnerBasis};
+	/// use abacas::multivariate::{MonomialOrder, MultiPolynomial};
+	///
+	/// let vars = [Symbol::new("t").unwrap(), Symbol::new("x").unwrap(), Symbol::new("y").unwrap()];
+	/// let order = MonomialOrder::Elimination(1);
+	/// let var = |index| MultiPolynomial::variable(&vars, order, index);
+	///
+	/// // The parametrized curve `x = t^2, y = t^3`
+	/// let polys = [var(1) - &(var(0) * &var(0)), var(2) - &(var(0) * &var(0) * &var(0))];
+	/// let basis = GroebnerBasis::new(&polys, Algorithm::F4);
+	///
+	/// let implicit = basis.eliminate(1).unwrap();
+	/// assert_eq!(implicit.iter().map(ToString::to_string).collect::<Vec<_>>(), ["x^3 - y^2"]);
+	/// assert_eq!(basis.eliminate(2), None);
+	/// ```
+	pub fn eliminate(&self, count: usize) -> Option<Vec<MultiPolynomial>> {
+		if !self.order.eliminates(count) {
+			return None;
+		}
+
+		let polys = self.polys.iter().filter(|poly| {
+			let leading = poly.leading().unwrap();
+			leading.degrees[..count].iter().all(|degree| *degree == 0)
+		});
+
+		Some(polys.cloned().collect())
+	}
+
+	/// Internal method to iterate over the leading monomials.
+	fn leading(&self) -> impl Iterator<Item = &MultiMonomial> {
+		self.polys.iter().map(|poly| poly.leading().unwrap())
+	}
+
+	/// Returns the polynomials of the basis.
+	pub fn polynomials(&self) -> &[MultiPolynomial] {
+		&self.polys
+	}
+
+	/// Reduces a polynomial to its normal form, which is the unique remainder of the division by the basis.
+	pub fn reduce(&self, poly: &MultiPolynomial) -> MultiPolynomial {
+		normal_form(poly.clone().with_order(self.order), &self.polys)
+	}
+
+	/// Decomposes the solutions of a zero-dimensional ideal into triangular systems. Returns [`None`] if the ideal is
+	/// not zero-dimensional.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::expr::Symbol;
+	/// use abacas::groebner::{Algorithm, GroebnerBasis};
+	/// use abacas::multivariate::{MonomialOrder, MultiPolynomial};
+	///
+	/// let vars = [Symbol::new("x").unwrap(), Symbol::new("y").unwrap()];
+	/// let var = |index| MultiPolynomial::variable(&vars, MonomialOrder::GrevLex, index);
+	/// let one = MultiPolynomial::constant(&vars, MonomialOrder::GrevLex, 1);
+	///
+	/// // `x^2 = 2` and `x y = 1`
+	/// let polys = [var(0) * &var(0) - &(one.clone() + &one), var(0) * &var(1) - &one];
+	/// let sets = GroebnerBasis::new(&polys, Algorithm::Buchberger).triangular().unwrap();
+	///
+	/// assert_eq!(sets.len(), 1);
+	/// assert_eq!(sets[0].minimal.to_string(), "x^2 - 0.5");
+	/// assert_eq!(sets[0].values[0].to_string(), "2x");
+	/// ```
+	pub fn triangular(&self) -> Option<Vec<TriangularSet>> {
+		if !self.is_zero_dimensional() {
+			return None;
+		}
+
+		if self.polys.iter().any(MultiPolynomial::is_constant) {
+			return Some(Vec::new());
+		}
+
+		// Adding the square-free parts of the minimal polynomials of the variables gives the radical ideal
+		let quotient = Quotient::new(self);
+		let mut generators = self.polys.clone();
+		let mut radical = true;
+
+		for index in 0..self.vars.len() {
+			let var = MultiPolynomial::variable(&self.vars, self.order, index);
+			let minimal = quotient.powers(&var).1;
+			let reduced = relation::square_free(&minimal);
+
+			if reduced.degree() != minimal.degree() {
+				generators.push(MultiPolynomial::from_univariate(&reduced, &self.vars, self.order, index).unwrap());
+				radical = false;
+			}
+		}
+
+		match radical {
+			true => Some(quotient.triangular()),
+			false => {
+				let basis = Self::new(&generators, Algorithm::Buchberger);
+				Some(Quotient::new(&basis).triangular())
+			}
+		}
+	}
+}
+
+impl<'a> Quotient<'a> {
+	/// Internal method to enumerate the standard monomials of a zero-dimensional ideal.
+	fn new(basis: &'a GroebnerBasis) -> Self {
+		let mut indices = HashMap::new();
+		let mut queue = vec![vec![0; basis.vars.len()]];
+
+		while let Some(degrees) = queue.pop() {
+			let mono = MultiMonomial::new(1, degrees.clone());
+
+			if indices.contains_key(&degrees) || basis.leading().any(|leading| leading.divides(&mono)) {
+				continue;
+			}
+
+			for index in 0..degrees.len() {
+				let mut next = degrees.clone();
+				next[index] += 1;
+				queue.push(next);
+			}
+
+			indices.insert(degrees, indices.len());
+		}
+
+		Self { basis, indices }
+	}
+
+	/// Internal method to get the coordinates of the normal form of a polynomial.
+	fn coordinates(&self, poly: &MultiPolynomial) -> Vec<Number> {
+		let mut coords = vec![Number::zero(); self.indices.len()];
+
+		for mono in self.basis.reduce(poly).monomials() {
+			coords[self.indices[&mono.degrees]] = mono.coeff.clone();
+		}
+
+		coords
+	}
+
+	/// Internal method to get the coordinates of the powers `1, p, p^2, ...` of a polynomial, up to the first power
+	/// that depends on the lower ones. Returns the coordinates together with the monic minimal polynomial of `p`.
+	fn powers(&self, poly: &MultiPolynomial) -> (Vec<Vec<Number>>, Polynomial) {
+		let one = MultiPolynomial::constant(&self.basis.vars, self.basis.order, 1);
+		let mut power = self.basis.reduce(&one);
+		let mut coords = vec![self.coordinates(&power)];
+
+		loop {
+			power = self.basis.reduce(&(power * poly));
+			let next = self.coordinates(&power);
+
+			// The powers are independent until the first linear relation, which is the minimal polynomial
+			let columns = Matrix::from_fn(self.indices.len(), coords.len() + 1, |row, col| match coords.get(col) {
+				Some(coords) => coords[row].clone(),
+				None => next[row].clone(),
+			});
+
+			if let Some(relation) = columns.nullspace().first() {
+				let leading = relation[(coords.len(), 0)].clone();
+				let monomials = (0..=coords.len())
+					.filter(|&degree| !relation[(degree, 0)].is_zero())
+					.map(|degree| Monomial::new(relation[(degree, 0)].clone() / &leading, degree));
+
+				return (coords, Polynomial::new(monomials));
+			}
+
+			coords.push(next);
+		}
+	}
+
+	/// Internal method to find a linear form that separates the solutions of a radical ideal and decompose the
+	/// solutions into triangular systems with it.
+	fn triangular(&self) -> Vec<TriangularSet> {
+		let count = self.basis.vars.len();
+		let size = self.indices.len();
+
+		// Single variables give the simplest values, before trying `x_1 + k x_2 + k^2 x_3 + ...`
+		let single = (0..count).rev().map(|index| {
+			let form = (0..count).map(|other| Number::from((other == index) as u32));
+			form.collect_vec()
+		});
+		let combined = (1u32..).map(|k| (0..count).map(|index| Number::from(k).pow(index as u32)).collect_vec());
+
+		for form in single.chain(combined) {
+			let terms = form
+				.iter()
+				.enumerate()
+				.filter(|(_, coeff)| !coeff.is_zero())
+				.map(|(index, coeff)| {
+					let mut degrees = vec![0; count];
+					degrees[index] = 1;
+					MultiMonomial::new(coeff.clone(), degrees)
+				});
+			let linear = MultiPolynomial::new(&self.basis.vars, self.basis.order, terms);
+
+			let (coords, minimal) = self.powers(&linear);
+
+			if coords.len() < size {
+				continue;
+			}
+
+			// The powers of a separating form are a basis, in which the coordinates of the variables are their values
+			let powers = Matrix::from_fn(size, size, |row, col| coords[col][row].clone());
+			let inverse = powers.inverse().unwrap();
+
+			let values = (0..count).map(|index| {
+				let var = MultiPolynomial::variable(&self.basis.vars, self.basis.order, index);
+				let value = inverse.clone() * &Matrix::column(self.coordinates(&var));
+				let monomials = (0..size)
+					.filter(|&degree| !value[(degree, 0)].is_zero())
+					.map(|degree| Monomial::new(value[(degree, 0)].clone(), degree));
+
+				Polynomial::new(monomials)
+			});
+
+			return split(form, minimal, values.collect());
+		}
+
+		unreachable!()
+	}
+}
+
+/// Solves a system of polynomial equations with rational coefficients for the given variables. Equations are
+/// relations `lhs == rhs`, and any other expression `expr` stands for the equation `expr == 0`. Returns the real
+/// solutions with the value of every variable in the order they were given, or [`None`] if the equations are not
+/// polynomials in the variables or have infinitely many complex solutions.
+///
+/// # Examples
+///
+/// ```
+/// use abacas::context::Context;
+/// use abacas::expr::{Expr, Symbol};
+/// use abacas::groebner::solve;
+/// use abacas::relation::Relation;
+/// use rug::ops::Pow;
+///
+/// let ctx = &mut Context::new();
+/// let vars = [Symbol::new("x").unwrap(), Symbol::new("y").unwrap()];
+/// let var = |index: usize| Expr::Poly(vars[index].clone(), "x".parse().unwrap());
+/// let num = |num: i64| Expr::Num(num.into());
+///
+/// // `x^2 + y^2 == 5` and `x y == 2`
+/// let equations = [
+///     Expr::Rel(Relation::Eq, (var(0).pow(num(2)) + var(1).pow(num(2))).into(), num(5).into()),
+///     Expr::Rel(Relation::Eq, (var(0) * var(1)).into(), num(2).into()),
+/// ];
+///
+/// let solutions = solve(&equations, &vars, ctx).unwrap().unwrap();
+/// let solutions = solutions.iter().map(|solution| format!("{}, {}", solution[0], solution[1]));
+/// assert_eq!(solutions.collect::<Vec<_>>(), ["-1, -2", "-2, -1", "2, 1", "1, 2"]);
+/// ```
+pub fn solve(equations: &[Expr], vars: &[Symbol], ctx: &mut Context) -> Result<Option<Vec<Vec<Expr>>>, SimplifyError> {
+	let order = MonomialOrder::GrevLex;
+	let mut polys = Vec::with_capacity(equations.len());
+
+	for equation in equations {
+		let diff = match equation.clone().simplify_builtin(ctx)? {
+			Expr::And(exprs) if exprs.is_empty() => continue,
+			Expr::Or(exprs) if exprs.is_empty() => return Ok(Some(Vec::new())),
+			Expr::Rel(Relation::Eq, lhs, rhs) => *lhs - *rhs,
+			expr if expr.is_logical() || expr.is_matrix() => return Ok(None),
+			expr => expr,
+		};
+
+		let Some(poly) = MultiPolynomial::from_expr(&diff, vars, order) else {
+			return Ok(None);
+		};
+
+		polys.push(poly);
+	}
+
+	if polys.is_empty() {
+		polys.push(MultiPolynomial::zero(vars, order));
+	}
+
+	let basis = GroebnerBasis::new(&polys, Algorithm::F4);
+
+	let Some(sets) = basis.triangular() else {
+		return Ok(None);
+	};
+
+	if sets.is_empty() {
+		return Ok(Some(Vec::new()));
+	}
+
+	// Every value is given as a root of the minimal polynomial of its variable, which is simpler than `g_i(u)`
+	let var = Symbol::new("x").unwrap();
+	let quotient = Quotient::new(&basis);
+	let mut roots = Vec::with_capacity(vars.len());
+
+	for index in 0..vars.len() {
+		let poly = MultiPolynomial::variable(vars, order, index);
+		let minimal = relation::square_free(&quotient.powers(&poly).1);
+		let intervals = relation::isolate(&minimal);
+		let quotients = [(minimal.clone(), Polynomial::from(1))];
+
+		let values = (0..intervals.len())
+			.map(|root| relation::exact_root(&quotients, &minimal, &intervals, root, &var, ctx))
+			.collect::<Result<Vec<_>, _>>()?;
+
+		roots.push((intervals, values));
+	}
+
+	let mut solutions = Vec::new();
+
+	for set in sets {
+		for index in 0..relation::isolate(&set.minimal).len() {
+			let solution = set
+				.values
+				.iter()
+				.zip(&roots)
+				.map(|(value, (intervals, values))| values[locate(&set.minimal, index, value, intervals)].clone());
+
+			solutions.push(solution.collect());
+		}
+	}
+
+	Ok(Some(solutions))
+}
+
+/// Internal helper to find which of the given isolating intervals contains the value `g(u)` of a variable, where `u`
+/// is the real root of `m` with the given index. The root is refined until exactly one interval is left.
+fn locate(minimal: &Polynomial, index: usize, value: &Polynomial, intervals: &[Interval]) -> usize {
+	let mut width = Number::one();
+
+	loop {
+		let root = &minimal.isolate_roots(&width).unwrap()[index];
+		let range = value.eval_interval(root, 64).unwrap();
+
+		// Isolating intervals may share their bounds with exact roots, so only their interiors are compared
+		let mut matches = intervals.iter().positions(|interval| match interval.is_point() {
+			true => range.contains(interval.lo()),
+			false => range.lo() < interval.hi() && interval.lo() < range.hi(),
+		});
+
+		if let (Some(position), None) = (matches.next(), matches.next()) {
+			return position;
+		}
+
+		width /= 16;
+	}
+}
+
+/// Internal helper to compute a Gröbner basis with Buchberger's algorithm, always reducing the pair with the
+/// smallest least common multiple of the leading monomials first.
+fn buchberger(mut basis: Vec<MultiPolynomial>) -> Vec<MultiPolynomial> {
+	let mut pairs = (0..basis.len())
+		.array_combinations()
+		.map(|[lhs, rhs]| (lhs, rhs))
+		.collect_vec();
+
+	while !pairs.is_empty() {
+		let order = basis[0].order();
+		let index = (0..pairs.len())
+			.min_by(|&lhs, &rhs| {
+				let (lhs, rhs) = (lcm(&basis, pairs[lhs]), lcm(&basis, pairs[rhs]));
+				order.cmp(&lhs.degrees, &rhs.degrees)
+			})
+			.unwrap();
+		let pair = pairs.swap_remove(index);
+
+		if skip(&basis, &pairs, pair) {
+			continue;
+		}
+
+		let remainder = normal_form(s_polynomial(&basis[pair.0], &basis[pair.1]), &basis);
+
+		if !remainder.is_zero() {
+			pairs.extend((0..basis.len()).map(|index| (index, basis.len())));
+			basis.push(remainder.monic());
+		}
+	}
+
+	interreduce(basis)
+}
+
+/// Internal helper to compute a Gröbner basis with the F4 algorithm, reducing all pairs whose least common multiple
+/// has the lowest total degree together.
+fn f4(mut basis: Vec<MultiPolynomial>) -> Vec<MultiPolynomial> {
+	let mut pairs = (0..basis.len())
+		.array_combinations()
+		.map(|[lhs, rhs]| (lhs, rhs))
+		.collect_vec();
+
+	while let Some(degree) = pairs.iter().map(|&pair| lcm(&basis, pair).total_degree()).min() {
+		let selected = pairs
+			.iter()
+			.copied()
+			.filter(|&pair| lcm(&basis, pair).total_degree() == degree)
+			.collect_vec();
+
+		// Both halves of every S-polynomial are rows of the matrix
+		let mut rows = Vec::new();
+
+		for pair in selected {
+			pairs.retain(|&other| other != pair);
+
+			if skip(&basis, &pairs, pair) {
+				continue;
+			}
+
+			let lcm = lcm(&basis, pair);
+
+			for poly in [&basis[pair.0], &basis[pair.1]] {
+				let leading = poly.leading().unwrap();
+				let factor = MultiMonomial::new(1, lcm.checked_div(leading).unwrap().degrees);
+				rows.push(poly.clone() * &factor);
+			}
+		}
+
+		let mut done = rows
+			.iter()
+			.map(|row| row.leading().unwrap().degrees.clone())
+			.collect::<HashSet<_>>();
+		let mut todo = rows
+			.iter()
+			.flat_map(|row| row.monomials())
+			.map(|mono| mono.degrees.clone())
+			.collect_vec();
+
+		// Symbolic preprocessing adds a reducer for every monomial divisible by a leading monomial of the basis
+		while let Some(degrees) = todo.pop() {
+			if !done.insert(degrees.clone()) {
+				continue;
+			}
+
+			let mono = MultiMonomial::new(1, degrees);
+			let reducer = basis.iter().find(|poly| poly.leading().unwrap().divides(&mono));
+
+			if let Some(reducer) = reducer {
+				let factor = MultiMonomial::new(1, mono.checked_div(reducer.leading().unwrap()).unwrap().degrees);
+				let row = reducer.clone() * &factor;
+
+				todo.extend(row.monomials().iter().map(|mono| mono.degrees.clone()));
+				rows.push(row);
+			}
+		}
+
+		if rows.is_empty() {
+			continue;
+		}
+
+		let (vars, order) = (basis[0].vars().to_vec(), basis[0].order());
+		let mut columns = rows
+			.iter()
+			.flat_map(|row| row.monomials())
+			.map(|mono| mono.degrees.clone())
+			.collect_vec();
+		columns.sort_by(|lhs, rhs| order.cmp(rhs, lhs));
+		columns.dedup();
+
+		let indices = columns
+			.iter()
+			.enumerate()
+			.map(|(index, degrees)| (degrees, index))
+			.collect::<HashMap<_, _>>();
+		let mut matrix = Matrix::from_fn(rows.len(), columns.len(), |_, _| Number::zero());
+
+		for (index, row) in rows.iter().enumerate() {
+			for mono in row.monomials() {
+				matrix[(index, indices[&mono.degrees])] = mono.coeff.clone();
+			}
+		}
+
+		// Reduced rows with a leading monomial that no row had before extend the basis
+		let leading = rows
+			.iter()
+			.map(|row| row.leading().unwrap().degrees.clone())
+			.collect::<HashSet<_>>();
+		let (reduced, pivots) = matrix.rref();
+
+		for (row, pivot) in pivots.into_iter().enumerate() {
+			if leading.contains(&columns[pivot]) {
+				continue;
+			}
+
+			let monomials = (pivot..columns.len())
+				.filter(|&col| !reduced[(row, col)].is_zero())
+				.map(|col| MultiMonomial::new(reduced[(row, col)].clone(), columns[col].clone()));
+
+			pairs.extend((0..basis.len()).map(|index| (index, basis.len())));
+			basis.push(MultiPolynomial::new(&vars, order, monomials));
+		}
+	}
+
+	interreduce(basis)
+}
+
+/// Internal helper to get the least common multiple of the leading monomials of a pair.
+fn lcm(basis: &[MultiPolynomial], (lhs, rhs): (usize, usize)) -> MultiMonomial {
+	basis[lhs].leading().unwrap().lcm(basis[rhs].leading().unwrap())
+}
+
+/// Internal helper to fully reduce a polynomial by a list of polynomials, dividing every monomial by the first
+/// leading monomial that divides it.
+fn normal_form(mut poly: MultiPolynomial, divisors: &[MultiPolynomial]) -> MultiPolynomial {
+	let (vars, order) = (poly.vars().to_vec(), poly.order());
+	let mut remainder = Vec::new();
+
+	while let Some(leading) = poly.leading().cloned() {
+		let quotient = divisors
+			.iter()
+			.find_map(|divisor| Some((divisor, leading.checked_div(divisor.leading()?)?)));
+
+		match quotient {
+			Some((divisor, quotient)) => poly -= &(divisor.clone() * &quotient),
+			None => {
+				poly -= &MultiPolynomial::new(&vars, order, [leading.clone()]);
+				remainder.push(leading);
+			}
+		}
+	}
+
+	MultiPolynomial::new(&vars, order, remainder)
+}
+
+/// Internal helper to turn a Gröbner basis into the reduced one by removing the elements whose leading monomial is
+/// divisible by another one, and reducing every remaining element by the others.
+fn interreduce(mut basis: Vec<MultiPolynomial>) -> Vec<MultiPolynomial> {
+	let Some(order) = basis.first().map(MultiPolynomial::order) else {
+		return basis;
+	};
+
+	basis.sort_by(|lhs, rhs| order.cmp(&lhs.leading().unwrap().degrees, &rhs.leading().unwrap().degrees));
+
+	let mut minimal: Vec<MultiPolynomial> = Vec::new();
+
+	for poly in basis {
+		if !minimal
+			.iter()
+			.any(|other| other.leading().unwrap().divides(poly.leading().unwrap()))
+		{
+			minimal.push(poly);
+		}
+	}
+
+	(0..minimal.len())
+		.map(|index| {
+			let others = minimal
+				.iter()
+				.enumerate()
+				.filter(|&(other, _)| other != index)
+				.map(|(_, poly)| poly);
+			normal_form(minimal[index].clone(), &others.cloned().collect_vec()).monic()
+		})
+		.collect()
+}
+
+/// Internal helper to get the S-polynomial of two polynomials, whose leading monomials cancel.
+fn s_polynomial(lhs: &MultiPolynomial, rhs: &MultiPolynomial) -> MultiPolynomial {
+	let (lhs_leading, rhs_leading) = (lhs.leading().unwrap(), rhs.leading().unwrap());
+	let lcm = lhs_leading.lcm(rhs_leading);
+
+	lhs.clone() * &lcm.checked_div(lhs_leading).unwrap() - &(rhs.clone() * &lcm.checked_div(rhs_leading).unwrap())
+}
+
+/// Internal helper to check Buchberger's criteria for a pair, which are sufficient for its S-polynomial to reduce to
+/// zero. The product criterion applies to coprime leading monomials, and the chain criterion to a third element whose
+/// leading monomial divides their least common multiple and whose pairs with both have already been treated.
+fn skip(basis: &[MultiPolynomial], pairs: &[(usize, usize)], (lhs, rhs): (usize, usize)) -> bool {
+	let (lhs_leading, rhs_leading) = (basis[lhs].leading().unwrap(), basis[rhs].leading().unwrap());
+	let lcm = lhs_leading.lcm(rhs_leading);
+
+	if lcm.total_degree() == lhs_leading.total_degree() + rhs_leading.total_degree() {
+		return true;
+	}
+
+	let pending = |a: usize, b: usize| pairs.contains(&(a.min(b), a.max(b)));
+
+	(0..basis.len()).any(|other| {
+		other != lhs
+			&& other != rhs
+			&& basis[other].leading().unwrap().divides(&lcm)
+			&& !pending(lhs, other)
+			&& !pending(rhs, other)
+	})
+}
+
+/// Internal helper to split a triangular system at the rational roots of its minimal polynomial, where the values of
+/// the variables become constants.
+fn split(form: Vec<Number>, minimal: Polynomial, values: Vec<Polynomial>) -> Vec<TriangularSet> {
+	let roots = minimal.rational_roots().unwrap_or_default();
+	let mut sets = Vec::with_capacity(roots.len() + 1);
+	let mut rest = minimal;
+
+	for root in roots {
+		let factor = Polynomial::from(Monomial::linear(1)) - root.clone();
+		rest = rest.div_rem(&factor).unwrap().0;
+
+		sets.push(TriangularSet {
+			form: form.clone(),
+			minimal: factor,
+			values: values
+				.iter()
+				.map(|value| Polynomial::from(value.eval(&root).unwrap()))
+				.collect(),
+		});
+	}
+
+	if rest.degree().is_some_and(Number::is_positive) {
+		let values = values
+			.into_iter()
+			.map(|value| value.div_rem(&rest).unwrap().1)
+			.collect();
+
+		sets.push(TriangularSet {
+			form,
+			minimal: rest,
+			values,
+		});
+	}
+
+	sets
+}
@@ -0,0 +1,176 @@
+//! Polynomial interpolation and least-squares fitting over the rationals.
+//!
+//! Interpolating polynomials are built with Newton's divided differences, or alternatively as a sum of Lagrange basis
+//! polynomials. Hermite interpolation repeats every point once for each known derivative, where the divided
+//! differences of a repeated point are given by the derivatives themselves. Least-squares fits solve the normal
+//! equations of the Vandermonde matrix exactly, so all results have exact rational coefficients.
+
+use rug::ops::Pow;
+
+use crate::matrix::Matrix;
+use crate::monomial::Monomial;
+use crate::number::Number;
+use crate::polynomial::Polynomial;
+
+// Constructors
+impl Polynomial {
+	/// Returns the polynomial of the lowest degree that passes through the given points `(x, y)`, using Newton's
+	/// divided differences. Returns [`None`] if two points have the same `x`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::number::Number;
+	/// use abacas::polynomial::Polynomial;
+	///
+	/// let points = [(0, 1), (1, 2), (2, 5), (3, 10)].map(|(x, y)| (Number::from(x), Number::from(y)));
+	///
+	/// let poly = Polynomial::interpolate(&points).unwrap();
+	/// assert_eq!(poly.to_string(), "x^2 + 1");
+	///
+	/// let points = [(0, 0), (0, 1)].map(|(x, y)| (Number::from(x), Number::from(y)));
+	/// assert_eq!(Polynomial::interpolate(&points), None);
+	/// ```
+	pub fn interpolate(points: &[(Number, Number)]) -> Option<Self> {
+		let points = points.iter().map(|(x, y)| (x.clone(), vec![y.clone()]));
+		Self::interpolate_hermite(&points.collect::<Vec<_>>())
+	}
+
+	/// Returns the polynomial of the lowest degree that passes through the given points `(x, [y, y', y'', ...])` and
+	/// has the given derivatives at them. Returns [`None`] if two points have the same `x` or a point has no values.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::number::Number;
+	/// use abacas::polynomial::Polynomial;
+	///
+	/// // `p(0) = 0`, `p'(0) = 1` and `p(1) = 0`, `p'(1) = 1`
+	/// let points = [0, 1].map(|x| (Number::from(x), vec![Number::from(0), Number::from(1)]));
+	///
+	/// let poly = Polynomial::interpolate_hermite(&points).unwrap();
+	/// assert_eq!(poly.to_string(), "2x^3 - 3x^2 + x");
+	/// ```
+	pub fn interpolate_hermite(points: &[(Number, Vec<Number>)]) -> Option<Self> {
+		if points.iter().any(|(_, values)| values.is_empty()) {
+			return None;
+		}
+
+		if (0..points.len()).any(|index| points[..index].iter().any(|(x, _)| *x == points[index].0)) {
+			return None;
+		}
+
+		// Every point is repeated once for each of its values, together with the index of the point
+		let nodes = points
+			.iter()
+			.enumerate()
+			.flat_map(|(index, (x, values))| std::iter::repeat_n((index, x), values.len()))
+			.collect::<Vec<_>>();
+
+		// Updating the table in place leaves the divided differences `f[z_0, ..., z_k]` in the first column
+		let mut table = nodes
+			.iter()
+			.map(|&(index, _)| points[index].1[0].clone())
+			.collect::<Vec<_>>();
+		let mut coeffs = Vec::with_capacity(nodes.len());
+
+		for order in 0..nodes.len() {
+			coeffs.push(table[0].clone());
+
+			let mut factorial = Number::one();
+			(1..=order as u32 + 1).for_each(|k| factorial *= k);
+
+			for start in 0..nodes.len() - order - 1 {
+				let ((lhs, lo), (rhs, hi)) = (nodes[start], nodes[start + order + 1]);
+
+				table[start] = match lhs == rhs {
+					true => points[lhs].1[order + 1].clone() / &factorial,
+					false => (table[start + 1].clone() - &table[start]) / &(hi.clone() - lo),
+				};
+			}
+		}
+
+		// Evaluating the Newton form `c_0 + (x - z_0) (c_1 + (x - z_1) (c_2 + ...))` like Horner's method
+		let poly = coeffs
+			.into_iter()
+			.zip(&nodes)
+			.rev()
+			.fold(Self::ZERO, |acc, (coeff, (_, x))| {
+				acc * &(Self::from(Monomial::linear(1)) - (*x).clone()) + coeff
+			});
+
+		Some(poly)
+	}
+
+	/// Returns the polynomial of the lowest degree that passes through the given points `(x, y)` as a sum of Lagrange
+	/// basis polynomials. This gives the same polynomial as [`Self::interpolate`]. Returns [`None`] if two points have
+	/// the same `x`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::number::Number;
+	/// use abacas::polynomial::Polynomial;
+	///
+	/// let points = [(-1, 2), (1, 0), (2, 5)].map(|(x, y)| (Number::from(x), Number::from(y)));
+	///
+	/// let poly = Polynomial::interpolate_lagrange(&points).unwrap();
+	/// assert_eq!(poly, Polynomial::interpolate(&points).unwrap());
+	/// assert_eq!(poly.to_string(), "2x^2 - x - 1");
+	/// ```
+	pub fn interpolate_lagrange(points: &[(Number, Number)]) -> Option<Self> {
+		let mut poly = Self::ZERO;
+
+		for (index, (x, y)) in points.iter().enumerate() {
+			let mut basis = Self::from(y.clone());
+
+			for (node, _) in points[..index].iter().chain(&points[index + 1..]) {
+				if node == x {
+					return None;
+				}
+
+				basis *= &(Self::from(Monomial::linear(1)) - node.clone());
+				basis /= &(x.clone() - node);
+			}
+
+			poly += basis;
+		}
+
+		Some(poly)
+	}
+
+	/// Returns the polynomial of at most the given degree that minimizes the sum of the squared errors at the given
+	/// points `(x, y)`. Returns [`None`] if there are fewer distinct `x` than coefficients, so that the fit is not
+	/// unique.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::number::Number;
+	/// use abacas::polynomial::Polynomial;
+	///
+	/// let points = [(0, 1), (1, 3), (2, 4), (3, 4)].map(|(x, y)| (Number::from(x), Number::from(y)));
+	///
+	/// let line = Polynomial::fit(&points, 1).unwrap();
+	/// assert_eq!(line.to_string(), "x + 1.5");
+	///
+	/// // Enough coefficients interpolate the points
+	/// assert_eq!(Polynomial::fit(&points, 3), Polynomial::interpolate(&points));
+	/// assert_eq!(Polynomial::fit(&points, 4), None);
+	/// ```
+	pub fn fit(points: &[(Number, Number)], degree: usize) -> Option<Self> {
+		let vandermonde = Matrix::from_fn(points.len(), degree + 1, |row, col| {
+			points[row].0.clone().pow(col as u32)
+		});
+		let values = Matrix::column(points.iter().map(|(_, y)| y.clone()).collect());
+
+		// The normal equations `V^T V c = V^T y` have a unique solution if `V` has full column rank
+		let transpose = vandermonde.transpose();
+		let coeffs = (transpose.clone() * &vandermonde).inverse()? * &(transpose * &values);
+		let monomials = (0..=degree)
+			.filter(|&degree| !coeffs[(degree, 0)].is_zero())
+			.map(|degree| Monomial::new(coeffs[(degree, 0)].clone(), degree));
+
+		Some(Self::new(monomials))
+	}
+}
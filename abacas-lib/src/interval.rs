@@ -0,0 +1,462 @@
+//! The interval structure for verified numerics and its related operations.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+
+use rug::Float;
+use rug::float::{Constant, Round};
+use rug::ops::Pow;
+
+use crate::number::Number;
+
+/// The maximum amount of bits of the endpoints of an integer power, larger powers are not enclosed.
+const MAX_POWER_BITS: u64 = 1 << 20;
+
+/// A closed interval `[lo, hi]` of real numbers with exact rational endpoints.
+///
+/// Arithmetic operations on intervals are exact. Elementary functions are evaluated with [`Float`]s of a given
+/// precision in bits and rounded outwards, so the resulting interval always encloses every possible exact result.
+///
+/// # Examples
+///
+/// Using arithmetic operations:
+///
+/// ```
+/// use abacas::interval::Interval;
+///
+/// let a = Interval::new(1.into(), 2.into());
+/// let b = Interval::new((-1).into(), 3.into());
+///
+/// assert_eq!(a.clone() + b.clone(), Interval::new(0.into(), 5.into()));
+/// assert_eq!(a.clone() - b.clone(), Interval::new((-2).into(), 3.into()));
+/// assert_eq!(a * b, Interval::new((-2).into(), 6.into()));
+/// ```
+///
+/// Enclosing an elementary function:
+///
+/// ```
+/// use abacas::interval::Interval;
+/// use abacas::number::Number;
+///
+/// let sqrt = Interval::point(2.into()).sqrt(64).unwrap();
+///
+/// assert!(sqrt.lo() < &(Number::from(99) / 70));
+/// assert!(sqrt.hi() > &(Number::from(140) / 99));
+/// assert!(sqrt.width() < Number::from(1) / 1_000_000_000);
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Interval {
+	lo: Number,
+	hi: Number,
+}
+
+// Constants
+impl Interval {
+	/// An enclosure of Euler's number `e` with the given precision.
+	pub fn e(prec: u32) -> Self {
+		Self::point(Number::one()).exp(prec).unwrap()
+	}
+
+	/// An enclosure of the number `pi` with the given precision.
+	pub fn pi(prec: u32) -> Self {
+		let lo = Float::with_val_round(prec, Constant::Pi, Round::Down).0;
+		let hi = Float::with_val_round(prec, Constant::Pi, Round::Up).0;
+
+		Self::new(Number::from_float(&lo).unwrap(), Number::from_float(&hi).unwrap())
+	}
+}
+
+// Guards
+impl Interval {
+	/// Whether this interval contains the given number.
+	pub fn contains(&self, num: &Number) -> bool {
+		&self.lo <= num && num <= &self.hi
+	}
+
+	/// Whether this interval contains zero.
+	pub const fn contains_zero(&self) -> bool {
+		!self.lo.is_positive() && !self.hi.is_negative()
+	}
+
+	/// Whether this interval consists of a single number.
+	pub fn is_point(&self) -> bool {
+		self.lo == self.hi
+	}
+}
+
+// Operations
+impl Interval {
+	/// Gets the interval enclosing the absolute values of this interval.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::interval::Interval;
+	///
+	/// let interval = Interval::new((-3).into(), 2.into());
+	/// assert_eq!(interval.abs(), Interval::new(0.into(), 3.into()));
+	/// ```
+	pub fn abs(&self) -> Self {
+		if !self.lo.is_negative() {
+			self.clone()
+		} else if !self.hi.is_positive() {
+			-self.clone()
+		} else {
+			Self::new(Number::zero(), self.hi.clone().max(-self.lo.clone()))
+		}
+	}
+
+	/// Encloses the arctangent of this interval.
+	pub fn atan(&self, prec: u32) -> Option<Self> {
+		self.increasing(prec, |x, round| Float::with_val_round(prec, x.atan_ref(), round).0)
+	}
+
+	/// Splits this interval at its midpoint into two halves.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::interval::Interval;
+	/// use abacas::number::Number;
+	///
+	/// let (left, right) = Interval::new(0.into(), 1.into()).bisect();
+	///
+	/// assert_eq!(left.hi(), right.lo());
+	/// assert_eq!(right.width(), Number::from(1) / 2);
+	/// ```
+	pub fn bisect(&self) -> (Self, Self) {
+		let mid = self.midpoint();
+		(Self::new(self.lo.clone(), mid.clone()), Self::new(mid, self.hi.clone()))
+	}
+
+	/// Divides this interval by another one. Returns [`None`] if the divisor contains zero.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::interval::Interval;
+	/// use abacas::number::Number;
+	///
+	/// let a = Interval::new(1.into(), 2.into());
+	/// let b = Interval::new((-4).into(), (-2).into());
+	///
+	/// assert_eq!(a.checked_div(&b), Some(Interval::new((-1).into(), Number::from(-1) / 4)));
+	/// assert_eq!(b.checked_div(&Interval::new((-1).into(), 1.into())), None);
+	/// ```
+	pub fn checked_div(&self, rhs: &Self) -> Option<Self> {
+		rhs.recip().map(|recip| self.clone() * recip)
+	}
+
+	/// Encloses the cosine of this interval.
+	pub fn cos(&self, prec: u32) -> Option<Self> {
+		self.periodic(prec, Number::zero(), |x, round| {
+			Float::with_val_round(prec, x.cos_ref(), round).0
+		})
+	}
+
+	/// Encloses the exponential function of this interval. Returns [`None`] if the result overflows.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::interval::Interval;
+	/// use abacas::number::Number;
+	///
+	/// let e = Interval::point(1.into()).exp(64).unwrap();
+	///
+	/// assert!(e.lo() > &(Number::from(27182818284u64) / 10000000000u64));
+	/// assert!(e.hi() < &(Number::from(27182818285u64) / 10000000000u64));
+	/// ```
+	pub fn exp(&self, prec: u32) -> Option<Self> {
+		self.increasing(prec, |x, round| Float::with_val_round(prec, x.exp_ref(), round).0)
+	}
+
+	/// Gets the upper bound of this interval.
+	pub fn hi(&self) -> &Number {
+		&self.hi
+	}
+
+	/// Gets the smallest interval containing both intervals.
+	pub fn hull(&self, other: &Self) -> Self {
+		Self::new(
+			self.lo.clone().min(other.lo.clone()),
+			self.hi.clone().max(other.hi.clone()),
+		)
+	}
+
+	/// Internal method to enclose a monotonically increasing function, which is evaluated with the given rounding.
+	fn increasing(&self, prec: u32, f: impl Fn(&Float, Round) -> Float) -> Option<Self> {
+		let lo = f(&self.lo.to_float(prec, Round::Down), Round::Down);
+		let hi = f(&self.hi.to_float(prec, Round::Up), Round::Up);
+
+		Some(Self::new(Number::from_float(&lo)?, Number::from_float(&hi)?))
+	}
+
+	/// Gets the intersection of both intervals, or [`None`] if they are disjoint.
+	pub fn intersect(&self, other: &Self) -> Option<Self> {
+		let lo = self.lo.clone().max(other.lo.clone());
+		let hi = self.hi.clone().min(other.hi.clone());
+
+		(lo <= hi).then(|| Self::new(lo, hi))
+	}
+
+	/// Encloses the natural logarithm of this interval. Returns [`None`] if the interval is not positive.
+	pub fn ln(&self, prec: u32) -> Option<Self> {
+		if !self.lo.is_positive() {
+			return None;
+		}
+
+		self.increasing(prec, |x, round| Float::with_val_round(prec, x.ln_ref(), round).0)
+	}
+
+	/// Gets the lower bound of this interval.
+	pub fn lo(&self) -> &Number {
+		&self.lo
+	}
+
+	/// Gets the midpoint of this interval.
+	pub fn midpoint(&self) -> Number {
+		(self.lo.clone() + &self.hi) / 2
+	}
+
+	/// Creates a new interval from its bounds. Panics if `lo` is greater than `hi`.
+	pub fn new(lo: Number, hi: Number) -> Self {
+		if lo > hi {
+			panic!("lower bound must not be greater than upper bound");
+		}
+
+		Self { lo, hi }
+	}
+
+	/// Internal method to enclose a sine-like function with maxima at `(shift + 2k) * pi` and minima in between.
+	fn periodic(&self, prec: u32, shift: Number, f: impl Fn(&Float, Round) -> Float) -> Option<Self> {
+		// The function has a Lipschitz constant of one, so the conversion error is added to the rounded values
+		let point = |x: &Number| {
+			let float = x.to_float(prec, Round::Nearest);
+			let error = (Number::from_float(&float)? - x).abs();
+
+			let lo = Number::from_float(&f(&float, Round::Down))? - &error;
+			let hi = Number::from_float(&f(&float, Round::Up))? + &error;
+
+			Some(Self::new(lo, hi))
+		};
+
+		let mut result = point(&self.lo)?.hull(&point(&self.hi)?);
+
+		// In units of pi, maxima are at even and minima at odd integers
+		let turns = self.checked_div(&Self::pi(prec))? - Self::point(shift);
+		let first = turns.lo.clone().ceil();
+
+		if first <= turns.hi {
+			let even = (first.clone() / 2).is_integer();
+			let both = turns.hi.clone() - &first >= 1;
+
+			if even || both {
+				result.hi = Number::one();
+			}
+
+			if !even || both {
+				result.lo = Number::neg_one();
+			}
+		}
+
+		Self::new(Number::neg_one(), Number::one()).intersect(&result)
+	}
+
+	/// Creates an interval consisting of a single number.
+	pub fn point(num: Number) -> Self {
+		Self::new(num.clone(), num)
+	}
+
+	/// Raises this interval to a rational power. Non-integer powers use the given precision and require the interval
+	/// to be non-negative. Returns [`None`] if the power is undefined somewhere on this interval or its endpoints would
+	/// be too large.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::interval::Interval;
+	/// use abacas::number::Number;
+	///
+	/// let interval = Interval::new((-2).into(), 3.into());
+	///
+	/// assert_eq!(interval.pow(&2.into(), 64), Some(Interval::new(0.into(), 9.into())));
+	/// assert_eq!(interval.pow(&3.into(), 64), Some(Interval::new((-8).into(), 27.into())));
+	/// assert_eq!(interval.pow(&(-1).into(), 64), None);
+	///
+	/// let interval = Interval::new(4.into(), 9.into());
+	/// assert_eq!(interval.pow(&(Number::from(1) / 2), 64), Some(Interval::new(2.into(), 3.into())));
+	/// ```
+	pub fn pow(&self, exp: &Number, prec: u32) -> Option<Self> {
+		if exp.is_negative() {
+			return self.recip()?.pow(&-exp.clone(), prec);
+		}
+
+		if exp.is_zero() {
+			return Some(Self::point(Number::one()));
+		}
+
+		let (numer, denom) = exp.clone().ratio();
+		let even = (numer.clone() / 2).is_integer();
+		let degree = denom.to_u32()?;
+
+		// Exponents whose power would take too many bits are rejected before raising the endpoints
+		let bits = self.lo.bits().max(self.hi.bits());
+		if bits.saturating_mul(numer.to_u32()?.into()) > MAX_POWER_BITS {
+			return None;
+		}
+
+		// Even powers are not monotonic when the interval contains zero
+		let mut result = if even && self.contains_zero() {
+			let max = self.lo.clone().abs().max(self.hi.clone().abs());
+			Self::new(Number::zero(), max.pow(&numer))
+		} else {
+			let (lo, hi) = (self.lo.clone().pow(&numer), self.hi.clone().pow(&numer));
+			Self::new(lo.clone().min(hi.clone()), lo.max(hi))
+		};
+
+		if degree > 1 {
+			if self.lo.is_negative() {
+				return None;
+			}
+
+			result = result.increasing(prec, |x, round| {
+				Float::with_val_round(prec, x.root_ref(degree), round).0
+			})?;
+		}
+
+		Some(result)
+	}
+
+	/// Gets the reciprocal of this interval, or [`None`] if it contains zero.
+	pub fn recip(&self) -> Option<Self> {
+		if self.contains_zero() {
+			return None;
+		}
+
+		Some(Self::new(self.hi.clone().recip(), self.lo.clone().recip()))
+	}
+
+	/// Gets the sign of every number in this interval, or [`None`] if the numbers have different signs.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use std::cmp::Ordering;
+	///
+	/// use abacas::interval::Interval;
+	///
+	/// assert_eq!(Interval::new(1.into(), 2.into()).sign(), Some(Ordering::Greater));
+	/// assert_eq!(Interval::point(0.into()).sign(), Some(Ordering::Equal));
+	/// assert_eq!(Interval::new((-1).into(), 2.into()).sign(), None);
+	/// ```
+	pub fn sign(&self) -> Option<Ordering> {
+		if self.lo.is_positive() {
+			Some(Ordering::Greater)
+		} else if self.hi.is_negative() {
+			Some(Ordering::Less)
+		} else if self.lo.is_zero() && self.hi.is_zero() {
+			Some(Ordering::Equal)
+		} else {
+			None
+		}
+	}
+
+	/// Encloses the sine of this interval.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::interval::Interval;
+	/// use abacas::number::Number;
+	///
+	/// let half_pi = Interval::pi(64) * Interval::point(Number::from(1) / 2);
+	/// let sin = half_pi.sin(64).unwrap();
+	/// assert_eq!(sin.hi(), &1);
+	///
+	/// let sin = Interval::new(0.into(), 10.into()).sin(64).unwrap();
+	/// assert_eq!(sin, Interval::new((-1).into(), 1.into()));
+	/// ```
+	pub fn sin(&self, prec: u32) -> Option<Self> {
+		self.periodic(prec, Number::from(1) / 2, |x, round| {
+			Float::with_val_round(prec, x.sin_ref(), round).0
+		})
+	}
+
+	/// Encloses the square root of this interval. Returns [`None`] if the interval contains negative numbers.
+	pub fn sqrt(&self, prec: u32) -> Option<Self> {
+		self.pow(&(Number::from(1) / 2), prec)
+	}
+
+	/// Encloses the tangent of this interval. Returns [`None`] if the interval contains a pole.
+	pub fn tan(&self, prec: u32) -> Option<Self> {
+		// In units of pi, the poles are at integers after shifting by one half
+		let turns = self.checked_div(&Self::pi(prec))? - Self::point(Number::from(1) / 2);
+
+		if turns.lo.clone().ceil() <= turns.hi {
+			return None;
+		}
+
+		self.increasing(prec, |x, round| Float::with_val_round(prec, x.tan_ref(), round).0)
+	}
+
+	/// Gets the width of this interval.
+	pub fn width(&self) -> Number {
+		self.hi.clone() - &self.lo
+	}
+}
+
+impl Add<Self> for Interval {
+	type Output = Self;
+
+	fn add(self, rhs: Self) -> Self::Output {
+		Self::new(self.lo + &rhs.lo, self.hi + &rhs.hi)
+	}
+}
+
+impl Mul<Self> for Interval {
+	type Output = Self;
+
+	fn mul(self, rhs: Self) -> Self::Output {
+		let products = [
+			self.lo.clone() * &rhs.lo,
+			self.lo * &rhs.hi,
+			self.hi.clone() * &rhs.lo,
+			self.hi * &rhs.hi,
+		];
+
+		let lo = products.iter().min().unwrap().clone();
+		let hi = products.iter().max().unwrap().clone();
+
+		Self::new(lo, hi)
+	}
+}
+
+impl Neg for Interval {
+	type Output = Self;
+
+	fn neg(self) -> Self::Output {
+		Self::new(-self.hi, -self.lo)
+	}
+}
+
+impl Sub<Self> for Interval {
+	type Output = Self;
+
+	fn sub(self, rhs: Self) -> Self::Output {
+		self + -rhs
+	}
+}
+
+impl From<Number> for Interval {
+	fn from(value: Number) -> Self {
+		Self::point(value)
+	}
+}
+
+impl fmt::Display for Interval {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "[{}, {}]", self.lo, self.hi)
+	}
+}
@@ -0,0 +1,366 @@
+//! LaTeX output for expressions, polynomials and numbers.
+//!
+//! The [`Latex`] adapter mirrors the plain formatting, so sums extract the minus signs of their terms and parentheses
+//! are placed in the same spots. Products are joined with `\cdot` and factors with negative exponents are moved into
+//! the denominator of a fraction. Numbers are written exactly as integers or fractions instead of decimals.
+
+use std::fmt;
+
+use itertools::Itertools;
+
+use crate::expr::{Expr, Symbol};
+use crate::matrix::Matrix;
+use crate::monomial::Monomial;
+use crate::number::Number;
+use crate::polynomial::Polynomial;
+use crate::relation::Relation;
+
+/// Names of symbols and functions that have their own LaTeX command.
+const COMMANDS: &[&str] = &[
+	"alpha", "beta", "gamma", "delta", "epsilon", "zeta", "eta", "theta", "iota", "kappa", "lambda", "mu", "nu", "xi",
+	"pi", "rho", "sigma", "tau", "upsilon", "phi", "chi", "psi", "omega", "Gamma", "Delta", "Theta", "Lambda", "Xi",
+	"Pi", "Sigma", "Upsilon", "Phi", "Psi", "Omega", "sin", "cos", "tan", "cot", "sec", "csc", "sinh", "cosh", "tanh",
+	"coth", "arcsin", "arccos", "arctan", "exp", "ln", "log", "det", "gcd", "min", "max",
+];
+
+/// A display adapter that writes the contained value as LaTeX.
+///
+/// # Examples
+///
+/// ```
+/// use abacas::latex::Latex;
+/// use abacas::number::Number;
+/// use abacas::polynomial::Polynomial;
+///
+/// let poly: Polynomial = "0.5x^2 - x".parse().unwrap();
+/// assert_eq!(Latex(&poly).to_string(), r"\frac{1}{2}x^{2} - x");
+///
+/// let num = Number::from(-4) / 6;
+/// assert_eq!(format!("y = {}", Latex(&num)), r"y = -\frac{2}{3}");
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Latex<'a, T: ?Sized>(pub &'a T);
+
+impl Expr {
+	/// Returns this expression as LaTeX.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::expr::{Expr, Symbol};
+	/// use rug::ops::Pow;
+	///
+	/// let x = Expr::Poly(Symbol::new("x").unwrap(), "x".parse().unwrap());
+	/// let sin = Expr::Fun(Symbol::new("sin").unwrap(), vec![x.clone()]);
+	/// let (one, two) = (Expr::Num(1.into()), Expr::Num(2.into()));
+	///
+	/// let expr = sin / (x + one).pow(two);
+	/// assert_eq!(expr.to_latex(), r"\frac{\sin\left(x\right)}{\left(x + 1\right)^{2}}");
+	/// ```
+	pub fn to_latex(&self) -> String {
+		Latex(self).to_string()
+	}
+}
+
+impl Number {
+	/// Returns this number as LaTeX, which is exact for fractions.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::number::Number;
+	///
+	/// assert_eq!(Number::from(12).to_latex(), "12");
+	/// assert_eq!((Number::from(-1) / 3).to_latex(), r"-\frac{1}{3}");
+	/// ```
+	pub fn to_latex(&self) -> String {
+		Latex(self).to_string()
+	}
+}
+
+impl Polynomial {
+	/// Returns this polynomial in `x` as LaTeX.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::polynomial::Polynomial;
+	///
+	/// let poly: Polynomial = "4x^12 - 2.5x + x^0.5".parse().unwrap();
+	/// assert_eq!(poly.to_latex(), r"4x^{12} - \frac{5}{2}x + \sqrt{x}");
+	/// ```
+	pub fn to_latex(&self) -> String {
+		Latex(self).to_string()
+	}
+}
+
+impl fmt::Display for Latex<'_, Expr> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self.0 {
+			Expr::Add(exprs) => write_add(f, exprs),
+			Expr::And(exprs) if exprs.is_empty() => write!(f, r"\text{{true}}"),
+			// Disjunctions bind weaker than conjunctions
+			Expr::And(exprs) => write!(
+				f,
+				"{}",
+				exprs.iter().format_with(r" \land ", |expr, f| match expr {
+					Expr::Or(_) => f(&Parens(expr)),
+					_ => f(&Latex(expr)),
+				})
+			),
+			Expr::Fun(name, args) => write_fun(f, name, args),
+			Expr::Matrix(matrix) => write_matrix(f, matrix),
+			Expr::Mul(exprs) => write_mul(f, exprs, false),
+			Expr::Not(expr) => match **expr {
+				Expr::Rel(_, _, _) => write!(f, r"\lnot {}", Latex(&**expr)),
+				_ => write!(f, r"\lnot {}", Parens(expr)),
+			},
+			Expr::Num(num) => write_number(f, num, false),
+			Expr::Or(exprs) if exprs.is_empty() => write!(f, r"\text{{false}}"),
+			Expr::Or(exprs) => write!(f, "{}", exprs.iter().map(Latex).format(r" \lor ")),
+			Expr::Piecewise(branches) => {
+				let branches = branches.iter().format_with(r" \\ ", |(value, condition), f| {
+					f(&format_args!("{} & {}", Latex(value), Latex(condition)))
+				});
+
+				write!(f, r"\begin{{cases}} {branches} \end{{cases}}")
+			}
+			Expr::Poly(sym, poly) => write_poly(f, poly, sym, false),
+			Expr::Pow(base, exp) => write_pow(f, base, exp),
+			Expr::Rel(rel, lhs, rhs) => {
+				let side = |expr: &Expr| match expr.is_logical() {
+					true => Parens(expr).to_string(),
+					false => Latex(expr).to_string(),
+				};
+
+				let rel = match rel {
+					Relation::Eq => "=",
+					Relation::Ge => r"\geq",
+					Relation::Gt => ">",
+					Relation::Le => r"\leq",
+					Relation::Lt => "<",
+					Relation::Ne => r"\neq",
+				};
+
+				write!(f, "{} {rel} {}", side(lhs), side(rhs))
+			}
+		}
+	}
+}
+
+impl fmt::Display for Latex<'_, Number> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write_number(f, self.0, false)
+	}
+}
+
+impl fmt::Display for Latex<'_, Polynomial> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write_poly(f, self.0, &Symbol::new("x").unwrap(), false)
+	}
+}
+
+/// Internal adapter to write an expression as LaTeX with parentheses if necessary, like `Expr::with_parens`.
+struct Parens<'a>(&'a Expr);
+
+impl fmt::Display for Parens<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self.0.needs_parens() {
+			true => write!(f, r"\left({}\right)", Latex(self.0)),
+			false => write!(f, "{}", Latex(self.0)),
+		}
+	}
+}
+
+/// Internal helper to write a sum, extracting the minus signs of negative terms like `Expr::write_add`.
+fn write_add(f: &mut fmt::Formatter<'_>, exprs: &[Expr]) -> fmt::Result {
+	// Format the first expression normally
+	if let Some(first) = exprs.first() {
+		write!(f, "{}", Latex(first))?;
+	}
+
+	for expr in exprs.iter().skip(1) {
+		match expr {
+			// If the number is negative, extract the minus
+			Expr::Num(num) if num.is_negative() => {
+				write!(f, " - ")?;
+				write_number(f, num, true)?;
+			}
+
+			// If the polynomial has a negative leading coefficient, extract the minus
+			Expr::Poly(sym, poly) if poly.leading().is_some_and(Number::is_negative) => {
+				write!(f, " - ")?;
+				write_poly(f, poly, sym, true)?;
+			}
+
+			// If the product has a negative coefficient, extract the minus
+			Expr::Mul(exprs) if Expr::split_fraction(exprs).0 => {
+				write!(f, " - ")?;
+				write_mul(f, exprs, true)?;
+			}
+
+			// Otherwise, write the expression normally
+			_ => write!(f, " + {}", Latex(expr))?,
+		}
+	}
+
+	Ok(())
+}
+
+/// Internal helper to write a function call, using LaTeX commands for known functions.
+fn write_fun(f: &mut fmt::Formatter<'_>, name: &Symbol, args: &[Expr]) -> fmt::Result {
+	match (name.name(), args) {
+		("abs", [arg]) => write!(f, r"\left|{}\right|", Latex(arg)),
+		("sqrt", [arg]) => write!(f, r"\sqrt{{{}}}", Latex(arg)),
+		("factorial", [arg]) => write!(f, "{}!", BaseParens(arg)),
+		("asin" | "acos" | "atan", _) => {
+			let name = Symbol::new(name.name().replacen('a', "arc", 1)).unwrap();
+			write_fun(f, &name, args)
+		}
+		(name, _) => {
+			match COMMANDS.contains(&name) {
+				true => write!(f, r"\{name}")?,
+				false => write!(f, r"\operatorname{{{name}}}")?,
+			}
+
+			write!(f, r"\left({}\right)", args.iter().map(Latex).format(", "))
+		}
+	}
+}
+
+/// Internal helper to write a matrix as a LaTeX `pmatrix` environment.
+fn write_matrix(f: &mut fmt::Formatter<'_>, matrix: &Matrix<Expr>) -> fmt::Result {
+	let rows = (0..matrix.rows()).map(|row| matrix.row(row).iter().map(Latex).join(" & "));
+	write!(f, r"\begin{{pmatrix}} {} \end{{pmatrix}}", rows.format(r" \\ "))
+}
+
+/// Internal helper to write a monomial, optionally without its sign.
+fn write_monomial(f: &mut fmt::Formatter<'_>, mono: &Monomial, sym: &Symbol, abs: bool) -> fmt::Result {
+	if mono.degree.is_zero() {
+		return write_number(f, &mono.coeff, abs);
+	}
+
+	if mono.coeff.is_neg_one() && !abs {
+		write!(f, "-")?;
+	} else if !mono.coeff.is_neg_one() && !mono.coeff.is_one() {
+		write_number(f, &mono.coeff, abs)?;
+	}
+
+	write_power(f, &LatexSymbol(sym), &LatexSymbol(sym), &mono.degree)
+}
+
+/// Internal helper to write a product as a fraction of the factors with positive and negative exponents, optionally
+/// without the sign of its numeric coefficient.
+fn write_mul(f: &mut fmt::Formatter<'_>, exprs: &[Expr], abs: bool) -> fmt::Result {
+	let (negative, numer, denom) = Expr::split_fraction(exprs);
+
+	if negative && !abs {
+		write!(f, "-")?;
+	}
+
+	// Parentheses are only needed around factors of a product, or after a minus sign that may have been extracted
+	let factors = |factors: &[Expr], sign: bool| match factors {
+		[] => "1".to_owned(),
+		[factor] if !sign => Latex(factor).to_string(),
+		_ => factors.iter().map(Parens).join(r" \cdot "),
+	};
+
+	match denom.is_empty() {
+		true => write!(f, "{}", factors(&numer, negative)),
+		false => write!(f, r"\frac{{{}}}{{{}}}", factors(&numer, false), factors(&denom, false)),
+	}
+}
+
+/// Internal helper to write a number exactly, optionally without its sign.
+fn write_number(f: &mut fmt::Formatter<'_>, num: &Number, abs: bool) -> fmt::Result {
+	if num.is_negative() && !abs {
+		write!(f, "-")?;
+	}
+
+	let (numer, denom) = num.clone().abs().ratio();
+
+	match denom.is_one() {
+		true => write!(f, "{}", numer.to_integer().unwrap()),
+		false => write!(
+			f,
+			r"\frac{{{}}}{{{}}}",
+			numer.to_integer().unwrap(),
+			denom.to_integer().unwrap()
+		),
+	}
+}
+
+/// Internal helper to write a polynomial in the given variable, optionally without the sign of its leading term.
+fn write_poly(f: &mut fmt::Formatter<'_>, poly: &Polynomial, sym: &Symbol, abs: bool) -> fmt::Result {
+	let mut monomials = poly.monomials();
+
+	match monomials.next() {
+		Some(first) => write_monomial(f, first, sym, abs)?,
+		None => write!(f, "0")?,
+	}
+
+	for mono in monomials {
+		match mono.coeff.is_negative() {
+			true => write!(f, " - ")?,
+			false => write!(f, " + ")?,
+		}
+
+		write_monomial(f, mono, sym, true)?;
+	}
+
+	Ok(())
+}
+
+/// Internal helper to write a power, using a fraction for negative exponents and roots for unit fractions.
+fn write_pow(f: &mut fmt::Formatter<'_>, base: &Expr, exp: &Expr) -> fmt::Result {
+	match exp {
+		Expr::Num(num) if num.is_negative() => {
+			write_mul(f, &[Expr::Pow(base.clone().into(), exp.clone().into())], false)
+		}
+		Expr::Num(num) => write_power(f, &Latex(base), &BaseParens(base), num),
+		_ => write!(f, "{}^{{{}}}", BaseParens(base), Latex(exp)),
+	}
+}
+
+/// Internal helper to write a power with a numeric exponent, using roots for unit fractions. The base is given both
+/// plainly for roots and with parentheses for exponents.
+fn write_power(
+	f: &mut fmt::Formatter<'_>,
+	base: &dyn fmt::Display,
+	parens: &dyn fmt::Display,
+	exp: &Number,
+) -> fmt::Result {
+	match exp.root_index() {
+		_ if exp.is_one() => write!(f, "{base}"),
+		Some(2) => write!(f, r"\sqrt{{{base}}}"),
+		Some(index) => write!(f, r"\sqrt[{index}]{{{base}}}"),
+		None => write!(f, "{parens}^{{{}}}", Latex(exp)),
+	}
+}
+
+/// Internal adapter to write the base of a power or factorial with parentheses if necessary.
+struct BaseParens<'a>(&'a Expr);
+
+impl fmt::Display for BaseParens<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self.0.needs_base_parens() {
+			true => write!(f, r"\left({}\right)", Latex(self.0)),
+			false => write!(f, "{}", Latex(self.0)),
+		}
+	}
+}
+
+/// Internal adapter to write a symbol, using LaTeX commands for Greek letters and upright text for longer names.
+struct LatexSymbol<'a>(&'a Symbol);
+
+impl fmt::Display for LatexSymbol<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let name = self.0.name();
+
+		match name.chars().count() {
+			_ if COMMANDS.contains(&name) => write!(f, r"\{name}"),
+			1 => write!(f, "{name}"),
+			_ => write!(f, r"\mathrm{{{name}}}"),
+		}
+	}
+}
@@ -1,14 +1,35 @@
 #![doc = include_str!("../../README.md")]
 #![warn(missing_docs)]
 
+pub mod assumption;
+pub mod calculus;
+pub mod combinatorics;
 pub mod context;
 pub mod error;
 pub mod expr;
 pub mod function;
+pub mod groebner;
+pub mod interpolation;
+pub mod interval;
+pub mod latex;
+pub mod limit;
+pub mod linsolve;
+pub mod log;
+pub mod mathml;
+pub mod matrix;
 pub mod monomial;
+pub mod multivariate;
 pub mod number;
+pub mod pattern;
 pub mod polynomial;
+pub mod pretty;
+pub mod relation;
+pub mod series;
+pub mod set;
+pub mod special;
 pub mod stdlib;
+pub mod summation;
+pub mod trig;
 
 /// The library version currently in use.
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
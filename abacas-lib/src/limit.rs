@@ -0,0 +1,800 @@
+//! Limits of expressions at points and at infinity.
+//!
+//! Limits at finite points are first read off the leading term of a series expansion. Otherwise, every limit is
+//! reduced to a limit at positive infinity by substitution, for example `x -> a+` becomes `a + 1/x` with `x -> inf`.
+//! Subexpressions are then evaluated to the values they approach, resolving indeterminate forms by comparing growth
+//! orders or with L'Hôpital's rule. As expressions, the symbol `inf` stands for infinity and `undefined` for a limit
+//! that does not exist.
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
+
+use itertools::Itertools;
+use rug::ops::Pow;
+
+use crate::context::Context;
+use crate::error::SimplifyError;
+use crate::expr::{Expr, Symbol};
+use crate::monomial::Monomial;
+use crate::number::Number;
+use crate::polynomial::Polynomial;
+use crate::summation::{evaluate, rational};
+
+/// The maximum depth of nested rewrites of indeterminate forms.
+const MAX_DEPTH: u32 = 4;
+
+/// The direction a finite point is approached from.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Direction {
+	/// From both sides, where both one-sided limits have to agree.
+	Both,
+	/// From below.
+	Left,
+	/// From above.
+	Right,
+}
+
+/// The point a limit is taken at.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Point {
+	/// A finite point.
+	Finite(Expr),
+	/// Positive infinity.
+	Infinity,
+	/// Negative infinity.
+	NegInfinity,
+}
+
+/// The result of a limit.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Limit {
+	/// The limit does not exist, for example because the one-sided limits differ or the expression oscillates.
+	DoesNotExist,
+	/// A finite limit.
+	Finite(Expr),
+	/// The expression grows without bound.
+	Infinity,
+	/// The expression decreases without bound.
+	NegInfinity,
+}
+
+/// The value a subexpression approaches as the variable goes to positive infinity.
+#[derive(Clone, Debug)]
+enum Value {
+	/// A bounded value oscillating without a limit, like `sin(x)`.
+	Bounded,
+	/// A finite, non-zero value.
+	Finite(Expr),
+	/// Positive or negative infinity.
+	Infinite(Ordering),
+	/// Zero, approached from above or below if the side is known, or [`Ordering::Equal`] if it is exactly zero.
+	Zero(Option<Ordering>),
+}
+
+/// The growth order of a factor as the variable goes to positive infinity, along with its eventual sign.
+enum Growth {
+	/// A factor with a finite, non-zero limit.
+	Constant(Expr),
+	/// An exponential `exp(u)` with the exponent `u`.
+	Exponential(Expr),
+	/// A logarithm `ln(u)^q` of an expression `u` going to infinity, with the power `q`.
+	Logarithmic(Number),
+	/// A power `c x^d` with the degree `d` and the coefficient `c`.
+	Power(Number, Expr),
+}
+
+/// Takes the limit of an expression as the symbol `var` approaches a point from the given direction. Returns
+/// [`None`] if the limit could not be determined.
+///
+/// # Examples
+///
+/// ```
+/// use abacas::context::Context;
+/// use abacas::expr::{Expr, Symbol};
+/// use abacas::limit::{Direction, Limit, Point, limit};
+///
+/// let ctx = &mut Context::new();
+/// let x = Symbol::new("x").unwrap();
+///
+/// // The removable singularity of `sin(x) / x`
+/// let poly = Expr::Poly(x.clone(), "x".parse().unwrap());
+/// let expr = Expr::Fun(Symbol::new("sin").unwrap(), vec![poly.clone()]) / poly.clone();
+///
+/// let result = limit(expr, &x, Point::Finite(Expr::zero()), Direction::Both, ctx).unwrap();
+/// assert_eq!(result, Some(Limit::Finite(Expr::one())));
+///
+/// // One-sided limits of `1 / x`
+/// let expr = Expr::Poly(x.clone(), "x^-1".parse().unwrap());
+///
+/// let result = limit(expr.clone(), &x, Point::Finite(Expr::zero()), Direction::Left, ctx).unwrap();
+/// assert_eq!(result, Some(Limit::NegInfinity));
+///
+/// let result = limit(expr, &x, Point::Finite(Expr::zero()), Direction::Both, ctx).unwrap();
+/// assert_eq!(result, Some(Limit::DoesNotExist));
+/// ```
+pub fn limit(
+	expr: Expr,
+	var: &Symbol,
+	point: Point,
+	direction: Direction,
+	ctx: &mut Context,
+) -> Result<Option<Limit>, SimplifyError> {
+	let expr = expr.simplify(ctx)?;
+
+	if let Point::Finite(point) = &point
+		&& let Some(limit) = series(&expr, var, point, direction)
+	{
+		return Ok(Some(limit));
+	}
+
+	let x = Expr::Poly(var.clone(), Monomial::linear(1).into());
+	let inverse = Expr::Poly(var.clone(), Monomial::new(1, -1).into());
+
+	// Substitutions that turn the limit into one at positive infinity
+	let sides = match (point, direction) {
+		(Point::Infinity, _) => vec![x],
+		(Point::NegInfinity, _) => vec![-x],
+		(Point::Finite(point), Direction::Right) => vec![point + inverse],
+		(Point::Finite(point), Direction::Left) => vec![point - inverse],
+		(Point::Finite(point), Direction::Both) => vec![point.clone() + inverse.clone(), point - inverse],
+	};
+
+	let mut limits = Vec::with_capacity(sides.len());
+
+	for side in sides {
+		let expr = expr.substitute(var, &side).simplify(ctx)?;
+
+		let Some(value) = value(&expr, var, 0, ctx)? else {
+			return Ok(None);
+		};
+
+		limits.push(match value {
+			Value::Bounded => Limit::DoesNotExist,
+			Value::Finite(expr) => Limit::Finite(expr),
+			Value::Infinite(Ordering::Less) => Limit::NegInfinity,
+			Value::Infinite(_) => Limit::Infinity,
+			Value::Zero(_) => Limit::Finite(Expr::zero()),
+		});
+	}
+
+	// Both one-sided limits have to agree
+	match limits.into_iter().all_equal_value() {
+		Ok(limit) => Ok(Some(limit)),
+		Err(_) => Ok(Some(Limit::DoesNotExist)),
+	}
+}
+
+/// Internal helper to get the value of a sum, resolving `inf - inf` by factoring out one of the infinite terms.
+fn add(terms: &[Expr], var: &Symbol, depth: u32, ctx: &mut Context) -> Result<Option<Value>, SimplifyError> {
+	let mut values = Vec::with_capacity(terms.len());
+
+	for term in terms {
+		let Some(value) = value(term, var, depth, ctx)? else {
+			return Ok(None);
+		};
+
+		values.push(value);
+	}
+
+	let infinite = |sign| {
+		values
+			.iter()
+			.any(|value| matches!(value, Value::Infinite(other) if *other == sign))
+	};
+
+	match (infinite(Ordering::Greater), infinite(Ordering::Less)) {
+		(true, true) => {
+			if depth >= MAX_DEPTH {
+				return Ok(None);
+			}
+
+			// Rewrite `f + g + ...` as `f (1 + g / f + ...)`, trying each infinite term as `f`
+			for (term, value) in terms.iter().zip(&values) {
+				if !matches!(value, Value::Infinite(_)) {
+					continue;
+				}
+
+				let quotients = terms.iter().map(|other| other.clone() / term.clone());
+				let rest = Expr::Add(quotients.collect()).simplify(ctx)?;
+
+				if let Some(value) = mul(&[term.clone(), rest], var, depth + 1, ctx)? {
+					return Ok(Some(value));
+				}
+			}
+
+			return Ok(None);
+		}
+		(true, false) => return Ok(Some(Value::Infinite(Ordering::Greater))),
+		(false, true) => return Ok(Some(Value::Infinite(Ordering::Less))),
+		(false, false) => {}
+	}
+
+	if values.iter().any(|value| matches!(value, Value::Bounded)) {
+		return Ok(Some(Value::Bounded));
+	}
+
+	// Finite values are added, zeros only matter for the side they are approached from
+	let mut finite = Vec::new();
+	let mut sides = Vec::new();
+
+	for value in values {
+		match value {
+			Value::Finite(expr) => finite.push(expr),
+			Value::Zero(side) => sides.push(side),
+			_ => unreachable!(),
+		}
+	}
+
+	if finite.is_empty() {
+		let side = sides
+			.into_iter()
+			.filter(|side| *side != Some(Ordering::Equal))
+			.all_equal_value()
+			.unwrap_or_else(|err| err.0.map_or(Some(Ordering::Equal), |_| None));
+
+		return Ok(Some(Value::Zero(side)));
+	}
+
+	finite_value(Expr::Add(finite), ctx).map(Some)
+}
+
+/// Internal helper to classify the growth order of a factor, or [`None`] if it has no known growth order.
+fn classify(
+	factor: &Expr,
+	value: &Value,
+	var: &Symbol,
+	depth: u32,
+	ctx: &mut Context,
+) -> Result<Option<(Growth, Option<Ordering>)>, SimplifyError> {
+	let goes_to_infinity = |expr: &Expr, ctx: &mut Context| -> Result<bool, SimplifyError> {
+		let value = crate::limit::value(expr, var, depth, ctx)?;
+		Ok(matches!(value, Some(Value::Infinite(_))))
+	};
+
+	let growth = match factor {
+		_ if let Value::Finite(expr) = value => (Growth::Constant(expr.clone()), expr.eval_sign(&HashMap::new())),
+		_ if let Some((degree, coeff)) = leading(factor, var) => {
+			let sign = coeff.eval_sign(&HashMap::new());
+			(Growth::Power(degree, coeff), sign)
+		}
+
+		Expr::Fun(name, args)
+			if let [arg] = args.as_slice()
+				&& name.name() == "exp"
+				&& goes_to_infinity(arg, ctx)? =>
+		{
+			(Growth::Exponential(arg.clone()), Some(Ordering::Greater))
+		}
+
+		Expr::Fun(name, args)
+			if let [arg] = args.as_slice()
+				&& name.name() == "ln"
+				&& goes_to_infinity(arg, ctx)? =>
+		{
+			(Growth::Logarithmic(Number::one()), Some(Ordering::Greater))
+		}
+
+		Expr::Fun(name, args)
+			if let [arg] = args.as_slice()
+				&& name.name() == "sqrt" =>
+		{
+			let power = arg.clone().pow(Expr::Num(Number::from(1) / 2));
+			return classify(&power, value, var, depth, ctx);
+		}
+
+		// Powers with numeric exponents scale the growth order of the base
+		Expr::Pow(base, exp) if let Expr::Num(exp) = exp.as_ref() => {
+			let Some(inner) = crate::limit::value(base, var, depth, ctx)? else {
+				return Ok(None);
+			};
+
+			let Some((growth, sign)) = classify(base, &inner, var, depth, ctx)? else {
+				return Ok(None);
+			};
+
+			let power = |expr: Expr| expr.pow(Expr::Num(exp.clone()));
+
+			let growth = match growth {
+				Growth::Constant(coeff) => Growth::Constant(power(coeff)),
+				Growth::Exponential(exponent) => {
+					Growth::Exponential((exponent * Expr::Num(exp.clone())).simplify(ctx)?)
+				}
+				Growth::Logarithmic(degree) => Growth::Logarithmic(degree * exp),
+				Growth::Power(degree, coeff) => Growth::Power(degree * exp, power(coeff)),
+			};
+
+			(growth, sign.and_then(|sign| parity(sign, exp)))
+		}
+
+		// Powers of positive constants are exponentials with the exponent `u ln(c)`
+		Expr::Pow(base, exp) if !base.has_symbol(var) && base.eval_sign(&HashMap::new()) == Some(Ordering::Greater) => {
+			let exponent = exp.as_ref().clone() * Expr::Fun(Symbol::new("ln").unwrap(), vec![base.as_ref().clone()]);
+			(Growth::Exponential(exponent.simplify(ctx)?), Some(Ordering::Greater))
+		}
+
+		_ => return Ok(None),
+	};
+
+	Ok(Some(growth))
+}
+
+/// Internal helper to create the value of a finite limit, evaluating calls to the StdLib like `exp(0)`.
+fn finite_value(expr: Expr, ctx: &mut Context) -> Result<Value, SimplifyError> {
+	let expr = evaluate(expr, ctx)?;
+
+	if expr.is_num_and(Number::is_zero) {
+		return Ok(Value::Zero(None));
+	}
+
+	Ok(Value::Finite(expr))
+}
+
+/// Internal helper to get the value of a function call from the value of its argument.
+fn fun(
+	name: &Symbol,
+	args: &[Expr],
+	var: &Symbol,
+	depth: u32,
+	ctx: &mut Context,
+) -> Result<Option<Value>, SimplifyError> {
+	let [arg] = args else {
+		return Ok(None);
+	};
+
+	// Square roots are powers
+	if name.name() == "sqrt" {
+		let power = arg.clone().pow(Expr::Num(Number::from(1) / 2)).simplify(ctx)?;
+		return value(&power, var, depth, ctx);
+	}
+
+	let Some(inner) = value(arg, var, depth, ctx)? else {
+		return Ok(None);
+	};
+
+	let value = match (name.name(), inner) {
+		// The elementary functions are continuous inside their domain
+		("exp" | "ln" | "sin" | "cos" | "tan" | "atan", Value::Finite(expr)) => {
+			return finite_value(Expr::Fun(name.clone(), vec![expr]), ctx).map(Some);
+		}
+
+		("exp", Value::Zero(_)) | ("cos", Value::Zero(_)) => Value::Finite(Expr::one()),
+		("exp", Value::Infinite(Ordering::Less)) => Value::Zero(Some(Ordering::Greater)),
+		("exp" | "ln", Value::Infinite(Ordering::Greater)) => Value::Infinite(Ordering::Greater),
+		("exp" | "sin" | "cos" | "atan", Value::Bounded) => Value::Bounded,
+		("ln", Value::Zero(Some(Ordering::Greater))) => Value::Infinite(Ordering::Less),
+		("sin" | "tan" | "atan", Value::Zero(side)) => Value::Zero(side),
+		("sin" | "cos", Value::Infinite(_)) => Value::Bounded,
+
+		// The inverse tangent approaches `pi / 2` or `-pi / 2`
+		("atan", Value::Infinite(sign)) => {
+			let half = if sign == Ordering::Less { "-1/2x" } else { "1/2x" };
+			Value::Finite(Expr::Poly(Symbol::new("pi").unwrap(), half.parse().unwrap()))
+		}
+
+		_ => return Ok(None),
+	};
+
+	Ok(Some(value))
+}
+
+/// Internal helper to resolve the indeterminate product `inf * 0` by comparing growth orders, where exponentials
+/// dominate powers, which dominate logarithms.
+fn growth(
+	factors: &[Expr],
+	values: &[Value],
+	var: &Symbol,
+	depth: u32,
+	ctx: &mut Context,
+) -> Result<Option<Value>, SimplifyError> {
+	let (mut exponents, mut degree, mut logarithm) = (Vec::new(), Number::zero(), Number::zero());
+	let (mut coeffs, mut logarithmic, mut sign) = (Vec::new(), false, Some(Ordering::Greater));
+
+	for (factor, value) in factors.iter().zip(values) {
+		let Some((growth, factor_sign)) = classify(factor, value, var, depth, ctx)? else {
+			return Ok(None);
+		};
+
+		sign = sign.zip(factor_sign).map(|(lhs, rhs)| mul_signs(lhs, rhs));
+
+		match growth {
+			Growth::Constant(coeff) => coeffs.push(coeff),
+			Growth::Exponential(exponent) => exponents.push(exponent),
+			Growth::Logarithmic(power) => {
+				logarithm += &power;
+				logarithmic = true;
+			}
+			Growth::Power(power, coeff) => {
+				degree += &power;
+				coeffs.push(coeff);
+			}
+		}
+	}
+
+	let Some(sign) = sign else {
+		return Ok(None);
+	};
+
+	// The first growth order that does not cancel decides the limit
+	let from_order = |order: Ordering| match order {
+		Ordering::Greater => Some(Value::Infinite(sign)),
+		Ordering::Less => Some(Value::Zero(Some(sign))),
+		Ordering::Equal => None,
+	};
+
+	if !exponents.is_empty() {
+		let exponent = Expr::Add(exponents).simplify(ctx)?;
+
+		match value(&exponent, var, depth, ctx)? {
+			Some(Value::Infinite(order)) => return Ok(from_order(order)),
+			Some(Value::Finite(expr)) => coeffs.push(Expr::Fun(Symbol::new("exp").unwrap(), vec![expr])),
+			Some(Value::Zero(_)) => {}
+			_ => return Ok(None),
+		}
+	}
+
+	if let Some(value) = from_order(degree.cmp(&Number::zero())) {
+		return Ok(Some(value));
+	}
+
+	// Logarithms with cancelling powers like `ln(2x) / ln(x)` need not approach their coefficients
+	if logarithmic {
+		return Ok(from_order(logarithm.cmp(&Number::zero())));
+	}
+
+	// Without logarithms, the product approaches the product of the leading coefficients
+	finite_value(Expr::Mul(coeffs), ctx).map(Some)
+}
+
+/// Internal helper to get the leading term `c x^d` of an expression built from powers of `var`, as the degree `d`
+/// and the coefficient `c`. Returns [`None`] for other expressions or if the leading terms of a sum cancel.
+fn leading(expr: &Expr, var: &Symbol) -> Option<(Number, Expr)> {
+	match expr {
+		_ if !expr.has_symbol(var) => Some((Number::zero(), expr.clone())),
+
+		Expr::Add(terms) => {
+			let terms = terms
+				.iter()
+				.map(|term| leading(term, var))
+				.collect::<Option<Vec<_>>>()?;
+			let degree = terms.iter().map(|(degree, _)| degree).max()?.clone();
+
+			let coeffs = terms
+				.into_iter()
+				.filter(|(other, _)| *other == degree)
+				.map(|(_, coeff)| coeff);
+			let coeff = Expr::Add(coeffs.collect()).simplify(&mut Context::new()).ok()?;
+
+			(!coeff.is_num_and(Number::is_zero)).then_some((degree, coeff))
+		}
+
+		Expr::Mul(factors) => factors
+			.iter()
+			.try_fold((Number::zero(), Expr::one()), |(degree, coeff), factor| {
+				let (factor_degree, factor_coeff) = leading(factor, var)?;
+				Some((degree + &factor_degree, coeff * factor_coeff))
+			}),
+
+		Expr::Poly(_, poly) => {
+			let lead = poly.monomials().next()?;
+			Some((lead.degree.clone(), Expr::Num(lead.coeff.clone())))
+		}
+
+		Expr::Pow(base, exp) if let Expr::Num(exp) = exp.as_ref() => {
+			let (degree, coeff) = leading(base, var)?;
+			Some((degree * exp, coeff.pow(Expr::Num(exp.clone()))))
+		}
+
+		_ => None,
+	}
+}
+
+/// Internal helper to get the limit of `numer / denom` with L'Hôpital's rule, which requires both to approach zero or
+/// both to approach infinity.
+fn lhopital(
+	numer: Expr,
+	denom: Expr,
+	var: &Symbol,
+	depth: u32,
+	ctx: &mut Context,
+) -> Result<Option<Value>, SimplifyError> {
+	let quotient = (numer.derivative(var) / denom.derivative(var)).simplify(ctx)?;
+	value(&quotient, var, depth + 1, ctx)
+}
+
+/// Internal helper to get the value of a product, resolving `inf * 0` by comparing growth orders or with L'Hôpital's
+/// rule.
+fn mul(factors: &[Expr], var: &Symbol, depth: u32, ctx: &mut Context) -> Result<Option<Value>, SimplifyError> {
+	let mut values = Vec::with_capacity(factors.len());
+
+	for factor in factors {
+		let Some(value) = value(factor, var, depth, ctx)? else {
+			return Ok(None);
+		};
+
+		values.push(value);
+	}
+
+	let infinite = values.iter().any(|value| matches!(value, Value::Infinite(_)));
+	let zero = values.iter().any(|value| matches!(value, Value::Zero(_)));
+
+	if infinite && values.iter().any(|value| matches!(value, Value::Bounded)) {
+		return Ok(None);
+	}
+
+	if !(infinite && zero) {
+		return product(values, ctx);
+	}
+
+	if let Some(value) = growth(factors, &values, var, depth, ctx)? {
+		return Ok(Some(value));
+	}
+
+	if depth >= MAX_DEPTH {
+		return Ok(None);
+	}
+
+	// Rewrite as the quotients `0 / 0` and `inf / inf` for L'Hôpital's rule, keeping finite factors aside
+	let (mut zeros, mut infinites, mut finite) = (Vec::new(), Vec::new(), Vec::new());
+
+	for (factor, value) in factors.iter().zip(values) {
+		match value {
+			Value::Zero(_) => zeros.push(factor.clone()),
+			Value::Infinite(_) => infinites.push(factor.clone()),
+			value => finite.push(value),
+		}
+	}
+
+	let (zeros, infinites) = (Expr::Mul(zeros), Expr::Mul(infinites));
+	let quotients = [
+		(infinites.clone(), zeros.clone().pow(Expr::neg_one())),
+		(zeros, infinites.pow(Expr::neg_one())),
+	];
+
+	for (numer, denom) in quotients {
+		let denom = denom.simplify(ctx)?;
+
+		if let Some(value) = lhopital(numer, denom, var, depth, ctx)? {
+			finite.push(value);
+			return product(finite, ctx);
+		}
+	}
+
+	Ok(None)
+}
+
+/// Internal helper to multiply two signs.
+fn mul_signs(lhs: Ordering, rhs: Ordering) -> Ordering {
+	match (lhs, rhs) {
+		(Ordering::Equal, _) | (_, Ordering::Equal) => Ordering::Equal,
+		_ if lhs == rhs => Ordering::Greater,
+		_ => Ordering::Less,
+	}
+}
+
+/// Internal helper to get the sign of a power with a rational exponent, or [`None`] for a negative base and an exponent
+/// that is not an integer.
+fn parity(sign: Ordering, exp: &Number) -> Option<Ordering> {
+	match sign {
+		Ordering::Less if !exp.is_integer() => None,
+		Ordering::Less if (exp.clone() / 2).is_integer() => Some(Ordering::Greater),
+		sign => Some(sign),
+	}
+}
+
+/// Internal helper to get the value of a power.
+fn pow(base: &Expr, exp: &Expr, var: &Symbol, depth: u32, ctx: &mut Context) -> Result<Option<Value>, SimplifyError> {
+	let Some(inner) = value(base, var, depth, ctx)? else {
+		return Ok(None);
+	};
+
+	// Other powers are only defined for positive bases and rewritten as `exp(e ln(b))`
+	let Expr::Num(exp) = exp else {
+		let positive = match &inner {
+			Value::Finite(expr) => expr.eval_sign(&HashMap::new()) == Some(Ordering::Greater),
+			Value::Infinite(sign) | Value::Zero(Some(sign)) => *sign == Ordering::Greater,
+			_ => false,
+		};
+
+		if !positive {
+			return Ok(None);
+		}
+
+		let ln = Expr::Fun(Symbol::new("ln").unwrap(), vec![base.clone()]);
+		let power = Expr::Fun(Symbol::new("exp").unwrap(), vec![exp.clone() * ln]);
+
+		return value(&power.simplify(ctx)?, var, depth, ctx);
+	};
+
+	let value = match inner {
+		Value::Bounded if exp.is_positive() => Value::Bounded,
+		Value::Finite(expr) => return finite_value(expr.pow(Expr::Num(exp.clone())), ctx).map(Some),
+		Value::Infinite(sign) if let Some(sign) = parity(sign, exp) => match exp.is_positive() {
+			true => Value::Infinite(sign),
+			false => Value::Zero(Some(sign)),
+		},
+		Value::Zero(side) if exp.is_positive() => Value::Zero(side.and_then(|side| parity(side, exp))),
+		Value::Zero(Some(side))
+			if side != Ordering::Equal
+				&& let Some(sign) = parity(side, exp) =>
+		{
+			Value::Infinite(sign)
+		}
+		_ => return Ok(None),
+	};
+
+	Ok(Some(value))
+}
+
+/// Internal helper to multiply values that do not form an indeterminate product.
+fn product(values: Vec<Value>, ctx: &mut Context) -> Result<Option<Value>, SimplifyError> {
+	let mut finite = Vec::new();
+	let (mut sign, mut zero, mut infinite, mut bounded) = (Some(Ordering::Greater), false, false, false);
+
+	for value in values {
+		let factor_sign = match value {
+			Value::Bounded => {
+				bounded = true;
+				None
+			}
+			Value::Finite(expr) => {
+				let factor_sign = expr.eval_sign(&HashMap::new());
+				finite.push(expr);
+				factor_sign
+			}
+			Value::Infinite(factor_sign) => {
+				infinite = true;
+				Some(factor_sign)
+			}
+			Value::Zero(side) => {
+				zero = true;
+				side
+			}
+		};
+
+		sign = sign.zip(factor_sign).map(|(lhs, rhs)| mul_signs(lhs, rhs));
+	}
+
+	let value = match (zero, infinite, bounded) {
+		(true, _, _) => Value::Zero(sign),
+		(false, true, _) => {
+			let Some(sign) = sign else {
+				return Ok(None);
+			};
+
+			Value::Infinite(sign)
+		}
+		(false, false, true) => Value::Bounded,
+		(false, false, false) => return finite_value(Expr::Mul(finite), ctx).map(Some),
+	};
+
+	Ok(Some(value))
+}
+
+/// Internal helper to get the value of a quotient of polynomials by comparing the degrees.
+fn ratio(numer: &Polynomial, denom: &Polynomial) -> Value {
+	let (Some(numer_lead), Some(denom_lead)) = (numer.monomials().next(), denom.monomials().next()) else {
+		return Value::Zero(Some(Ordering::Equal));
+	};
+
+	let coeff = numer_lead.coeff.clone() / &denom_lead.coeff;
+
+	match numer_lead.degree.cmp(&denom_lead.degree) {
+		Ordering::Greater => Value::Infinite(coeff.cmp(&Number::zero())),
+		Ordering::Equal => Value::Finite(Expr::Num(coeff)),
+		Ordering::Less => Value::Zero(Some(coeff.cmp(&Number::zero()))),
+	}
+}
+
+/// Internal helper to get a limit at a finite point from the leading term `c (x - a)^d` of a series expansion, or
+/// [`None`] if there is no expansion that is valid on the required sides.
+fn series(expr: &Expr, var: &Symbol, point: &Expr, direction: Direction) -> Option<Limit> {
+	let series = expr.series(var, point, 1)?;
+
+	// Fractional degrees are only real to the right of the point
+	if direction != Direction::Right && !series.terms().all(|(degree, _)| degree.is_integer()) {
+		return None;
+	}
+
+	let Some((degree, coeff)) = series.terms().next() else {
+		return Some(Limit::Finite(Expr::zero()));
+	};
+
+	match degree.cmp(&Number::zero()) {
+		Ordering::Greater => Some(Limit::Finite(Expr::zero())),
+		Ordering::Equal => Some(Limit::Finite(coeff.clone())),
+		Ordering::Less => {
+			let infinity = |sign| match sign {
+				Ordering::Less => Limit::NegInfinity,
+				_ => Limit::Infinity,
+			};
+
+			// Odd powers change their sign to the left of the point
+			let right = coeff.eval_sign(&HashMap::new())?;
+			let left = || parity(Ordering::Less, degree).map(|sign| mul_signs(sign, right));
+
+			match direction {
+				Direction::Both if left()? != right => Some(Limit::DoesNotExist),
+				Direction::Both | Direction::Right => Some(infinity(right)),
+				Direction::Left => left().map(infinity),
+			}
+		}
+	}
+}
+
+/// Internal helper to get the value a simplified expression approaches as `var` goes to positive infinity, or
+/// [`None`] if it is unknown. The depth limits how often indeterminate forms are rewritten.
+fn value(expr: &Expr, var: &Symbol, depth: u32, ctx: &mut Context) -> Result<Option<Value>, SimplifyError> {
+	if !expr.has_symbol(var) {
+		if expr.is_num_and(Number::is_zero) {
+			return Ok(Some(Value::Zero(Some(Ordering::Equal))));
+		}
+
+		return finite_value(expr.clone(), ctx).map(Some);
+	}
+
+	// Rational functions are compared by the degrees of the numerator and the denominator
+	if let Some((numer, denom)) = rational(expr, var) {
+		return Ok(Some(ratio(&numer, &denom)));
+	}
+
+	// Sums and products of powers approach their leading terms
+	if let Some((degree, coeff)) = leading(expr, var) {
+		let sign = coeff.eval_sign(&HashMap::new());
+
+		let value = match (degree.cmp(&Number::zero()), sign) {
+			(Ordering::Equal, _) => return finite_value(coeff, ctx).map(Some),
+			(Ordering::Greater, Some(sign)) => Value::Infinite(sign),
+			(Ordering::Less, sign) => Value::Zero(sign),
+			_ => return Ok(None),
+		};
+
+		return Ok(Some(value));
+	}
+
+	match expr {
+		Expr::Add(terms) => add(terms, var, depth, ctx),
+		Expr::And(_) | Expr::Matrix(_) | Expr::Not(_) | Expr::Or(_) | Expr::Piecewise(_) | Expr::Rel(..) => Ok(None),
+		Expr::Fun(name, args) => fun(name, args, var, depth, ctx),
+		Expr::Mul(factors) => mul(factors, var, depth, ctx),
+		Expr::Num(_) | Expr::Poly(..) => unreachable!(),
+		Expr::Pow(base, exp) => pow(base, exp, var, depth, ctx),
+	}
+}
+
+impl From<Expr> for Point {
+	/// Converts an expression into a point, where the symbol `inf` stands for infinity.
+	fn from(value: Expr) -> Self {
+		let infinity = Symbol::new("inf").unwrap();
+
+		match &value {
+			Expr::Poly(sym, poly) if *sym == infinity && *poly == Polynomial::from(Monomial::linear(1)) => {
+				Self::Infinity
+			}
+			Expr::Poly(sym, poly) if *sym == infinity && *poly == Polynomial::from(Monomial::linear(-1)) => {
+				Self::NegInfinity
+			}
+			_ => Self::Finite(value),
+		}
+	}
+}
+
+impl From<Limit> for Expr {
+	/// Converts a limit into an expression, using the symbols `inf` and `undefined`.
+	fn from(value: Limit) -> Self {
+		let symbol = |name: &str, coeff: i32| Self::Poly(Symbol::new(name).unwrap(), Monomial::linear(coeff).into());
+
+		match value {
+			Limit::DoesNotExist => symbol("undefined", 1),
+			Limit::Finite(expr) => expr,
+			Limit::Infinity => symbol("inf", 1),
+			Limit::NegInfinity => symbol("inf", -1),
+		}
+	}
+}
+
+impl fmt::Display for Limit {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}", Expr::from(self.clone()))
+	}
+}
@@ -0,0 +1,296 @@
+//! Systems of linear equations.
+//!
+//! Every equation `lhs == rhs` is moved to one side and expanded, and the coefficient of each variable is read off as
+//! the derivative of the difference, which has to be free of all variables for the system to be linear. Systems with
+//! numeric coefficients are solved exactly with Gaussian elimination over the rationals. Otherwise, fraction-free
+//! elimination finds the rank and the pivot variables, which are then solved for with Cramer's rule, so that every
+//! value is a single quotient of expanded determinants. Symbolic coefficients are treated as generic: an expression
+//! counts as zero only if it expands to zero, so special values of the symbols that make a pivot vanish are ignored.
+
+use itertools::Itertools;
+use rug::ops::Pow;
+
+use crate::context::Context;
+use crate::error::SimplifyError;
+use crate::expr::{Expr, Symbol};
+use crate::matrix::Matrix;
+use crate::monomial::Monomial;
+use crate::number::Number;
+use crate::relation::Relation;
+use crate::trig;
+
+/// The solution of a system of linear equations.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Solution {
+	/// The system has no solution.
+	Inconsistent,
+	/// The system has infinitely many solutions, where every variable is given in terms of the free variables. The
+	/// value of a free variable is the variable itself.
+	Parametric {
+		/// The value of every variable, in the order they were given.
+		values: Vec<Expr>,
+		/// The free variables, which can take any value.
+		free: Vec<Symbol>,
+	},
+	/// The system has exactly one solution, with the value of every variable in the order they were given.
+	Unique(Vec<Expr>),
+}
+
+impl Solution {
+	/// Returns the solution as an equivalent condition on the variables, which is a conjunction of equations
+	/// `var == value` for every variable that is not free.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::expr::{Expr, Symbol};
+	/// use abacas::linsolve::Solution;
+	///
+	/// let vars = [Symbol::new("x").unwrap(), Symbol::new("y").unwrap()];
+	/// let solution = Solution::Unique(vec![Expr::Num(2.into()), Expr::Num(3.into())]);
+	///
+	/// assert_eq!(solution.condition(&vars).to_string(), "x == 2 and y == 3");
+	/// assert_eq!(Solution::Inconsistent.condition(&vars).to_string(), "false");
+	/// ```
+	pub fn condition(&self, vars: &[Symbol]) -> Expr {
+		let (values, free) = match self {
+			Self::Inconsistent => return Expr::boolean(false),
+			Self::Parametric { values, free } => (values, free.as_slice()),
+			Self::Unique(values) => (values, [].as_slice()),
+		};
+
+		let mut equations = vars
+			.iter()
+			.zip(values)
+			.filter(|(var, _)| !free.contains(var))
+			.map(|(var, value)| Expr::Rel(Relation::Eq, symbol(var).into(), value.clone().into()))
+			.collect_vec();
+
+		match equations.len() {
+			1 => equations.pop().unwrap(),
+			_ => Expr::And(equations),
+		}
+	}
+}
+
+/// Solves a system of linear equations for the given variables. Equations are relations `lhs == rhs`, and any other
+/// expression `expr` stands for the equation `expr == 0`. Returns [`None`] if the system is not linear in the
+/// variables.
+///
+/// # Examples
+///
+/// ```
+/// use abacas::context::Context;
+/// use abacas::expr::{Expr, Symbol};
+/// use abacas::linsolve::{Solution, linsolve};
+/// use abacas::relation::Relation;
+///
+/// let ctx = &mut Context::new();
+/// let vars = [Symbol::new("x").unwrap(), Symbol::new("y").unwrap(), Symbol::new("z").unwrap()];
+/// let var = |index: usize| Expr::Poly(vars[index].clone(), "x".parse().unwrap());
+/// let num = |num: i64| Expr::Num(num.into());
+///
+/// // `x + y == 3` and `x - y == 1`
+/// let equations = [
+///     Expr::Rel(Relation::Eq, (var(0) + var(1)).into(), num(3).into()),
+///     Expr::Rel(Relation::Eq, (var(0) - var(1)).into(), num(1).into()),
+/// ];
+///
+/// let solution = linsolve(&equations, &vars[..2], ctx).unwrap().unwrap();
+/// assert_eq!(solution, Solution::Unique(vec![num(2), num(1)]));
+///
+/// // `x + y + z == 1` leaves two free variables
+/// let equations = [var(0) + var(1) + var(2) - num(1)];
+///
+/// let solution = linsolve(&equations, &vars, ctx).unwrap().unwrap();
+/// assert_eq!(solution.condition(&vars).to_string(), "x == 1 - y - z");
+///
+/// // `x + y == 1` and `x + y == 2` contradict each other
+/// let equations = [var(0) + var(1) - num(1), var(0) + var(1) - num(2)];
+/// assert_eq!(linsolve(&equations, &vars[..2], ctx).unwrap(), Some(Solution::Inconsistent));
+/// ```
+pub fn linsolve(equations: &[Expr], vars: &[Symbol], ctx: &mut Context) -> Result<Option<Solution>, SimplifyError> {
+	let mut rows = Vec::with_capacity(equations.len());
+
+	for equation in equations {
+		let diff = match equation.clone().simplify_builtin(ctx)? {
+			Expr::And(exprs) if exprs.is_empty() => continue,
+			Expr::Or(exprs) if exprs.is_empty() => return Ok(Some(Solution::Inconsistent)),
+			Expr::Rel(Relation::Eq, lhs, rhs) => *lhs - *rhs,
+			expr if expr.is_logical() || expr.is_matrix() => return Ok(None),
+			expr => expr,
+		};
+
+		let Some(row) = coefficients(diff, vars, ctx)? else {
+			return Ok(None);
+		};
+
+		rows.push(row);
+	}
+
+	let augmented = Matrix::from_fn(rows.len(), vars.len() + 1, |row, col| rows[row][col].clone());
+
+	match augmented.to_numbers() {
+		Some(augmented) => Ok(Some(solve_numeric(&augmented, vars, ctx)?)),
+		None => solve_symbolic(&augmented, vars, ctx).map(Some),
+	}
+}
+
+/// Internal helper to get the coefficients of the variables in a linear expression, followed by the negated constant
+/// term. Returns [`None`] if the expression is not linear in the variables.
+fn coefficients(diff: Expr, vars: &[Symbol], ctx: &mut Context) -> Result<Option<Vec<Expr>>, SimplifyError> {
+	let diff = trig::collect(diff, ctx)?;
+	let mut row = Vec::with_capacity(vars.len() + 1);
+
+	for var in vars {
+		let coeff = trig::collect(diff.derivative(var), ctx)?;
+
+		if vars.iter().any(|var| coeff.has_symbol(var)) {
+			return Ok(None);
+		}
+
+		row.push(coeff);
+	}
+
+	let constant = vars.iter().fold(diff, |diff, var| diff.substitute(var, &Expr::zero()));
+
+	row.push(trig::collect(-constant, ctx)?);
+	Ok(Some(row))
+}
+
+/// Internal helper to solve a system given by its augmented matrix of numbers with Gaussian elimination.
+fn solve_numeric(augmented: &Matrix<Number>, vars: &[Symbol], ctx: &mut Context) -> Result<Solution, SimplifyError> {
+	let (reduced, pivots) = augmented.rref();
+
+	if pivots.last() == Some(&vars.len()) {
+		return Ok(Solution::Inconsistent);
+	}
+
+	let free = (0..vars.len()).filter(|col| !pivots.contains(col)).collect_vec();
+	let mut values = vars.iter().map(symbol).collect_vec();
+
+	// Every pivot variable is its row's right-hand side minus the terms of the free variables
+	for (row, &pivot) in pivots.iter().enumerate() {
+		let terms = free
+			.iter()
+			.map(|&col| Expr::Num(-reduced[(row, col)].clone()) * symbol(&vars[col]));
+		let value = Expr::Add(
+			std::iter::once(Expr::Num(reduced[(row, vars.len())].clone()))
+				.chain(terms)
+				.collect(),
+		);
+
+		values[pivot] = trig::collect(value, ctx)?;
+	}
+
+	Ok(solution(values, free, vars))
+}
+
+/// Internal helper to solve a system given by its augmented matrix of expressions. Fraction-free elimination on a copy
+/// finds the independent equations and the pivot variables, which are then solved for with Cramer's rule.
+fn solve_symbolic(augmented: &Matrix<Expr>, vars: &[Symbol], ctx: &mut Context) -> Result<Solution, SimplifyError> {
+	let mut rows = (0..augmented.rows())
+		.map(|row| (row, augmented.row(row).to_vec()))
+		.collect_vec();
+	let mut pivots = Vec::new();
+
+	for col in 0..=vars.len() {
+		let step = pivots.len();
+
+		if step == rows.len() {
+			break;
+		}
+
+		let Some(pivot) = (step..rows.len()).find(|&row| !is_zero(&rows[row].1[col])) else {
+			continue;
+		};
+
+		rows.swap(pivot, step);
+
+		// Cross-multiply every later row with the pivot to eliminate the column without dividing
+		for row in step + 1..rows.len() {
+			let factor = rows[row].1[col].clone();
+
+			if is_zero(&factor) {
+				continue;
+			}
+
+			for entry in col..=vars.len() {
+				let value = rows[step].1[col].clone() * rows[row].1[entry].clone()
+					- factor.clone() * rows[step].1[entry].clone();
+				rows[row].1[entry] = trig::collect(value, ctx)?;
+			}
+		}
+
+		pivots.push(col);
+	}
+
+	if pivots.last() == Some(&vars.len()) {
+		return Ok(Solution::Inconsistent);
+	}
+
+	// The original equations of the pivot rows are independent, and the free variables move to the right-hand side
+	let free = (0..vars.len()).filter(|col| !pivots.contains(col)).collect_vec();
+	let equations = rows[..pivots.len()].iter().map(|&(row, _)| row).collect_vec();
+
+	let rhs = equations
+		.iter()
+		.map(|&row| {
+			let terms = free
+				.iter()
+				.map(|&col| -augmented[(row, col)].clone() * symbol(&vars[col]));
+			Expr::Add(
+				std::iter::once(augmented[(row, vars.len())].clone())
+					.chain(terms)
+					.collect(),
+			)
+		})
+		.collect_vec();
+
+	let coeffs = Matrix::from_fn(pivots.len(), pivots.len(), |row, col| {
+		augmented[(equations[row], pivots[col])].clone()
+	});
+
+	let determinant = coeffs.determinant(ctx)?.unwrap();
+	let numeric = determinant.is_num();
+	let recip = determinant.pow(Expr::neg_one());
+	let mut values = vars.iter().map(symbol).collect_vec();
+
+	for (index, &pivot) in pivots.iter().enumerate() {
+		let mut replaced = coeffs.clone();
+
+		for (row, value) in rhs.iter().enumerate() {
+			replaced[(row, index)] = value.clone();
+		}
+
+		// Dividing by a number distributes over the terms, while symbolic quotients are kept whole
+		let numer = replaced.determinant(ctx)?.unwrap();
+		values[pivot] = match numeric {
+			true => trig::collect(numer * recip.clone(), ctx)?,
+			false => (numer * recip.clone()).simplify_builtin(ctx)?,
+		};
+	}
+
+	Ok(solution(values, free, vars))
+}
+
+/// Internal helper to check whether an expanded expression is zero.
+fn is_zero(expr: &Expr) -> bool {
+	expr.is_num_and(Number::is_zero)
+}
+
+/// Internal helper to create a solution from the values of all variables and the indices of the free ones.
+fn solution(values: Vec<Expr>, free: Vec<usize>, vars: &[Symbol]) -> Solution {
+	match free.is_empty() {
+		true => Solution::Unique(values),
+		false => Solution::Parametric {
+			values,
+			free: free.into_iter().map(|col| vars[col].clone()).collect(),
+		},
+	}
+}
+
+/// Internal helper to create a symbol as an expression.
+fn symbol(var: &Symbol) -> Expr {
+	Expr::Poly(var.clone(), Monomial::linear(1).into())
+}
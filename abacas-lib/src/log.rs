@@ -0,0 +1,258 @@
+//! Logarithm and exponential simplification.
+//!
+//! Logarithms of products and powers are only split up where this is valid for real numbers, which is when all but one
+//! factor of a product are known to be positive and when the base of a power is positive or the power is odd. The
+//! remaining factor of a product has to be positive for the logarithm to be defined in the first place. Positivity is
+//! decided with the assumptions of the context, see [`Expr::is_positive`].
+
+use itertools::Itertools;
+use rug::Integer;
+use rug::ops::Pow;
+
+use crate::context::Context;
+use crate::error::SimplifyError;
+use crate::expr::{Expr, Symbol};
+use crate::monomial::Monomial;
+use crate::number::Number;
+use crate::polynomial::Polynomial;
+
+// Operations
+impl Expr {
+	/// Combines sums of logarithms with numeric coefficients into a single logarithm, like
+	/// `ln(a) + 2 ln(b) = ln(a b^2)`. Logarithms of one vanish afterwards, and exponentials cancel the logarithms.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::context::Context;
+	/// use abacas::expr::{Expr, Symbol};
+	///
+	/// let ctx = &mut Context::new();
+	/// let ln = |poly: &str| Expr::Fun(Symbol::new("ln").unwrap(), vec![Expr::Poly(Symbol::new("x").unwrap(), poly.parse().unwrap())]);
+	///
+	/// let expr = ln("x + 1") - ln("x");
+	/// assert_eq!(expr.combine_log(ctx).unwrap().to_string(), "ln(1 + x^-1)");
+	///
+	/// let expr = Expr::Num(2.into()) * ln("x") - ln("x^2");
+	/// assert_eq!(expr.combine_log(ctx).unwrap(), Expr::zero());
+	/// ```
+	pub fn combine_log(&self, ctx: &mut Context) -> Result<Self, SimplifyError> {
+		let expr = self.clone().simplify_builtin(ctx)?;
+
+		let expr = expr.bottom_up(ctx, &mut |expr, ctx| match expr {
+			Self::Add(terms) => {
+				let (logs, mut others): (Vec<_>, Vec<_>) =
+					terms.into_iter().partition(|term| scaled_log(term).is_some());
+
+				if logs.is_empty() {
+					return Ok(Self::Add(others));
+				}
+
+				others.push(combine(logs.iter().filter_map(scaled_log), ctx)?);
+				Self::Add(others).simplify_builtin(ctx)
+			}
+			Self::Mul(_) if let Some(log) = scaled_log(&expr) => combine([log], ctx),
+			expr => Ok(expr),
+		})?;
+
+		expr.simplify_builtin(ctx)
+	}
+
+	/// Expands logarithms of products, powers and rational perfect powers, like `ln(a b^2) = ln(a) + 2 ln(b)` or
+	/// `ln(8) = 3 ln(2)`. Only valid expansions are made, see the [module level documentation](self).
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::context::Context;
+	/// use abacas::expr::{Expr, Symbol};
+	///
+	/// let ctx = &mut Context::new();
+	/// let x = || Expr::Poly(Symbol::new("x").unwrap(), "x".parse().unwrap());
+	/// let ln = |arg| Expr::Fun(Symbol::new("ln").unwrap(), vec![arg]);
+	///
+	/// let expr = ln(Expr::Num(8.into()) * x());
+	/// assert_eq!(expr.expand_log(ctx).unwrap().to_string(), "ln(x) + ln(2) * 3");
+	///
+	/// // The square of x could be negative before squaring
+	/// let expr = ln(Expr::Poly(Symbol::new("x").unwrap(), "x^2".parse().unwrap()));
+	/// assert_eq!(expr.expand_log(ctx).unwrap().to_string(), "ln(x^2)");
+	/// ```
+	pub fn expand_log(&self, ctx: &mut Context) -> Result<Self, SimplifyError> {
+		let expr = self.clone().simplify_builtin(ctx)?;
+
+		let expr = expr.bottom_up(ctx, &mut |expr, ctx| match expr {
+			Self::Fun(name, args) if name.name() == "ln" && args.len() == 1 => match expand(&args[0], ctx) {
+				Some(result) => result.simplify_builtin(ctx),
+				None => Ok(Self::Fun(name, args)),
+			},
+			expr => Ok(expr),
+		})?;
+
+		expr.simplify_builtin(ctx)
+	}
+}
+
+/// Returns the logarithm of `x` to the given base if it is rational, which is the case for two powers of the same
+/// number, like `log(8, 2) = 3` or `log(1/9, 27) = -2/3`.
+///
+/// # Examples
+///
+/// ```
+/// use abacas::log;
+/// use abacas::number::Number;
+///
+/// assert_eq!(log::log(&8.into(), &2.into()), Some(3.into()));
+/// assert_eq!(log::log(&(Number::from(1) / 9), &27.into()), Some(Number::from(-2) / 3));
+/// assert_eq!(log::log(&1.into(), &5.into()), Some(0.into()));
+/// assert_eq!(log::log(&6.into(), &2.into()), None);
+/// assert_eq!(log::log(&4.into(), &1.into()), None);
+/// ```
+pub fn log(x: &Number, base: &Number) -> Option<Number> {
+	if !x.is_positive() || !base.is_positive() || base.is_one() {
+		return None;
+	}
+
+	if x.is_one() {
+		return Some(Number::zero());
+	}
+
+	let (x_root, x_exp) = perfect_power(x);
+	let (base_root, base_exp) = perfect_power(base);
+
+	(x_root == base_root).then(|| Number::from(x_exp) / base_exp)
+}
+
+/// Internal helper to combine logarithms with numeric coefficients into a single logarithm.
+fn combine<'a>(logs: impl IntoIterator<Item = (Number, &'a Expr)>, ctx: &mut Context) -> Result<Expr, SimplifyError> {
+	let product = logs
+		.into_iter()
+		.map(|(coeff, arg)| arg.clone().pow(Expr::Num(coeff)))
+		.collect();
+
+	match Expr::Mul(product).simplify_builtin(ctx)? {
+		product if product == Expr::one() => Ok(Expr::zero()),
+		product => Ok(ln(product)),
+	}
+}
+
+/// Internal helper to expand the logarithm of an expression, returning [`None`] if that is not possible.
+fn expand(arg: &Expr, ctx: &Context) -> Option<Expr> {
+	let expanded = |arg: &Expr| expand(arg, ctx).unwrap_or_else(|| ln(arg.clone()));
+	let is_positive = |expr: &Expr| expr.is_positive(ctx) == Some(true);
+
+	match arg {
+		Expr::Num(num) if num.is_one() => Some(Expr::zero()),
+		Expr::Num(num) if num.is_positive() => match perfect_power(num) {
+			(_, 1) => None,
+			(root, exp) => Some(Expr::Num(exp.into()) * ln(Expr::Num(root))),
+		},
+		// All but one factor have to be positive
+		Expr::Mul(factors) if factors.iter().filter(|factor| !is_positive(factor)).count() <= 1 => {
+			Some(Expr::Add(factors.iter().map(expanded).collect()))
+		}
+		Expr::Pow(base, exp) if is_positive(base) || exp.is_num_and(is_odd) => Some((**exp).clone() * expanded(base)),
+		Expr::Poly(sym, poly) if let Ok(mono) = poly.monomials().exactly_one() => {
+			let poly = |mono| Expr::Poly(sym.clone(), Polynomial::from(mono));
+			let mut terms = Vec::new();
+
+			if !mono.coeff.is_one() {
+				terms.push(
+					mono.coeff
+						.is_positive()
+						.then(|| expanded(&Expr::Num(mono.coeff.clone())))?,
+				);
+			}
+
+			if !mono.degree.is_one() && (is_odd(&mono.degree) || is_positive(&poly(Monomial::linear(1)))) {
+				terms.push(Expr::Num(mono.degree.clone()) * ln(poly(Monomial::linear(1))));
+			} else if terms.is_empty() {
+				return None;
+			} else {
+				terms.push(ln(poly(Monomial::new(1, mono.degree.clone()))));
+			}
+
+			Some(Expr::Add(terms))
+		}
+		_ => None,
+	}
+}
+
+/// Internal helper to compute the greatest common divisor of two exponents.
+fn gcd(lhs: u32, rhs: u32) -> u32 {
+	match rhs {
+		0 => lhs,
+		rhs => gcd(rhs, lhs % rhs),
+	}
+}
+
+/// Internal helper to decompose a positive integer into a perfect power with the largest possible exponent.
+fn integer_perfect_power(value: &Integer) -> (Integer, u32) {
+	let (mut root, mut exp) = (value.clone(), 1);
+	let mut prime = 2;
+
+	// Prime roots are taken as long as they exist, so their product is the largest exponent
+	while root.is_perfect_power() && prime < root.significant_bits() {
+		let (next, rem) = root.clone().root_rem(Integer::new(), prime);
+
+		if rem.is_zero() {
+			root = next;
+			exp *= prime;
+		} else {
+			prime = Integer::from(prime).next_prime().to_u32().unwrap();
+		}
+	}
+
+	(root, exp)
+}
+
+/// Internal helper to check whether a number is an integer or fraction with an odd numerator.
+fn is_odd(num: &Number) -> bool {
+	!(num.clone().numer() / 2).is_integer()
+}
+
+/// Internal helper to create the natural logarithm of an expression.
+fn ln(arg: Expr) -> Expr {
+	Expr::Fun(Symbol::new("ln").unwrap(), vec![arg])
+}
+
+/// Internal helper to decompose a positive number into a perfect power `root^exp` with the largest possible exponent,
+/// where the root is greater than one. One is returned as `1^1`.
+fn perfect_power(num: &Number) -> (Number, i32) {
+	let (numer, denom) = num.clone().ratio();
+	let (numer_root, numer_exp) = integer_perfect_power(numer.to_integer().unwrap());
+	let (denom_root, denom_exp) = integer_perfect_power(denom.to_integer().unwrap());
+
+	// The exponent has to divide the exponents of both numerator and denominator
+	let exp = match (numer.is_one(), denom.is_one()) {
+		(true, true) => return (num.clone(), 1),
+		(_, true) => numer_exp,
+		(true, _) => denom_exp,
+		_ => gcd(numer_exp, denom_exp),
+	};
+
+	let pow = |root: Integer, power: u32| Number::from_integer(root.pow(power));
+	let root = pow(numer_root, numer_exp / exp) / &pow(denom_root, denom_exp / exp);
+
+	match root > Number::one() {
+		true => (root, exp as i32),
+		false => (root.recip(), -(exp as i32)),
+	}
+}
+
+/// Internal helper to split a term into the numeric coefficient and argument of a logarithm.
+fn scaled_log(term: &Expr) -> Option<(Number, &Expr)> {
+	fn log_arg(expr: &Expr) -> Option<&Expr> {
+		match expr {
+			Expr::Fun(name, args) if name.name() == "ln" && args.len() == 1 => Some(&args[0]),
+			_ => None,
+		}
+	}
+
+	match term {
+		Expr::Mul(factors) if let [Expr::Num(coeff), log] | [log, Expr::Num(coeff)] = factors.as_slice() => {
+			Some((coeff.clone(), log_arg(log)?))
+		}
+		term => Some((Number::one(), log_arg(term)?)),
+	}
+}
@@ -0,0 +1,340 @@
+//! MathML output of expressions for embedding in web pages.
+//!
+//! The [`MathMl`] adapter writes presentation markup inside a `<math>` element, laid out like the LaTeX output. Sums
+//! extract the minus signs of their terms, products with negative exponents become fractions and unit fraction
+//! exponents become roots. Every expression is written as a single element, so it can be nested in elements like
+//! `<mfrac>` and `<msup>` that expect a fixed number of children.
+
+use std::fmt;
+
+use itertools::Itertools;
+
+use crate::expr::{Expr, Symbol};
+use crate::matrix::Matrix;
+use crate::monomial::Monomial;
+use crate::number::Number;
+use crate::polynomial::Polynomial;
+use crate::relation::Relation;
+
+/// A display adapter that writes the contained expression as a MathML `<math>` element.
+///
+/// # Examples
+///
+/// ```
+/// use abacas::expr::{Expr, Symbol};
+/// use abacas::mathml::MathMl;
+///
+/// let x = Expr::Poly(Symbol::new("x").unwrap(), "x^2".parse().unwrap());
+///
+/// assert_eq!(
+///     MathMl(&x).to_string(),
+///     r#"<math xmlns="http://www.w3.org/1998/Math/MathML"><msup><mi>x</mi><mn>2</mn></msup></math>"#
+/// );
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct MathMl<'a, T: ?Sized>(pub &'a T);
+
+impl Expr {
+	/// Returns this expression as a MathML `<math>` element.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::expr::{Expr, Symbol};
+	///
+	/// let x = Expr::Poly(Symbol::new("x").unwrap(), "x".parse().unwrap());
+	/// let expr = x / Expr::Num(2.into());
+	///
+	/// assert!(expr.to_mathml().ends_with("<mfrac><mi>x</mi><mn>2</mn></mfrac></math>"));
+	/// ```
+	pub fn to_mathml(&self) -> String {
+		MathMl(self).to_string()
+	}
+}
+
+impl fmt::Display for MathMl<'_, Expr> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(
+			f,
+			r#"<math xmlns="http://www.w3.org/1998/Math/MathML">{}</math>"#,
+			Node(self.0)
+		)
+	}
+}
+
+/// Internal adapter to write an expression as a single MathML element, without the surrounding `<math>` element.
+struct Node<'a>(&'a Expr);
+
+impl fmt::Display for Node<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self.0 {
+			Expr::Add(exprs) => write_add(f, exprs),
+			Expr::And(exprs) if exprs.is_empty() => write!(f, "<mtext>true</mtext>"),
+			// Disjunctions bind weaker than conjunctions
+			Expr::And(exprs) => write!(
+				f,
+				"<mrow>{}</mrow>",
+				exprs.iter().format_with("<mo>∧</mo>", |expr, f| match expr {
+					Expr::Or(_) => f(&format_args!("<mrow><mo>(</mo>{}<mo>)</mo></mrow>", Node(expr))),
+					_ => f(&Node(expr)),
+				})
+			),
+			Expr::Fun(name, args) => write_fun(f, name, args),
+			Expr::Matrix(matrix) => write_matrix(f, matrix),
+			Expr::Mul(exprs) => write_mul(f, exprs, false),
+			Expr::Not(expr) => match **expr {
+				Expr::Rel(_, _, _) => write!(f, "<mrow><mo>¬</mo>{}</mrow>", Node(expr)),
+				_ => write!(f, "<mrow><mo>¬</mo>{}</mrow>", Parens(expr)),
+			},
+			Expr::Num(num) => write_number(f, num, false),
+			Expr::Or(exprs) if exprs.is_empty() => write!(f, "<mtext>false</mtext>"),
+			Expr::Or(exprs) => write!(f, "<mrow>{}</mrow>", exprs.iter().map(Node).format("<mo>∨</mo>")),
+			Expr::Piecewise(branches) => {
+				let branches = branches.iter().format_with("", |(value, condition), f| {
+					f(&format_args!(
+						"<mtr><mtd>{}</mtd><mtd>{}</mtd></mtr>",
+						Node(value),
+						Node(condition)
+					))
+				});
+
+				write!(f, "<mrow><mo>{{</mo><mtable>{branches}</mtable></mrow>")
+			}
+			Expr::Poly(sym, poly) => write_poly(f, poly, sym, false),
+			Expr::Pow(base, exp) => write_pow(f, base, exp),
+			Expr::Rel(rel, lhs, rhs) => {
+				let side = |expr: &Expr| match expr.is_logical() {
+					true => format!("<mrow><mo>(</mo>{}<mo>)</mo></mrow>", Node(expr)),
+					false => Node(expr).to_string(),
+				};
+
+				// The comparisons `<` and `>` have to be escaped in markup
+				let rel = match rel {
+					Relation::Eq => "=",
+					Relation::Ge => "≥",
+					Relation::Gt => "&gt;",
+					Relation::Le => "≤",
+					Relation::Lt => "&lt;",
+					Relation::Ne => "≠",
+				};
+
+				write!(f, "<mrow>{}<mo>{rel}</mo>{}</mrow>", side(lhs), side(rhs))
+			}
+		}
+	}
+}
+
+/// Internal adapter to write an expression as MathML with parentheses if necessary, like `Expr::with_parens`.
+struct Parens<'a>(&'a Expr);
+
+impl fmt::Display for Parens<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self.0.needs_parens() {
+			true => write!(f, "<mrow><mo>(</mo>{}<mo>)</mo></mrow>", Node(self.0)),
+			false => write!(f, "{}", Node(self.0)),
+		}
+	}
+}
+
+/// Internal adapter to write the base of a power or factorial with parentheses if necessary.
+struct BaseParens<'a>(&'a Expr);
+
+impl fmt::Display for BaseParens<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self.0.needs_base_parens() {
+			true => write!(f, "<mrow><mo>(</mo>{}<mo>)</mo></mrow>", Node(self.0)),
+			false => write!(f, "{}", Node(self.0)),
+		}
+	}
+}
+
+/// Internal helper to write a sum, extracting the minus signs of negative terms like `Expr::write_add`.
+fn write_add(f: &mut fmt::Formatter<'_>, exprs: &[Expr]) -> fmt::Result {
+	write!(f, "<mrow>")?;
+
+	// Format the first expression normally
+	if let Some(first) = exprs.first() {
+		write!(f, "{}", Node(first))?;
+	}
+
+	for expr in exprs.iter().skip(1) {
+		match expr {
+			// If the number is negative, extract the minus
+			Expr::Num(num) if num.is_negative() => {
+				write!(f, "<mo>-</mo>")?;
+				write_number(f, num, true)?;
+			}
+
+			// If the polynomial has a negative leading coefficient, extract the minus
+			Expr::Poly(sym, poly) if poly.leading().is_some_and(Number::is_negative) => {
+				write!(f, "<mo>-</mo>")?;
+				write_poly(f, poly, sym, true)?;
+			}
+
+			// If the product has a negative coefficient, extract the minus
+			Expr::Mul(exprs) if Expr::split_fraction(exprs).0 => {
+				write!(f, "<mo>-</mo>")?;
+				write_mul(f, exprs, true)?;
+			}
+
+			// Otherwise, write the expression normally
+			_ => write!(f, "<mo>+</mo>{}", Node(expr))?,
+		}
+	}
+
+	write!(f, "</mrow>")
+}
+
+/// Internal helper to write a function call, with special forms for some functions.
+fn write_fun(f: &mut fmt::Formatter<'_>, name: &Symbol, args: &[Expr]) -> fmt::Result {
+	match (name.name(), args) {
+		("abs", [arg]) => write!(f, "<mrow><mo>|</mo>{}<mo>|</mo></mrow>", Node(arg)),
+		("sqrt", [arg]) => write!(f, "<msqrt>{}</msqrt>", Node(arg)),
+		("factorial", [arg]) => write!(f, "<mrow>{}<mo>!</mo></mrow>", BaseParens(arg)),
+		(name, _) => write!(
+			f,
+			"<mrow><mi>{name}</mi><mo>&#x2061;</mo><mrow><mo>(</mo>{}<mo>)</mo></mrow></mrow>",
+			args.iter().map(Node).format("<mo>,</mo>")
+		),
+	}
+}
+
+/// Internal helper to write a matrix as a table between parentheses.
+fn write_matrix(f: &mut fmt::Formatter<'_>, matrix: &Matrix<Expr>) -> fmt::Result {
+	let rows = (0..matrix.rows()).map(|row| {
+		let cells = matrix
+			.row(row)
+			.iter()
+			.format_with("", |expr, f| f(&format_args!("<mtd>{}</mtd>", Node(expr))));
+		format!("<mtr>{cells}</mtr>")
+	});
+
+	write!(
+		f,
+		"<mrow><mo>(</mo><mtable>{}</mtable><mo>)</mo></mrow>",
+		rows.format("")
+	)
+}
+
+/// Internal helper to write a monomial, optionally without its sign.
+fn write_monomial(f: &mut fmt::Formatter<'_>, mono: &Monomial, sym: &Symbol, abs: bool) -> fmt::Result {
+	if mono.degree.is_zero() {
+		return write_number(f, &mono.coeff, abs);
+	}
+
+	let sym = format!("<mi>{}</mi>", sym.name());
+
+	// Coefficients are only written apart from one, so the monomial needs a row around them
+	match mono.coeff.is_one() || mono.coeff.is_neg_one() {
+		true if mono.coeff.is_negative() && !abs => write!(f, "<mrow><mo>-</mo>")?,
+		true => return write_power(f, &sym, &sym, &mono.degree),
+		false => {
+			write!(f, "<mrow>")?;
+			write_number(f, &mono.coeff, abs)?;
+		}
+	}
+
+	write_power(f, &sym, &sym, &mono.degree)?;
+	write!(f, "</mrow>")
+}
+
+/// Internal helper to write a product as a fraction of the factors with positive and negative exponents, optionally
+/// without the sign of its numeric coefficient.
+fn write_mul(f: &mut fmt::Formatter<'_>, exprs: &[Expr], abs: bool) -> fmt::Result {
+	let (negative, numer, denom) = Expr::split_fraction(exprs);
+
+	// Parentheses are only needed around factors of a product, or after a minus sign that may have been extracted
+	let factors = |factors: &[Expr], sign: bool| match factors {
+		[] => "<mn>1</mn>".to_owned(),
+		[factor] if !sign => Node(factor).to_string(),
+		_ => format!("<mrow>{}</mrow>", factors.iter().map(Parens).format("<mo>⋅</mo>")),
+	};
+
+	let product = match denom.is_empty() {
+		true => factors(&numer, negative),
+		false => format!("<mfrac>{}{}</mfrac>", factors(&numer, false), factors(&denom, false)),
+	};
+
+	match negative && !abs {
+		true => write!(f, "<mrow><mo>-</mo>{product}</mrow>"),
+		false => write!(f, "{product}"),
+	}
+}
+
+/// Internal helper to write a number exactly, optionally without its sign.
+fn write_number(f: &mut fmt::Formatter<'_>, num: &Number, abs: bool) -> fmt::Result {
+	let (numer, denom) = num.clone().abs().ratio();
+
+	let number = match denom.is_one() {
+		true => format!("<mn>{}</mn>", numer.to_integer().unwrap()),
+		false => format!(
+			"<mfrac><mn>{}</mn><mn>{}</mn></mfrac>",
+			numer.to_integer().unwrap(),
+			denom.to_integer().unwrap()
+		),
+	};
+
+	match num.is_negative() && !abs {
+		true => write!(f, "<mrow><mo>-</mo>{number}</mrow>"),
+		false => write!(f, "{number}"),
+	}
+}
+
+/// Internal helper to write a polynomial in the given variable, optionally without the sign of its leading term.
+fn write_poly(f: &mut fmt::Formatter<'_>, poly: &Polynomial, sym: &Symbol, abs: bool) -> fmt::Result {
+	let mut monomials = poly.monomials().peekable();
+
+	let Some(first) = monomials.next() else {
+		return write!(f, "<mn>0</mn>");
+	};
+
+	// A single monomial is already a single element
+	if monomials.peek().is_none() {
+		return write_monomial(f, first, sym, abs);
+	}
+
+	write!(f, "<mrow>")?;
+	write_monomial(f, first, sym, abs)?;
+
+	for mono in monomials {
+		match mono.coeff.is_negative() {
+			true => write!(f, "<mo>-</mo>")?,
+			false => write!(f, "<mo>+</mo>")?,
+		}
+
+		write_monomial(f, mono, sym, true)?;
+	}
+
+	write!(f, "</mrow>")
+}
+
+/// Internal helper to write a power, using a fraction for negative exponents and roots for unit fractions.
+fn write_pow(f: &mut fmt::Formatter<'_>, base: &Expr, exp: &Expr) -> fmt::Result {
+	match exp {
+		Expr::Num(num) if num.is_negative() => {
+			write_mul(f, &[Expr::Pow(base.clone().into(), exp.clone().into())], false)
+		}
+		Expr::Num(num) => write_power(f, &Node(base), &BaseParens(base), num),
+		_ => write!(f, "<msup>{}{}</msup>", BaseParens(base), Node(exp)),
+	}
+}
+
+/// Internal helper to write a power with a numeric exponent, using roots for unit fractions. The base is given both
+/// plainly for roots and with parentheses for exponents.
+fn write_power(
+	f: &mut fmt::Formatter<'_>,
+	base: &dyn fmt::Display,
+	parens: &dyn fmt::Display,
+	exp: &Number,
+) -> fmt::Result {
+	match exp.root_index() {
+		_ if exp.is_one() => write!(f, "{base}"),
+		Some(2) => write!(f, "<msqrt>{base}</msqrt>"),
+		Some(index) => write!(f, "<mroot>{base}<mn>{index}</mn></mroot>"),
+		None => {
+			write!(f, "<msup>{parens}")?;
+			write_number(f, exp, false)?;
+			write!(f, "</msup>")
+		}
+	}
+}
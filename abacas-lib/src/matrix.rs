@@ -0,0 +1,908 @@
+//! The matrix structure and exact linear algebra.
+//!
+//! Matrices of [`Number`]s support the usual algorithms of linear algebra over the rationals, which are all exact.
+//! Matrices of [`Expr`]s hold symbolic entries: like the operators on [`Expr`], their arithmetic builds unsimplified
+//! expressions, and their determinants and inverses are computed without dividing by symbolic entries.
+
+use std::fmt;
+use std::ops::{Add, Index, IndexMut, Mul, Neg, Sub};
+
+use itertools::Itertools;
+use rug::ops::Pow;
+
+use crate::context::Context;
+use crate::error::SimplifyError;
+use crate::expr::{Expr, Symbol};
+use crate::monomial::Monomial;
+use crate::number::Number;
+use crate::polynomial::Polynomial;
+use crate::{relation, trig};
+
+/// A matrix with its entries stored row by row.
+///
+/// # Examples
+///
+/// Creating a [`Matrix`]:
+///
+/// ```
+/// use abacas::matrix::Matrix;
+/// use abacas::number::Number;
+///
+/// let matrix = Matrix::from_rows(vec![vec![Number::from(1), 2.into()], vec![3.into(), 4.into()]]).unwrap();
+/// assert_eq!(matrix.to_string(), "[[1, 2], [3, 4]]");
+/// assert_eq!(matrix[(1, 0)], 3);
+/// ```
+///
+/// Using arithmetic operations:
+///
+/// ```
+/// use abacas::matrix::Matrix;
+/// use abacas::number::Number;
+///
+/// let a = Matrix::from_rows(vec![vec![Number::from(1), 2.into()], vec![3.into(), 4.into()]]).unwrap();
+/// let b = Matrix::identity(2);
+///
+/// assert_eq!((a.clone() + &b).to_string(), "[[2, 2], [3, 5]]");
+/// assert_eq!((a.clone() * &a).to_string(), "[[7, 10], [15, 22]]");
+/// assert_eq!(a.clone() * &b, a);
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Matrix<T> {
+	rows: usize,
+	cols: usize,
+	entries: Vec<T>,
+}
+
+// Constructors
+impl<T> Matrix<T> {
+	/// Creates a column vector from its entries.
+	pub fn column(entries: Vec<T>) -> Self {
+		Self {
+			rows: entries.len(),
+			cols: 1,
+			entries,
+		}
+	}
+
+	/// Creates a matrix by calling a function with the row and column of every entry.
+	pub fn from_fn(rows: usize, cols: usize, mut f: impl FnMut(usize, usize) -> T) -> Self {
+		let entries = (0..rows).flat_map(|row| (0..cols).map(move |col| (row, col)));
+
+		Self {
+			rows,
+			cols,
+			entries: entries.map(|(row, col)| f(row, col)).collect(),
+		}
+	}
+
+	/// Creates a matrix from its rows. Returns [`None`] if the rows have different lengths.
+	pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Self> {
+		let cols = rows.first().map_or(0, Vec::len);
+
+		if rows.iter().any(|row| row.len() != cols) {
+			return None;
+		}
+
+		Some(Self {
+			rows: rows.len(),
+			cols,
+			entries: rows.into_iter().flatten().collect(),
+		})
+	}
+
+	/// Creates a matrix from its entries row by row. Returns [`None`] if the amount of entries does not match.
+	pub fn new(rows: usize, cols: usize, entries: Vec<T>) -> Option<Self> {
+		(rows * cols == entries.len()).then_some(Self { rows, cols, entries })
+	}
+}
+
+// Guards
+impl<T> Matrix<T> {
+	/// Whether this matrix has as many rows as columns.
+	pub const fn is_square(&self) -> bool {
+		self.rows == self.cols
+	}
+}
+
+// Operations
+impl<T> Matrix<T> {
+	/// Returns the amount of columns.
+	pub const fn cols(&self) -> usize {
+		self.cols
+	}
+
+	/// Returns the entries row by row.
+	pub fn entries(&self) -> &[T] {
+		&self.entries
+	}
+
+	/// Returns the entry at a row and column, or [`None`] if it is out of bounds.
+	pub fn get(&self, row: usize, col: usize) -> Option<&T> {
+		(row < self.rows && col < self.cols).then(|| &self.entries[row * self.cols + col])
+	}
+
+	/// Returns the entries row by row, consuming the matrix.
+	pub fn into_entries(self) -> Vec<T> {
+		self.entries
+	}
+
+	/// Applies a function to every entry.
+	pub fn map<U>(self, f: impl FnMut(T) -> U) -> Matrix<U> {
+		Matrix {
+			rows: self.rows,
+			cols: self.cols,
+			entries: self.entries.into_iter().map(f).collect(),
+		}
+	}
+
+	/// Returns the entries of a row.
+	///
+	/// # Panics
+	///
+	/// Panics if the row is out of bounds.
+	pub fn row(&self, row: usize) -> &[T] {
+		assert!(row < self.rows, "row out of bounds");
+		&self.entries[row * self.cols..(row + 1) * self.cols]
+	}
+
+	/// Returns the amount of rows.
+	pub const fn rows(&self) -> usize {
+		self.rows
+	}
+
+	/// Returns the transposed matrix, whose rows are the columns of this matrix.
+	pub fn transpose(&self) -> Self
+	where
+		T: Clone,
+	{
+		Self::from_fn(self.cols, self.rows, |row, col| self[(col, row)].clone())
+	}
+
+	/// Internal method to get the matrix without a row and a column.
+	fn minor(&self, row: usize, col: usize) -> Self
+	where
+		T: Clone,
+	{
+		Self::from_fn(self.rows - 1, self.cols - 1, |i, j| {
+			self[(i + usize::from(i >= row), j + usize::from(j >= col))].clone()
+		})
+	}
+
+	/// Internal method to swap two rows.
+	fn swap_rows(&mut self, lhs: usize, rhs: usize) {
+		for col in 0..self.cols {
+			self.entries.swap(lhs * self.cols + col, rhs * self.cols + col);
+		}
+	}
+}
+
+// Constants
+impl Matrix<Number> {
+	/// The identity matrix of the given size.
+	pub fn identity(size: usize) -> Self {
+		Self::from_fn(size, size, |row, col| Number::from(u8::from(row == col)))
+	}
+
+	/// The zero matrix of the given size.
+	pub fn zero(rows: usize, cols: usize) -> Self {
+		Self::from_fn(rows, cols, |_, _| Number::zero())
+	}
+}
+
+// Linear algebra
+impl Matrix<Number> {
+	/// Computes the characteristic polynomial `det(x I - A)` with the Faddeev–LeVerrier algorithm. Returns [`None`] if
+	/// the matrix is not square.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::matrix::Matrix;
+	/// use abacas::number::Number;
+	///
+	/// let matrix = Matrix::from_rows(vec![vec![Number::from(2), 1.into()], vec![1.into(), 2.into()]]).unwrap();
+	/// assert_eq!(matrix.charpoly().unwrap().to_string(), "x^2 - 4x + 3");
+	/// ```
+	pub fn charpoly(&self) -> Option<Polynomial> {
+		if !self.is_square() {
+			return None;
+		}
+
+		// With `M_0 = 0`, every step computes `M_k = A M_(k-1) + c_(n-k+1) I` and `c_(n-k) = -tr(A M_k) / k`
+		let size = self.rows;
+		let mut coeffs = vec![Number::one()];
+		let mut product = Self::zero(size, size);
+
+		for step in 1..=size {
+			product = self.clone() * &product;
+
+			for index in 0..size {
+				product[(index, index)] += coeffs.last().unwrap();
+			}
+
+			let trace = (self.clone() * &product).trace().unwrap();
+			coeffs.push(-trace / &Number::from(step));
+		}
+
+		let monomials = coeffs
+			.into_iter()
+			.enumerate()
+			.filter(|(_, coeff)| !coeff.is_zero())
+			.map(|(index, coeff)| Monomial::new(coeff, size - index));
+
+		Some(Polynomial::new(monomials))
+	}
+
+	/// Computes the determinant with the fraction-free Bareiss algorithm, whose intermediate results stay small.
+	/// Returns [`None`] if the matrix is not square.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::matrix::Matrix;
+	/// use abacas::number::Number;
+	///
+	/// let rows = vec![
+	///     vec![Number::from(2), 0.into(), 1.into()],
+	///     vec![1.into(), 3.into(), 2.into()],
+	///     vec![1.into(), 1.into(), 2.into()],
+	/// ];
+	/// let matrix = Matrix::from_rows(rows).unwrap();
+	///
+	/// assert_eq!(matrix.determinant(), Some(6.into()));
+	/// ```
+	pub fn determinant(&self) -> Option<Number> {
+		if !self.is_square() {
+			return None;
+		}
+
+		let size = self.rows;
+		let mut matrix = self.clone();
+		let mut negate = false;
+		let mut previous = Number::one();
+
+		for step in 0..size {
+			// Swap a row with a non-zero pivot into place, which negates the determinant
+			let Some(pivot) = (step..size).find(|&row| !matrix[(row, step)].is_zero()) else {
+				return Some(Number::zero());
+			};
+
+			if pivot != step {
+				matrix.swap_rows(pivot, step);
+				negate = !negate;
+			}
+
+			// Every division is exact by Sylvester's identity
+			for row in step + 1..size {
+				for col in step + 1..size {
+					let value = matrix[(row, col)].clone() * &matrix[(step, step)]
+						- &(matrix[(row, step)].clone() * &matrix[(step, col)]);
+					matrix[(row, col)] = value / &previous;
+				}
+			}
+
+			previous = matrix[(step, step)].clone();
+		}
+
+		match negate {
+			true => Some(-previous),
+			false => Some(previous),
+		}
+	}
+
+	/// Computes the real eigenvalues with their algebraic multiplicities as the roots of the characteristic
+	/// polynomial, sorted in ascending order. Irrational eigenvalues are written exactly like the solutions of
+	/// [`Expr::solve_inequality`]. Returns [`None`] if the matrix is not square.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::context::Context;
+	/// use abacas::expr::Expr;
+	/// use abacas::matrix::Matrix;
+	/// use abacas::number::Number;
+	///
+	/// let ctx = &mut Context::new();
+	/// let rows = vec![
+	///     vec![Number::from(2), 1.into(), 0.into()],
+	///     vec![0.into(), 2.into(), 0.into()],
+	///     vec![0.into(), 0.into(), 5.into()],
+	/// ];
+	/// let matrix = Matrix::from_rows(rows).unwrap();
+	///
+	/// let eigenvalues = matrix.eigenvalues(ctx).unwrap().unwrap();
+	/// assert_eq!(eigenvalues, [(Expr::Num(2.into()), 2), (Expr::Num(5.into()), 1)]);
+	/// ```
+	pub fn eigenvalues(&self, ctx: &mut Context) -> Result<Option<Vec<(Expr, usize)>>, SimplifyError> {
+		let Some(charpoly) = self.charpoly() else {
+			return Ok(None);
+		};
+
+		let product = relation::square_free(&charpoly);
+
+		if !product.degree().is_some_and(Number::is_positive) {
+			return Ok(Some(Vec::new()));
+		}
+
+		// Every root belongs to exactly one square-free factor, which determines its multiplicity
+		let factors = square_free_factors(&charpoly);
+		let quotients: Vec<_> = factors
+			.iter()
+			.map(|(factor, _)| (factor.clone(), Polynomial::from(1)))
+			.collect();
+
+		let var = Symbol::new("x").unwrap();
+		let intervals = relation::isolate(&product);
+
+		(0..intervals.len())
+			.map(|index| {
+				let value = relation::exact_root(&quotients, &product, &intervals, index, &var, ctx)?;
+				let multiplicity = factors
+					.iter()
+					.find(|(factor, _)| relation::sign_at(factor, &intervals[index]).is_eq())
+					.map_or(1, |(_, multiplicity)| *multiplicity);
+
+				Ok((value, multiplicity))
+			})
+			.collect::<Result<_, _>>()
+			.map(Some)
+	}
+
+	/// Computes the inverse with Gauss–Jordan elimination. Returns [`None`] if the matrix is not square or singular.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::matrix::Matrix;
+	/// use abacas::number::Number;
+	///
+	/// let matrix = Matrix::from_rows(vec![vec![Number::from(1), 2.into()], vec![3.into(), 4.into()]]).unwrap();
+	/// let inverse = matrix.inverse().unwrap();
+	///
+	/// assert_eq!(inverse.to_string(), "[[-2, 1], [1.5, -0.5]]");
+	/// assert_eq!(inverse * &matrix, Matrix::identity(2));
+	/// ```
+	pub fn inverse(&self) -> Option<Self> {
+		if !self.is_square() {
+			return None;
+		}
+
+		// Reducing `[A | I]` turns the left half into the identity and the right half into the inverse
+		let size = self.rows;
+		let augmented = Self::from_fn(size, 2 * size, |row, col| match col < size {
+			true => self[(row, col)].clone(),
+			false => Number::from(u8::from(col - size == row)),
+		});
+
+		let (reduced, pivots) = augmented.rref();
+
+		if pivots.len() < size || pivots.iter().any(|&pivot| pivot >= size) {
+			return None;
+		}
+
+		Some(Self::from_fn(size, size, |row, col| reduced[(row, col + size)].clone()))
+	}
+
+	/// Computes the LU decomposition with row pivoting, returning the permutation matrix `P`, the unit lower
+	/// triangular matrix `L` and the upper triangular matrix `U` with `P A = L U`. Singular matrices have zeros on the
+	/// diagonal of `U`. Returns [`None`] if the matrix is not square.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::matrix::Matrix;
+	/// use abacas::number::Number;
+	///
+	/// let matrix = Matrix::from_rows(vec![vec![Number::from(0), 1.into()], vec![2.into(), 3.into()]]).unwrap();
+	/// let (p, l, u) = matrix.lu().unwrap();
+	///
+	/// assert_eq!(p.to_string(), "[[0, 1], [1, 0]]");
+	/// assert_eq!(u.to_string(), "[[2, 3], [0, 1]]");
+	/// assert_eq!(p * &matrix, l * &u);
+	/// ```
+	pub fn lu(&self) -> Option<(Self, Self, Self)> {
+		if !self.is_square() {
+			return None;
+		}
+
+		let size = self.rows;
+		let mut lower = Self::identity(size);
+		let mut upper = self.clone();
+		let mut permutation: Vec<_> = (0..size).collect();
+
+		for step in 0..size {
+			let Some(pivot) = (step..size).find(|&row| !upper[(row, step)].is_zero()) else {
+				continue;
+			};
+
+			// Swapping rows also swaps the multipliers found so far
+			if pivot != step {
+				upper.swap_rows(pivot, step);
+				permutation.swap(pivot, step);
+
+				for col in 0..step {
+					let (lhs, rhs) = (lower[(pivot, col)].clone(), lower[(step, col)].clone());
+					(lower[(pivot, col)], lower[(step, col)]) = (rhs, lhs);
+				}
+			}
+
+			for row in step + 1..size {
+				let factor = upper[(row, step)].clone() / &upper[(step, step)];
+
+				for col in step..size {
+					let value = factor.clone() * &upper[(step, col)];
+					upper[(row, col)] -= &value;
+				}
+
+				lower[(row, step)] = factor;
+			}
+		}
+
+		let permutation = Self::from_fn(size, size, |row, col| Number::from(u8::from(permutation[row] == col)));
+
+		Some((permutation, lower, upper))
+	}
+
+	/// Computes a basis of the nullspace as column vectors, with one vector for every column without a pivot in the
+	/// reduced row echelon form.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::matrix::Matrix;
+	/// use abacas::number::Number;
+	///
+	/// let matrix = Matrix::from_rows(vec![vec![Number::from(1), 2.into(), 3.into()]]).unwrap();
+	/// let basis = matrix.nullspace();
+	///
+	/// assert_eq!(basis.len(), 2);
+	/// assert_eq!(basis[0].to_string(), "[[-2], [1], [0]]");
+	/// assert_eq!(matrix * &basis[1], Matrix::zero(1, 1));
+	/// ```
+	pub fn nullspace(&self) -> Vec<Self> {
+		let (reduced, pivots) = self.rref();
+
+		let free = (0..self.cols).filter(|col| !pivots.contains(col));
+
+		free.map(|free| {
+			let mut vector = Self::zero(self.cols, 1);
+			vector[(free, 0)] = Number::one();
+
+			for (row, &pivot) in pivots.iter().enumerate() {
+				vector[(pivot, 0)] = -reduced[(row, free)].clone();
+			}
+
+			vector
+		})
+		.collect()
+	}
+
+	/// Computes the QR decomposition with the Gram–Schmidt process, returning a matrix `Q` with orthogonal columns
+	/// and the unit upper triangular matrix `R` with `A = Q R`. The columns of `Q` are not normalized, which would
+	/// need square roots, and columns that depend on the previous ones become zero.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::matrix::Matrix;
+	/// use abacas::number::Number;
+	///
+	/// let matrix = Matrix::from_rows(vec![vec![Number::from(1), 1.into()], vec![1.into(), 0.into()]]).unwrap();
+	/// let (q, r) = matrix.qr();
+	///
+	/// assert_eq!(q.to_string(), "[[1, 0.5], [1, -0.5]]");
+	/// assert_eq!(r.to_string(), "[[1, 0.5], [0, 1]]");
+	/// assert_eq!(q * &r, matrix);
+	/// ```
+	pub fn qr(&self) -> (Self, Self) {
+		let dot = |matrix: &Self, lhs: usize, rhs: usize| {
+			(0..matrix.rows).fold(Number::zero(), |acc, row| {
+				acc + &(matrix[(row, lhs)].clone() * &matrix[(row, rhs)])
+			})
+		};
+
+		let mut orthogonal = self.clone();
+		let mut triangular = Self::identity(self.cols);
+
+		// Subtract the projections onto all previous columns from every column
+		for col in 0..self.cols {
+			for prev in 0..col {
+				let norm = dot(&orthogonal, prev, prev);
+
+				if norm.is_zero() {
+					continue;
+				}
+
+				let coeff = dot(&orthogonal, col, prev) / &norm;
+
+				for row in 0..self.rows {
+					let value = coeff.clone() * &orthogonal[(row, prev)];
+					orthogonal[(row, col)] -= &value;
+				}
+
+				triangular[(prev, col)] = coeff;
+			}
+		}
+
+		(orthogonal, triangular)
+	}
+
+	/// Computes the rank, which is the amount of linearly independent rows or columns.
+	pub fn rank(&self) -> usize {
+		self.rref().1.len()
+	}
+
+	/// Computes the reduced row echelon form with Gauss–Jordan elimination, returning it together with the columns
+	/// of its pivots.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::matrix::Matrix;
+	/// use abacas::number::Number;
+	///
+	/// let rows = vec![vec![Number::from(1), 2.into(), 3.into()], vec![2.into(), 4.into(), 7.into()]];
+	/// let (reduced, pivots) = Matrix::from_rows(rows).unwrap().rref();
+	///
+	/// assert_eq!(reduced.to_string(), "[[1, 2, 0], [0, 0, 1]]");
+	/// assert_eq!(pivots, [0, 2]);
+	/// ```
+	pub fn rref(&self) -> (Self, Vec<usize>) {
+		let mut reduced = self.clone();
+		let mut pivots = Vec::new();
+
+		for col in 0..self.cols {
+			let step = pivots.len();
+
+			if step == self.rows {
+				break;
+			}
+
+			let Some(pivot) = (step..self.rows).find(|&row| !reduced[(row, col)].is_zero()) else {
+				continue;
+			};
+
+			reduced.swap_rows(pivot, step);
+
+			// Scale the pivot to one, then eliminate the column in every other row
+			let scale = reduced[(step, col)].clone().recip();
+
+			for entry in col..self.cols {
+				reduced[(step, entry)] *= &scale;
+			}
+
+			for row in (0..self.rows).filter(|&row| row != step) {
+				let factor = reduced[(row, col)].clone();
+
+				if factor.is_zero() {
+					continue;
+				}
+
+				for entry in col..self.cols {
+					let value = factor.clone() * &reduced[(step, entry)];
+					reduced[(row, entry)] -= &value;
+				}
+			}
+
+			pivots.push(col);
+		}
+
+		(reduced, pivots)
+	}
+
+	/// Computes the trace, which is the sum of the diagonal. Returns [`None`] if the matrix is not square.
+	pub fn trace(&self) -> Option<Number> {
+		self.is_square()
+			.then(|| (0..self.rows).fold(Number::zero(), |acc, index| acc + &self[(index, index)]))
+	}
+}
+
+// Symbolic linear algebra
+impl Matrix<Expr> {
+	/// Computes the coefficients of the characteristic polynomial `det(x I - A)`, starting with the leading one, with
+	/// the division-free Berkowitz algorithm. Returns [`None`] if the matrix is not square.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::context::Context;
+	/// use abacas::expr::{Expr, Symbol};
+	/// use abacas::matrix::Matrix;
+	///
+	/// let ctx = &mut Context::new();
+	/// let a = Expr::Poly(Symbol::new("a").unwrap(), "x".parse().unwrap());
+	///
+	/// let matrix = Matrix::from_rows(vec![vec![a, Expr::one()], vec![Expr::one(), Expr::zero()]]).unwrap();
+	/// let coeffs = matrix.charpoly(ctx).unwrap().unwrap();
+	///
+	/// assert_eq!(coeffs.iter().map(Expr::to_string).collect::<Vec<_>>(), ["1", "-a", "-1"]);
+	/// ```
+	pub fn charpoly(&self, ctx: &mut Context) -> Result<Option<Vec<Expr>>, SimplifyError> {
+		if !self.is_square() {
+			return Ok(None);
+		}
+
+		let mut coeffs = vec![Expr::one()];
+
+		// Extend the characteristic polynomial of the leading block by one row and column at a time
+		for size in 0..self.rows {
+			let last = &self.row(size)[..size];
+			let block = Self::from_fn(size, size, |row, col| self[(row, col)].clone());
+
+			// The first column of a lower triangular Toeplitz matrix `1, -a, -R S, -R A S, -R A^2 S, ...`
+			let mut toeplitz = vec![Expr::one(), -self[(size, size)].clone()];
+			let mut column = Self::from_fn(size, 1, |row, _| self[(row, size)].clone());
+
+			for _ in 0..size {
+				let product = Expr::Add(
+					last.iter()
+						.cloned()
+						.zip(column.entries.clone())
+						.map(|(lhs, rhs)| lhs * rhs)
+						.collect(),
+				);
+				toeplitz.push(trig::collect(-product, ctx)?);
+				column = (block.clone() * column).simplify(ctx)?;
+			}
+
+			coeffs = (0..=size + 1)
+				.map(|index| {
+					let terms =
+						(0..=index.min(size)).map(|other| toeplitz[index - other].clone() * coeffs[other].clone());
+					trig::collect(Expr::Add(terms.collect()), ctx)
+				})
+				.collect::<Result<_, _>>()?;
+		}
+
+		Ok(Some(coeffs))
+	}
+
+	/// Computes the determinant, exactly with [`Matrix::determinant`] if all entries are numbers and without divisions
+	/// otherwise. Returns [`None`] if the matrix is not square.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::context::Context;
+	/// use abacas::expr::{Expr, Symbol};
+	/// use abacas::matrix::Matrix;
+	///
+	/// let ctx = &mut Context::new();
+	/// let x = |poly: &str| Expr::Poly(Symbol::new("x").unwrap(), poly.parse().unwrap());
+	///
+	/// let matrix = Matrix::from_rows(vec![vec![x("x"), x("1")], vec![x("1"), x("x")]]).unwrap();
+	/// assert_eq!(matrix.determinant(ctx).unwrap().unwrap().to_string(), "x^2 - 1");
+	/// ```
+	pub fn determinant(&self, ctx: &mut Context) -> Result<Option<Expr>, SimplifyError> {
+		if let Some(matrix) = self.to_numbers() {
+			return Ok(matrix.determinant().map(Expr::Num));
+		}
+
+		let Some(mut coeffs) = self.charpoly(ctx)? else {
+			return Ok(None);
+		};
+
+		// The constant coefficient is `det(-A) = (-1)^n det(A)`
+		let constant = coeffs.pop().unwrap();
+
+		match self.rows % 2 {
+			0 => Ok(Some(constant)),
+			_ => trig::collect(-constant, ctx).map(Some),
+		}
+	}
+
+	/// Computes the inverse, exactly with [`Matrix::inverse`] if all entries are numbers and as the adjugate divided
+	/// by the determinant otherwise. Returns [`None`] if the matrix is not square or known to be singular.
+	pub fn inverse(&self, ctx: &mut Context) -> Result<Option<Self>, SimplifyError> {
+		if let Some(matrix) = self.to_numbers() {
+			return Ok(matrix.inverse().map(|inverse| inverse.map(Expr::Num)));
+		}
+
+		let Some(determinant) = self.determinant(ctx)? else {
+			return Ok(None);
+		};
+
+		if determinant.is_num_and(Number::is_zero) {
+			return Ok(None);
+		}
+
+		// Every entry of the adjugate is a cofactor of the transposed position
+		let recip = determinant.pow(Expr::neg_one());
+		let mut entries = Vec::with_capacity(self.entries.len());
+
+		for row in 0..self.rows {
+			for col in 0..self.cols {
+				let minor = self.minor(col, row).determinant(ctx)?.unwrap();
+				let sign = if (row + col) % 2 == 0 {
+					Expr::one()
+				} else {
+					Expr::neg_one()
+				};
+
+				entries.push(Expr::Mul(vec![sign, minor, recip.clone()]).simplify_builtin(ctx)?);
+			}
+		}
+
+		Ok(Some(Self {
+			rows: self.rows,
+			cols: self.cols,
+			entries,
+		}))
+	}
+
+	/// Simplifies every entry.
+	pub fn simplify(self, ctx: &mut Context) -> Result<Self, SimplifyError> {
+		let entries = self
+			.entries
+			.into_iter()
+			.map(|entry| entry.simplify_builtin(ctx))
+			.collect::<Result<_, _>>()?;
+
+		Ok(Self { entries, ..self })
+	}
+
+	/// Returns a matrix of numbers if every entry is a number, otherwise returns [`None`].
+	pub fn to_numbers(&self) -> Option<Matrix<Number>> {
+		let entries = self
+			.entries
+			.iter()
+			.map(|entry| entry.clone().into_num())
+			.collect::<Option<_>>()?;
+
+		Some(Matrix {
+			rows: self.rows,
+			cols: self.cols,
+			entries,
+		})
+	}
+}
+
+impl<T> Index<(usize, usize)> for Matrix<T> {
+	type Output = T;
+
+	fn index(&self, (row, col): (usize, usize)) -> &Self::Output {
+		self.get(row, col).expect("index out of bounds")
+	}
+}
+
+impl<T> IndexMut<(usize, usize)> for Matrix<T> {
+	fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut Self::Output {
+		assert!(row < self.rows && col < self.cols, "index out of bounds");
+		&mut self.entries[row * self.cols + col]
+	}
+}
+
+impl Add<&Self> for Matrix<Number> {
+	type Output = Self;
+
+	/// # Panics
+	///
+	/// Panics if the matrices have different sizes.
+	fn add(mut self, rhs: &Self) -> Self::Output {
+		assert!(
+			self.rows == rhs.rows && self.cols == rhs.cols,
+			"matrices have different sizes"
+		);
+
+		self.entries
+			.iter_mut()
+			.zip(&rhs.entries)
+			.for_each(|(lhs, rhs)| *lhs += rhs);
+		self
+	}
+}
+
+impl Add<Self> for Matrix<Expr> {
+	type Output = Self;
+
+	/// # Panics
+	///
+	/// Panics if the matrices have different sizes.
+	fn add(self, rhs: Self) -> Self::Output {
+		assert!(
+			self.rows == rhs.rows && self.cols == rhs.cols,
+			"matrices have different sizes"
+		);
+
+		let entries = self.entries.into_iter().zip(rhs.entries).map(|(lhs, rhs)| lhs + rhs);
+		Self {
+			entries: entries.collect(),
+			..self
+		}
+	}
+}
+
+impl Mul<&Self> for Matrix<Number> {
+	type Output = Self;
+
+	/// # Panics
+	///
+	/// Panics if the left matrix has a different amount of columns than the right one has rows.
+	fn mul(self, rhs: &Self) -> Self::Output {
+		assert_eq!(self.cols, rhs.rows, "matrices have incompatible sizes");
+
+		Self::from_fn(self.rows, rhs.cols, |row, col| {
+			(0..self.cols).fold(Number::zero(), |acc, index| {
+				acc + &(self[(row, index)].clone() * &rhs[(index, col)])
+			})
+		})
+	}
+}
+
+impl Mul<Self> for Matrix<Expr> {
+	type Output = Self;
+
+	/// # Panics
+	///
+	/// Panics if the left matrix has a different amount of columns than the right one has rows.
+	fn mul(self, rhs: Self) -> Self::Output {
+		assert_eq!(self.cols, rhs.rows, "matrices have incompatible sizes");
+
+		Self::from_fn(self.rows, rhs.cols, |row, col| {
+			let terms = (0..self.cols).map(|index| self[(row, index)].clone() * rhs[(index, col)].clone());
+			Expr::Add(terms.collect())
+		})
+	}
+}
+
+impl Neg for Matrix<Number> {
+	type Output = Self;
+
+	fn neg(self) -> Self::Output {
+		self.map(Neg::neg)
+	}
+}
+
+impl Sub<&Self> for Matrix<Number> {
+	type Output = Self;
+
+	/// # Panics
+	///
+	/// Panics if the matrices have different sizes.
+	fn sub(mut self, rhs: &Self) -> Self::Output {
+		assert!(
+			self.rows == rhs.rows && self.cols == rhs.cols,
+			"matrices have different sizes"
+		);
+
+		self.entries
+			.iter_mut()
+			.zip(&rhs.entries)
+			.for_each(|(lhs, rhs)| *lhs -= rhs);
+		self
+	}
+}
+
+impl<T: fmt::Display> fmt::Display for Matrix<T> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let rows = (0..self.rows).map(|row| format!("[{}]", self.row(row).iter().format(", ")));
+		write!(f, "[{}]", rows.format(", "))
+	}
+}
+
+/// Internal helper to split a polynomial into square-free factors with Yun's algorithm, returning every factor of
+/// positive degree together with the multiplicity of its roots.
+fn square_free_factors(poly: &Polynomial) -> Vec<(Polynomial, usize)> {
+	let slope = poly.derivative();
+	let gcd = poly.clone().gcd(slope.clone());
+
+	let mut rest = poly.clone().div_rem(&gcd).unwrap().0;
+	let mut diff = slope.div_rem(&gcd).unwrap().0 - rest.derivative();
+	let mut factors = Vec::new();
+
+	for multiplicity in 1.. {
+		if !rest.degree().is_some_and(Number::is_positive) {
+			break;
+		}
+
+		let factor = rest.clone().gcd(diff.clone());
+		rest = rest.div_rem(&factor).unwrap().0;
+		diff = diff.div_rem(&factor).unwrap().0 - rest.derivative();
+
+		if factor.degree().is_some_and(Number::is_positive) {
+			factors.push((factor, multiplicity));
+		}
+	}
+
+	factors
+}
@@ -6,6 +6,7 @@ use std::{fmt, str};
 use rug::ops::{NegAssign, Pow, PowAssign};
 
 use crate::error::ParseError;
+use crate::expr::Symbol;
 use crate::number::Number;
 use crate::polynomial::Polynomial;
 
@@ -59,6 +60,29 @@ impl Monomial {
 		Self::new(coeff, 0)
 	}
 
+	/// Returns a value that displays this monomial in the given variable instead of `x`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::expr::Symbol;
+	/// use abacas::monomial::Monomial;
+	///
+	/// let y = Symbol::new("y").unwrap();
+	/// assert_eq!(Monomial::new(-3, 2).display_with(&y).to_string(), "-3y^2");
+	/// ```
+	pub fn display_with<'a>(&'a self, sym: &'a Symbol) -> impl fmt::Display + 'a {
+		struct DisplayWith<'a>(&'a Monomial, &'a Symbol);
+
+		impl fmt::Display for DisplayWith<'_> {
+			fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+				self.0.write(f, false, self.1.name())
+			}
+		}
+
+		DisplayWith(self, sym)
+	}
+
 	/// Creates a linear monomial. Panics if `coeff` is zero.
 	///
 	/// # Examples
@@ -94,6 +118,67 @@ impl Monomial {
 		Self { coeff, degree }
 	}
 
+	/// Parses a monomial in the given variable instead of `x`, allowing whitespace and an optional `*` between the
+	/// coefficient and the variable.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::expr::Symbol;
+	/// use abacas::monomial::Monomial;
+	///
+	/// let t = Symbol::new("t").unwrap();
+	///
+	/// assert_eq!(Monomial::parse_with("-3t^2", &t).unwrap(), Monomial::new(-3, 2));
+	/// assert_eq!(Monomial::parse_with("1/2 * t", &t).unwrap().display_with(&t).to_string(), "0.5t");
+	/// assert!(Monomial::parse_with("3x", &t).is_err());
+	/// ```
+	pub fn parse_with(s: &str, sym: &Symbol) -> Result<Self, ParseError> {
+		let (s, name) = (s.trim(), sym.name());
+
+		// The last occurrence of the variable that is followed by a degree or nothing, so that `0x1F` stays a number
+		let split = s.rmatch_indices(name).find_map(|(index, _)| {
+			let tail = s[index + name.len()..].trim_start();
+			(tail.is_empty() || tail.starts_with('^')).then(|| (&s[..index], tail))
+		});
+
+		let (init, degree) = match split {
+			Some((init, "")) => (init, Number::one()),
+			Some((init, tail)) => {
+				let degree = tail[1..].trim();
+				let degree = degree
+					.strip_prefix('(')
+					.and_then(|inner| inner.strip_suffix(')'))
+					.unwrap_or(degree);
+				(init, degree.trim().parse()?)
+			}
+			None => (s, Number::zero()),
+		};
+
+		let init = init.trim_end();
+		let init = init.strip_suffix('*').unwrap_or(init).trim_end();
+
+		let (negative, init) = match init.strip_prefix('-') {
+			Some(rest) => (true, rest.trim_start()),
+			None => (false, init.strip_prefix('+').unwrap_or(init).trim_start()),
+		};
+
+		let mut coeff = match init {
+			"" if !degree.is_zero() => Number::one(),
+			_ => init.parse()?,
+		};
+
+		if coeff.is_zero() {
+			return Err(ParseError::InvalidNumber(coeff));
+		}
+
+		if negative {
+			coeff.neg_assign();
+		}
+
+		Ok(Self::new(coeff, degree))
+	}
+
 	/// Internal method to write this monomial with specific configuration.
 	pub(crate) fn write(&self, f: &mut fmt::Formatter<'_>, abs: bool, sym: &str) -> fmt::Result {
 		if self.degree.is_zero() {
@@ -104,10 +189,18 @@ impl Monomial {
 			write!(f, "-")?;
 		} else if !self.coeff.is_neg_one() && !self.coeff.is_one() {
 			self.coeff.write(f, abs)?;
+
+			// A symbol like `e1` would be read as the exponent of the number, like in `2e1`
+			if sym.starts_with(['e', 'E']) && sym[1..].starts_with(|char: char| char.is_ascii_digit()) {
+				write!(f, " * ")?;
+			}
 		}
 
+		// Fractions in the degree need parentheses to be read back
 		if self.degree.is_one() {
 			write!(f, "{sym}")
+		} else if self.degree.decimal_places().is_none() {
+			write!(f, "{sym}^({})", self.degree)
 		} else {
 			write!(f, "{sym}^{}", self.degree)
 		}
@@ -245,24 +338,6 @@ impl str::FromStr for Monomial {
 	type Err = ParseError;
 
 	fn from_str(s: &str) -> Result<Self, Self::Err> {
-		let (init, degree) = if let Some((init, tail)) = s.split_once("x^") {
-			(init, tail.parse()?)
-		} else if let Some(init) = s.strip_suffix('x') {
-			(init, Number::one())
-		} else {
-			(s, Number::zero())
-		};
-
-		let coeff = match init {
-			"" | "+" if !degree.is_zero() => Number::one(),
-			"-" if !degree.is_zero() => Number::neg_one(),
-			_ => init.parse()?,
-		};
-
-		if coeff.is_zero() {
-			return Err(ParseError::InvalidNumber(coeff));
-		}
-
-		Ok(Self::new(coeff, degree))
+		Self::parse_with(s, &Symbol::new("x").unwrap())
 	}
 }
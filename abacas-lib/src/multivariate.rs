@@ -0,0 +1,510 @@
+//! The multivariate polynomial structure over the rationals and its monomial orders.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use itertools::{EitherOrBoth, Itertools};
+use rug::ops::Pow;
+
+use crate::expr::{Expr, Symbol};
+use crate::monomial::Monomial;
+use crate::number::Number;
+use crate::polynomial::Polynomial;
+
+/// An order on the monomials of several variables, which decides the leading monomial of a multivariate polynomial.
+/// Variables are ordered as they were given, so the first variable is the largest.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum MonomialOrder {
+	/// The elimination order for the given number of leading variables, which compares the total degree in these
+	/// variables first and breaks ties with the graded reverse lexicographic order.
+	Elimination(usize),
+	/// The graded lexicographic order, which compares the total degree first and breaks ties lexicographically.
+	GrLex,
+	/// The graded reverse lexicographic order, which compares the total degree first and breaks ties in favor of the
+	/// smaller degree in the last variable where the degrees differ.
+	GrevLex,
+	/// The lexicographic order, which compares the degrees of the variables one by one.
+	Lex,
+}
+
+impl MonomialOrder {
+	/// Compares two monomials given by the degrees of their variables.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use std::cmp::Ordering;
+	///
+	/// use abacas::multivariate::MonomialOrder;
+	///
+	/// // `x y^2` against `x^2`
+	/// assert_eq!(MonomialOrder::Lex.cmp(&[1, 2], &[2, 0]), Ordering::Less);
+	/// assert_eq!(MonomialOrder::GrLex.cmp(&[1, 2], &[2, 0]), Ordering::Greater);
+	///
+	/// // `x z^2` against `y^3`
+	/// assert_eq!(MonomialOrder::GrLex.cmp(&[1, 0, 2], &[0, 3, 0]), Ordering::Greater);
+	/// assert_eq!(MonomialOrder::GrevLex.cmp(&[1, 0, 2], &[0, 3, 0]), Ordering::Less);
+	/// ```
+	pub fn cmp(self, lhs: &[u32], rhs: &[u32]) -> Ordering {
+		let total = |degrees: &[u32]| degrees.iter().sum::<u32>();
+		let lex = || lhs.cmp(rhs);
+		let revlex = || lhs.iter().rev().cmp(rhs.iter().rev()).reverse();
+
+		match self {
+			Self::Elimination(count) => {
+				let count = count.min(lhs.len());
+				let eliminated = total(&lhs[..count]).cmp(&total(&rhs[..count]));
+				eliminated.then_with(|| Self::GrevLex.cmp(lhs, rhs))
+			}
+			Self::GrLex => total(lhs).cmp(&total(rhs)).then_with(lex),
+			Self::GrevLex => total(lhs).cmp(&total(rhs)).then_with(revlex),
+			Self::Lex => lex(),
+		}
+	}
+
+	/// Whether every monomial containing one of the given number of leading variables is larger than all monomials
+	/// without them. The basis elements free of these variables then generate the elimination ideal.
+	pub const fn eliminates(self, count: usize) -> bool {
+		match self {
+			Self::Elimination(other) => count == 0 || count == other,
+			Self::GrLex | Self::GrevLex => count == 0,
+			Self::Lex => true,
+		}
+	}
+}
+
+/// A term `c x_1^d_1 ... x_n^d_n` of a multivariate polynomial, consisting of the coefficient `c` and the natural
+/// degrees `d_i` of the variables.
+///
+/// # Examples
+///
+/// ```
+/// use abacas::multivariate::MultiMonomial;
+///
+/// let lhs = MultiMonomial::new(2, vec![1, 2]);
+/// let rhs = MultiMonomial::new(3, vec![2, 0]);
+///
+/// assert_eq!(lhs.clone() * &rhs, MultiMonomial::new(6, vec![3, 2]));
+/// assert_eq!(lhs.lcm(&rhs), MultiMonomial::new(1, vec![2, 2]));
+/// assert!(!lhs.divides(&rhs));
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct MultiMonomial {
+	/// The coefficient of the monomial
+	pub coeff: Number,
+	/// The degree of every variable
+	pub degrees: Vec<u32>,
+}
+
+impl MultiMonomial {
+	/// Creates a new monomial. Panics if `coeff` is zero.
+	pub fn new(coeff: impl Into<Number>, degrees: Vec<u32>) -> Self {
+		let coeff = coeff.into();
+
+		if coeff.is_zero() {
+			panic!("coefficient must not be zero");
+		}
+
+		Self { coeff, degrees }
+	}
+
+	/// Divides this monomial by another one. Returns [`None`] if the other monomial does not divide this one.
+	pub fn checked_div(&self, rhs: &Self) -> Option<Self> {
+		let degrees = self
+			.degrees
+			.iter()
+			.zip(&rhs.degrees)
+			.map(|(lhs, rhs)| lhs.checked_sub(*rhs));
+
+		Some(Self {
+			coeff: self.coeff.clone() / &rhs.coeff,
+			degrees: degrees.collect::<Option<_>>()?,
+		})
+	}
+
+	/// Whether this monomial divides another one, ignoring the coefficients.
+	pub fn divides(&self, other: &Self) -> bool {
+		self.degrees.iter().zip(&other.degrees).all(|(lhs, rhs)| lhs <= rhs)
+	}
+
+	/// Returns the least common multiple of two monomials with coefficient one.
+	pub fn lcm(&self, other: &Self) -> Self {
+		let degrees = self.degrees.iter().zip(&other.degrees).map(|(lhs, rhs)| *lhs.max(rhs));
+		Self::new(1, degrees.collect())
+	}
+
+	/// Returns the total degree, which is the sum of the degrees of all variables.
+	pub fn total_degree(&self) -> u32 {
+		self.degrees.iter().sum()
+	}
+}
+
+impl Mul<&Self> for MultiMonomial {
+	type Output = Self;
+
+	#[expect(clippy::suspicious_arithmetic_impl)]
+	fn mul(mut self, rhs: &Self) -> Self::Output {
+		self.coeff *= &rhs.coeff;
+		self.degrees
+			.iter_mut()
+			.zip(&rhs.degrees)
+			.for_each(|(lhs, rhs)| *lhs += rhs);
+		self
+	}
+}
+
+/// A polynomial in several variables with rational coefficients, with its monomials sorted by a monomial order in
+/// descending order.
+///
+/// # Examples
+///
+/// ```
+/// use abacas::expr::{Expr, Symbol};
+/// use abacas::multivariate::{MonomialOrder, MultiPolynomial};
+///
+/// let vars = [Symbol::new("x").unwrap(), Symbol::new("y").unwrap()];
+/// let x = MultiPolynomial::variable(&vars, MonomialOrder::Lex, 0);
+/// let y = MultiPolynomial::variable(&vars, MonomialOrder::Lex, 1);
+///
+/// let poly = (x.clone() + &y) * &(x - &y);
+/// assert_eq!(poly.to_string(), "x^2 - y^2");
+///
+/// let expr = Expr::Poly(vars[1].clone(), "3x^2 + 1".parse().unwrap());
+/// let poly = MultiPolynomial::from_expr(&expr, &vars, MonomialOrder::Lex).unwrap();
+/// assert_eq!(poly.to_string(), "3y^2 + 1");
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct MultiPolynomial {
+	/// The variables, starting with the largest
+	vars: Vec<Symbol>,
+	/// The order of the monomials
+	order: MonomialOrder,
+	/// The monomials in descending order
+	monomials: Vec<MultiMonomial>,
+}
+
+// Constructors
+impl MultiPolynomial {
+	/// Creates a constant polynomial.
+	pub fn constant(vars: &[Symbol], order: MonomialOrder, value: impl Into<Number>) -> Self {
+		let value = value.into();
+		let monomials = (!value.is_zero()).then(|| MultiMonomial::new(value, vec![0; vars.len()]));
+		Self::new(vars, order, monomials)
+	}
+
+	/// Converts an expression into a polynomial in the given variables. Returns [`None`] if the expression is not a
+	/// polynomial in these variables with rational coefficients.
+	pub fn from_expr(expr: &Expr, vars: &[Symbol], order: MonomialOrder) -> Option<Self> {
+		match expr {
+			Expr::Add(terms) => terms.iter().try_fold(Self::zero(vars, order), |acc, term| {
+				Some(acc + &Self::from_expr(term, vars, order)?)
+			}),
+			Expr::Mul(factors) => factors.iter().try_fold(Self::constant(vars, order, 1), |acc, factor| {
+				Some(acc * &Self::from_expr(factor, vars, order)?)
+			}),
+			Expr::Num(num) => Some(Self::constant(vars, order, num.clone())),
+			Expr::Poly(sym, poly) => {
+				let index = vars.iter().position(|var| var == sym)?;
+				Self::from_univariate(poly, vars, order, index)
+			}
+			Expr::Pow(base, exp) => {
+				let exp = exp.clone().into_num()?.to_u32()?;
+				let base = Self::from_expr(base, vars, order)?;
+				Some((0..exp).fold(Self::constant(vars, order, 1), |acc, _| acc * &base))
+			}
+			_ => None,
+		}
+	}
+
+	/// Converts a univariate polynomial into a polynomial in the variable at the given index. Returns [`None`] if any
+	/// degree is not a natural number.
+	pub fn from_univariate(poly: &Polynomial, vars: &[Symbol], order: MonomialOrder, index: usize) -> Option<Self> {
+		let monomials = poly.monomials().map(|mono| {
+			let mut degrees = vec![0; vars.len()];
+			degrees[index] = mono.degree.to_u32()?;
+			Some(MultiMonomial::new(mono.coeff.clone(), degrees))
+		});
+
+		Some(Self::new(vars, order, monomials.collect::<Option<Vec<_>>>()?))
+	}
+
+	/// Creates a new polynomial from its monomials, which are combined and sorted.
+	pub fn new(vars: &[Symbol], order: MonomialOrder, monomials: impl IntoIterator<Item = MultiMonomial>) -> Self {
+		let mut monomials = monomials.into_iter().collect_vec();
+		monomials.sort_by(|lhs, rhs| order.cmp(&rhs.degrees, &lhs.degrees));
+
+		// Combine neighbouring monomials with the same degrees
+		let monomials = monomials
+			.into_iter()
+			.coalesce(|mut lhs, rhs| match lhs.degrees == rhs.degrees {
+				true => {
+					lhs.coeff += &rhs.coeff;
+					Ok(lhs)
+				}
+				false => Err((lhs, rhs)),
+			});
+
+		Self {
+			vars: vars.to_vec(),
+			order,
+			monomials: monomials.filter(|mono| !mono.coeff.is_zero()).collect(),
+		}
+	}
+
+	/// Creates the polynomial of the variable at the given index.
+	pub fn variable(vars: &[Symbol], order: MonomialOrder, index: usize) -> Self {
+		let mut degrees = vec![0; vars.len()];
+		degrees[index] = 1;
+		Self::new(vars, order, [MultiMonomial::new(1, degrees)])
+	}
+
+	/// The zero polynomial.
+	pub fn zero(vars: &[Symbol], order: MonomialOrder) -> Self {
+		Self::new(vars, order, [])
+	}
+}
+
+// Guards
+impl MultiPolynomial {
+	/// Whether this is a non-zero constant.
+	pub fn is_constant(&self) -> bool {
+		self.monomials.len() == 1 && self.monomials[0].total_degree() == 0
+	}
+
+	/// Whether this is the zero polynomial.
+	pub const fn is_zero(&self) -> bool {
+		self.monomials.is_empty()
+	}
+}
+
+// Operations
+impl MultiPolynomial {
+	/// Returns the leading monomial, or [`None`] for the zero polynomial.
+	pub fn leading(&self) -> Option<&MultiMonomial> {
+		self.monomials.first()
+	}
+
+	/// Returns the polynomial divided by its leading coefficient, so that it becomes one.
+	pub fn monic(mut self) -> Self {
+		if let Some(leading) = self.leading() {
+			let scale = leading.coeff.clone().recip();
+			self.monomials.iter_mut().for_each(|mono| mono.coeff *= &scale);
+		}
+
+		self
+	}
+
+	/// Returns the monomials in descending order.
+	pub fn monomials(&self) -> &[MultiMonomial] {
+		&self.monomials
+	}
+
+	/// Returns the order of the monomials.
+	pub const fn order(&self) -> MonomialOrder {
+		self.order
+	}
+
+	/// Substitutes a number for the variable at the given index, which keeps the variable with degree zero.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::expr::Symbol;
+	/// use abacas::multivariate::{MonomialOrder, MultiPolynomial};
+	///
+	/// let vars = [Symbol::new("x").unwrap(), Symbol::new("y").unwrap()];
+	/// let x = MultiPolynomial::variable(&vars, MonomialOrder::Lex, 0);
+	/// let y = MultiPolynomial::variable(&vars, MonomialOrder::Lex, 1);
+	///
+	/// let poly = x.clone() * &x * &y + &y;
+	/// assert_eq!(poly.substitute(0, &2.into()).to_string(), "5y");
+	/// ```
+	pub fn substitute(&self, index: usize, value: &Number) -> Self {
+		let monomials = self.monomials.iter().filter_map(|mono| {
+			let coeff = mono.coeff.clone() * &value.clone().pow(mono.degrees[index]);
+			let mut degrees = mono.degrees.clone();
+			degrees[index] = 0;
+			(!coeff.is_zero()).then(|| MultiMonomial::new(coeff, degrees))
+		});
+
+		Self::new(&self.vars, self.order, monomials)
+	}
+
+	/// Converts the polynomial into an expression.
+	pub fn to_expr(&self) -> Expr {
+		let mut terms = self
+			.monomials
+			.iter()
+			.map(|mono| self.term(mono.coeff.clone(), &mono.degrees));
+
+		match self.monomials.len() {
+			0 => Expr::zero(),
+			1 => terms.next().unwrap(),
+			_ => Expr::Add(terms.collect()),
+		}
+	}
+	/// Converts the polynomial into a univariate polynomial in the variable at the given index. Returns [`None`] if
+	/// any other variable occurs.
+	pub fn to_univariate(&self, index: usize) -> Option<Polynomial> {
+		let monomials = self.monomials.iter().map(|mono| {
+			let others = mono
+				.degrees
+				.iter()
+				.enumerate()
+				.all(|(other, degree)| other == index || *degree == 0);
+			others.then(|| Monomial::new(mono.coeff.clone(), mono.degrees[index]))
+		});
+
+		Some(Polynomial::new(monomials.collect::<Option<Vec<_>>>()?))
+	}
+
+	/// Returns the variables, starting with the largest.
+	pub fn vars(&self) -> &[Symbol] {
+		&self.vars
+	}
+
+	/// Returns the same polynomial with its monomials sorted by another order.
+	pub fn with_order(self, order: MonomialOrder) -> Self {
+		Self::new(&self.vars, order, self.monomials)
+	}
+
+	/// Internal method to convert a monomial into an expression, with the coefficient on the first variable.
+	fn term(&self, coeff: Number, degrees: &[u32]) -> Expr {
+		let mut coeff = Some(coeff);
+		let factors = self
+			.vars
+			.iter()
+			.zip(degrees)
+			.filter(|(_, degree)| **degree > 0)
+			.map(|(var, degree)| {
+				let coeff = coeff.take().unwrap_or_else(Number::one);
+				Expr::Poly(var.clone(), Monomial::new(coeff, *degree).into())
+			})
+			.collect_vec();
+
+		match (coeff, factors.len()) {
+			(Some(coeff), _) => Expr::Num(coeff),
+			(None, 1) => factors.into_iter().next().unwrap(),
+			(None, _) => Expr::Mul(factors),
+		}
+	}
+
+	/// Internal method to add a multiple `scale * rhs` of another polynomial, merging the sorted monomials.
+	fn add_scaled(&mut self, rhs: &Self, scale: &MultiMonomial) {
+		let order = self.order;
+		let lhs = std::mem::take(&mut self.monomials);
+		let rhs = rhs.monomials.iter().map(|mono| mono.clone() * scale);
+
+		let merged = lhs
+			.into_iter()
+			.merge_join_by(rhs, |lhs, rhs| order.cmp(&rhs.degrees, &lhs.degrees))
+			.filter_map(|either| match either {
+				EitherOrBoth::Both(mut lhs, rhs) => {
+					lhs.coeff += &rhs.coeff;
+					(!lhs.coeff.is_zero()).then_some(lhs)
+				}
+				EitherOrBoth::Left(mono) | EitherOrBoth::Right(mono) => Some(mono),
+			});
+
+		self.monomials = merged.collect();
+	}
+}
+
+impl<T> Add<T> for MultiPolynomial
+where
+	Self: AddAssign<T>,
+{
+	type Output = Self;
+
+	fn add(mut self, rhs: T) -> Self::Output {
+		self += rhs;
+		self
+	}
+}
+
+impl AddAssign<&Self> for MultiPolynomial {
+	fn add_assign(&mut self, rhs: &Self) {
+		self.add_scaled(rhs, &MultiMonomial::new(1, vec![0; self.vars.len()]));
+	}
+}
+
+impl<T> Mul<T> for MultiPolynomial
+where
+	Self: MulAssign<T>,
+{
+	type Output = Self;
+
+	fn mul(mut self, rhs: T) -> Self::Output {
+		self *= rhs;
+		self
+	}
+}
+
+impl MulAssign<&MultiMonomial> for MultiPolynomial {
+	fn mul_assign(&mut self, rhs: &MultiMonomial) {
+		// Monomial orders are compatible with multiplication, so the monomials stay sorted
+		self.monomials.iter_mut().for_each(|mono| *mono = mono.clone() * rhs);
+	}
+}
+
+impl MulAssign<&Self> for MultiPolynomial {
+	fn mul_assign(&mut self, rhs: &Self) {
+		let lhs = std::mem::replace(self, Self::zero(&rhs.vars, self.order));
+
+		for mono in &rhs.monomials {
+			self.add_scaled(&lhs, mono);
+		}
+	}
+}
+
+impl Neg for MultiPolynomial {
+	type Output = Self;
+
+	fn neg(mut self) -> Self::Output {
+		self.monomials
+			.iter_mut()
+			.for_each(|mono| mono.coeff = -mono.coeff.clone());
+		self
+	}
+}
+
+impl<T> Sub<T> for MultiPolynomial
+where
+	Self: SubAssign<T>,
+{
+	type Output = Self;
+
+	fn sub(mut self, rhs: T) -> Self::Output {
+		self -= rhs;
+		self
+	}
+}
+
+impl SubAssign<&Self> for MultiPolynomial {
+	fn sub_assign(&mut self, rhs: &Self) {
+		self.add_scaled(rhs, &MultiMonomial::new(-1, vec![0; self.vars.len()]));
+	}
+}
+
+impl fmt::Display for MultiPolynomial {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		if self.is_zero() {
+			return write!(f, "0");
+		}
+
+		// Extract the minus of negative coefficients, like univariate polynomials
+		for (index, mono) in self.monomials.iter().enumerate() {
+			let sign = match (index, mono.coeff.is_negative()) {
+				(0, true) => "-",
+				(0, false) => "",
+				(_, true) => " - ",
+				(_, false) => " + ",
+			};
+
+			write!(f, "{sign}{}", self.term(mono.coeff.clone().abs(), &mono.degrees))?;
+		}
+
+		Ok(())
+	}
+}
@@ -4,11 +4,15 @@ use std::cmp::Ordering;
 use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign};
 use std::{fmt, str};
 
-use rug::Rational;
+use rug::float::Round;
 use rug::ops::{DivRounding, DivRoundingAssign, NegAssign, Pow, PowAssign, RemRounding, RemRoundingAssign};
+use rug::{Float, Integer, Rational};
 
 use crate::error::ParseError;
 
+/// The maximum exponent of ten in a number literal like `1e100`, larger ones are rejected.
+const MAX_EXPONENT: u32 = 10_000;
+
 /// Represents a specific number. Currently uses [`Rational`] under the hood, however this should not be relied upon.
 #[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct Number(Rational);
@@ -66,11 +70,188 @@ impl Number {
 
 // Operations
 impl Number {
+	/// Internal method to estimate the size of this number in bits from its numerator and denominator, which is zero
+	/// for `0` and `±1`, so that the size of a power is estimated by multiplying with the exponent.
+	pub(crate) fn bits(&self) -> u64 {
+		let bits = u64::from(self.0.numer().significant_bits()) + u64::from(self.0.denom().significant_bits());
+		bits.saturating_sub(2)
+	}
+
+	/// Raises this number to a rational power exactly.
+	/// Returns [`None`] if the result is not rational, or zero is raised to a negative power.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::number::Number;
+	///
+	/// let number = Number::from(8) / 27;
+	///
+	/// assert_eq!(number.checked_pow(&(Number::from(2) / 3)), Some(Number::from(4) / 9));
+	/// assert_eq!(number.checked_pow(&(Number::from(-1) / 3)), Some(Number::from(3) / 2));
+	/// assert_eq!(number.checked_pow(&(Number::from(1) / 2)), None);
+	/// assert_eq!(Number::from(-8).checked_pow(&(Number::from(1) / 3)), Some((-2).into()));
+	/// ```
+	pub fn checked_pow(&self, exp: &Self) -> Option<Self> {
+		if self.is_zero() && exp.is_negative() {
+			return None;
+		}
+
+		let power = exp.0.numer().as_abs().to_u32()?;
+		let degree = exp.0.denom().to_u32()?;
+
+		// Even roots of negative numbers are not real
+		if self.is_negative() && degree % 2 == 0 {
+			return None;
+		}
+
+		// Both numerator and denominator have to be perfect powers for the root to be rational
+		let root = |value: &Integer| {
+			let (root, rem) = value.clone().root_rem(Integer::new(), degree);
+			rem.is_zero().then_some(root)
+		};
+
+		let mut result = Self(Rational::from((root(self.0.numer())?, root(self.0.denom())?)));
+		result.0.pow_assign(power);
+
+		if exp.is_negative() {
+			result.0.recip_mut();
+		}
+
+		Some(result)
+	}
+
+	/// Gets the simple continued fraction `[a0; a1, a2, ...]` of this number, whose terms are all integers.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::number::Number;
+	///
+	/// let number = Number::from(415) / 93;
+	/// assert_eq!(number.continued_fraction(), [4, 2, 6, 7].map(Number::from));
+	///
+	/// let number = Number::from(-7) / 2;
+	/// assert_eq!(number.continued_fraction(), [-4, 2].map(Number::from));
+	/// ```
+	pub fn continued_fraction(&self) -> Vec<Self> {
+		let mut terms = Vec::new();
+		let mut rest = self.0.clone();
+
+		loop {
+			let (fract, floor) = rest.fract_floor(Integer::new());
+			terms.push(Self::from_integer(floor));
+
+			if fract.is_zero() {
+				return terms;
+			}
+
+			rest = fract.recip();
+		}
+	}
+
+	/// Gets the convergents of the continued fraction of this number, the last one being the number itself.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::number::Number;
+	///
+	/// let number = Number::from(415) / 93;
+	/// let expected = [(4, 1), (9, 2), (58, 13), (415, 93)].map(|(n, d)| Number::from(n) / d);
+	///
+	/// assert_eq!(number.convergents(), expected);
+	/// ```
+	pub fn convergents(&self) -> Vec<Self> {
+		let (mut numer, mut prev_numer) = (Self::one(), Self::zero());
+		let (mut denom, mut prev_denom) = (Self::zero(), Self::one());
+
+		let convergents = self.continued_fraction().into_iter().map(|term| {
+			(numer, prev_numer) = (term.clone() * &numer + &prev_numer, numer.clone());
+			(denom, prev_denom) = (term * &denom + &prev_denom, denom.clone());
+
+			numer.clone() / &denom
+		});
+
+		convergents.collect()
+	}
+
+	/// Internal method to get the amount of digits after the decimal point if this number is a terminating decimal with
+	/// at most 16 of them, which requires its denominator to have no prime factors besides 2 and 5.
+	pub(crate) fn decimal_places(&self) -> Option<u32> {
+		let mut denom = self.0.denom().clone();
+		let twos = denom.remove_factor_mut(&Integer::from(2));
+		let fives = denom.remove_factor_mut(&Integer::from(5));
+
+		(denom == 1).then_some(twos.max(fives)).filter(|&places| places <= 16)
+	}
+
 	/// Gets the denominator of this number.
 	pub fn denom(self) -> Self {
 		Self(self.0.into_numer_denom().1.into())
 	}
 
+	/// Creates a number from the terms of its continued fraction `[a0; a1, a2, ...]`.
+	/// Returns [`None`] if there are no terms or the continued fraction divides by zero.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::number::Number;
+	///
+	/// let number = Number::from_continued_fraction(&[4, 2, 6, 7].map(Number::from));
+	/// assert_eq!(number, Some(Number::from(415) / 93));
+	///
+	/// let number = Number::from_continued_fraction(&[1, 0].map(Number::from));
+	/// assert_eq!(number, None);
+	/// ```
+	pub fn from_continued_fraction(terms: &[Self]) -> Option<Self> {
+		let (last, init) = terms.split_last()?;
+
+		init.iter()
+			.rev()
+			.try_fold(last.clone(), |acc, term| (!acc.is_zero()).then(|| acc.recip() + term))
+	}
+
+	/// Internal method to create a number from a finite float, or [`None`] if it is infinite or NaN.
+	pub(crate) fn from_float(value: &Float) -> Option<Self> {
+		value.to_rational().map(Self)
+	}
+
+	/// Internal method to create a number from an integer.
+	pub(crate) fn from_integer(value: Integer) -> Self {
+		Self(value.into())
+	}
+
+	/// Parses a number literal like [`str::parse`], however non-integer decimals with a finite amount of digits are
+	/// parsed as the simplest rational number that rounds to the literal at its printed precision.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::number::Number;
+	///
+	/// assert_eq!(Number::from_str_approx("3.14159").unwrap(), Number::from(355) / 113);
+	/// assert_eq!(Number::from_str_approx("0.333").unwrap(), Number::from(1) / 3);
+	/// assert_eq!(Number::from_str_approx("-0.5").unwrap(), Number::from(-1) / 2);
+	/// assert_eq!(Number::from_str_approx("1.4286e-1").unwrap(), Number::from(1) / 7);
+	/// assert_eq!(Number::from_str_approx("42").unwrap(), 42);
+	/// ```
+	pub fn from_str_approx(s: &str) -> Result<Self, ParseError> {
+		let (value, precision) = Literal::parse(s)?;
+
+		// Integers already have the smallest possible denominator
+		let Some(precision) = precision.filter(|_| !value.is_integer()) else {
+			return Ok(value);
+		};
+
+		// Every number within half a unit of the last printed digit rounds to the literal
+		let half = Self::from(10).pow(precision) / 2;
+		let (lo, hi) = (value.clone() - &half, value + &half);
+
+		Ok(Self::simplest_between(&lo, &hi))
+	}
+
 	/// Gets the greatest common divisor.
 	pub fn gcd(mut self, rhs: &Self) -> Self {
 		self.gcd_mut(rhs);
@@ -99,6 +280,56 @@ impl Number {
 		});
 	}
 
+	/// Gets the closest number to this one whose denominator is at most `max`. Panics if `max` is less than one.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::number::Number;
+	///
+	/// let number = Number::from(314159) / 100000;
+	///
+	/// assert_eq!(number.limit_denominator(&10.into()), Number::from(22) / 7);
+	/// assert_eq!(number.limit_denominator(&1000.into()), Number::from(355) / 113);
+	/// assert_eq!(number.limit_denominator(&1.into()), 3);
+	/// ```
+	pub fn limit_denominator(&self, max: &Self) -> Self {
+		if *max < 1 {
+			panic!("maximum denominator must be at least one");
+		}
+
+		if self.0.denom() <= &max.0 {
+			return self.clone();
+		}
+
+		let (mut numer, mut denom) = self.clone().ratio();
+		let (mut p0, mut q0, mut p1, mut q1) = (Self::zero(), Self::one(), Self::one(), Self::zero());
+
+		// Walk the convergents until the next denominator would exceed the maximum
+		loop {
+			let term = numer.clone().div_floor(&denom);
+			let q2 = q0.clone() + &(term.clone() * &q1);
+
+			if q2 > *max {
+				break;
+			}
+
+			(p0, q0, p1, q1) = (p1.clone(), q1, p0 + &(term.clone() * &p1), q2);
+			(numer, denom) = (denom.clone(), numer - &(term * &denom));
+		}
+
+		// The best approximation is either the last convergent or the best semiconvergent
+		let k = (max.clone() - &q0).div_floor(&q1);
+		let semi = (p0 + &(k.clone() * &p1)) / &(q0 + &(k * &q1));
+		let last = p1 / &q1;
+
+		if (last.clone() - self).abs() <= (semi.clone() - self).abs() {
+			last
+		} else {
+			semi
+		}
+	}
+
 	/// Gets the numerator of this number.
 	pub fn numer(self) -> Self {
 		Self(self.0.into_numer_denom().0.into())
@@ -110,12 +341,93 @@ impl Number {
 		(Self(numer.into()), Self(denom.into()))
 	}
 
-	/// Internal method to write this number with specific configuration.
+	/// Internal method to get `n` if this number is a unit fraction `1 / n` with `n > 1`, like the exponent of a root.
+	pub(crate) fn root_index(&self) -> Option<u32> {
+		let (numer, denom) = self.clone().ratio();
+		denom.to_u32().filter(|&index| numer.is_one() && index > 1)
+	}
+
+	/// Gets the simplest number strictly between `lo` and `hi`, which is the one with the smallest denominator and
+	/// then the smallest absolute numerator. Returns `lo` if both bounds are equal.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::number::Number;
+	///
+	/// let lo = Number::from(3) / 10;
+	/// let hi = Number::from(4) / 10;
+	/// assert_eq!(Number::simplest_between(&lo, &hi), Number::from(1) / 3);
+	///
+	/// let lo = Number::from(-5) / 2;
+	/// let hi = Number::from(7) / 2;
+	/// assert_eq!(Number::simplest_between(&lo, &hi), 0);
+	/// ```
+	pub fn simplest_between(lo: &Self, hi: &Self) -> Self {
+		match lo.cmp(hi) {
+			Ordering::Equal => return lo.clone(),
+			Ordering::Greater => return Self::simplest_between(hi, lo),
+			Ordering::Less => {}
+		}
+
+		if lo.is_negative() && hi.is_positive() {
+			return Self::zero();
+		}
+
+		if !hi.is_positive() {
+			return -Self::simplest_between(&-hi.clone(), &-lo.clone());
+		}
+
+		// If an integer lies between both bounds, the smallest one is the simplest
+		let floor = lo.clone().floor();
+		let next = floor.clone() + 1;
+
+		if next < *hi {
+			return next;
+		}
+
+		// Otherwise, both bounds share the integer part and the fractional part is found recursively
+		let lower_recip = (hi.clone() - &floor).recip();
+
+		if *lo == floor {
+			return floor + &(lower_recip.floor() + 1).recip();
+		}
+
+		let upper_recip = (lo.clone() - &floor).recip();
+		floor + &Self::simplest_between(&lower_recip, &upper_recip).recip()
+	}
+
+	/// Internal method to convert this number into a float with the given precision and rounding direction.
+	pub(crate) fn to_float(&self, prec: u32, round: Round) -> Float {
+		Float::with_val_round(prec, &self.0, round).0
+	}
+
+	/// Internal method to get this number as an integer, or [`None`] if it is not an integer.
+	pub(crate) fn to_integer(&self) -> Option<&Integer> {
+		self.is_integer().then(|| self.0.numer())
+	}
+
+	/// Internal method to get this number as a [`u32`], or [`None`] if it is not a non-negative integer below `2^32`.
+	pub(crate) fn to_u32(&self) -> Option<u32> {
+		self.to_integer().and_then(Integer::to_u32)
+	}
+
+	/// Internal method to write this number exactly, optionally without its sign. Integers and short terminating
+	/// decimals are written with their digits and other numbers as a fraction `a/b`, which the parser reads back.
 	pub(crate) fn write(&self, f: &mut fmt::Formatter<'_>, abs: bool) -> fmt::Result {
-		if abs {
-			write!(f, "{}", self.0.to_f64().abs())
-		} else {
-			write!(f, "{}", self.0.to_f64())
+		let sign = if self.is_negative() && !abs { "-" } else { "" };
+		let numer = Integer::from(self.0.numer().abs_ref());
+
+		match self.decimal_places() {
+			Some(0) => write!(f, "{sign}{numer}"),
+			Some(places) => {
+				let digits = (numer * Integer::from(10).pow(places) / self.0.denom()).to_string();
+				let digits = format!("{digits:0>width$}", width = places as usize + 1);
+				let (int, frac) = digits.split_at(digits.len() - places as usize);
+
+				write!(f, "{sign}{int}.{frac}")
+			}
+			None => write!(f, "{sign}{numer}/{}", self.0.denom()),
 		}
 	}
 }
@@ -364,13 +676,213 @@ impl fmt::Display for Number {
 impl str::FromStr for Number {
 	type Err = ParseError;
 
+	/// Parses a number literal exactly. Supported are an optional sign followed by either a single literal or a
+	/// fraction `a/b` of two literals. Each literal may be
+	///
+	/// - an integer with optional digit separators, like `1_000_000`,
+	/// - an integer with a radix prefix, like `0xFF`, `0o17` or `0b101`,
+	/// - a decimal with an optional repeating part and exponent, like `1.5e-3` or `0.(142857)`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::number::Number;
+	///
+	/// let parse = |s: &str| s.parse::<Number>().unwrap();
+	///
+	/// assert_eq!(parse("1_000_000"), 1000000);
+	/// assert_eq!(parse("-2/3"), Number::from(-2) / 3);
+	/// assert_eq!(parse("1.5e-3"), Number::from(3) / 2000);
+	/// assert_eq!(parse("0.(142857)"), Number::from(1) / 7);
+	/// assert_eq!(parse("0x1F"), 31);
+	/// ```
 	fn from_str(s: &str) -> Result<Self, Self::Err> {
-		let full = match s.split_once('.') {
-			Some((int, fract)) => format!("{}{}/1{}", int, fract, "0".repeat(fract.len())),
-			None => s.into(),
+		Literal::parse(s).map(|(value, _)| value)
+	}
+}
+
+/// Internal parser for number literals, keeping track of the current byte index for error reporting.
+struct Literal<'a> {
+	input: &'a str,
+	index: usize,
+}
+
+impl Literal<'_> {
+	/// Parses a full literal, returning its value and the power of ten of its last digit if it is an inexact decimal.
+	fn parse(input: &str) -> Result<(Number, Option<i64>), ParseError> {
+		let mut literal = Literal { input, index: 0 };
+
+		let negative = match literal.peek() {
+			Some(sign @ ('+' | '-')) => {
+				literal.index += 1;
+				sign == '-'
+			}
+			_ => false,
+		};
+
+		let (mut value, mut precision) = literal.unsigned()?;
+
+		if literal.eat('/') {
+			let start = literal.index;
+			let (denom, _) = literal.unsigned()?;
+
+			if denom.is_zero() {
+				return Err(ParseError::ZeroDenominator(start));
+			}
+
+			value /= &denom;
+			precision = None;
+		}
+
+		if let Some(char) = literal.peek() {
+			return Err(ParseError::UnexpectedChar(char, literal.index));
+		}
+
+		if negative {
+			value.neg_assign();
+		}
+
+		Ok((value, precision))
+	}
+
+	/// Parses an unsigned literal, returning its value and the power of ten of its last digit if it is an inexact decimal.
+	fn unsigned(&mut self) -> Result<(Number, Option<i64>), ParseError> {
+		let rest = &self.input[self.index..];
+
+		// Integers with a radix prefix
+		for (prefix, radix) in [("0x", 16), ("0X", 16), ("0o", 8), ("0O", 8), ("0b", 2), ("0B", 2)] {
+			if rest.starts_with(prefix) {
+				self.index += prefix.len();
+				let (digits, _) = self.digits(radix)?;
+
+				return Ok((Number::from_integer(digits), None));
+			}
+		}
+
+		// Decimals with an optional fractional and repeating part
+		let int = match self.peek() {
+			Some('.') => None,
+			_ => Some(self.digits(10)?.0),
 		};
 
-		full.parse().map(Self).map_err(|_| ParseError::InvalidString(full))
+		let mut value = Number::from_integer(int.clone().unwrap_or_default());
+		let mut precision = None;
+
+		if self.eat('.') {
+			let mut scale = 0;
+
+			// Without an integer part, at least one fractional or repeating digit is required
+			if int.is_none() && self.peek() != Some('(') || self.peek().is_some_and(|char| char.is_ascii_digit()) {
+				let (fract, count) = self.digits(10)?;
+
+				scale = count;
+				value += &(Number::from_integer(fract) / &Number::from(10).pow(count));
+			}
+
+			if self.eat('(') {
+				let (repeat, count) = self.digits(10)?;
+				self.expect(')')?;
+
+				let period = Number::from(10).pow(count) - 1;
+				value += &(Number::from_integer(repeat) / &period / &Number::from(10).pow(scale));
+			} else {
+				precision = Some(-scale);
+			}
+		}
+
+		// An optional exponent of ten
+		if let Some('e' | 'E') = self.peek() {
+			self.index += 1;
+
+			let negative = match self.peek() {
+				Some(sign @ ('+' | '-')) => {
+					self.index += 1;
+					sign == '-'
+				}
+				_ => false,
+			};
+
+			let start = self.index;
+			let (exponent, _) = self.digits(10)?;
+
+			// Exponents are bounded, since the power of ten is computed exactly
+			let Some(exponent) = exponent
+				.to_u32()
+				.filter(|&exponent| exponent <= MAX_EXPONENT)
+				.map(i64::from)
+			else {
+				return Err(ParseError::InvalidString(self.input[start..self.index].into()));
+			};
+
+			let exponent = if negative { -exponent } else { exponent };
+
+			value *= &Number::from(10).pow(exponent);
+			precision = precision.map(|precision| precision + exponent);
+		}
+
+		Ok((value, precision))
+	}
+
+	/// Parses a non-empty sequence of digits in the given radix, allowing single underscores between digits.
+	/// Returns the value and the amount of digits.
+	fn digits(&mut self, radix: u32) -> Result<(Integer, i64), ParseError> {
+		let start = self.index;
+		let mut digits = String::new();
+
+		while let Some(char) = self.peek() {
+			if char.is_digit(radix) {
+				digits.push(char);
+			} else if char == '_'
+				&& !digits.is_empty()
+				&& self.input[self.index + 1..].starts_with(|c: char| c.is_digit(radix))
+			{
+				// Separators are only allowed between two digits
+			} else {
+				break;
+			}
+
+			self.index += 1;
+		}
+
+		if digits.is_empty() {
+			return Err(match self.peek() {
+				Some(char) => ParseError::UnexpectedChar(char, self.index),
+				None => ParseError::UnexpectedEnd(start),
+			});
+		}
+
+		let count = digits.len() as i64;
+		let value = Integer::from_str_radix(&digits, radix as i32).unwrap();
+
+		Ok((value, count))
+	}
+
+	/// Consumes the given character if it is next, returning whether it was consumed.
+	fn eat(&mut self, expected: char) -> bool {
+		let found = self.peek() == Some(expected);
+
+		if found {
+			self.index += expected.len_utf8();
+		}
+
+		found
+	}
+
+	/// Consumes the given character, returning an error if it is not next.
+	fn expect(&mut self, expected: char) -> Result<(), ParseError> {
+		if self.eat(expected) {
+			return Ok(());
+		}
+
+		Err(match self.peek() {
+			Some(char) => ParseError::UnexpectedChar(char, self.index),
+			None => ParseError::UnexpectedEnd(self.index),
+		})
+	}
+
+	/// Returns the next character without consuming it.
+	fn peek(&self) -> Option<char> {
+		self.input[self.index..].chars().next()
 	}
 }
 
@@ -0,0 +1,596 @@
+//! Pattern matching and rewrite rules for expressions.
+//!
+//! Patterns are expressions in which symbols ending in an underscore are wildcards. A wildcard like `a_` matches any
+//! single expression, while a sequence wildcard like `b__` matches any number of terms of a sum, factors of a product or
+//! arguments of a function call, including none. Sums and products match regardless of the order of their terms, and a
+//! wildcard that appears several times in a pattern has to match the same expression every time.
+
+use std::collections::HashMap;
+
+use itertools::Itertools;
+
+use crate::context::Context;
+use crate::error::SimplifyError;
+use crate::expr::{Expr, Symbol};
+use crate::matrix::Matrix;
+use crate::monomial::Monomial;
+use crate::polynomial::Polynomial;
+
+/// The maximum amount of passes of [`Expr::replace_repeated`] before giving up on reaching a fixed point.
+const MAX_PASSES: u32 = 64;
+
+/// The name of the sequence wildcard that matches the remaining terms of a sum or product in a rule.
+const REST: &str = "rest#__";
+
+/// The expressions matched by the wildcards of a pattern. Sequence wildcards are bound to the sum or product of the
+/// matched sequence, depending on where they appear.
+pub type Bindings = HashMap<Symbol, Expr>;
+
+/// A condition on the bindings of a match that has to hold for a rule to apply.
+pub type Condition = fn(&Bindings) -> bool;
+
+/// A rewrite rule `lhs -> rhs`, replacing expressions that match the pattern `lhs` with `rhs`, where the wildcards are
+/// substituted with what they matched.
+///
+/// A sum or product without sequence wildcards also matches a part of a larger sum or product, with the remaining terms
+/// kept as they are. For example, `sin(a_)^2 + cos(a_)^2 -> 1` rewrites `sin(x)^2 + cos(x)^2 + y` to `1 + y`.
+#[derive(Clone, Debug)]
+pub struct Rule {
+	lhs: Expr,
+	rhs: Expr,
+	condition: Option<Condition>,
+}
+
+impl Rule {
+	/// Applies this rule to the whole expression, returning the simplified result or [`None`] if it does not match.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::context::Context;
+	/// use abacas::expr::{Expr, Symbol};
+	/// use abacas::pattern::Rule;
+	///
+	/// let ctx = &mut Context::new();
+	/// let a = Expr::Poly(Symbol::new("a_").unwrap(), "x".parse().unwrap());
+	/// let ln = |arg| Expr::Fun(Symbol::new("ln").unwrap(), vec![arg]);
+	///
+	/// // ln(a_^2) -> 2ln(a_)
+	/// let rule = Rule::new(ln(a.clone() * a.clone()), Expr::Num(2.into()) * ln(a));
+	///
+	/// let x = Expr::Poly(Symbol::new("x").unwrap(), "x^2".parse().unwrap());
+	/// assert_eq!(rule.apply(&ln(x), ctx).unwrap().unwrap().to_string(), "ln(x) * 2");
+	/// ```
+	pub fn apply(&self, expr: &Expr, ctx: &mut Context) -> Result<Option<Expr>, SimplifyError> {
+		let expr = tree(&expr.clone().simplify_builtin(ctx)?);
+
+		match self.rewrite(&expr, ctx)? {
+			Some(result) => Ok(Some(result.simplify_builtin(ctx)?)),
+			None => Ok(None),
+		}
+	}
+
+	/// Creates a new rule replacing `lhs` with `rhs`. Both sides are simplified first, so that the pattern is in the
+	/// same form as the expressions it is matched against.
+	pub fn new(lhs: Expr, rhs: Expr) -> Self {
+		let ctx = &mut Context::new();
+		let lhs = tree(&lhs.clone().simplify_builtin(ctx).unwrap_or(lhs));
+		let rhs = tree(&rhs.clone().simplify_builtin(ctx).unwrap_or(rhs));
+
+		let rest = || Expr::Poly(Symbol::new(REST).unwrap(), Monomial::linear(1).into());
+
+		// Sums and products without sequence wildcards also match parts of larger ones
+		let (lhs, rhs) = match lhs {
+			Expr::Add(mut patterns) if !patterns.iter().any(is_sequence) => {
+				patterns.push(rest());
+				(Expr::Add(patterns), Expr::Add(vec![rhs, rest()]))
+			}
+			Expr::Mul(mut patterns) if !patterns.iter().any(is_sequence) => {
+				patterns.push(rest());
+				(Expr::Mul(patterns), Expr::Mul(vec![rhs, rest()]))
+			}
+			lhs => (lhs, rhs),
+		};
+
+		Self {
+			lhs,
+			rhs,
+			condition: None,
+		}
+	}
+
+	/// Internal method to rewrite an expression written as a tree, without simplifying the result.
+	fn rewrite(&self, expr: &Expr, ctx: &mut Context) -> Result<Option<Expr>, SimplifyError> {
+		for bindings in match_expr(&self.lhs, expr, &Bindings::new()) {
+			if let Some(condition) = self.condition {
+				let simplified: Bindings = bindings
+					.iter()
+					.map(|(sym, value)| Ok::<_, SimplifyError>((sym.clone(), value.clone().simplify_builtin(ctx)?)))
+					.try_collect()?;
+
+				if !condition(&simplified) {
+					continue;
+				}
+			}
+
+			return Ok(Some(instantiate(&self.rhs, &bindings)));
+		}
+
+		Ok(None)
+	}
+
+	/// Restricts this rule to matches whose simplified bindings fulfill the condition.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::context::Context;
+	/// use abacas::expr::{Expr, Symbol};
+	/// use abacas::pattern::{Bindings, Rule};
+	///
+	/// let ctx = &mut Context::new();
+	/// let abs = |arg| Expr::Fun(Symbol::new("abs").unwrap(), vec![arg]);
+	/// let is_num = |bindings: &Bindings| bindings[&Symbol::new("a_").unwrap()].is_num();
+	///
+	/// // abs(a_) -> a_ if a_ is a number
+	/// let a = Expr::Poly(Symbol::new("a_").unwrap(), "x".parse().unwrap());
+	/// let rule = Rule::new(abs(a.clone()), a).when(is_num);
+	///
+	/// let x = Expr::Poly(Symbol::new("x").unwrap(), "x".parse().unwrap());
+	/// assert_eq!(rule.apply(&abs(Expr::Num(2.into())), ctx).unwrap(), Some(Expr::Num(2.into())));
+	/// assert_eq!(rule.apply(&abs(x), ctx).unwrap(), None);
+	/// ```
+	pub fn when(mut self, condition: Condition) -> Self {
+		self.condition = Some(condition);
+		self
+	}
+}
+
+/// A named collection of rules, which can be registered on a [`Context`] to be applied whenever expressions are
+/// simplified.
+#[derive(Clone, Debug)]
+pub struct RuleSet {
+	/// The name of this rule set.
+	pub name: String,
+	/// The rules of this set, which are tried in order.
+	pub rules: Vec<Rule>,
+}
+
+impl RuleSet {
+	/// Creates a new rule set with the given name and rules.
+	pub fn new(name: impl Into<String>, rules: impl IntoIterator<Item = Rule>) -> Self {
+		Self {
+			name: name.into(),
+			rules: rules.into_iter().collect(),
+		}
+	}
+}
+
+// Operations
+impl Expr {
+	/// Matches this expression against a pattern, returning the simplified bindings of the wildcards of the first match
+	/// or [`None`] if the pattern does not match.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::context::Context;
+	/// use abacas::expr::{Expr, Symbol};
+	///
+	/// let ctx = &mut Context::new();
+	/// let a = Symbol::new("a_").unwrap();
+	/// let b = Symbol::new("b_").unwrap();
+	///
+	/// // a_ * x + b_
+	/// let x = Expr::Poly(Symbol::new("x").unwrap(), "x".parse().unwrap());
+	/// let pattern = Expr::Poly(a.clone(), "x".parse().unwrap()) * x + Expr::Poly(b.clone(), "x".parse().unwrap());
+	///
+	/// let expr = Expr::Poly(Symbol::new("x").unwrap(), "3x - 1".parse().unwrap());
+	/// let bindings = expr.matches(&pattern, ctx).unwrap().unwrap();
+	///
+	/// assert_eq!(bindings[&a], Expr::Num(3.into()));
+	/// assert_eq!(bindings[&b], Expr::Num((-1).into()));
+	/// ```
+	pub fn matches(&self, pattern: &Self, ctx: &mut Context) -> Result<Option<Bindings>, SimplifyError> {
+		let pattern = tree(&pattern.clone().simplify_builtin(ctx)?);
+		let expr = tree(&self.clone().simplify_builtin(ctx)?);
+
+		match match_expr(&pattern, &expr, &Bindings::new()).into_iter().next() {
+			Some(bindings) => Ok(Some(
+				bindings
+					.into_iter()
+					.map(|(sym, value)| Ok::<_, SimplifyError>((sym, value.simplify_builtin(ctx)?)))
+					.try_collect()?,
+			)),
+			None => Ok(None),
+		}
+	}
+
+	/// Applies the first matching rule to each subexpression once, starting at the whole expression and not descending
+	/// into replaced parts. The result is simplified.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::context::Context;
+	/// use abacas::expr::{Expr, Symbol};
+	/// use abacas::pattern::Rule;
+	/// use rug::ops::Pow;
+	///
+	/// let ctx = &mut Context::new();
+	/// let fun = |name, arg| Expr::Fun(Symbol::new(name).unwrap(), vec![arg]);
+	/// let square = |expr: Expr| expr.pow(Expr::Num(2.into()));
+	///
+	/// // sin(a_)^2 + cos(a_)^2 -> 1
+	/// let a = Expr::Poly(Symbol::new("a_").unwrap(), "x".parse().unwrap());
+	/// let rule = Rule::new(square(fun("sin", a.clone())) + square(fun("cos", a)), Expr::one());
+	///
+	/// let x = Expr::Poly(Symbol::new("x").unwrap(), "2x".parse().unwrap());
+	/// let expr = square(fun("sin", x.clone())) + square(fun("cos", x)) + Expr::Num(3.into());
+	///
+	/// assert_eq!(expr.replace_all(&[rule], ctx).unwrap(), Expr::Num(4.into()));
+	/// ```
+	pub fn replace_all(&self, rules: &[Rule], ctx: &mut Context) -> Result<Self, SimplifyError> {
+		let expr = self.clone().simplify_builtin(ctx)?;
+
+		match replace(&tree(&expr), rules, ctx)? {
+			Some(result) => result.simplify_builtin(ctx),
+			None => Ok(expr),
+		}
+	}
+
+	/// Applies the rules with [`Self::replace_all`] until the expression no longer changes.
+	pub fn replace_repeated(&self, rules: &[Rule], ctx: &mut Context) -> Result<Self, SimplifyError> {
+		let mut expr = self.clone().simplify_builtin(ctx)?;
+
+		for _ in 0..MAX_PASSES {
+			let Some(result) = replace(&tree(&expr), rules, ctx)? else {
+				break;
+			};
+
+			let result = result.simplify_builtin(ctx)?;
+
+			if result == expr {
+				break;
+			}
+
+			expr = result;
+		}
+
+		Ok(expr)
+	}
+}
+
+/// Internal helper to bind a wildcard, failing if it is already bound to a different expression.
+fn bind(sym: &Symbol, value: Expr, bindings: &Bindings) -> Option<Bindings> {
+	match bindings.get(sym) {
+		Some(bound) => (*bound == value).then(|| bindings.clone()),
+		None => {
+			let mut bindings = bindings.clone();
+			bindings.insert(sym.clone(), value);
+			Some(bindings)
+		}
+	}
+}
+
+/// Internal helper to substitute the bound wildcards of an expression, splicing sequences into function arguments.
+fn instantiate(expr: &Expr, bindings: &Bindings) -> Expr {
+	if let Some((sym, _)) = wildcard(expr)
+		&& let Some(value) = bindings.get(sym)
+	{
+		return value.clone();
+	}
+
+	let each = |exprs: &[Expr]| exprs.iter().map(|expr| instantiate(expr, bindings)).collect();
+
+	match expr {
+		Expr::Add(terms) => Expr::Add(each(terms)),
+		Expr::And(exprs) => Expr::And(each(exprs)),
+		Expr::Fun(name, args) => {
+			let args = args.iter().flat_map(|arg| match wildcard(arg) {
+				Some((sym, true)) if let Some(Expr::Add(items)) = bindings.get(sym) => items.clone(),
+				_ => vec![instantiate(arg, bindings)],
+			});
+
+			Expr::Fun(name.clone(), args.collect())
+		}
+		Expr::Matrix(matrix) => Expr::Matrix(matrix.clone().map(|entry| instantiate(&entry, bindings))),
+		Expr::Mul(factors) => Expr::Mul(each(factors)),
+		Expr::Not(expr) => Expr::Not(instantiate(expr, bindings).into()),
+		Expr::Num(_) | Expr::Poly(_, _) => expr.clone(),
+		Expr::Or(exprs) => Expr::Or(each(exprs)),
+		Expr::Piecewise(branches) => Expr::Piecewise(
+			branches
+				.iter()
+				.map(|(value, condition)| (instantiate(value, bindings), instantiate(condition, bindings)))
+				.collect(),
+		),
+		Expr::Pow(base, exp) => Expr::Pow(instantiate(base, bindings).into(), instantiate(exp, bindings).into()),
+		Expr::Rel(rel, lhs, rhs) => Expr::Rel(
+			*rel,
+			instantiate(lhs, bindings).into(),
+			instantiate(rhs, bindings).into(),
+		),
+	}
+}
+
+/// Internal helper to check if a pattern is a sequence wildcard.
+fn is_sequence(pattern: &Expr) -> bool {
+	matches!(wildcard(pattern), Some((_, true)))
+}
+
+/// Internal helper to find all ways in which a pattern matches an expression, extending the given bindings.
+fn match_expr(pattern: &Expr, expr: &Expr, bindings: &Bindings) -> Vec<Bindings> {
+	if let Some((sym, _)) = wildcard(pattern) {
+		return bind(sym, expr.clone(), bindings).into_iter().collect();
+	}
+
+	let mut results = Vec::new();
+
+	match (pattern, expr) {
+		// Other expressions are treated as sums or products with a single term
+		(Expr::Add(patterns), _) => {
+			let items = match expr {
+				Expr::Add(items) => items.as_slice(),
+				_ => std::slice::from_ref(expr),
+			};
+
+			match_unordered(patterns, items, bindings, Expr::Add, &mut results);
+		}
+		(Expr::Mul(patterns), _) => {
+			let items = match expr {
+				Expr::Mul(items) => items.as_slice(),
+				_ => std::slice::from_ref(expr),
+			};
+
+			match_unordered(patterns, items, bindings, Expr::Mul, &mut results);
+		}
+		(Expr::Fun(name, patterns), Expr::Fun(other, args)) if name == other => {
+			match_ordered(patterns, args, bindings.clone(), &mut results);
+		}
+		// Connectives are sorted when simplified, so their elements are matched in order
+		(Expr::And(patterns), Expr::And(items)) | (Expr::Or(patterns), Expr::Or(items)) => {
+			match_ordered(patterns, items, bindings.clone(), &mut results);
+		}
+		(Expr::Not(pattern), Expr::Not(expr)) => results.extend(match_expr(pattern, expr, bindings)),
+		(Expr::Pow(pattern_lhs, pattern_rhs), Expr::Pow(lhs, rhs)) => {
+			for bindings in match_expr(pattern_lhs, lhs, bindings) {
+				results.extend(match_expr(pattern_rhs, rhs, &bindings));
+			}
+		}
+		(Expr::Rel(rel, pattern_lhs, pattern_rhs), Expr::Rel(other, lhs, rhs)) if rel == other => {
+			for bindings in match_expr(pattern_lhs, lhs, bindings) {
+				results.extend(match_expr(pattern_rhs, rhs, &bindings));
+			}
+		}
+		_ if pattern == expr => results.push(bindings.clone()),
+		_ => {}
+	}
+
+	results
+}
+
+/// Internal helper to match patterns against function arguments in order.
+fn match_ordered(patterns: &[Expr], args: &[Expr], bindings: Bindings, results: &mut Vec<Bindings>) {
+	let Some((pattern, rest)) = patterns.split_first() else {
+		if args.is_empty() {
+			results.push(bindings);
+		}
+
+		return;
+	};
+
+	match wildcard(pattern) {
+		// Sequence wildcards match any amount of the next arguments
+		Some((sym, true)) => {
+			for count in 0..=args.len() {
+				if let Some(bindings) = bind(sym, Expr::Add(args[..count].to_vec()), &bindings) {
+					match_ordered(rest, &args[count..], bindings, results);
+				}
+			}
+		}
+		_ => {
+			let Some((arg, args)) = args.split_first() else {
+				return;
+			};
+
+			for bindings in match_expr(pattern, arg, &bindings) {
+				match_ordered(rest, args, bindings, results);
+			}
+		}
+	}
+}
+
+/// Internal helper to match the patterns of a sum or product against its items in any order. The first sequence
+/// wildcard matches the items left over by the other patterns, and any other sequence wildcards match nothing.
+fn match_unordered(
+	patterns: &[Expr],
+	items: &[Expr],
+	bindings: &Bindings,
+	kind: fn(Vec<Expr>) -> Expr,
+	results: &mut Vec<Bindings>,
+) {
+	let (sequences, mut singles): (Vec<_>, Vec<_>) = patterns.iter().partition(|pattern| is_sequence(pattern));
+
+	// Try more specific patterns first, so that wildcards are bound consistently early on
+	singles.sort_by_key(|pattern| wildcard(pattern).is_some());
+
+	if singles.len() > items.len() {
+		return;
+	}
+
+	let mut used = vec![false; items.len()];
+	match_unused(&singles, &sequences, items, &mut used, bindings.clone(), kind, results);
+}
+
+/// Internal helper to match the remaining patterns of [`match_unordered`] against the unused items.
+fn match_unused(
+	singles: &[&Expr],
+	sequences: &[&Expr],
+	items: &[Expr],
+	used: &mut [bool],
+	bindings: Bindings,
+	kind: fn(Vec<Expr>) -> Expr,
+	results: &mut Vec<Bindings>,
+) {
+	let Some((pattern, rest)) = singles.split_first() else {
+		let remaining = items
+			.iter()
+			.zip(used.iter())
+			.filter(|(_, used)| !**used)
+			.map(|(item, _)| item.clone())
+			.collect_vec();
+
+		let Some((first, others)) = sequences.split_first() else {
+			if remaining.is_empty() {
+				results.push(bindings);
+			}
+
+			return;
+		};
+
+		let bindings = others.iter().try_fold(bindings, |bindings, pattern| {
+			bind(wildcard(pattern).unwrap().0, kind(Vec::new()), &bindings)
+		});
+
+		results.extend(bindings.and_then(|bindings| bind(wildcard(first).unwrap().0, kind(remaining), &bindings)));
+		return;
+	};
+
+	for index in 0..items.len() {
+		if used[index] {
+			continue;
+		}
+
+		for bindings in match_expr(pattern, &items[index], &bindings) {
+			used[index] = true;
+			match_unused(rest, sequences, items, used, bindings, kind, results);
+			used[index] = false;
+		}
+	}
+}
+
+/// Internal helper to apply the first matching rule to each subexpression, returning [`None`] if nothing changed.
+fn replace(expr: &Expr, rules: &[Rule], ctx: &mut Context) -> Result<Option<Expr>, SimplifyError> {
+	for rule in rules {
+		if let Some(result) = rule.rewrite(expr, ctx)? {
+			return Ok(Some(result));
+		}
+	}
+
+	match expr {
+		Expr::Add(terms) => Ok(replace_each(terms, rules, ctx)?.map(Expr::Add)),
+		Expr::And(exprs) => Ok(replace_each(exprs, rules, ctx)?.map(Expr::And)),
+		Expr::Fun(name, args) => Ok(replace_each(args, rules, ctx)?.map(|args| Expr::Fun(name.clone(), args))),
+		Expr::Matrix(matrix) => Ok(replace_each(matrix.entries(), rules, ctx)?
+			.map(|entries| Expr::Matrix(Matrix::new(matrix.rows(), matrix.cols(), entries).unwrap()))),
+		Expr::Mul(factors) => Ok(replace_each(factors, rules, ctx)?.map(Expr::Mul)),
+		Expr::Not(expr) => Ok(replace(expr, rules, ctx)?.map(|expr| Expr::Not(expr.into()))),
+		Expr::Num(_) | Expr::Poly(_, _) => Ok(None),
+		Expr::Or(exprs) => Ok(replace_each(exprs, rules, ctx)?.map(Expr::Or)),
+		Expr::Piecewise(branches) => {
+			let parts: Vec<_> = branches
+				.iter()
+				.flat_map(|(value, condition)| [value.clone(), condition.clone()])
+				.collect();
+
+			Ok(replace_each(&parts, rules, ctx)?.map(|parts| {
+				let branches = parts.into_iter().tuples().collect();
+				Expr::Piecewise(branches)
+			}))
+		}
+		Expr::Pow(base, exp) => {
+			let parts = [base.as_ref().clone(), exp.as_ref().clone()];
+
+			Ok(replace_each(&parts, rules, ctx)?.map(|parts| {
+				let [base, exp] = <[Expr; 2]>::try_from(parts).unwrap();
+				Expr::Pow(base.into(), exp.into())
+			}))
+		}
+		Expr::Rel(rel, lhs, rhs) => {
+			let parts = [lhs.as_ref().clone(), rhs.as_ref().clone()];
+
+			Ok(replace_each(&parts, rules, ctx)?.map(|parts| {
+				let [lhs, rhs] = <[Expr; 2]>::try_from(parts).unwrap();
+				Expr::Rel(*rel, lhs.into(), rhs.into())
+			}))
+		}
+	}
+}
+
+/// Internal helper to apply [`replace`] to several expressions, returning [`None`] if none of them changed.
+fn replace_each(exprs: &[Expr], rules: &[Rule], ctx: &mut Context) -> Result<Option<Vec<Expr>>, SimplifyError> {
+	let mut changed = false;
+	let mut result = Vec::with_capacity(exprs.len());
+
+	for expr in exprs {
+		match replace(expr, rules, ctx)? {
+			Some(replaced) => {
+				changed = true;
+				result.push(replaced);
+			}
+			None => result.push(expr.clone()),
+		}
+	}
+
+	Ok(changed.then_some(result))
+}
+
+/// Internal helper to write polynomials as sums of products of powers of their symbol, so that their terms can be
+/// matched like those of any other sum.
+fn tree(expr: &Expr) -> Expr {
+	let each = |exprs: &[Expr]| exprs.iter().map(tree).collect();
+
+	match expr {
+		Expr::Add(terms) => Expr::Add(each(terms)),
+		Expr::And(exprs) => Expr::And(each(exprs)),
+		Expr::Fun(name, args) => Expr::Fun(name.clone(), each(args)),
+		Expr::Matrix(matrix) => Expr::Matrix(matrix.clone().map(|entry| tree(&entry))),
+		Expr::Mul(factors) => Expr::Mul(each(factors)),
+		Expr::Not(expr) => Expr::Not(tree(expr).into()),
+		Expr::Num(_) => expr.clone(),
+		Expr::Or(exprs) => Expr::Or(each(exprs)),
+		Expr::Piecewise(branches) => Expr::Piecewise(
+			branches
+				.iter()
+				.map(|(value, condition)| (tree(value), tree(condition)))
+				.collect(),
+		),
+		Expr::Rel(rel, lhs, rhs) => Expr::Rel(*rel, tree(lhs).into(), tree(rhs).into()),
+		Expr::Poly(_, poly) if *poly == Polynomial::from(Monomial::linear(1)) => expr.clone(),
+		Expr::Poly(sym, poly) => {
+			let symbol = Expr::Poly(sym.clone(), Monomial::linear(1).into());
+
+			let mut terms = poly.monomials().map(|mono| {
+				let power = match &mono.degree {
+					degree if degree.is_zero() => return Expr::Num(mono.coeff.clone()),
+					degree if degree.is_one() => symbol.clone(),
+					degree => Expr::Pow(symbol.clone().into(), Expr::Num(degree.clone()).into()),
+				};
+
+				match &mono.coeff {
+					coeff if coeff.is_one() => power,
+					coeff => Expr::Mul(vec![Expr::Num(coeff.clone()), power]),
+				}
+			});
+
+			match poly.monomials().count() {
+				1 => terms.next().unwrap(),
+				_ => Expr::Add(terms.collect()),
+			}
+		}
+		Expr::Pow(base, exp) => Expr::Pow(tree(base).into(), tree(exp).into()),
+	}
+}
+
+/// Internal helper to get the symbol of a wildcard, and whether it is a sequence wildcard ending in two underscores.
+fn wildcard(expr: &Expr) -> Option<(&Symbol, bool)> {
+	let Expr::Poly(sym, poly) = expr else {
+		return None;
+	};
+
+	let name = sym.name().strip_suffix('_')?;
+	let is_symbol = *poly == Polynomial::from(Monomial::linear(1));
+
+	(is_symbol && !name.trim_end_matches('_').is_empty()).then_some((sym, name.ends_with('_')))
+}
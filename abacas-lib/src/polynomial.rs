@@ -7,9 +7,14 @@ use std::{fmt, mem, str};
 use rug::ops::NegAssign;
 
 use crate::error::ParseError;
+use crate::expr::Symbol;
+use crate::interval::Interval;
 use crate::monomial::Monomial;
 use crate::number::Number;
 
+/// The maximum degree of a polynomial whose coefficients of all degrees are worked on, like in compositions and shifts.
+const MAX_DENSE_DEGREE: u32 = 10_000;
+
 /// A polynomial with its monomials sorted by `degree` in descending order.
 ///
 /// # Examples
@@ -56,6 +61,93 @@ impl Polynomial {
 		self.0.retain(|mono| !mono.coeff.is_zero());
 	}
 
+	/// Returns the composition `p(q(x))` with another polynomial `q`.
+	/// Returns [`None`] if the polynomial has degrees that are not non-negative integers or a degree above 10000.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::polynomial::Polynomial;
+	///
+	/// let outer: Polynomial = "x^2 + 1".parse().unwrap();
+	/// let inner: Polynomial = "x - 1".parse().unwrap();
+	///
+	/// assert_eq!(outer.compose(&inner).unwrap().to_string(), "x^2 - 2x + 2");
+	/// assert_eq!(inner.compose(&outer).unwrap().to_string(), "x^2");
+	/// ```
+	pub fn compose(&self, inner: &Self) -> Option<Self> {
+		let coeffs = self.dense()?;
+
+		// Uses Horner's method, multiplying by the inner polynomial once for every degree
+		let result = coeffs
+			.into_iter()
+			.rev()
+			.fold(Self::ZERO, |acc, coeff| acc * inner + coeff);
+
+		Some(result)
+	}
+
+	/// Finds a functional decomposition `p(x) = g(h(x))` into polynomials `g` and `h` of degrees greater than one,
+	/// where `h` is monic without a constant term and has the smallest possible degree. Returns [`None`] if there is
+	/// no such decomposition or the polynomial has degrees that are not non-negative integers or a degree above 10000.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::polynomial::Polynomial;
+	///
+	/// let poly: Polynomial = "2x^4 + 4x^3 + 2x^2 + 3".parse().unwrap();
+	/// let (outer, inner) = poly.decompose().unwrap();
+	///
+	/// assert_eq!(outer.to_string(), "2x^2 + 3");
+	/// assert_eq!(inner.to_string(), "x^2 + x");
+	/// assert_eq!(outer.compose(&inner), Some(poly));
+	///
+	/// let poly: Polynomial = "x^4 + x".parse().unwrap();
+	/// assert_eq!(poly.decompose(), None);
+	/// ```
+	pub fn decompose(&self) -> Option<(Self, Self)> {
+		let coeffs = self.dense()?;
+		let degree = coeffs.len().checked_sub(1)?;
+		let leading = &coeffs[degree];
+
+		(2..degree)
+			.filter(|&inner| degree.is_multiple_of(inner))
+			.find_map(|inner| {
+				// The reversed inner polynomial is the `r`-th root of the reversed monic polynomial up to `x^inner`,
+				// using the recurrence `k g_k = sum((j / r - k + j) f_j g_(k - j), j = 1..=k)` for the power `g = f^(1 / r)`
+				let exp = Number::from(degree / inner).recip();
+				let mut root = vec![Number::one()];
+
+				for k in 1..inner {
+					let sum = (1..=k).fold(Number::zero(), |acc, j| {
+						let factor = exp.clone() * j - (k - j);
+						acc + &(factor * &coeffs[degree - j] / leading * &root[k - j])
+					});
+
+					root.push(sum / k);
+				}
+
+				let inner = Self::from_dense(std::iter::once(Number::zero()).chain(root.into_iter().rev()).collect());
+
+				// The outer polynomial has the digits of the expansion of `p` in base `h`, which must all be constants
+				let mut rest = self.clone();
+				let mut outer = Vec::new();
+
+				while !rest.is_zero() {
+					let remainder = rest.div_rem_mut(&inner)?;
+
+					if !remainder.is_constant() {
+						return None;
+					}
+
+					outer.push(remainder.split_constant().0);
+				}
+
+				Some((Self::from_dense(outer), inner))
+			})
+	}
+
 	/// Returns the degree of the polynomial, or [`None`] for the zero polynomial.
 	///
 	/// # Examples
@@ -70,6 +162,71 @@ impl Polynomial {
 		self.0.first().map(|mono| &mono.degree)
 	}
 
+	/// Internal method to get the coefficients of all degrees in ascending order, starting with the constant term.
+	/// Returns [`None`] if the polynomial has degrees that are not non-negative integers or a degree above
+	/// `MAX_DENSE_DEGREE`.
+	fn dense(&self) -> Option<Vec<Number>> {
+		let Some(degree) = self.degree() else {
+			return Some(Vec::new());
+		};
+
+		let degree = degree.to_u32().filter(|&degree| degree <= MAX_DENSE_DEGREE)?;
+		let mut coeffs = vec![Number::zero(); degree as usize + 1];
+
+		for mono in self.monomials() {
+			coeffs[mono.degree.to_u32()? as usize] = mono.coeff.clone();
+		}
+
+		Some(coeffs)
+	}
+
+	/// Returns the derivative of the polynomial.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::polynomial::Polynomial;
+	///
+	/// let poly: Polynomial = "4x^3 - 2x + 7 + x^-1".parse().unwrap();
+	/// assert_eq!(poly.derivative().to_string(), "12x^2 - 2 - x^-2");
+	/// ```
+	pub fn derivative(&self) -> Self {
+		let monomials = self
+			.monomials()
+			.filter(|mono| !mono.degree.is_zero())
+			.map(|mono| Monomial {
+				coeff: mono.coeff.clone() * &mono.degree,
+				degree: mono.degree.clone() - 1,
+			});
+
+		Self(monomials.collect())
+	}
+
+	/// Returns a value that displays this polynomial in the given variable instead of `x`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::expr::Symbol;
+	/// use abacas::polynomial::Polynomial;
+	///
+	/// let poly: Polynomial = "3x^2 - x + 1".parse().unwrap();
+	/// let y = Symbol::new("y").unwrap();
+	///
+	/// assert_eq!(poly.display_with(&y).to_string(), "3y^2 - y + 1");
+	/// ```
+	pub fn display_with<'a>(&'a self, sym: &'a Symbol) -> impl fmt::Display + 'a {
+		struct DisplayWith<'a>(&'a Polynomial, &'a Symbol);
+
+		impl fmt::Display for DisplayWith<'_> {
+			fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+				self.0.write(f, false, self.1.name())
+			}
+		}
+
+		DisplayWith(self, sym)
+	}
+
 	/// Calculates division and remainder at the same time, returning [`None`] if the divisor is zero.
 	///
 	/// # Examples
@@ -143,6 +300,47 @@ impl Polynomial {
 		Some(remainder)
 	}
 
+	/// Evaluates the polynomial exactly at the given number.
+	/// Returns [`None`] if the result is not rational or the polynomial is undefined at this number.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::number::Number;
+	/// use abacas::polynomial::Polynomial;
+	///
+	/// let poly: Polynomial = "2x^2 - 3x + 1".parse().unwrap();
+	///
+	/// assert_eq!(poly.eval(&3.into()), Some(10.into()));
+	/// assert_eq!(poly.eval(&(Number::from(1) / 2)), Some(0.into()));
+	/// ```
+	pub fn eval(&self, x: &Number) -> Option<Number> {
+		self.monomials().try_fold(Number::zero(), |acc, mono| {
+			Some(acc + &(x.checked_pow(&mono.degree)? * &mono.coeff))
+		})
+	}
+
+	/// Evaluates the polynomial on an interval, returning an interval guaranteed to contain every value of the
+	/// polynomial on it. Non-integer degrees are enclosed using the given precision.
+	/// Returns [`None`] if the polynomial is undefined somewhere on the interval.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::interval::Interval;
+	/// use abacas::polynomial::Polynomial;
+	///
+	/// let poly: Polynomial = "x^2 - 2".parse().unwrap();
+	/// let value = poly.eval_interval(&Interval::new((-1).into(), 3.into()), 64).unwrap();
+	///
+	/// assert_eq!(value, Interval::new((-2).into(), 7.into()));
+	/// ```
+	pub fn eval_interval(&self, x: &Interval, prec: u32) -> Option<Interval> {
+		self.monomials().try_fold(Interval::point(Number::zero()), |acc, mono| {
+			Some(acc + x.pow(&mono.degree, prec)? * Interval::point(mono.coeff.clone()))
+		})
+	}
+
 	/// Extracts the common factor of all monomials.
 	/// Returns [`None`] if the polynomial is zero or has coprime coefficients.
 	///
@@ -190,6 +388,21 @@ impl Polynomial {
 		Some(factor)
 	}
 
+	/// Internal method to create a polynomial from the coefficients of all degrees in ascending order.
+	fn from_dense(coeffs: Vec<Number>) -> Self {
+		let monomials = coeffs
+			.into_iter()
+			.enumerate()
+			.rev()
+			.filter(|(_, coeff)| !coeff.is_zero())
+			.map(|(degree, coeff)| Monomial {
+				coeff,
+				degree: degree.into(),
+			});
+
+		Self(monomials.collect())
+	}
+
 	/// Returns the GCD of two polynomials in monic form.
 	///
 	/// # Examples
@@ -344,6 +557,110 @@ impl Polynomial {
 		self.0.is_empty()
 	}
 
+	/// Isolates the distinct real roots of the polynomial, returning intervals of at most the given width that each
+	/// contain exactly one root, sorted in ascending order. Roots that are found exactly are returned as a single
+	/// point. Returns [`None`] if the polynomial is zero or has non-integer degrees.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::interval::Interval;
+	/// use abacas::number::Number;
+	/// use abacas::polynomial::Polynomial;
+	///
+	/// let poly: Polynomial = "x^3 - 2x".parse().unwrap();
+	/// let width = Number::from(1) / 1000;
+	/// let roots = poly.isolate_roots(&width).unwrap();
+	///
+	/// assert_eq!(roots.len(), 3);
+	/// assert_eq!(roots[1], Interval::point(0.into()));
+	/// assert!(roots[2].contains(&(Number::from(14142) / 10000)));
+	/// assert!(roots[2].width() <= width);
+	/// ```
+	pub fn isolate_roots(&self, width: &Number) -> Option<Vec<Interval>> {
+		if self.is_zero() || self.monomials().any(|mono| !mono.degree.is_integer()) {
+			return None;
+		}
+
+		// Multiplying away negative degrees keeps all non-zero roots and introduces no new ones
+		let mut poly = self.clone();
+		let lowest = self.0.last().unwrap().degree.clone();
+
+		if lowest.is_negative() {
+			poly *= &Monomial::new(1, -lowest);
+		}
+
+		// Dividing by the GCD with the derivative removes multiple roots
+		poly /= &poly.clone().gcd(poly.derivative());
+
+		let slope = poly.derivative();
+		let sign = |x: &Number| poly.eval(x).unwrap().is_positive();
+
+		let bound = poly.root_bound();
+
+		// The bounds of all pending intervals are never roots
+		let mut pending = vec![Interval::new(-bound.clone(), bound)];
+		let mut roots = Vec::new();
+
+		while let Some(interval) = pending.pop() {
+			// If the polynomial is bounded away from zero, there is no root
+			if !poly.eval_interval(&interval, 64)?.contains_zero() {
+				continue;
+			}
+
+			// If the polynomial is strictly monotonic, there is a root exactly if the signs at the bounds differ
+			if !slope.eval_interval(&interval, 64)?.contains_zero() {
+				let (mut lo, mut hi) = (interval.lo().clone(), interval.hi().clone());
+
+				if sign(&lo) == sign(&hi) {
+					continue;
+				}
+
+				// Refine the root with bisection until the interval is narrow enough
+				while hi.clone() - &lo > *width {
+					let mid = (lo.clone() + &hi) / 2;
+
+					if poly.eval(&mid).unwrap().is_zero() {
+						(lo, hi) = (mid.clone(), mid);
+					} else if sign(&mid) == sign(&lo) {
+						lo = mid;
+					} else {
+						hi = mid;
+					}
+				}
+
+				roots.push(Interval::new(lo, hi));
+				continue;
+			}
+
+			let mid = interval.midpoint();
+
+			if !poly.eval(&mid).unwrap().is_zero() {
+				let (left, right) = interval.bisect();
+				pending.extend([left, right]);
+				continue;
+			}
+
+			// The midpoint is a root, so exclude a neighbourhood on which it is the only one
+			let mut radius = interval.width() / 4;
+
+			while slope
+				.eval_interval(&Interval::new(mid.clone() - &radius, mid.clone() + &radius), 64)?
+				.contains_zero()
+			{
+				radius /= 2;
+			}
+
+			pending.push(Interval::new(interval.lo().clone(), mid.clone() - &radius));
+			pending.push(Interval::new(mid.clone() + &radius, interval.hi().clone()));
+			roots.push(Interval::point(mid));
+		}
+
+		roots.sort_by(|lhs, rhs| lhs.lo().cmp(rhs.lo()));
+
+		Some(roots)
+	}
+
 	/// Returns the leading coefficient of the polynomial, or [`None`] for the zero polynomial.
 	///
 	/// # Examples
@@ -402,6 +719,48 @@ impl Polynomial {
 		Some(factor)
 	}
 
+	/// Returns the Möbius transform `(c x + d)^n p((a x + b) / (c x + d))` of a polynomial of degree `n`, which maps
+	/// every root `r` of `p` to `(d r - b) / (a - c r)`. Returns [`None`] if the polynomial has degrees that are not
+	/// non-negative integers or a degree above 10000.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::number::Number;
+	/// use abacas::polynomial::Polynomial;
+	///
+	/// let poly: Polynomial = "x^2 - 3x + 2".parse().unwrap();
+	/// let one = Number::one();
+	///
+	/// // Substituting `1 / (x + 1)` maps the roots `1` and `2` to `0` and `-0.5`
+	/// let result = poly.mobius_transform(&Number::zero(), &one, &one, &one).unwrap();
+	/// assert_eq!(result.to_string(), "2x^2 + x");
+	/// ```
+	pub fn mobius_transform(&self, a: &Number, b: &Number, c: &Number, d: &Number) -> Option<Self> {
+		let coeffs = self.dense()?;
+		let degree = coeffs.len().saturating_sub(1);
+		let numer = Self::from_dense(vec![b.clone(), a.clone()]);
+		let denom = Self::from_dense(vec![d.clone(), c.clone()]);
+
+		let mut powers = vec![Self::from(1)];
+
+		for index in 0..degree {
+			let power = powers[index].clone() * &denom;
+			powers.push(power);
+		}
+
+		// Uses the homogeneous form of Horner's method, with `h_k = h_(k + 1) (a x + b) + p_k (c x + d)^(n - k)`
+		let result = coeffs
+			.into_iter()
+			.enumerate()
+			.rev()
+			.fold(Self::ZERO, |acc, (index, coeff)| {
+				acc * &numer + powers[degree - index].clone() * &coeff
+			});
+
+		Some(result)
+	}
+
 	/// Returns an iterator over the contained monomials.
 	///
 	/// # Examples
@@ -431,11 +790,180 @@ impl Polynomial {
 		monomials.into_iter().fold(Self::ZERO, Self::add)
 	}
 
+	/// Parses a polynomial in the given variable instead of `x`. Terms may be separated by `+` and `-` with any
+	/// whitespace, and an invalid term is reported with its byte index.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::error::ParseError;
+	/// use abacas::expr::Symbol;
+	/// use abacas::polynomial::Polynomial;
+	///
+	/// let y = Symbol::new("y").unwrap();
+	///
+	/// let poly = Polynomial::parse_with("4y^2+5y^3-1", &y).unwrap();
+	/// assert_eq!(poly.display_with(&y).to_string(), "5y^3 + 4y^2 - 1");
+	///
+	/// let error = Polynomial::parse_with("y^2 + 2x - 1", &y).unwrap_err();
+	/// assert!(matches!(error, ParseError::InvalidTerm(term, 6) if term == "2x"));
+	/// ```
+	pub fn parse_with(s: &str, sym: &Symbol) -> Result<Self, ParseError> {
+		let mut result = Self::ZERO;
+
+		for (index, negative, term) in terms(s)? {
+			let monomial = match Monomial::parse_with(term, sym) {
+				Ok(monomial) => monomial,
+				Err(ParseError::InvalidNumber(number)) if number.is_zero() => continue,
+				Err(_) => return Err(ParseError::InvalidTerm(term.into(), index)),
+			};
+
+			if negative {
+				result -= monomial;
+			} else {
+				result += monomial;
+			}
+		}
+
+		Ok(result)
+	}
+
+	/// Finds the distinct rational roots of the polynomial, sorted in ascending order.
+	/// Returns [`None`] if the polynomial is zero or has non-integer degrees.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::number::Number;
+	/// use abacas::polynomial::Polynomial;
+	///
+	/// let poly: Polynomial = "6x^4 - 5x^3 - 12x^2 + 10x".parse().unwrap();
+	/// let roots = poly.rational_roots().unwrap();
+	///
+	/// assert_eq!(roots, [Number::zero(), Number::from(5) / 6]);
+	/// ```
+	pub fn rational_roots(&self) -> Option<Vec<Number>> {
+		// After clearing denominators, the denominator of every rational root divides the leading coefficient `a`,
+		// so two different rational roots are at least `1 / a^2` apart and each interval contains at most one
+		let scale = self
+			.monomials()
+			.fold(Number::one(), |scale, mono| scale.lcm(&mono.coeff.clone().denom()));
+		let leading = (self.leading()?.clone() * &scale).abs();
+		let width = (leading.square() * 2).recip();
+
+		let roots = self.isolate_roots(&width)?.into_iter().filter_map(|interval| {
+			let root = Number::simplest_between(interval.lo(), interval.hi());
+			self.eval(&root).is_some_and(|value| value.is_zero()).then_some(root)
+		});
+
+		Some(roots.collect())
+	}
+
+	/// Returns the reversed polynomial `x^n p(1 / x)` of a polynomial of degree `n`, which has its coefficients in
+	/// reverse order.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::polynomial::Polynomial;
+	///
+	/// let poly: Polynomial = "2x^3 - x + 5".parse().unwrap();
+	/// assert_eq!(poly.reverse().to_string(), "5x^3 - x^2 + 2");
+	/// ```
+	pub fn reverse(&self) -> Self {
+		let Some(degree) = self.degree() else {
+			return Self::ZERO;
+		};
+
+		let monomials = self.monomials().rev().map(|mono| Monomial {
+			coeff: mono.coeff.clone(),
+			degree: degree.clone() - &mono.degree,
+		});
+
+		Self(monomials.collect())
+	}
+
+	/// Internal method to get Cauchy's bound, which every root of a non-zero polynomial is strictly smaller than in
+	/// absolute value.
+	pub(crate) fn root_bound(&self) -> Number {
+		let leading = self.leading().unwrap();
+
+		self.monomials()
+			.skip(1)
+			.map(|mono| (mono.coeff.clone() / leading).abs())
+			.max()
+			.unwrap_or_default()
+			+ 1
+	}
+
+	/// Returns the scaled polynomial `p(c x)`.
+	/// Returns [`None`] if a power of `c` is undefined, like for negative degrees and `c = 0`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::polynomial::Polynomial;
+	///
+	/// let poly: Polynomial = "x^3 + x^2 - 4".parse().unwrap();
+	/// assert_eq!(poly.scale(&2.into()).unwrap().to_string(), "8x^3 + 4x^2 - 4");
+	/// assert_eq!(poly.scale(&0.into()).unwrap().to_string(), "-4");
+	/// ```
+	pub fn scale(&self, by: &Number) -> Option<Self> {
+		let monomials = self.monomials().map(|mono| {
+			Some(Monomial {
+				coeff: mono.coeff.clone() * &by.checked_pow(&mono.degree)?,
+				degree: mono.degree.clone(),
+			})
+		});
+
+		let mut result = Self(monomials.collect::<Option<_>>()?);
+		result.clean();
+
+		Some(result)
+	}
+
 	/// Internal method to search for the index of the given degree.
 	fn search(&self, degree: &Number) -> Result<usize, usize> {
 		self.0.binary_search_by(|mono| degree.cmp(&mono.degree))
 	}
 
+	/// Returns the shifted polynomial `p(x + a)`, using the Taylor shift that updates the coefficients in place with
+	/// only additions and multiplications by `a`. Returns [`None`] if the polynomial has degrees that are not
+	/// non-negative integers or a degree above 10000.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::polynomial::Polynomial;
+	///
+	/// let poly: Polynomial = "x^3 - 2x + 1".parse().unwrap();
+	/// assert_eq!(poly.shift(&1.into()).unwrap().to_string(), "x^3 + 3x^2 + x");
+	/// assert_eq!(poly.shift(&(-2).into()).unwrap().to_string(), "x^3 - 6x^2 + 10x - 3");
+	/// ```
+	pub fn shift(&self, by: &Number) -> Option<Self> {
+		let mut coeffs = self.dense()?;
+		let degree = coeffs.len().saturating_sub(1);
+
+		if by.is_zero() {
+			return Some(self.clone());
+		}
+
+		// Every pass of `c_k += a c_(k + 1)` from the top moves one more factor `x + a` into the lower coefficients
+		for start in 0..degree {
+			for index in (start..degree).rev() {
+				let (lower, upper) = coeffs.split_at_mut(index + 1);
+
+				match by {
+					_ if by.is_one() => lower[index] += &upper[0],
+					_ if by.is_neg_one() => lower[index] -= &upper[0],
+					_ => lower[index] += &(upper[0].clone() * by),
+				}
+			}
+		}
+
+		Some(Self::from_dense(coeffs))
+	}
+
 	/// Splits the constant part from the polynomial and returns it.
 	///
 	/// # Examples
@@ -714,24 +1242,40 @@ impl str::FromStr for Polynomial {
 	type Err = ParseError;
 
 	fn from_str(s: &str) -> Result<Self, Self::Err> {
-		let mut result = Self::ZERO;
+		Self::parse_with(s, &Symbol::new("x").unwrap())
+	}
+}
 
-		for full in s.split(" + ") {
-			for (index, part) in full.split(" - ").enumerate() {
-				let monomial: Monomial = match part.parse() {
-					Ok(monomial) => monomial,
-					Err(ParseError::InvalidNumber(number)) if number.is_zero() => continue,
-					Err(error) => return Err(error),
-				};
-
-				if index == 0 {
-					result += monomial;
-				} else {
-					result -= monomial;
-				}
+/// Internal helper to split a polynomial into its terms, along with the byte index of every term and whether it is
+/// subtracted. Signs directly after `^`, `(` or the exponent of a number like `1e-3` belong to the term.
+fn terms(s: &str) -> Result<Vec<(usize, bool, &str)>, ParseError> {
+	let mut terms = Vec::new();
+	let (mut start, mut negative) = (None, false);
+
+	for (index, char) in s.char_indices() {
+		let Some(begin) = start else {
+			if !char.is_whitespace() {
+				start = Some(index);
 			}
-		}
 
-		Ok(result)
+			continue;
+		};
+
+		let term = &s[begin..index];
+		let exponent = term
+			.strip_suffix(['e', 'E'])
+			.is_some_and(|init| init.ends_with(|char: char| char.is_ascii_digit() || matches!(char, '.' | ')')));
+
+		if matches!(char, '+' | '-') && !term.trim_end().ends_with(['^', '(']) && !exponent {
+			terms.push((begin, negative, term.trim_end()));
+			(start, negative) = (None, char == '-');
+		}
 	}
+
+	let Some(begin) = start else {
+		return Err(ParseError::UnexpectedEnd(s.len()));
+	};
+
+	terms.push((begin, negative, s[begin..].trim_end()));
+	Ok(terms)
 }
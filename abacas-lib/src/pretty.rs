@@ -0,0 +1,574 @@
+//! Two-dimensional pretty printing of expressions for terminal output.
+//!
+//! Expressions are laid out as rectangular blocks of lines that are aligned on a baseline. Fractions are stacked over
+//! a bar, square roots get a radical sign with a bar over the radicand, and matrices are aligned in columns between
+//! brackets. Exponents become Unicode superscripts like `x²` where every character has one, and are otherwise raised
+//! onto the lines above. Writing with the alternate flag `{:#}` restricts the output to ASCII characters.
+
+use std::fmt;
+
+use itertools::Itertools;
+
+use crate::expr::{Expr, Symbol};
+use crate::matrix::Matrix;
+use crate::monomial::Monomial;
+use crate::number::Number;
+use crate::polynomial::Polynomial;
+use crate::relation::Relation;
+
+/// Characters with a Unicode superscript, along with that superscript.
+const SUPERSCRIPTS: [(char, char); 17] = [
+	('0', '⁰'),
+	('1', '¹'),
+	('2', '²'),
+	('3', '³'),
+	('4', '⁴'),
+	('5', '⁵'),
+	('6', '⁶'),
+	('7', '⁷'),
+	('8', '⁸'),
+	('9', '⁹'),
+	('+', '⁺'),
+	('-', '⁻'),
+	('=', '⁼'),
+	('(', '⁽'),
+	(')', '⁾'),
+	('i', 'ⁱ'),
+	('n', 'ⁿ'),
+];
+
+/// A display adapter that writes the contained expression in two dimensions, using Unicode characters unless the
+/// alternate flag `{:#}` is given.
+///
+/// # Examples
+///
+/// ```
+/// use abacas::expr::{Expr, Symbol};
+/// use abacas::pretty::Pretty;
+/// use rug::ops::Pow;
+///
+/// let x = Expr::Poly(Symbol::new("x").unwrap(), "x".parse().unwrap());
+/// let expr = x.clone().pow(Expr::Num(2.into())) / (x + Expr::Num(1.into()));
+///
+/// assert_eq!(Pretty(&expr).to_string(), " x²\n─────\nx + 1");
+/// assert_eq!(format!("{:#}", Pretty(&expr)), "  2\n x\n-----\nx + 1");
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Pretty<'a, T: ?Sized>(pub &'a T);
+
+impl Expr {
+	/// Returns this expression pretty printed in two dimensions with Unicode characters.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::expr::{Expr, Symbol};
+	/// use abacas::number::Number;
+	/// use rug::ops::Pow;
+	///
+	/// let x = Expr::Poly(Symbol::new("x").unwrap(), "x^2 + 1".parse().unwrap());
+	/// let expr = x.pow(Expr::Num(Number::from(1) / 2));
+	///
+	/// assert_eq!(expr.to_pretty(), " ______\n√x² + 1");
+	/// ```
+	pub fn to_pretty(&self) -> String {
+		Pretty(self).to_string()
+	}
+}
+
+impl fmt::Display for Pretty<'_, Expr> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let block = Layout {
+			unicode: !f.alternate(),
+		}
+		.expr(self.0);
+		let lines = block.lines.iter().map(|line| line.trim_end());
+
+		write!(f, "{}", lines.format("\n"))
+	}
+}
+
+/// Internal rectangular block of text, which is aligned with other blocks on its baseline.
+#[derive(Clone, Debug)]
+struct Block {
+	/// The lines of the block, which all have the same width.
+	lines: Vec<String>,
+	/// The index of the line that the block is aligned on.
+	baseline: usize,
+	/// The width of the block in characters.
+	width: usize,
+}
+
+impl Block {
+	/// Internal method to create a block with a single line of text.
+	fn text(text: impl Into<String>) -> Self {
+		let text = text.into();
+
+		Self {
+			width: text.chars().count(),
+			lines: vec![text],
+			baseline: 0,
+		}
+	}
+
+	/// Internal method to create a block from lines of different widths, padding them on the right.
+	fn from_lines(lines: Vec<String>, baseline: usize) -> Self {
+		let width = lines.iter().map(|line| line.chars().count()).max().unwrap_or_default();
+		let lines = lines.into_iter().map(|line| pad(&line, width, false)).collect();
+
+		Self { lines, baseline, width }
+	}
+
+	/// Internal method to place blocks next to each other, aligned on their baselines.
+	fn beside(blocks: impl IntoIterator<Item = Self>) -> Self {
+		let blocks = blocks.into_iter().collect::<Vec<_>>();
+		let above = blocks.iter().map(|block| block.baseline).max().unwrap_or_default();
+		let below = blocks
+			.iter()
+			.map(|block| block.lines.len() - block.baseline)
+			.max()
+			.unwrap_or(1);
+
+		let mut lines = vec![String::new(); above + below];
+
+		for block in &blocks {
+			let top = above - block.baseline;
+
+			for (index, line) in lines.iter_mut().enumerate() {
+				match index.checked_sub(top).and_then(|row| block.lines.get(row)) {
+					Some(row) => line.push_str(row),
+					None => line.push_str(&" ".repeat(block.width)),
+				}
+			}
+		}
+
+		Self {
+			lines,
+			baseline: above,
+			width: blocks.iter().map(|block| block.width).sum(),
+		}
+	}
+
+	/// Internal method to stack two blocks as a fraction with a bar between them.
+	fn fraction(numer: Self, denom: Self, unicode: bool) -> Self {
+		let width = numer.width.max(denom.width);
+		let bar = if unicode { "─" } else { "-" };
+
+		let mut lines = numer
+			.lines
+			.iter()
+			.map(|line| pad(line, width, true))
+			.collect::<Vec<_>>();
+		lines.push(bar.repeat(width));
+		lines.extend(denom.lines.iter().map(|line| pad(line, width, true)));
+
+		Self {
+			lines,
+			baseline: numer.lines.len(),
+			width,
+		}
+	}
+
+	/// Internal method to surround this block with the given delimiters, which grow with the height of the block. The
+	/// delimiters are given as the top, middle and bottom pieces for the left and right side.
+	fn delimit(self, single: (&str, &str), left: [&str; 3], right: [&str; 3]) -> Self {
+		let height = self.lines.len();
+
+		let side = |pieces: [&str; 3], single: &str| {
+			let pieces = (0..height).map(|index| match index {
+				_ if height == 1 => single,
+				0 => pieces[0],
+				_ if index + 1 == height => pieces[2],
+				_ => pieces[1],
+			});
+
+			Self::from_lines(pieces.map(str::to_owned).collect(), self.baseline)
+		};
+
+		let (left, right) = (side(left, single.0), side(right, single.1));
+		Self::beside([left, self, right])
+	}
+
+	/// Internal method to surround this block with parentheses.
+	fn parens(self, unicode: bool) -> Self {
+		match unicode {
+			true => self.delimit(("(", ")"), ["⎛", "⎜", "⎝"], ["⎞", "⎟", "⎠"]),
+			false => self.delimit(("(", ")"), ["/", "|", "\\"], ["\\", "|", "/"]),
+		}
+	}
+
+	/// Internal method to raise an exponent onto the lines above this block, or to attach it as a Unicode superscript
+	/// to the top line if possible.
+	fn power(self, exp: Self, unicode: bool) -> Self {
+		let superscript = (unicode && exp.lines.len() == 1)
+			.then(|| exp.lines[0].chars().map(superscript).collect::<Option<String>>())
+			.flatten();
+
+		if let Some(superscript) = superscript {
+			let mut lines = vec![superscript];
+			lines.resize(self.baseline + 1, String::new());
+
+			let exp = Self::from_lines(lines, self.baseline);
+			return Self::beside([self, exp]);
+		}
+
+		let lines = exp
+			.lines
+			.iter()
+			.map(|line| format!("{}{line}", " ".repeat(self.width)))
+			.chain(self.lines.iter().cloned());
+
+		Self::from_lines(lines.collect(), exp.lines.len() + self.baseline)
+	}
+
+	/// Internal method to draw a radical sign with the given index over this block.
+	fn root(self, index: u32, unicode: bool) -> Self {
+		let height = self.lines.len();
+		let sign = match (unicode, index) {
+			(true, 2) => "√",
+			(true, 3) => "∛",
+			(true, _) => "∜",
+			(false, _) => "\\/",
+		};
+
+		let prefix = sign.chars().count();
+		let side = if unicode { "│" } else { " |" };
+		let top = format!("{}{}", " ".repeat(prefix), "_".repeat(self.width));
+
+		let lines = self
+			.lines
+			.iter()
+			.enumerate()
+			.map(|(row, line)| match row + 1 == height {
+				true => format!("{sign}{line}"),
+				false => format!("{}{line}", pad(side, prefix, false)),
+			});
+
+		Self::from_lines(std::iter::once(top).chain(lines).collect(), self.baseline + 1)
+	}
+
+	/// Internal method to stack blocks vertically, with their left edges aligned.
+	fn stack(blocks: Vec<Self>, baseline: usize) -> Self {
+		let lines = blocks.into_iter().flat_map(|block| block.lines);
+		Self::from_lines(lines.collect(), baseline)
+	}
+}
+
+/// Internal layout configuration, which turns expressions into blocks.
+struct Layout {
+	/// Whether Unicode characters may be used, or only ASCII.
+	unicode: bool,
+}
+
+impl Layout {
+	/// Internal method to lay out an expression.
+	fn expr(&self, expr: &Expr) -> Block {
+		match expr {
+			Expr::Add(exprs) => self.add(exprs),
+			Expr::And(exprs) if exprs.is_empty() => Block::text("true"),
+			// Disjunctions bind weaker than conjunctions
+			Expr::And(exprs) => {
+				let exprs = exprs.iter().map(|expr| match expr {
+					Expr::Or(_) => self.expr(expr).parens(self.unicode),
+					_ => self.expr(expr),
+				});
+
+				self.join(exprs, self.pick(" ∧ ", " and "))
+			}
+			Expr::Fun(name, args) => self.fun(name, args),
+			Expr::Matrix(matrix) => self.matrix(matrix),
+			Expr::Mul(exprs) => self.mul(exprs, false),
+			Expr::Not(expr) => {
+				let inner = match **expr {
+					Expr::Rel(_, _, _) => self.expr(expr),
+					_ => self.parens(expr),
+				};
+
+				Block::beside([Block::text(self.pick("¬", "not ")), inner])
+			}
+			Expr::Num(num) => self.number(num, false),
+			Expr::Or(exprs) if exprs.is_empty() => Block::text("false"),
+			Expr::Or(exprs) => self.join(exprs.iter().map(|expr| self.expr(expr)), self.pick(" ∨ ", " or ")),
+			Expr::Piecewise(branches) => self.piecewise(branches),
+			Expr::Poly(sym, poly) => self.poly(poly, sym, false),
+			Expr::Pow(base, exp) => self.pow(base, exp),
+			Expr::Rel(rel, lhs, rhs) => {
+				let side = |expr: &Expr| match expr.is_logical() {
+					true => self.parens(expr),
+					false => self.expr(expr),
+				};
+
+				let rel = match rel {
+					Relation::Eq => " = ",
+					Relation::Ge => self.pick(" ≥ ", " >= "),
+					Relation::Gt => " > ",
+					Relation::Le => self.pick(" ≤ ", " <= "),
+					Relation::Lt => " < ",
+					Relation::Ne => self.pick(" ≠ ", " != "),
+				};
+
+				Block::beside([side(lhs), Block::text(rel), side(rhs)])
+			}
+		}
+	}
+
+	/// Internal method to lay out a sum, extracting the minus signs of negative terms like `Expr::write_add`.
+	fn add(&self, exprs: &[Expr]) -> Block {
+		let terms = exprs.iter().enumerate().flat_map(|(index, expr)| {
+			let (minus, term) = match expr {
+				_ if index == 0 => (false, self.expr(expr)),
+				Expr::Num(num) if num.is_negative() => (true, self.number(num, true)),
+				Expr::Poly(sym, poly) if poly.leading().is_some_and(Number::is_negative) => {
+					(true, self.poly(poly, sym, true))
+				}
+				Expr::Mul(exprs) if Expr::split_fraction(exprs).0 => (true, self.mul(exprs, true)),
+				_ => (false, self.expr(expr)),
+			};
+
+			let sign = match (index, minus) {
+				(0, _) => None,
+				(_, true) => Some(Block::text(" - ")),
+				(_, false) => Some(Block::text(" + ")),
+			};
+
+			sign.into_iter().chain([term])
+		});
+
+		Block::beside(terms)
+	}
+
+	/// Internal method to lay out a function call, with special forms for some functions.
+	fn fun(&self, name: &Symbol, args: &[Expr]) -> Block {
+		match (name.name(), args) {
+			("abs", [arg]) => self.expr(arg).delimit(("|", "|"), ["|"; 3], ["|"; 3]),
+			("sqrt", [arg]) => self.expr(arg).root(2, self.unicode),
+			("factorial", [arg]) => Block::beside([self.base(arg), Block::text("!")]),
+			_ => {
+				let args = self.join(args.iter().map(|arg| self.expr(arg)), ", ");
+				Block::beside([Block::text(name.name()), args.parens(self.unicode)])
+			}
+		}
+	}
+
+	/// Internal method to lay out a matrix, with columns centered and aligned between brackets.
+	fn matrix(&self, matrix: &Matrix<Expr>) -> Block {
+		let cells = (0..matrix.rows())
+			.map(|row| matrix.row(row).iter().map(|expr| self.expr(expr)).collect::<Vec<_>>())
+			.collect::<Vec<_>>();
+
+		let widths = (0..matrix.cols())
+			.map(|col| cells.iter().map(|row| row[col].width).max().unwrap_or_default())
+			.collect::<Vec<_>>();
+
+		let rows = cells.into_iter().map(|row| {
+			let row = row.into_iter().zip(&widths).map(|(mut cell, &width)| {
+				cell.lines.iter_mut().for_each(|line| *line = pad(line, width, true));
+				cell.width = width;
+				cell
+			});
+
+			Block::beside(Itertools::intersperse(row, Block::text("  ")))
+		});
+
+		let rows = rows.collect::<Vec<_>>();
+		let height = rows.iter().map(|row| row.lines.len()).sum::<usize>();
+		let matrix = Block::stack(rows, height.saturating_sub(1) / 2);
+
+		match self.unicode {
+			true => matrix.delimit(("[", "]"), ["⎡", "⎢", "⎣"], ["⎤", "⎥", "⎦"]),
+			false => matrix.delimit(("[", "]"), ["["; 3], ["]"; 3]),
+		}
+	}
+
+	/// Internal method to lay out a product as a fraction of the factors with positive and negative exponents,
+	/// optionally without the sign of its numeric coefficient.
+	fn mul(&self, exprs: &[Expr], abs: bool) -> Block {
+		let (negative, numer, denom) = Expr::split_fraction(exprs);
+
+		// Parentheses are only needed around factors of a product, or after a minus sign that may have been extracted
+		let factors = |factors: &[Expr], sign: bool| match factors {
+			[] => Block::text("1"),
+			[factor] if !sign => self.expr(factor),
+			_ => self.join(factors.iter().map(|factor| self.parens(factor)), self.pick("⋅", "*")),
+		};
+
+		let product = match denom.is_empty() {
+			true => factors(&numer, negative),
+			false => Block::fraction(factors(&numer, false), factors(&denom, false), self.unicode),
+		};
+
+		match negative && !abs {
+			true => Block::beside([Block::text("-"), product]),
+			false => product,
+		}
+	}
+
+	/// Internal method to lay out a number, with fractions stacked unless they are inline, optionally without its sign.
+	fn number(&self, num: &Number, abs: bool) -> Block {
+		let (numer, denom) = num.clone().abs().ratio();
+		let numer = Block::text(numer.to_integer().unwrap().to_string());
+
+		let number = match denom.is_one() {
+			true => numer,
+			false => Block::fraction(
+				numer,
+				Block::text(denom.to_integer().unwrap().to_string()),
+				self.unicode,
+			),
+		};
+
+		match num.is_negative() && !abs {
+			true => Block::beside([Block::text("-"), number]),
+			false => number,
+		}
+	}
+
+	/// Internal method to lay out a monomial, optionally without its sign.
+	fn monomial(&self, mono: &Monomial, sym: &Symbol, abs: bool) -> Block {
+		if mono.degree.is_zero() {
+			return self.number(&mono.coeff, abs);
+		}
+
+		let coeff = match mono.coeff.is_one() || mono.coeff.is_neg_one() {
+			true if mono.coeff.is_negative() && !abs => Block::text("-"),
+			true => Block::text(""),
+			false => self.number(&mono.coeff, abs),
+		};
+
+		Block::beside([coeff, self.power(Block::text(sym.name()), &mono.degree)])
+	}
+
+	/// Internal method to lay out a piecewise expression as its branches behind a brace.
+	fn piecewise(&self, branches: &[(Expr, Expr)]) -> Block {
+		let values = branches.iter().map(|(value, _)| self.expr(value)).collect::<Vec<_>>();
+		let width = values.iter().map(|value| value.width).max().unwrap_or_default();
+
+		let rows = values.into_iter().zip(branches).map(|(mut value, (_, condition))| {
+			value.lines.iter_mut().for_each(|line| *line = pad(line, width, false));
+			value.width = width;
+
+			let condition = match condition {
+				Expr::And(exprs) if exprs.is_empty() => Block::text("otherwise"),
+				_ => Block::beside([Block::text("for "), self.expr(condition)]),
+			};
+
+			Block::beside([value, Block::text("  "), condition])
+		});
+
+		let rows = rows.collect::<Vec<_>>();
+		let height = rows.iter().map(|row| row.lines.len()).sum::<usize>();
+		let branches = Block::stack(rows, height.saturating_sub(1) / 2);
+
+		let brace = (0..height).map(|index| match index {
+			_ if !self.unicode || height == 1 => "{",
+			0 => "⎧",
+			_ if index + 1 == height => "⎩",
+			_ if index == height.saturating_sub(1) / 2 => "⎨",
+			_ => "⎪",
+		});
+
+		let brace = Block::from_lines(brace.map(str::to_owned).collect(), branches.baseline);
+		Block::beside([brace, branches])
+	}
+
+	/// Internal method to lay out a polynomial in the given variable, optionally without the sign of its leading term.
+	fn poly(&self, poly: &Polynomial, sym: &Symbol, abs: bool) -> Block {
+		if poly.is_zero() {
+			return Block::text("0");
+		}
+
+		let terms = poly.monomials().enumerate().flat_map(|(index, mono)| {
+			let sign = match (index, mono.coeff.is_negative()) {
+				(0, _) => None,
+				(_, true) => Some(Block::text(" - ")),
+				(_, false) => Some(Block::text(" + ")),
+			};
+
+			sign.into_iter().chain([self.monomial(mono, sym, abs || index > 0)])
+		});
+
+		Block::beside(terms)
+	}
+
+	/// Internal method to lay out a power, using a fraction for negative exponents and roots for unit fractions.
+	fn pow(&self, base: &Expr, exp: &Expr) -> Block {
+		match exp {
+			Expr::Num(num) if num.is_negative() => {
+				self.mul(&[Expr::Pow(base.clone().into(), exp.clone().into())], false)
+			}
+			Expr::Num(num) => match num.root_index() {
+				Some(index @ 2..=4) if self.unicode || index == 2 => self.expr(base).root(index, self.unicode),
+				_ => self.base(base).power(self.number_inline(num), self.unicode),
+			},
+			_ => self.base(base).power(self.expr(exp), self.unicode),
+		}
+	}
+
+	/// Internal method to raise a block to a numeric exponent, using a square root for `1 / 2`.
+	fn power(&self, base: Block, exp: &Number) -> Block {
+		match exp.root_index() {
+			_ if exp.is_one() => base,
+			Some(2) => base.root(2, self.unicode),
+			_ => base.power(self.number_inline(exp), self.unicode),
+		}
+	}
+
+	/// Internal method to lay out the base of a power or factorial with parentheses if necessary.
+	fn base(&self, expr: &Expr) -> Block {
+		match expr.needs_base_parens() {
+			true => self.expr(expr).parens(self.unicode),
+			false => self.expr(expr),
+		}
+	}
+
+	/// Internal method to join blocks with a separator between them.
+	fn join(&self, blocks: impl IntoIterator<Item = Block>, separator: &str) -> Block {
+		Block::beside(Itertools::intersperse(blocks.into_iter(), Block::text(separator)))
+	}
+
+	/// Internal method to lay out a number on a single line, like in exponents.
+	fn number_inline(&self, num: &Number) -> Block {
+		let (numer, denom) = num.clone().ratio();
+
+		match denom.is_one() {
+			true => Block::text(numer.to_integer().unwrap().to_string()),
+			false => Block::text(format!(
+				"{}/{}",
+				numer.to_integer().unwrap(),
+				denom.to_integer().unwrap()
+			)),
+		}
+	}
+
+	/// Internal method to lay out an expression with parentheses if necessary, like `Expr::with_parens`.
+	fn parens(&self, expr: &Expr) -> Block {
+		match expr.needs_parens() {
+			true => self.expr(expr).parens(self.unicode),
+			false => self.expr(expr),
+		}
+	}
+
+	/// Internal method to pick between a Unicode string and its ASCII replacement.
+	const fn pick(&self, unicode: &'static str, ascii: &'static str) -> &'static str {
+		match self.unicode {
+			true => unicode,
+			false => ascii,
+		}
+	}
+}
+
+/// Internal helper to pad a line with spaces to the given width, either centered or on the right.
+fn pad(line: &str, width: usize, center: bool) -> String {
+	let missing = width.saturating_sub(line.chars().count());
+	let left = if center { missing / 2 } else { 0 };
+
+	format!("{}{line}{}", " ".repeat(left), " ".repeat(missing - left))
+}
+
+/// Internal helper to get the Unicode superscript of a character, if there is one.
+fn superscript(char: char) -> Option<char> {
+	SUPERSCRIPTS
+		.iter()
+		.find(|(normal, _)| *normal == char)
+		.map(|(_, superscript)| *superscript)
+}
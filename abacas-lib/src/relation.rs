@@ -0,0 +1,373 @@
+//! Relations between expressions and the solving of inequalities.
+//!
+//! Inequalities are solved with sign charts. The real roots of all numerators and denominators are isolated, which
+//! splits the real line into points and open gaps on which every relation has a constant truth value. Each gap is
+//! decided with an exact rational sample point, and each root by whether the polynomials vanish there. Rational roots
+//! and the roots of quadratic factors are written exactly, while other roots are written as `root(p, k)` for the
+//! `k`-th real root of the polynomial `p`, counted from one in ascending order.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{BitAnd, BitOr};
+
+use rug::ops::Pow;
+
+use crate::context::Context;
+use crate::error::SimplifyError;
+use crate::expr::{Expr, Symbol};
+use crate::interval::Interval;
+use crate::monomial::Monomial;
+use crate::number::Number;
+use crate::polynomial::Polynomial;
+use crate::set::Set;
+use crate::summation::rational;
+
+/// A comparison between two real expressions.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Relation {
+	/// Equal to (`==`).
+	Eq,
+	/// Greater than or equal to (`>=`).
+	Ge,
+	/// Greater than (`>`).
+	Gt,
+	/// Less than or equal to (`<=`).
+	Le,
+	/// Less than (`<`).
+	Lt,
+	/// Not equal to (`!=`).
+	Ne,
+}
+
+impl Relation {
+	/// Checks whether the relation holds between two values with the given ordering.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use std::cmp::Ordering;
+	///
+	/// use abacas::relation::Relation;
+	///
+	/// assert!(Relation::Le.holds(Ordering::Less));
+	/// assert!(!Relation::Ne.holds(Ordering::Equal));
+	/// ```
+	pub fn holds(self, ordering: Ordering) -> bool {
+		match self {
+			Self::Eq => ordering.is_eq(),
+			Self::Ge => ordering.is_ge(),
+			Self::Gt => ordering.is_gt(),
+			Self::Le => ordering.is_le(),
+			Self::Lt => ordering.is_lt(),
+			Self::Ne => ordering.is_ne(),
+		}
+	}
+
+	/// Returns the relation that holds exactly if this one does not, like `>=` for `<`.
+	pub const fn negate(self) -> Self {
+		match self {
+			Self::Eq => Self::Ne,
+			Self::Ge => Self::Lt,
+			Self::Gt => Self::Le,
+			Self::Le => Self::Gt,
+			Self::Lt => Self::Ge,
+			Self::Ne => Self::Eq,
+		}
+	}
+
+	/// Returns the relation that holds with both sides swapped, like `>` for `<`.
+	pub const fn reverse(self) -> Self {
+		match self {
+			Self::Eq => Self::Eq,
+			Self::Ge => Self::Le,
+			Self::Gt => Self::Lt,
+			Self::Le => Self::Ge,
+			Self::Lt => Self::Gt,
+			Self::Ne => Self::Ne,
+		}
+	}
+}
+
+impl fmt::Display for Relation {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Eq => write!(f, "=="),
+			Self::Ge => write!(f, ">="),
+			Self::Gt => write!(f, ">"),
+			Self::Le => write!(f, "<="),
+			Self::Lt => write!(f, "<"),
+			Self::Ne => write!(f, "!="),
+		}
+	}
+}
+
+/// A critical point of a sign chart, which is a real root of one of the polynomials.
+struct Root {
+	/// An interval isolating the root from all other critical points, which is a single point for rational roots.
+	interval: Interval,
+	/// The exact value of the root.
+	value: Expr,
+}
+
+// Operations
+impl Expr {
+	/// Solves an inequality in a single variable, which can be a relation or a boolean connective of relations between
+	/// rational functions in `var` with numeric coefficients. Returns the set of real values for which it holds, or
+	/// [`None`] if it cannot be solved.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::context::Context;
+	/// use abacas::expr::{Expr, Symbol};
+	/// use abacas::relation::Relation;
+	///
+	/// let ctx = &mut Context::new();
+	/// let x = Symbol::new("x").unwrap();
+	/// let poly = |poly: &str| Expr::Poly(x.clone(), poly.parse().unwrap());
+	///
+	/// let expr = Expr::Rel(Relation::Le, poly("x^2 - 3x").into(), poly("-2").into());
+	/// assert_eq!(expr.solve_inequality(&x, ctx).unwrap().unwrap().to_string(), "[1, 2]");
+	///
+	/// let expr = Expr::Rel(Relation::Gt, (poly("x + 1") / poly("x - 1")).into(), Expr::zero().into());
+	/// assert_eq!(expr.solve_inequality(&x, ctx).unwrap().unwrap().to_string(), "(-inf, -1) ∪ (1, inf)");
+	///
+	/// let expr = Expr::Rel(Relation::Lt, poly("x^2").into(), poly("2").into());
+	/// assert_eq!(expr.solve_inequality(&x, ctx).unwrap().unwrap().to_string(), "(-1 * 2^0.5, 2^0.5)");
+	/// ```
+	pub fn solve_inequality(&self, var: &Symbol, ctx: &mut Context) -> Result<Option<Set>, SimplifyError> {
+		let expr = self.clone().simplify_builtin(ctx)?;
+
+		// Write every relation as a comparison of a quotient of polynomials with zero
+		let mut quotients = Vec::new();
+
+		if !quotients_of(&expr, var, ctx, &mut quotients)? {
+			return Ok(None);
+		}
+
+		// The critical points are the distinct real roots of all numerators and denominators
+		let product = quotients
+			.iter()
+			.flat_map(|(numer, denom)| [numer, denom])
+			.filter(|poly| !poly.is_zero())
+			.fold(Polynomial::from(1), |acc, poly| acc * poly);
+
+		let intervals = match product.degree().is_some_and(|degree| degree.is_positive()) {
+			true => isolate(&square_free(&product)),
+			false => Vec::new(),
+		};
+
+		let roots: Vec<_> = (0..intervals.len())
+			.map(|index| {
+				let value = exact_root(&quotients, &product, &intervals, index, var, ctx)?;
+				Ok(Root {
+					interval: intervals[index].clone(),
+					value,
+				})
+			})
+			.collect::<Result<_, SimplifyError>>()?;
+
+		// Decide the relations on every gap with a sample point, and at every root
+		let gap = |index: usize| -> bool {
+			let sample = match (index.checked_sub(1).map(|index| &roots[index]), roots.get(index)) {
+				(None, None) => Number::zero(),
+				(None, Some(next)) => next.interval.lo().clone() - &Number::one(),
+				(Some(prev), None) => prev.interval.hi().clone() + &Number::one(),
+				(Some(prev), Some(next)) => (prev.interval.hi().clone() + next.interval.lo()) / 2,
+			};
+
+			holds(
+				&expr,
+				&mut quotients.iter().map(|(numer, denom)| {
+					sign_of_quotient(
+						numer.eval(&sample).unwrap().cmp(&Number::zero()),
+						denom.eval(&sample).unwrap().cmp(&Number::zero()),
+					)
+				}),
+			)
+		};
+
+		let point = |root: &Root| -> bool {
+			let signs: Option<Vec<_>> = quotients
+				.iter()
+				.map(|(numer, denom)| sign_of_quotient(sign_at(numer, &root.interval), sign_at(denom, &root.interval)))
+				.collect();
+
+			// Points where a denominator vanishes are never part of the solution
+			signs.is_some_and(|signs| holds(&expr, &mut signs.into_iter().map(Some)))
+		};
+
+		// The chart alternates between the gaps and the roots, starting and ending with a gap
+		let mut atoms = vec![gap(0)];
+
+		for (index, root) in roots.iter().enumerate() {
+			atoms.extend([point(root), gap(index + 1)]);
+		}
+
+		let points = roots.into_iter().map(|root| root.value).collect();
+		Ok(Some(Set::from_chart(points, &atoms)))
+	}
+}
+
+/// Internal helper to write an exact expression for the real root isolated by an interval. Rational roots are exact
+/// points already, roots of quadratic factors are written with square roots, and any other root as `root(p, k)`.
+pub(crate) fn exact_root(
+	quotients: &[(Polynomial, Polynomial)],
+	product: &Polynomial,
+	intervals: &[Interval],
+	index: usize,
+	var: &Symbol,
+	ctx: &mut Context,
+) -> Result<Expr, SimplifyError> {
+	let interval = &intervals[index];
+
+	if interval.is_point() {
+		return Ok(Expr::Num(interval.lo().clone()));
+	}
+
+	let irrational = |poly: &Polynomial| {
+		let poly = square_free(poly);
+
+		poly.rational_roots().into_iter().flatten().fold(poly, |acc, root| {
+			let factor = Polynomial::from(Monomial::linear(1)) + Polynomial::from(-root);
+			acc.div_rem(&factor).unwrap().0
+		})
+	};
+
+	// Look for a quadratic factor without rational roots that vanishes at the root
+	let quadratic = quotients
+		.iter()
+		.flat_map(|(numer, denom)| [numer, denom])
+		.map(irrational)
+		.find(|poly| poly.degree() == Some(&2.into()) && sign_at(poly, interval).is_eq());
+
+	let Some(poly) = quadratic else {
+		// Count the real roots of the remaining factor below this root
+		let rest = irrational(product);
+		let below = intervals[..index]
+			.iter()
+			.filter(|other| sign_at(&rest, other).is_eq())
+			.count();
+
+		let args = vec![Expr::Poly(var.clone(), rest), Expr::Num((below + 1).into())];
+		return Ok(Expr::Fun(Symbol::new("root").unwrap(), args));
+	};
+
+	// The roots of `a x^2 + b x + c` lie symmetrically around the vertex at `-b / 2a`
+	let coeff = |degree: i32| {
+		poly.get(&degree.into())
+			.map_or_else(Number::zero, |mono| mono.coeff.clone())
+	};
+	let (a, b, c) = (coeff(2), coeff(1), coeff(0));
+
+	let vertex = -(b / &(a.clone() * 2));
+	let radicand = vertex.clone() * &vertex - &(c / &a);
+
+	let lo = interval.lo().clone();
+	let sign = |x: &Number| poly.eval(x).unwrap().cmp(&Number::zero());
+	let below = interval.hi() <= &vertex || (lo < vertex && sign(&lo) != sign(&vertex));
+
+	let root = Expr::Num(radicand).pow(Expr::Num(Number::from(1) / 2));
+	let root = if below { -root } else { root };
+
+	(Expr::Num(vertex) + root).simplify_builtin(ctx)
+}
+
+/// Internal helper to decide a relation or boolean connective of relations, taking the signs of the quotients in the
+/// order they were collected by [`quotients_of`]. A quotient without a sign is undefined, which makes its relation
+/// false.
+fn holds(expr: &Expr, signs: &mut impl Iterator<Item = Option<Ordering>>) -> bool {
+	match expr {
+		// Every relation has to be visited to keep the signs in order
+		Expr::And(exprs) => exprs.iter().map(|expr| holds(expr, signs)).fold(true, BitAnd::bitand),
+		Expr::Not(expr) => !holds(expr, signs),
+		Expr::Or(exprs) => exprs.iter().map(|expr| holds(expr, signs)).fold(false, BitOr::bitor),
+		Expr::Rel(rel, _, _) => signs.next().unwrap().is_some_and(|sign| rel.holds(sign)),
+		_ => unreachable!(),
+	}
+}
+
+/// Internal helper to isolate the real roots of a square-free polynomial, where every rational root is isolated by a
+/// single point.
+pub(crate) fn isolate(poly: &Polynomial) -> Vec<Interval> {
+	let rational = poly.rational_roots().unwrap();
+
+	poly.isolate_roots(&Number::one())
+		.unwrap()
+		.into_iter()
+		.map(|interval| match rational.iter().find(|root| interval.contains(root)) {
+			Some(root) => Interval::point(root.clone()),
+			None => interval,
+		})
+		.collect()
+}
+
+/// Internal helper to collect the quotients `p / q` of every relation `lhs ~ rhs` with `lhs - rhs = p / q` in order.
+/// Returns `false` if the expression is not a relation or boolean connective of relations between rational functions.
+fn quotients_of(
+	expr: &Expr,
+	var: &Symbol,
+	ctx: &mut Context,
+	quotients: &mut Vec<(Polynomial, Polynomial)>,
+) -> Result<bool, SimplifyError> {
+	match expr {
+		Expr::And(exprs) | Expr::Or(exprs) => {
+			for expr in exprs {
+				if !quotients_of(expr, var, ctx, quotients)? {
+					return Ok(false);
+				}
+			}
+
+			Ok(true)
+		}
+		Expr::Not(expr) => quotients_of(expr, var, ctx, quotients),
+		Expr::Rel(_, lhs, rhs) => {
+			let diff = ((**lhs).clone() - (**rhs).clone()).simplify_builtin(ctx)?;
+
+			match rational(&diff, var) {
+				Some(quotient) => {
+					quotients.push(quotient);
+					Ok(true)
+				}
+				None => Ok(false),
+			}
+		}
+		_ => Ok(false),
+	}
+}
+
+/// Internal helper to get the sign of a polynomial at a root isolated by an interval.
+pub(crate) fn sign_at(poly: &Polynomial, interval: &Interval) -> Ordering {
+	let sign = |poly: &Polynomial, x: &Number| poly.eval(x).unwrap().cmp(&Number::zero());
+
+	if interval.is_point() {
+		return sign(poly, interval.lo());
+	}
+
+	// The bounds are never roots, so a square-free polynomial vanishes inside exactly if its sign changes
+	let factor = square_free(poly);
+
+	match sign(&factor, interval.lo()) != sign(&factor, interval.hi()) {
+		true => Ordering::Equal,
+		false => sign(poly, interval.lo()),
+	}
+}
+
+/// Internal helper to get the sign of a quotient from the signs of its numerator and denominator, or [`None`] if the
+/// denominator is zero.
+fn sign_of_quotient(numer: Ordering, denom: Ordering) -> Option<Ordering> {
+	match denom {
+		Ordering::Equal => None,
+		Ordering::Greater => Some(numer),
+		Ordering::Less => Some(numer.reverse()),
+	}
+}
+
+/// Internal helper to remove multiple roots from a polynomial by dividing it by its GCD with its derivative.
+pub(crate) fn square_free(poly: &Polynomial) -> Polynomial {
+	if poly.is_zero() {
+		return poly.clone();
+	}
+
+	poly.clone().div_rem(&poly.clone().gcd(poly.derivative())).unwrap().0
+}
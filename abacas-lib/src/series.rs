@@ -0,0 +1,681 @@
+//! Truncated power series and series expansions of expressions.
+//!
+//! A [`PowerSeries`] keeps its terms up to an error term `O(x^n)`. Its degrees may be negative or rational, which
+//! covers Laurent and Puiseux series, and its coefficients are expressions, so expansions around points like `x = 1`
+//! keep exact coefficients like `exp(1)`.
+
+use std::collections::BTreeMap;
+use std::ops::{Add, Mul, Neg, Sub};
+use std::slice::Iter;
+use std::{fmt, iter};
+
+use rug::ops::Pow;
+
+use crate::context::Context;
+use crate::expr::{Expr, Symbol};
+use crate::monomial::Monomial;
+use crate::number::Number;
+use crate::summation::evaluate;
+
+/// The maximum amount of times an expansion is repeated with more terms when cancellations lose precision.
+const MAX_RETRIES: u32 = 4;
+
+/// A truncated power series `c_1 x^d_1 + c_2 x^d_2 + ... + O(x^n)` with its terms sorted by `degree` in ascending
+/// order.
+///
+/// # Examples
+///
+/// ```
+/// use abacas::expr::Expr;
+/// use abacas::series::PowerSeries;
+///
+/// let x = PowerSeries::new([(1.into(), Expr::one())], 4);
+///
+/// let exp = x.exp().unwrap();
+/// assert_eq!(exp.to_string(), "1 + x + 0.5x^2 + 1/6x^3 + O(x^4)");
+///
+/// // The logarithm undoes the exponential
+/// let ln = exp.ln().unwrap();
+/// assert_eq!(ln, x);
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct PowerSeries {
+	/// The terms as pairs of degrees and non-zero coefficients, all below the order.
+	terms: Vec<(Number, Expr)>,
+	/// The order `n` of the error term `O(x^n)`.
+	order: Number,
+}
+
+impl PowerSeries {
+	/// Internal method to evaluate `c_0 + c_1 s + c_2 s^2 + ...` for this series `s` of positive valuation, using as
+	/// many terms as the order of this series requires. An empty series of order zero or less only keeps `c_0`.
+	fn apply(&self, coeffs: impl Fn(u32) -> Number) -> Self {
+		let valuation = self.valuation();
+
+		if !valuation.is_positive() {
+			return Self::new([(Number::zero(), Expr::Num(coeffs(0)))], self.order.clone());
+		}
+
+		let count = (self.order.clone() / valuation).ceil().to_u32().unwrap_or(1).max(1);
+		let order = valuation.clone() * count;
+
+		let mut power = Self::new([(Number::zero(), Expr::one())], order.clone());
+		let mut result = Self::new([(Number::zero(), Expr::Num(coeffs(0)))], order);
+
+		for k in 1..count {
+			power = power * self;
+			result = result + &power.scale(&Expr::Num(coeffs(k)), &Number::zero());
+		}
+
+		result
+	}
+
+	/// Returns the inverse tangent of this series, which requires no terms of negative degree.
+	pub fn atan(&self) -> Option<Self> {
+		let (constant, _) = self.split_constant()?;
+
+		// atan(s) = atan(c) + integral of s' / (1 + s^2)
+		let one = Self::new([(Number::zero(), Expr::one())], self.order.clone());
+		let integrand = self.derivative() * &(one + &(self.clone() * self)).inverse()?;
+		let constant = Self::new([(Number::zero(), call("atan", constant))], self.order.clone());
+
+		Some(integrand.integrate()? + &constant)
+	}
+
+	/// Returns the coefficient of a degree, which is zero if there is no term of this degree.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::expr::Expr;
+	/// use abacas::series::PowerSeries;
+	///
+	/// let series = PowerSeries::new([(2.into(), Expr::Num(5.into()))], 4);
+	/// assert_eq!(series.coeff(&2.into()), Expr::Num(5.into()));
+	/// assert_eq!(series.coeff(&3.into()), Expr::zero());
+	/// ```
+	pub fn coeff(&self, degree: &Number) -> Expr {
+		let term = self.terms.iter().find(|(other, _)| other == degree);
+		term.map_or_else(Expr::zero, |(_, coeff)| coeff.clone())
+	}
+
+	/// Composes this series with an inner series as `f(g(x))`, which requires integer degrees in this series and a
+	/// positive valuation of the inner series.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::expr::Expr;
+	/// use abacas::series::PowerSeries;
+	///
+	/// let x = PowerSeries::new([(1.into(), Expr::one())], 4);
+	/// let square = PowerSeries::new([(2.into(), Expr::one())], 4);
+	///
+	/// // exp(x^2)
+	/// let result = x.exp().unwrap().compose(&square).unwrap();
+	/// assert_eq!(result.to_string(), "1 + x^2 + O(x^4)");
+	/// ```
+	pub fn compose(&self, inner: &Self) -> Option<Self> {
+		if !inner.valuation().is_positive() || !self.terms.iter().all(|(degree, _)| degree.is_integer()) {
+			return None;
+		}
+
+		let mut result = Self::new([], self.order.clone() * inner.valuation());
+
+		for (degree, coeff) in &self.terms {
+			// Constant terms stay exact
+			let power = match degree.is_zero() {
+				true => Self::one(&result.order),
+				false => inner.pow(degree)?,
+			};
+
+			result = result + &power.scale(coeff, &Number::zero());
+		}
+
+		Some(result)
+	}
+
+	/// Returns the cosine of this series, which requires no terms of negative degree.
+	pub fn cos(&self) -> Option<Self> {
+		let (constant, rest) = self.split_constant()?;
+
+		// cos(c + s) = cos(c) cos(s) - sin(c) sin(s)
+		let cos = rest
+			.apply(cos_coeff)
+			.scale(&call("cos", constant.clone()), &Number::zero());
+		let sin = rest.apply(sin_coeff).scale(&call("sin", constant), &Number::zero());
+
+		Some(cos - &sin)
+	}
+
+	/// Returns the derivative of this series.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::expr::Expr;
+	/// use abacas::series::PowerSeries;
+	///
+	/// let series = PowerSeries::new([(0.into(), Expr::one()), (3.into(), Expr::Num(2.into()))], 5);
+	/// assert_eq!(series.derivative().to_string(), "6x^2 + O(x^4)");
+	/// ```
+	pub fn derivative(&self) -> Self {
+		let terms = self.terms.iter().map(|(degree, coeff)| {
+			let coeff = coeff.clone() * Expr::Num(degree.clone());
+			(degree.clone() - 1, coeff)
+		});
+
+		Self::new(terms, self.order.clone() - 1)
+	}
+
+	/// Returns the exponential of this series, which requires no terms of negative degree.
+	pub fn exp(&self) -> Option<Self> {
+		let (constant, rest) = self.split_constant()?;
+
+		// exp(c + s) = exp(c) (1 + s + s^2 / 2 + ...)
+		let mut factorial = Number::one();
+		let coeffs = (0..).map(|k| {
+			if k > 0 {
+				factorial *= k;
+			}
+
+			factorial.clone().recip()
+		});
+
+		let coeffs = coeffs.take(rest.terms_needed() as usize).collect::<Vec<_>>();
+		Some(
+			rest.apply(|k| coeffs[k as usize].clone())
+				.scale(&call("exp", constant), &Number::zero()),
+		)
+	}
+
+	/// Returns the antiderivative of this series without a constant term, or [`None`] if it has a term of degree `-1`.
+	pub fn integrate(&self) -> Option<Self> {
+		let terms = self.terms.iter().map(|(degree, coeff)| {
+			let degree = degree.clone() + 1;
+			(!degree.is_zero()).then(|| (degree.clone(), coeff.clone() / Expr::Num(degree)))
+		});
+
+		Some(Self::new(terms.collect::<Option<Vec<_>>>()?, self.order.clone() + 1))
+	}
+
+	/// Returns the multiplicative inverse of this series, or [`None`] for the zero series.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::expr::Expr;
+	/// use abacas::series::PowerSeries;
+	///
+	/// // 1 / (x - x^2) is a Laurent series
+	/// let series = PowerSeries::new([(1.into(), Expr::one()), (2.into(), Expr::neg_one())], 4);
+	/// assert_eq!(series.inverse().unwrap().to_string(), "x^-1 + 1 + x + O(x^2)");
+	/// ```
+	pub fn inverse(&self) -> Option<Self> {
+		self.pow(&Number::neg_one())
+	}
+
+	/// Returns the natural logarithm of this series, which requires a non-zero constant term and no terms of negative
+	/// degree.
+	pub fn ln(&self) -> Option<Self> {
+		let (constant, _) = self.split_constant()?;
+
+		if constant.is_num_and(Number::is_zero) {
+			return None;
+		}
+
+		// ln(c (1 + s)) = ln(c) + s - s^2 / 2 + s^3 / 3 - ...
+		let rest = self.scale(&constant.clone().pow(Expr::neg_one()), &Number::zero()) - &Self::one(&self.order);
+		let series = rest.apply(|k| match k {
+			0 => Number::zero(),
+			k if k % 2 == 0 => Number::from(-1) / k,
+			k => Number::from(1) / k,
+		});
+
+		Some(series + &Self::new([(Number::zero(), call("ln", constant))], self.order.clone()))
+	}
+
+	/// Creates a new power series from pairs of degrees and coefficients, dropping terms at or above the order.
+	pub fn new(terms: impl IntoIterator<Item = (Number, Expr)>, order: impl Into<Number>) -> Self {
+		let order = order.into();
+		let mut merged = BTreeMap::<Number, Vec<Expr>>::new();
+
+		for (degree, coeff) in terms {
+			if degree < order {
+				merged.entry(degree).or_default().push(coeff);
+			}
+		}
+
+		let terms = merged.into_iter().filter_map(|(degree, coeffs)| {
+			let coeff = simplify(Expr::Add(coeffs));
+			(!coeff.is_num_and(Number::is_zero)).then_some((degree, coeff))
+		});
+
+		Self {
+			terms: terms.collect(),
+			order,
+		}
+	}
+
+	/// Internal helper to create the series `1 + O(x^n)`.
+	fn one(order: &Number) -> Self {
+		Self::new([(Number::zero(), Expr::one())], order.clone())
+	}
+
+	/// Returns the order `n` of the error term `O(x^n)`.
+	pub fn order(&self) -> &Number {
+		&self.order
+	}
+
+	/// Raises this series to a rational power, or returns [`None`] for the zero series.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::expr::Expr;
+	/// use abacas::number::Number;
+	/// use abacas::series::PowerSeries;
+	///
+	/// // sqrt(4 + x)
+	/// let series = PowerSeries::new([(0.into(), Expr::Num(4.into())), (1.into(), Expr::one())], 3);
+	/// let sqrt = series.pow(&(Number::from(1) / 2)).unwrap();
+	/// assert_eq!(sqrt.to_string(), "2 + 0.25x - 0.015625x^2 + O(x^3)");
+	/// ```
+	pub fn pow(&self, exp: &Number) -> Option<Self> {
+		let (degree, coeff) = self.terms.first()?;
+
+		// (c x^d (1 + s))^q = c^q x^(dq) (1 + qs + binomial(q, 2) s^2 + ...)
+		let recip = simplify(coeff.clone().pow(Expr::neg_one()));
+		let rest = self.scale(&recip, &-degree.clone()) - &Self::one(&(self.order.clone() - degree));
+
+		let mut binomial = Number::one();
+		let coeffs = (0..rest.terms_needed()).map(|k| {
+			if k > 0 {
+				binomial = binomial.clone() * &(exp.clone() - (k - 1)) / k;
+			}
+
+			binomial.clone()
+		});
+
+		let coeffs = coeffs.collect::<Vec<_>>();
+		let power = simplify(coeff.clone().pow(Expr::Num(exp.clone())));
+
+		Some(
+			rest.apply(|k| coeffs[k as usize].clone())
+				.scale(&power, &(degree.clone() * exp)),
+		)
+	}
+
+	/// Returns the compositional inverse `g` with `f(g(x)) = x` of this series `f`, which requires integer degrees
+	/// starting with a linear term.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::expr::Expr;
+	/// use abacas::series::PowerSeries;
+	///
+	/// // The inverse of x + x^2
+	/// let series = PowerSeries::new([(1.into(), Expr::one()), (2.into(), Expr::one())], 5);
+	/// assert_eq!(series.reversion().unwrap().to_string(), "x - x^2 + 2x^3 - 5x^4 + O(x^5)");
+	/// ```
+	pub fn reversion(&self) -> Option<Self> {
+		let (degree, linear) = self.terms.first()?;
+
+		if !degree.is_one() || !self.terms.iter().all(|(degree, _)| degree.is_integer()) {
+			return None;
+		}
+
+		let x = Self::new([(Number::one(), Expr::one())], self.order.clone());
+		let recip = simplify(linear.clone().pow(Expr::neg_one()));
+		let mut result = x.scale(&recip, &Number::zero());
+
+		// Each step of g = (x - (f(g) - a g)) / a fixes the next coefficient
+		for _ in 2..self.terms_needed() {
+			let rest = self.compose(&result)? - &result.scale(linear, &Number::zero());
+			result = (x.clone() - &rest).scale(&recip, &Number::zero());
+		}
+
+		Some(result)
+	}
+
+	/// Internal method to multiply this series by a monomial `c x^d`.
+	fn scale(&self, coeff: &Expr, degree: &Number) -> Self {
+		let terms = self.terms.iter().map(|(other, term)| {
+			let coeff = term.clone() * coeff.clone();
+			(other.clone() + degree, coeff)
+		});
+
+		Self::new(terms, self.order.clone() + degree)
+	}
+
+	/// Returns the sine of this series, which requires no terms of negative degree.
+	pub fn sin(&self) -> Option<Self> {
+		let (constant, rest) = self.split_constant()?;
+
+		// sin(c + s) = sin(c) cos(s) + cos(c) sin(s)
+		let cos = rest
+			.apply(cos_coeff)
+			.scale(&call("sin", constant.clone()), &Number::zero());
+		let sin = rest.apply(sin_coeff).scale(&call("cos", constant), &Number::zero());
+
+		Some(cos + &sin)
+	}
+
+	/// Internal method to split off the constant term, returning [`None`] if there are terms of negative degree.
+	fn split_constant(&self) -> Option<(Expr, Self)> {
+		if self.valuation().is_negative() {
+			return None;
+		}
+
+		let constant = self.coeff(&Number::zero());
+		let rest = self.terms.iter().filter(|(degree, _)| !degree.is_zero()).cloned();
+
+		Some((constant, Self::new(rest, self.order.clone())))
+	}
+
+	/// Returns the square root of this series, or [`None`] for the zero series.
+	pub fn sqrt(&self) -> Option<Self> {
+		self.pow(&(Number::from(1) / 2))
+	}
+
+	/// Returns the tangent of this series, which requires no terms of negative degree.
+	pub fn tan(&self) -> Option<Self> {
+		Some(self.sin()? * &self.cos()?.inverse()?)
+	}
+
+	/// Internal method to get the amount of powers of this series of positive valuation that are needed for its order.
+	fn terms_needed(&self) -> u32 {
+		// An empty series of order zero or less has no valuation to divide by
+		if !self.valuation().is_positive() {
+			return 1;
+		}
+
+		(self.order.clone() / self.valuation())
+			.ceil()
+			.to_u32()
+			.unwrap_or(1)
+			.max(1)
+	}
+
+	/// Returns an iterator over the terms as pairs of degrees and coefficients.
+	pub fn terms(&self) -> Iter<'_, (Number, Expr)> {
+		self.terms.iter()
+	}
+
+	/// Converts this series into an expression in `var` around a point by dropping the error term.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::expr::{Expr, Symbol};
+	/// use abacas::series::PowerSeries;
+	///
+	/// let x = Symbol::new("x").unwrap();
+	/// let series = PowerSeries::new([(0.into(), Expr::one()), (2.into(), Expr::Num(3.into()))], 4);
+	///
+	/// let expr = series.to_expr(&x, &Expr::one());
+	/// assert_eq!(expr.to_string(), "3x^2 - 6x + 4");
+	/// ```
+	pub fn to_expr(&self, var: &Symbol, point: &Expr) -> Expr {
+		let offset = Expr::Poly(var.clone(), "x".parse().unwrap()) - point.clone();
+		let terms = self.terms.iter().map(|(degree, coeff)| {
+			let power = offset.clone().pow(Expr::Num(degree.clone()));
+			coeff.clone() * power
+		});
+
+		simplify(Expr::Add(terms.collect()))
+	}
+
+	/// Truncates this series to a lower order.
+	pub fn truncate(self, order: impl Into<Number>) -> Self {
+		let order = order.into().min(self.order);
+		Self::new(self.terms, order)
+	}
+
+	/// Returns the lowest degree of a term, or the order for a series without terms.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::expr::Expr;
+	/// use abacas::number::Number;
+	/// use abacas::series::PowerSeries;
+	///
+	/// let series = PowerSeries::new([(3.into(), Expr::one()), ((-2).into(), Expr::one())], 6);
+	/// assert_eq!(series.valuation(), &Number::from(-2));
+	///
+	/// let series = PowerSeries::new([], 6);
+	/// assert_eq!(series.valuation(), &Number::from(6));
+	/// ```
+	pub fn valuation(&self) -> &Number {
+		self.terms.first().map_or(&self.order, |(degree, _)| degree)
+	}
+}
+
+// Operations
+impl Expr {
+	/// Expands this expression into a Taylor, Laurent or Puiseux series in `var` around a point, with the error term
+	/// `O((x - a)^n)`. The series is in the offset `x - a` from the point. Returns [`None`] for functions without known
+	/// expansions and singular points of them, like `ln(x)` at `x = 0`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::expr::{Expr, Symbol};
+	///
+	/// let x = Symbol::new("x").unwrap();
+	/// let poly = Expr::Poly(x.clone(), "x".parse().unwrap());
+	/// let expr = Expr::Fun(Symbol::new("sin").unwrap(), vec![poly.clone()]) / poly;
+	///
+	/// let series = expr.series(&x, &Expr::zero(), 5).unwrap();
+	/// assert_eq!(series.to_string(), "1 - 1/6x^2 + 1/120x^4 + O(x^5)");
+	/// ```
+	pub fn series(&self, var: &Symbol, point: &Self, order: impl Into<Number>) -> Option<PowerSeries> {
+		let order = order.into();
+		let mut working = order.clone();
+
+		for _ in 0..MAX_RETRIES {
+			let series = expand(self, var, point, &working)?;
+
+			if series.order >= order {
+				return Some(series.truncate(order));
+			}
+
+			// Cancellations and negative degrees lose precision, so expand again with more terms
+			working = working + &order - &series.order;
+		}
+
+		None
+	}
+}
+
+/// Internal helper to create a call to a function of the StdLib and evaluate it.
+fn call(name: &str, arg: Expr) -> Expr {
+	let expr = Expr::Fun(Symbol::new(name).unwrap(), vec![arg]);
+	evaluate(expr.clone(), &mut Context::new()).unwrap_or(expr)
+}
+
+/// Internal helper to get the coefficients of `cos(s) = 1 - s^2 / 2 + s^4 / 24 - ...`.
+fn cos_coeff(k: u32) -> Number {
+	match k % 4 {
+		0 => factorial(k).recip(),
+		2 => -factorial(k).recip(),
+		_ => Number::zero(),
+	}
+}
+
+/// Internal helper to expand an expression into a series around a point with the given working order.
+fn expand(expr: &Expr, var: &Symbol, point: &Expr, order: &Number) -> Option<PowerSeries> {
+	if !expr.has_symbol(var) {
+		return Some(PowerSeries::new([(Number::zero(), expr.clone())], order.clone()));
+	}
+
+	match expr {
+		Expr::Add(terms) => terms.iter().try_fold(PowerSeries::new([], order.clone()), |acc, term| {
+			Some(acc + &expand(term, var, point, order)?)
+		}),
+
+		Expr::Fun(name, args) => {
+			let [arg] = args.as_slice() else {
+				return None;
+			};
+
+			let arg = expand(arg, var, point, order)?;
+
+			match name.name() {
+				"atan" => arg.atan(),
+				"cos" => arg.cos(),
+				"exp" => arg.exp(),
+				"ln" => arg.ln(),
+				"sin" => arg.sin(),
+				"sqrt" => arg.sqrt(),
+				"tan" => arg.tan(),
+				_ => None,
+			}
+		}
+
+		Expr::Mul(factors) => factors.iter().try_fold(PowerSeries::one(order), |acc, factor| {
+			Some(acc * &expand(factor, var, point, order)?)
+		}),
+
+		// Polynomials in `x` are polynomials in `x - a` around zero, and powers of `a + (x - a)` otherwise
+		Expr::Poly(_, poly) if point.is_num_and(Number::is_zero) => {
+			let terms = poly
+				.monomials()
+				.map(|mono| (mono.degree.clone(), Expr::Num(mono.coeff.clone())));
+			Some(PowerSeries::new(terms, order.clone()))
+		}
+
+		Expr::Poly(_, poly) => {
+			let offset = PowerSeries::new(
+				[(Number::zero(), point.clone()), (Number::one(), Expr::one())],
+				order.clone(),
+			);
+
+			poly.monomials()
+				.try_fold(PowerSeries::new([], order.clone()), |acc, mono| {
+					let power = offset.pow(&mono.degree)?;
+					Some(acc + &power.scale(&Expr::Num(mono.coeff.clone()), &Number::zero()))
+				})
+		}
+
+		Expr::Pow(base, exp) if let Expr::Num(exp) = exp.as_ref() => {
+			let mut series = expand(base, var, point, order)?;
+			let mut working = order.clone();
+
+			// Powers need the lowest term of the base, which may be above the working order
+			for _ in 0..MAX_RETRIES {
+				if !series.terms.is_empty() {
+					break;
+				}
+
+				working = working.clone() + &(working.abs() + 1);
+				series = expand(base, var, point, &working)?;
+			}
+
+			series.pow(exp)
+		}
+
+		// Other powers are rewritten as `exp(e ln(b))`
+		Expr::Pow(base, exp) => {
+			let ln = Expr::Fun(Symbol::new("ln").unwrap(), vec![base.as_ref().clone()]);
+			let power = Expr::Fun(Symbol::new("exp").unwrap(), vec![exp.as_ref().clone() * ln]);
+
+			expand(&power, var, point, order)
+		}
+
+		Expr::And(_) | Expr::Matrix(_) | Expr::Not(_) | Expr::Or(_) | Expr::Piecewise(_) | Expr::Rel(..) => None,
+		Expr::Num(_) => unreachable!(),
+	}
+}
+
+/// Internal helper to compute the factorial of a small number.
+fn factorial(k: u32) -> Number {
+	(1..=k).fold(Number::one(), |acc, i| acc * i)
+}
+
+/// Internal helper to simplify a coefficient, keeping it as is if simplifying fails.
+fn simplify(expr: Expr) -> Expr {
+	expr.clone().simplify(&mut Context::new()).unwrap_or(expr)
+}
+
+/// Internal helper to get the coefficients of `sin(s) = s - s^3 / 6 + s^5 / 120 - ...`.
+fn sin_coeff(k: u32) -> Number {
+	match k % 4 {
+		1 => factorial(k).recip(),
+		3 => -factorial(k).recip(),
+		_ => Number::zero(),
+	}
+}
+
+impl Add<&Self> for PowerSeries {
+	type Output = Self;
+
+	fn add(self, rhs: &Self) -> Self::Output {
+		let order = self.order.clone().min(rhs.order.clone());
+		Self::new(self.terms.into_iter().chain(rhs.terms.iter().cloned()), order)
+	}
+}
+
+impl Mul<&Self> for PowerSeries {
+	type Output = Self;
+
+	fn mul(self, rhs: &Self) -> Self::Output {
+		// Each error term is multiplied by the lowest term of the other series
+		let lhs_order = self.order.clone() + rhs.valuation();
+		let rhs_order = rhs.order.clone() + self.valuation();
+		let order = lhs_order.min(rhs_order);
+
+		let terms = self.terms.iter().flat_map(|(lhs_degree, lhs_coeff)| {
+			let terms = rhs
+				.terms
+				.iter()
+				.filter(|(rhs_degree, _)| lhs_degree.clone() + rhs_degree < order);
+			terms
+				.map(|(rhs_degree, rhs_coeff)| (lhs_degree.clone() + rhs_degree, lhs_coeff.clone() * rhs_coeff.clone()))
+		});
+
+		Self::new(terms.collect::<Vec<_>>(), order)
+	}
+}
+
+impl Neg for PowerSeries {
+	type Output = Self;
+
+	fn neg(self) -> Self::Output {
+		self.scale(&Expr::neg_one(), &Number::zero())
+	}
+}
+
+impl Sub<&Self> for PowerSeries {
+	type Output = Self;
+
+	fn sub(self, rhs: &Self) -> Self::Output {
+		self + &-rhs.clone()
+	}
+}
+
+impl fmt::Display for PowerSeries {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let x = Symbol::new("x").unwrap();
+		let error = iter::once(format!("O(x^{})", self.order));
+
+		let terms = self.terms.iter().map(|(degree, coeff)| {
+			let power = Expr::Poly(x.clone(), Monomial::new(1, degree.clone()).into());
+			simplify(coeff.clone() * power).to_string()
+		});
+
+		let mut terms = terms.chain(error);
+		write!(f, "{}", terms.next().unwrap())?;
+
+		for term in terms {
+			match term.strip_prefix('-') {
+				Some(term) => write!(f, " - {term}")?,
+				None => write!(f, " + {term}")?,
+			}
+		}
+
+		Ok(())
+	}
+}
@@ -0,0 +1,710 @@
+//! Sets of real numbers, used for solution sets and domains.
+//!
+//! Infinite endpoints of intervals are written with the symbol `inf` like limits, and are always excluded. Unions,
+//! intersections and complements of finite sets and intervals are evaluated whenever all their endpoints can be
+//! ordered, using the assumptions of the context. They split the real line into points and open gaps like a sign
+//! chart, see the [`relation`](crate::relation) module. Other combinations are kept unevaluated.
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
+
+use itertools::Itertools;
+
+use crate::context::Context;
+use crate::error::SimplifyError;
+use crate::expr::{Expr, Symbol};
+use crate::monomial::Monomial;
+use crate::number::Number;
+use crate::relation::Relation;
+
+/// A set of real numbers.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Set {
+	/// The elements of the first set that are not in the second one.
+	Complement(Box<Self>, Box<Self>),
+	/// The elements of a base set for which a condition holds, like `{x ∈ ℝ | x^2 > 2}`.
+	Condition {
+		/// The symbol standing for an element.
+		var: Symbol,
+		/// The condition, which is a relation or boolean connective of relations in `var`.
+		condition: Expr,
+		/// The set the elements are taken from.
+		base: Box<Self>,
+	},
+	/// The empty set.
+	Empty,
+	/// A finite set of distinct elements.
+	Finite(Vec<Expr>),
+	/// The image of a base set under an expression, like `{n pi | n ∈ ℤ}`.
+	Image {
+		/// The expression mapping the elements of the base set.
+		expr: Expr,
+		/// The symbol standing for an element of the base set.
+		var: Symbol,
+		/// The set that is mapped.
+		base: Box<Self>,
+	},
+	/// The set of integers.
+	Integers,
+	/// The intersection of multiple sets.
+	Intersection(Vec<Self>),
+	/// An interval between two endpoints, which are included if the interval is closed at that end.
+	Interval {
+		/// The lower endpoint.
+		lo: Expr,
+		/// The upper endpoint.
+		hi: Expr,
+		/// Whether the lower endpoint is included.
+		lo_closed: bool,
+		/// Whether the upper endpoint is included.
+		hi_closed: bool,
+	},
+	/// The union of multiple sets, which are disjoint and sorted in ascending order if evaluated.
+	Union(Vec<Self>),
+}
+
+impl Set {
+	/// Returns the condition for a symbol to be an element of this set, as a relation or a boolean connective of
+	/// relations. Returns [`None`] for integers and images, which cannot be described by relations.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::context::Context;
+	/// use abacas::expr::{Expr, Symbol};
+	/// use abacas::set::Set;
+	///
+	/// let ctx = &mut Context::new();
+	/// let x = Symbol::new("x").unwrap();
+	/// let inf = Expr::Poly(Symbol::new("inf").unwrap(), "x".parse().unwrap());
+	///
+	/// let set = Set::union([
+	///     Set::interval(Expr::Num(1.into()), Expr::Num(2.into()), true, false),
+	///     Set::interval(Expr::Num(3.into()), inf, false, false),
+	/// ], ctx).unwrap();
+	///
+	/// assert_eq!(set.condition(&x).unwrap().to_string(), "x >= 1 and x < 2 or x > 3");
+	/// ```
+	pub fn condition(&self, var: &Symbol) -> Option<Expr> {
+		let x = Expr::Poly(var.clone(), Monomial::linear(1).into());
+		let rel = |rel, value: &Expr| Expr::Rel(rel, x.clone().into(), value.clone().into());
+
+		let condition = match self {
+			Self::Complement(universe, set) => {
+				Expr::And(vec![universe.condition(var)?, Expr::Not(set.condition(var)?.into())])
+			}
+			Self::Condition {
+				var: other,
+				condition,
+				base,
+			} => Expr::And(vec![base.condition(var)?, condition.substitute(other, &x)]),
+			Self::Empty => Expr::boolean(false),
+			Self::Finite(elems) if elems.len() == 1 => rel(Relation::Eq, &elems[0]),
+			Self::Finite(elems) => Expr::Or(elems.iter().map(|elem| rel(Relation::Eq, elem)).collect()),
+			Self::Image { .. } | Self::Integers => return None,
+			Self::Intersection(sets) => Expr::And(sets.iter().map(|set| set.condition(var)).collect::<Option<_>>()?),
+			Self::Interval {
+				lo,
+				hi,
+				lo_closed,
+				hi_closed,
+			} => {
+				let mut bounds = Vec::new();
+
+				if !is_infinite(lo) {
+					bounds.push(rel(if *lo_closed { Relation::Ge } else { Relation::Gt }, lo));
+				}
+
+				if !is_infinite(hi) {
+					bounds.push(rel(if *hi_closed { Relation::Le } else { Relation::Lt }, hi));
+				}
+
+				match bounds.len() {
+					1 => bounds.pop().unwrap(),
+					_ => Expr::And(bounds),
+				}
+			}
+			Self::Union(sets) => Expr::Or(sets.iter().map(|set| set.condition(var)).collect::<Option<_>>()?),
+		};
+
+		Some(condition)
+	}
+
+	/// Checks whether an expression is an element of this set, using the assumptions of the context.
+	/// Returns [`None`] if this cannot be decided.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::context::Context;
+	/// use abacas::expr::{Expr, Symbol};
+	/// use abacas::set::Set;
+	///
+	/// let ctx = &mut Context::new();
+	/// let n = Symbol::new("n").unwrap();
+	/// let pi = || Expr::Poly(Symbol::new("pi").unwrap(), "x".parse().unwrap());
+	///
+	/// let set = Set::interval(Expr::zero(), pi(), true, false);
+	/// assert_eq!(set.contains(&Expr::Num(3.into()), ctx).unwrap(), Some(true));
+	/// assert_eq!(set.contains(&pi(), ctx).unwrap(), Some(false));
+	///
+	/// let multiples = Set::Image {
+	///     expr: Expr::Poly(n.clone(), "x".parse().unwrap()) * pi(),
+	///     var: n,
+	///     base: Set::Integers.into(),
+	/// };
+	/// assert_eq!(multiples.contains(&(Expr::Num((-2).into()) * pi()), ctx).unwrap(), Some(true));
+	/// assert_eq!(multiples.to_string(), "{n * pi | n ∈ ℤ}");
+	/// ```
+	pub fn contains(&self, elem: &Expr, ctx: &mut Context) -> Result<Option<bool>, SimplifyError> {
+		let result = match self {
+			Self::Complement(universe, set) => {
+				and([universe.contains(elem, ctx)?, set.contains(elem, ctx)?.map(|x| !x)])
+			}
+			Self::Condition { var, condition, base } => {
+				let holds = condition.substitute(var, elem).simplify_builtin(ctx)?.as_bool();
+				and([base.contains(elem, ctx)?, holds])
+			}
+			Self::Empty => Some(false),
+			Self::Finite(elems) => {
+				let equal = elems
+					.iter()
+					.map(|other| Ok(compare(elem, other, ctx)?.map(Ordering::is_eq)))
+					.collect::<Result<Vec<_>, SimplifyError>>()?;
+
+				or(equal)
+			}
+			Self::Image { expr, var, base } => match preimage(expr, var, elem, ctx)? {
+				Some(preimage) => base.contains(&preimage, ctx)?,
+				None => None,
+			},
+			Self::Integers => elem.is_integer(ctx),
+			Self::Intersection(sets) => and(sets
+				.iter()
+				.map(|set| set.contains(elem, ctx))
+				.try_collect::<_, Vec<_>, _>()?),
+			Self::Interval {
+				lo,
+				hi,
+				lo_closed,
+				hi_closed,
+			} => {
+				// Only real numbers lie between infinite endpoints
+				let above = match is_infinite(lo) {
+					true => elem.is_real(ctx),
+					false => compare(elem, lo, ctx)?.map(|ord| ord.is_gt() || *lo_closed && ord.is_eq()),
+				};
+
+				let below = match is_infinite(hi) {
+					true => elem.is_real(ctx),
+					false => compare(elem, hi, ctx)?.map(|ord| ord.is_lt() || *hi_closed && ord.is_eq()),
+				};
+
+				and([above, below])
+			}
+			Self::Union(sets) => or(sets
+				.iter()
+				.map(|set| set.contains(elem, ctx))
+				.try_collect::<_, Vec<_>, _>()?),
+		};
+
+		Ok(result)
+	}
+
+	/// Returns the complement of this set in a universe, which are the elements of the universe that are not in this
+	/// set.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::context::Context;
+	/// use abacas::expr::Expr;
+	/// use abacas::set::Set;
+	///
+	/// let ctx = &mut Context::new();
+	///
+	/// let set = Set::interval(Expr::Num((-1).into()), Expr::Num(1.into()), true, false);
+	/// assert_eq!(set.complement(&Set::reals(), ctx).unwrap().to_string(), "(-inf, -1) ∪ [1, inf)");
+	///
+	/// let set = Set::finite([Expr::Num(0.into())]);
+	/// let universe = Set::finite([Expr::Num(0.into()), Expr::Num(1.into())]);
+	/// assert_eq!(set.complement(&universe, ctx).unwrap().to_string(), "{1}");
+	/// ```
+	pub fn complement(&self, universe: &Self, ctx: &mut Context) -> Result<Self, SimplifyError> {
+		if *self == Self::Empty {
+			return Ok(universe.clone());
+		}
+
+		if let Some((points, charts)) = chart(&[universe, self], ctx)? {
+			let atoms: Vec<_> = charts[0]
+				.iter()
+				.zip(&charts[1])
+				.map(|(&lhs, &rhs)| lhs && !rhs)
+				.collect();
+			return Ok(Self::from_chart(points, &atoms));
+		}
+
+		// Elements of finite sets can be checked one by one
+		if let Self::Finite(elems) = universe
+			&& let Some(elems) = filter(elems, ctx, |elem, ctx| Ok(self.contains(elem, ctx)?.map(|x| !x)))?
+		{
+			return Ok(Self::finite(elems));
+		}
+
+		Ok(Self::Complement(universe.clone().into(), self.clone().into()))
+	}
+
+	/// Creates a finite set, removing duplicate elements. Numbers are sorted in ascending order before all other
+	/// elements.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::expr::Expr;
+	/// use abacas::set::Set;
+	///
+	/// let set = Set::finite([Expr::Num(2.into()), Expr::Num(1.into()), Expr::Num(2.into())]);
+	/// assert_eq!(set.to_string(), "{1, 2}");
+	/// assert_eq!(Set::finite([]), Set::Empty);
+	/// ```
+	pub fn finite(elems: impl IntoIterator<Item = Expr>) -> Self {
+		let (mut nums, others): (Vec<_>, Vec<_>) = elems.into_iter().unique().partition(Expr::is_num);
+		nums.sort_by(|lhs, rhs| match (lhs, rhs) {
+			(Expr::Num(lhs), Expr::Num(rhs)) => lhs.cmp(rhs),
+			_ => unreachable!(),
+		});
+
+		match nums.len() + others.len() {
+			0 => Self::Empty,
+			_ => Self::Finite(nums.into_iter().chain(others).collect()),
+		}
+	}
+
+	/// Creates the intersection of multiple sets, evaluating it where possible.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::context::Context;
+	/// use abacas::expr::Expr;
+	/// use abacas::set::Set;
+	///
+	/// let ctx = &mut Context::new();
+	/// let num = |num: i32| Expr::Num(num.into());
+	///
+	/// let set = Set::intersection([
+	///     Set::interval(num(0), num(2), true, true),
+	///     Set::interval(num(1), num(3), false, true),
+	/// ], ctx).unwrap();
+	/// assert_eq!(set.to_string(), "(1, 2]");
+	///
+	/// let set = Set::intersection([Set::finite([num(-1), num(1), num(2)]), Set::Integers], ctx).unwrap();
+	/// assert_eq!(set.to_string(), "{-1, 1, 2}");
+	/// ```
+	pub fn intersection(sets: impl IntoIterator<Item = Self>, ctx: &mut Context) -> Result<Self, SimplifyError> {
+		let mut sets: Vec<_> = sets
+			.into_iter()
+			.flat_map(|set| match set {
+				Self::Intersection(sets) => sets,
+				set => vec![set],
+			})
+			.unique()
+			.collect();
+
+		if sets.contains(&Self::Empty) {
+			return Ok(Self::Empty);
+		}
+
+		if sets.len() == 1 {
+			return Ok(sets.pop().unwrap());
+		}
+
+		if let Some((points, charts)) = chart(&sets.iter().collect_vec(), ctx)? {
+			let atoms: Vec<_> = (0..points.len() * 2 + 1)
+				.map(|index| charts.iter().all(|atoms| atoms[index]))
+				.collect();
+
+			return Ok(Self::from_chart(points, &atoms));
+		}
+
+		// Elements of finite sets can be checked one by one
+		if let Some(Self::Finite(elems)) = sets.iter().find(|set| matches!(set, Self::Finite(_)))
+			&& let Some(elems) = filter(elems, ctx, |elem, ctx| {
+				let contained = sets
+					.iter()
+					.map(|set| set.contains(elem, ctx))
+					.try_collect::<_, Vec<_>, _>()?;
+				Ok(and(contained))
+			})? {
+			return Ok(Self::finite(elems));
+		}
+
+		Ok(Self::Intersection(sets))
+	}
+
+	/// Creates an interval between two endpoints, which are included if the interval is closed at that end.
+	/// Infinite endpoints are always excluded.
+	pub fn interval(lo: Expr, hi: Expr, lo_closed: bool, hi_closed: bool) -> Self {
+		Self::Interval {
+			lo_closed: lo_closed && !is_infinite(&lo),
+			hi_closed: hi_closed && !is_infinite(&hi),
+			lo,
+			hi,
+		}
+	}
+
+	/// Creates the set of all real numbers, which is the interval between negative and positive infinity.
+	pub fn reals() -> Self {
+		Self::interval(infinity(-1), infinity(1), false, false)
+	}
+
+	/// Creates the union of multiple sets, evaluating it where possible.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::context::Context;
+	/// use abacas::expr::Expr;
+	/// use abacas::set::Set;
+	///
+	/// let ctx = &mut Context::new();
+	/// let num = |num: i32| Expr::Num(num.into());
+	///
+	/// let set = Set::union([
+	///     Set::interval(num(0), num(1), false, false),
+	///     Set::finite([num(1), num(3)]),
+	///     Set::interval(num(1), num(2), false, true),
+	/// ], ctx).unwrap();
+	/// assert_eq!(set.to_string(), "(0, 2] ∪ {3}");
+	/// ```
+	pub fn union(sets: impl IntoIterator<Item = Self>, ctx: &mut Context) -> Result<Self, SimplifyError> {
+		let mut sets: Vec<_> = sets
+			.into_iter()
+			.flat_map(|set| match set {
+				Self::Empty => Vec::new(),
+				Self::Union(sets) => sets,
+				set => vec![set],
+			})
+			.unique()
+			.collect();
+
+		match sets.len() {
+			0 => return Ok(Self::Empty),
+			1 => return Ok(sets.pop().unwrap()),
+			_ => {}
+		}
+
+		if let Some((points, charts)) = chart(&sets.iter().collect_vec(), ctx)? {
+			let atoms: Vec<_> = (0..points.len() * 2 + 1)
+				.map(|index| charts.iter().any(|atoms| atoms[index]))
+				.collect();
+
+			return Ok(Self::from_chart(points, &atoms));
+		}
+
+		Ok(Self::Union(sets))
+	}
+
+	/// Creates a set from a chart of the real line, which is split by the given points in ascending order. The atoms
+	/// say whether the open gaps and the points belong to the set, alternating and starting with the gap below the
+	/// first point, so there is one more gap than there are points.
+	pub(crate) fn from_chart(points: Vec<Expr>, atoms: &[bool]) -> Self {
+		let mut parts = Vec::new();
+		let mut start = atoms[0].then(|| (infinity(-1), false));
+
+		for (index, point) in points.into_iter().enumerate() {
+			let (at_point, after) = (atoms[2 * index + 1], atoms[2 * index + 2]);
+
+			start = match (start, at_point, after) {
+				(Some((lo, closed)), false, after) => {
+					parts.push(Self::interval(lo, point.clone(), closed, false));
+					after.then_some((point, false))
+				}
+				(Some((lo, closed)), true, false) => {
+					parts.push(Self::interval(lo, point, closed, true));
+					None
+				}
+				(Some(start), true, true) => Some(start),
+				(None, true, false) => {
+					// Consecutive isolated points form a single finite set
+					match parts.last_mut() {
+						Some(Self::Finite(elems)) => elems.push(point),
+						_ => parts.push(Self::Finite(vec![point])),
+					}
+					None
+				}
+				(None, at_point, true) => Some((point, at_point)),
+				(None, false, false) => None,
+			};
+		}
+
+		if let Some((lo, closed)) = start {
+			parts.push(Self::interval(lo, infinity(1), closed, false));
+		}
+
+		match parts.len() {
+			0 => Self::Empty,
+			1 => parts.pop().unwrap(),
+			_ => Self::Union(parts),
+		}
+	}
+
+	/// Internal helper to check whether the set is a finite set, an interval or an evaluated union of those.
+	fn is_simple(&self) -> bool {
+		match self {
+			Self::Empty | Self::Finite(_) | Self::Interval { .. } => true,
+			Self::Union(sets) => sets.iter().all(Self::is_simple),
+			_ => false,
+		}
+	}
+}
+
+impl fmt::Display for Set {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		// Combinations of sets are parenthesized inside other combinations
+		let nested = |set: &Self| match set {
+			Self::Complement(..) | Self::Intersection(_) | Self::Union(_) => format!("({set})"),
+			set => set.to_string(),
+		};
+
+		match self {
+			Self::Complement(universe, set) => write!(f, "{} \\ {}", nested(universe), nested(set)),
+			Self::Condition { var, condition, base } => write!(f, "{{{var} ∈ {base} | {condition}}}"),
+			Self::Empty => write!(f, "∅"),
+			Self::Finite(elems) => write!(f, "{{{}}}", elems.iter().format(", ")),
+			Self::Image { expr, var, base } => write!(f, "{{{expr} | {var} ∈ {base}}}"),
+			Self::Integers => write!(f, "ℤ"),
+			Self::Intersection(sets) => write!(f, "{}", sets.iter().map(nested).format(" ∩ ")),
+			Self::Interval { lo, hi, .. } if is_infinite(lo) && is_infinite(hi) => write!(f, "ℝ"),
+			Self::Interval {
+				lo,
+				hi,
+				lo_closed,
+				hi_closed,
+			} => {
+				let open = if *lo_closed { '[' } else { '(' };
+				let close = if *hi_closed { ']' } else { ')' };
+
+				write!(f, "{open}{lo}, {hi}{close}")
+			}
+			Self::Union(sets) => write!(f, "{}", sets.iter().map(nested).format(" ∪ ")),
+		}
+	}
+}
+
+/// Internal helper to combine truth values with a conjunction, which is [`None`] if undecided.
+fn and(values: impl IntoIterator<Item = Option<bool>>) -> Option<bool> {
+	let mut result = Some(true);
+
+	for value in values {
+		match value {
+			Some(false) => return Some(false),
+			Some(true) => {}
+			None => result = None,
+		}
+	}
+
+	result
+}
+
+/// Internal helper to split the real line at all endpoints and elements of simple sets, returning the sorted points
+/// and the atoms of every set as described in [`Set::from_chart`]. Returns [`None`] if a set is not simple or the
+/// points cannot be ordered.
+#[allow(clippy::type_complexity)]
+fn chart(sets: &[&Set], ctx: &mut Context) -> Result<Option<(Vec<Expr>, Vec<Vec<bool>>)>, SimplifyError> {
+	fn endpoints(set: &Set) -> Vec<&Expr> {
+		match set {
+			Set::Finite(elems) => elems.iter().collect(),
+			Set::Interval { lo, hi, .. } => [lo, hi].into_iter().filter(|end| !is_infinite(end)).collect(),
+			Set::Union(sets) => sets.iter().flat_map(endpoints).collect(),
+			_ => Vec::new(),
+		}
+	}
+
+	if !sets.iter().all(|set| set.is_simple()) {
+		return Ok(None);
+	}
+
+	// Insert every endpoint at its place, skipping those equal to a previous one
+	let mut points: Vec<Expr> = Vec::new();
+
+	for end in sets.iter().flat_map(|set| endpoints(set)) {
+		match position(&points, end, ctx)? {
+			Some(Ok(_)) => {}
+			Some(Err(index)) => points.insert(index, end.clone()),
+			None => return Ok(None),
+		}
+	}
+
+	let mut charts = Vec::new();
+
+	for set in sets {
+		let mut atoms = vec![false; points.len() * 2 + 1];
+
+		if !mark(set, &points, &mut atoms, ctx)? {
+			return Ok(None);
+		}
+
+		charts.push(atoms);
+	}
+
+	Ok(Some((points, charts)))
+}
+
+/// Internal helper to compare two real expressions, deciding with their sign of their difference or numerically.
+/// Returns [`None`] if they cannot be compared.
+fn compare(lhs: &Expr, rhs: &Expr, ctx: &mut Context) -> Result<Option<Ordering>, SimplifyError> {
+	if lhs == rhs {
+		return Ok(Some(Ordering::Equal));
+	}
+
+	match (infinity_sign(lhs), infinity_sign(rhs)) {
+		(Some(lhs), Some(rhs)) => return Ok(Some(lhs.cmp(&rhs))),
+		(Some(lhs), None) => return Ok(Some(lhs)),
+		(None, Some(rhs)) => return Ok(Some(rhs.reverse())),
+		(None, None) => {}
+	}
+
+	let diff = (lhs.clone() - rhs.clone()).simplify_builtin(ctx)?;
+
+	let sign = match (diff.is_positive(ctx), diff.is_negative(ctx), diff.is_nonzero(ctx)) {
+		(Some(true), ..) => Some(Ordering::Greater),
+		(_, Some(true), _) => Some(Ordering::Less),
+		(.., Some(false)) => Some(Ordering::Equal),
+		_ => diff.eval_interval(&HashMap::new(), 64).and_then(|value| value.sign()),
+	};
+
+	Ok(sign)
+}
+
+/// Internal helper to keep the elements for which a predicate holds, returning [`None`] if it is undecided for any.
+fn filter(
+	elems: &[Expr],
+	ctx: &mut Context,
+	mut predicate: impl FnMut(&Expr, &mut Context) -> Result<Option<bool>, SimplifyError>,
+) -> Result<Option<Vec<Expr>>, SimplifyError> {
+	let mut kept = Vec::new();
+
+	for elem in elems {
+		match predicate(elem, ctx)? {
+			Some(true) => kept.push(elem.clone()),
+			Some(false) => {}
+			None => return Ok(None),
+		}
+	}
+
+	Ok(Some(kept))
+}
+
+/// Internal helper to create positive or negative infinity as an expression, using the symbol `inf` like limits.
+fn infinity(sign: i32) -> Expr {
+	Expr::Poly(Symbol::new("inf").unwrap(), Monomial::linear(sign).into())
+}
+
+/// Internal helper to get the sign of an infinite endpoint, or [`None`] for finite ones.
+fn infinity_sign(expr: &Expr) -> Option<Ordering> {
+	match expr {
+		Expr::Poly(sym, poly)
+			if sym.name() == "inf"
+				&& let Some(mono) = poly.get(&1.into()) =>
+		{
+			Some(mono.coeff.cmp(&Number::zero()))
+		}
+		_ => None,
+	}
+}
+
+/// Internal helper to check whether an endpoint is positive or negative infinity.
+fn is_infinite(expr: &Expr) -> bool {
+	infinity_sign(expr).is_some()
+}
+
+/// Internal helper to mark the atoms of a simple set on a chart. Returns `false` if an endpoint is not on the chart.
+fn mark(set: &Set, points: &[Expr], atoms: &mut [bool], ctx: &mut Context) -> Result<bool, SimplifyError> {
+	let index = |end: &Expr, ctx: &mut Context| Ok(position(points, end, ctx)?.and_then(Result::ok));
+
+	match set {
+		Set::Finite(elems) => {
+			for elem in elems {
+				let Some(index) = index(elem, ctx)? else {
+					return Ok(false);
+				};
+
+				atoms[2 * index + 1] = true;
+			}
+		}
+		Set::Interval {
+			lo,
+			hi,
+			lo_closed,
+			hi_closed,
+		} => {
+			// Points are at odd atoms, with the gaps around them at the neighbouring even ones
+			let start = match infinity_sign(lo) {
+				Some(_) => 0,
+				None => match index(lo, ctx)? {
+					Some(index) => 2 * index + if *lo_closed { 1 } else { 2 },
+					None => return Ok(false),
+				},
+			};
+
+			let end = match infinity_sign(hi) {
+				Some(_) => atoms.len() - 1,
+				None => match index(hi, ctx)? {
+					Some(index) => 2 * index + if *hi_closed { 1 } else { 0 },
+					None => return Ok(false),
+				},
+			};
+
+			for atom in atoms.iter_mut().take(end + 1).skip(start) {
+				*atom = true;
+			}
+		}
+		Set::Union(sets) => {
+			for set in sets {
+				if !mark(set, points, atoms, ctx)? {
+					return Ok(false);
+				}
+			}
+		}
+		_ => {}
+	}
+
+	Ok(true)
+}
+
+/// Internal helper to combine truth values with a disjunction, which is [`None`] if undecided.
+fn or(values: impl IntoIterator<Item = Option<bool>>) -> Option<bool> {
+	and(values.into_iter().map(|value| value.map(|x| !x))).map(|x| !x)
+}
+
+/// Internal helper to find a point in sorted points, like [`slice::binary_search`]. Returns [`None`] if it cannot be
+/// compared with some of them.
+fn position(points: &[Expr], point: &Expr, ctx: &mut Context) -> Result<Option<Result<usize, usize>>, SimplifyError> {
+	for (index, other) in points.iter().enumerate() {
+		match compare(point, other, ctx)? {
+			Some(Ordering::Equal) => return Ok(Some(Ok(index))),
+			Some(Ordering::Less) => return Ok(Some(Err(index))),
+			Some(Ordering::Greater) => {}
+			None => return Ok(None),
+		}
+	}
+
+	Ok(Some(Err(points.len())))
+}
+
+/// Internal helper to find the element of the base set of an image that is mapped to a value, which works for
+/// expressions linear in the variable. Returns [`None`] for other expressions.
+fn preimage(expr: &Expr, var: &Symbol, value: &Expr, ctx: &mut Context) -> Result<Option<Expr>, SimplifyError> {
+	let slope = expr.derivative(var).simplify_builtin(ctx)?;
+
+	if slope.has_symbol(var) || slope.is_nonzero(ctx) != Some(true) {
+		return Ok(None);
+	}
+
+	let offset = expr.substitute(var, &Expr::zero());
+	let preimage = ((value.clone() - offset) / slope).simplify_builtin(ctx)?;
+
+	Ok(Some(preimage))
+}
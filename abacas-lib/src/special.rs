@@ -0,0 +1,246 @@
+//! Orthogonal and other special families of polynomials.
+//!
+//! The classical orthogonal polynomials are built exactly with their three-term recurrences. Jacobi polynomials use
+//! their explicit sum instead, since the recurrence divides by zero for some parameters. Bernoulli polynomials are
+//! built from the Bernoulli numbers, and cyclotomic polynomials from the factors `x^d - 1` for the divisors `d` of `n`.
+
+use crate::combinatorics::{self, bernoulli_numbers};
+use crate::monomial::Monomial;
+use crate::number::Number;
+use crate::polynomial::Polynomial;
+
+// Constructors
+impl Polynomial {
+	/// Returns the Bernoulli polynomial `B_n(x) = sum(binomial(n, k) B(k) x^(n - k), k = 0..=n)`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::polynomial::Polynomial;
+	///
+	/// assert_eq!(Polynomial::bernoulli(1).to_string(), "x - 0.5");
+	/// assert_eq!(Polynomial::bernoulli(3).to_string(), "x^3 - 1.5x^2 + 0.5x");
+	/// ```
+	pub fn bernoulli(n: u32) -> Self {
+		let monomials = bernoulli_numbers(n)
+			.into_iter()
+			.enumerate()
+			.filter(|(_, number)| !number.is_zero())
+			.map(|(k, number)| {
+				let binomial = combinatorics::binomial(&n.into(), &k.into()).unwrap();
+				Monomial::new(binomial * &number, n - k as u32)
+			});
+
+		Self::new(monomials)
+	}
+
+	/// Returns the Chebyshev polynomial of the first kind `T_n`, with `T_(n + 1) = 2x T_n - T_(n - 1)`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::polynomial::Polynomial;
+	///
+	/// assert_eq!(Polynomial::chebyshev_t(0).to_string(), "1");
+	/// assert_eq!(Polynomial::chebyshev_t(4).to_string(), "8x^4 - 8x^2 + 1");
+	/// ```
+	pub fn chebyshev_t(n: u32) -> Self {
+		recurrence(n, linear(1, 0), |_| (linear(2, 0), Number::one()))
+	}
+
+	/// Returns the Chebyshev polynomial of the second kind `U_n`, with `U_(n + 1) = 2x U_n - U_(n - 1)`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::polynomial::Polynomial;
+	///
+	/// assert_eq!(Polynomial::chebyshev_u(1).to_string(), "2x");
+	/// assert_eq!(Polynomial::chebyshev_u(4).to_string(), "16x^4 - 12x^2 + 1");
+	/// ```
+	pub fn chebyshev_u(n: u32) -> Self {
+		recurrence(n, linear(2, 0), |_| (linear(2, 0), Number::one()))
+	}
+
+	/// Returns the cyclotomic polynomial `Φ_n`, whose roots are the primitive `n`-th roots of unity. Returns [`None`] if
+	/// `n` is zero.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::polynomial::Polynomial;
+	///
+	/// assert_eq!(Polynomial::cyclotomic(1).unwrap().to_string(), "x - 1");
+	/// assert_eq!(Polynomial::cyclotomic(12).unwrap().to_string(), "x^4 - x^2 + 1");
+	/// assert_eq!(Polynomial::cyclotomic(0), None);
+	/// ```
+	pub fn cyclotomic(n: u32) -> Option<Self> {
+		if n == 0 {
+			return None;
+		}
+
+		// `Φ_n = prod((x^d - 1)^μ(n / d), d | n)` with the Möbius function `μ`
+		let mut numer = Self::from(1);
+		let mut denom = Self::from(1);
+
+		for d in (1..=n).filter(|&d| n.is_multiple_of(d)) {
+			let factor = Self::from(Monomial::new(1, d)) - 1;
+
+			match mobius(n / d) {
+				1 => numer *= &factor,
+				-1 => denom *= &factor,
+				_ => (),
+			}
+		}
+
+		Some(numer / &denom)
+	}
+
+	/// Returns the physicists' Hermite polynomial `H_n`, with `H_(n + 1) = 2x H_n - 2n H_(n - 1)`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::polynomial::Polynomial;
+	///
+	/// assert_eq!(Polynomial::hermite(1).to_string(), "2x");
+	/// assert_eq!(Polynomial::hermite(4).to_string(), "16x^4 - 48x^2 + 12");
+	/// ```
+	pub fn hermite(n: u32) -> Self {
+		recurrence(n, linear(2, 0), |k| (linear(2, 0), Number::from(2 * u64::from(k))))
+	}
+
+	/// Returns the probabilists' Hermite polynomial `He_n`, with `He_(n + 1) = x He_n - n He_(n - 1)`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::polynomial::Polynomial;
+	///
+	/// assert_eq!(Polynomial::hermite_prob(1).to_string(), "x");
+	/// assert_eq!(Polynomial::hermite_prob(4).to_string(), "x^4 - 6x^2 + 3");
+	/// ```
+	pub fn hermite_prob(n: u32) -> Self {
+		recurrence(n, linear(1, 0), |k| (linear(1, 0), Number::from(k)))
+	}
+
+	/// Returns the Jacobi polynomial `P_n^(α, β)` with the given parameters, which is
+	/// `sum(binomial(n + α, n - s) binomial(n + β, s) ((x - 1) / 2)^s ((x + 1) / 2)^(n - s), s = 0..=n)`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::number::Number;
+	/// use abacas::polynomial::Polynomial;
+	///
+	/// let poly = Polynomial::jacobi(1, &1.into(), &2.into());
+	/// assert_eq!(poly.to_string(), "2.5x - 0.5");
+	///
+	/// // `α = β = 0` gives the Legendre polynomials
+	/// let poly = Polynomial::jacobi(3, &Number::zero(), &Number::zero());
+	/// assert_eq!(poly, Polynomial::legendre(3));
+	/// ```
+	pub fn jacobi(n: u32, alpha: &Number, beta: &Number) -> Self {
+		let minus = linear(1, -1) / 2;
+		let plus = linear(1, 1) / 2;
+		let mut poly = Self::ZERO;
+
+		for s in 0..=n {
+			let lhs = combinatorics::binomial(&(alpha.clone() + n), &(n - s).into()).unwrap();
+			let rhs = combinatorics::binomial(&(beta.clone() + n), &s.into()).unwrap();
+
+			let mut term = Self::from(lhs * &rhs);
+			(0..s).for_each(|_| term *= &minus);
+			(s..n).for_each(|_| term *= &plus);
+
+			poly += term;
+		}
+
+		poly
+	}
+
+	/// Returns the Laguerre polynomial `L_n`, with `(n + 1) L_(n + 1) = (2n + 1 - x) L_n - n L_(n - 1)`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::polynomial::Polynomial;
+	///
+	/// assert_eq!(Polynomial::laguerre(1).to_string(), "-x + 1");
+	/// assert_eq!(Polynomial::laguerre(2).to_string(), "0.5x^2 - 2x + 1");
+	/// ```
+	pub fn laguerre(n: u32) -> Self {
+		recurrence(n, linear(-1, 1), |k| {
+			let scale = Number::from(k + 1).recip();
+			(linear(-1, 2 * u64::from(k) + 1) * &scale, scale * k)
+		})
+	}
+
+	/// Returns the Legendre polynomial `P_n`, with `(n + 1) P_(n + 1) = (2n + 1) x P_n - n P_(n - 1)`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::polynomial::Polynomial;
+	///
+	/// assert_eq!(Polynomial::legendre(1).to_string(), "x");
+	/// assert_eq!(Polynomial::legendre(2).to_string(), "1.5x^2 - 0.5");
+	/// ```
+	pub fn legendre(n: u32) -> Self {
+		recurrence(n, linear(1, 0), |k| {
+			let scale = Number::from(k + 1).recip();
+			(linear(2 * u64::from(k) + 1, 0) * &scale, scale * k)
+		})
+	}
+}
+
+/// Internal helper to create the linear polynomial `a x + b`.
+fn linear(a: impl Into<Number>, b: impl Into<Number>) -> Polynomial {
+	Polynomial::from(Monomial::new(a, 1)) + b.into()
+}
+
+/// Internal helper to get the Möbius function `μ(n)`, which is zero if `n` has a repeated prime factor and otherwise
+/// `1` or `-1` for an even or odd amount of prime factors.
+fn mobius(mut n: u32) -> i32 {
+	let mut result = 1;
+	let mut prime = 2;
+
+	// Compared through a division, since the square overflows for large `n`
+	while prime <= n / prime {
+		if n.is_multiple_of(prime) {
+			n /= prime;
+
+			if n.is_multiple_of(prime) {
+				return 0;
+			}
+
+			result = -result;
+		}
+
+		prime += 1;
+	}
+
+	match n {
+		1 => result,
+		_ => -result,
+	}
+}
+
+/// Internal helper to build the `n`-th polynomial of a family with `p_0 = 1`, the given `p_1` and the three-term
+/// recurrence `p_(k + 1) = a_k p_k - c_k p_(k - 1)`, where `step(k)` returns the linear polynomial `a_k` and the number
+/// `c_k`.
+fn recurrence(n: u32, first: Polynomial, step: impl Fn(u32) -> (Polynomial, Number)) -> Polynomial {
+	let (mut prev, mut curr) = (Polynomial::from(1), first);
+
+	if n == 0 {
+		return prev;
+	}
+
+	for k in 1..n {
+		let (a, c) = step(k);
+		let next = a * &curr - prev * &Polynomial::from(c);
+		(prev, curr) = (curr, next);
+	}
+
+	curr
+}
@@ -2,8 +2,27 @@
 
 use std::collections::HashMap;
 
-use crate::context::Context;
+use rug::ops::Pow;
+
+use crate::context::{Context, Pass};
+use crate::error::SimplifyError;
 use crate::expr::{Expr, Symbol};
+use crate::groebner::{Algorithm, GroebnerBasis};
+use crate::limit::{Direction, Point};
+use crate::matrix::Matrix;
+use crate::monomial::Monomial;
+use crate::multivariate::{MonomialOrder, MultiPolynomial};
+use crate::number::Number;
+use crate::pattern::Rule;
+use crate::polynomial::Polynomial;
+use crate::relation::Relation;
+use crate::{
+	combinatorics, groebner as groebners, limit as limits, linsolve as linsolves, log as logs, summation, trig,
+};
+
+/// The largest degree `n` for which a polynomial family like `legendre(n, x)` is evaluated, since building the
+/// polynomial takes at least quadratic time in `n`.
+const MAX_FAMILY_DEGREE: u32 = 1000;
 
 /// StdLib struct containing all the global functions.
 #[derive(Clone, Debug, Default)]
@@ -12,55 +31,130 @@ pub struct StdLib(pub HashMap<Symbol, StdLibFunction>);
 impl StdLib {
 	/// Creates a new copy of the StdLib.
 	pub fn new() -> Self {
-		let mut functions = HashMap::new();
-
-		functions.insert(
-			Symbol::new("echo").unwrap(),
-			StdLibFunction {
-				name: Symbol::new("echo").unwrap(),
-				execute: echo,
-			},
-		);
-
-		functions.insert(
-			Symbol::new("round").unwrap(),
-			StdLibFunction {
-				name: Symbol::new("round").unwrap(),
-				execute: round,
-			},
-		);
-
-		functions.insert(
-			Symbol::new("ceil").unwrap(),
-			StdLibFunction {
-				name: Symbol::new("ceil").unwrap(),
-				execute: ceil,
-			},
-		);
-
-		functions.insert(
-			Symbol::new("floor").unwrap(),
-			StdLibFunction {
-				name: Symbol::new("floor").unwrap(),
-				execute: floor,
-			},
-		);
-
-		Self(functions)
+		let functions: &[(&str, StdLibFn)] = &[
+			("echo", echo),
+			("round", round),
+			("ceil", ceil),
+			("floor", floor),
+			("abs", abs),
+			("sign", sign),
+			("min", min),
+			("max", max),
+			("piecewise", piecewise),
+			("factorial", factorial),
+			("factorial2", factorial2),
+			("binomial", binomial),
+			("multinomial", multinomial),
+			("fib", fib),
+			("lucas", lucas),
+			("bernoulli", bernoulli),
+			("stirling1", stirling1),
+			("stirling2", stirling2),
+			("catalan", catalan),
+			("partitions", partitions),
+			("exp", exp),
+			("ln", ln),
+			("log", log),
+			("sqrt", sqrt),
+			("sin", sin),
+			("cos", cos),
+			("tan", tan),
+			("atan", atan),
+			("sum", sum),
+			("product", product),
+			("diff", diff),
+			("integrate", integrate),
+			("limit", limit),
+			("series", series),
+			("replace_all", replace_all),
+			("replace_repeated", replace_repeated),
+			("trigsimp", trigsimp),
+			("expand_trig", expand_trig),
+			("trig_to_exp", trig_to_exp),
+			("exp_to_trig", exp_to_trig),
+			("expand_log", expand_log),
+			("combine_log", combine_log),
+			("solve_inequality", solve_inequality),
+			("identity", identity),
+			("transpose", transpose),
+			("trace", trace),
+			("det", det),
+			("inverse", inverse),
+			("rank", rank),
+			("rref", rref),
+			("nullspace", nullspace),
+			("charpoly", charpoly),
+			("eigenvals", eigenvals),
+			("linsolve", linsolve),
+			("groebner", groebner),
+			("solve", solve),
+			("interpolate", interpolate),
+			("interpolate_hermite", interpolate_hermite),
+			("fit", fit),
+			("chebyshevt", chebyshevt),
+			("chebyshevu", chebyshevu),
+			("legendre", legendre),
+			("hermiteh", hermiteh),
+			("hermitehe", hermitehe),
+			("laguerre", laguerre),
+			("jacobi", jacobi),
+			("cyclotomic", cyclotomic),
+		];
+
+		let functions = functions.iter().map(|&(name, execute)| {
+			let name = Symbol::new(name).unwrap();
+			(name.clone(), StdLibFunction { name, execute })
+		});
+
+		Self(functions.collect())
+	}
+
+	/// Evaluates all calls to functions of the StdLib inside an expression, starting with the innermost calls.
+	pub fn evaluate(&self, expr: Expr, ctx: &mut Context) -> Expr {
+		match expr {
+			Expr::Add(exprs) => Expr::Add(exprs.into_iter().map(|expr| self.evaluate(expr, ctx)).collect()),
+			Expr::And(exprs) => Expr::And(exprs.into_iter().map(|expr| self.evaluate(expr, ctx)).collect()),
+			Expr::Fun(name, args) => {
+				let args = args.into_iter().map(|arg| self.evaluate(arg, ctx)).collect();
+
+				match self.0.get(&name) {
+					Some(function) => (function.execute)(args, ctx),
+					None => Expr::Fun(name, args),
+				}
+			}
+			Expr::Matrix(matrix) => Expr::Matrix(matrix.map(|expr| self.evaluate(expr, ctx))),
+			Expr::Mul(exprs) => Expr::Mul(exprs.into_iter().map(|expr| self.evaluate(expr, ctx)).collect()),
+			Expr::Not(expr) => Expr::Not(self.evaluate(*expr, ctx).into()),
+			Expr::Num(_) | Expr::Poly(_, _) => expr,
+			Expr::Or(exprs) => Expr::Or(exprs.into_iter().map(|expr| self.evaluate(expr, ctx)).collect()),
+			Expr::Piecewise(branches) => Expr::Piecewise(
+				branches
+					.into_iter()
+					.map(|(value, condition)| (self.evaluate(value, ctx), self.evaluate(condition, ctx)))
+					.collect(),
+			),
+			Expr::Pow(base, exp) => Expr::Pow(self.evaluate(*base, ctx).into(), self.evaluate(*exp, ctx).into()),
+			Expr::Rel(rel, lhs, rhs) => {
+				Expr::Rel(rel, self.evaluate(*lhs, ctx).into(), self.evaluate(*rhs, ctx).into())
+			}
+		}
 	}
 }
 
+/// The signature of a StdLib function.
+pub type StdLibFn = fn(args: Vec<Expr>, ctx: &mut Context) -> Expr;
+
 /// A StdLib Function.
 #[derive(Clone, Debug)]
 pub struct StdLibFunction {
 	/// Name of the function.
 	pub name: Symbol,
 	/// The implementation of the function.
-	pub execute: fn(args: Vec<Expr>, ctx: &mut Context) -> Expr,
+	pub execute: StdLibFn,
 }
 
 //TODO: Add proper error mechanism
-//TODO: Add `abs`, `exp`, `ln`, `log10`, `log`, `sin`, `cos`, `tan`, `sqrt`, `nrt` methods
+//TODO: Add `log10`, `nrt` methods
 
 /// echo(a) -> a
 ///
@@ -76,43 +170,1024 @@ pub fn echo(args: Vec<Expr>, _: &mut Context) -> Expr {
 ///
 /// Returns the rounded number following Banker's Rounding.
 pub fn round(args: Vec<Expr>, ctx: &mut Context) -> Expr {
-	if args.len() != 1 {
-		panic!("expected one argument")
+	rounded("round", args, ctx, Number::round)
+}
+
+/// ceil(n) -> n
+///
+/// Returns the smallest integer more than or equal to n.
+pub fn ceil(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	rounded("ceil", args, ctx, Number::ceil)
+}
+
+/// floor(n) -> n
+///
+/// Returns the smallest integer less than or equal to n.
+pub fn floor(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	rounded("floor", args, ctx, Number::floor)
+}
+
+/// abs(x) -> |x|
+///
+/// Returns the absolute value, which is piecewise for arguments of unknown sign.
+pub fn abs(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	piecewise_of("abs", args, ctx, |x| {
+		vec![
+			(x.clone(), compare(Relation::Ge, x.clone(), Expr::zero())),
+			(-x, Expr::boolean(true)),
+		]
+	})
+}
+
+/// sign(x) -> -1, 0 or 1
+///
+/// Returns the sign, which is piecewise for arguments of unknown sign.
+pub fn sign(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	piecewise_of("sign", args, ctx, |x| {
+		vec![
+			(Expr::one(), compare(Relation::Gt, x.clone(), Expr::zero())),
+			(Expr::zero(), compare(Relation::Eq, x, Expr::zero())),
+			(Expr::neg_one(), Expr::boolean(true)),
+		]
+	})
+}
+
+/// min(a, b, ...) -> smallest argument
+///
+/// Returns the smallest argument, which is piecewise if the arguments cannot be compared.
+pub fn min(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	extremum("min", Relation::Le, args, ctx)
+}
+
+/// max(a, b, ...) -> largest argument
+///
+/// Returns the largest argument, which is piecewise if the arguments cannot be compared.
+pub fn max(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	extremum("max", Relation::Ge, args, ctx)
+}
+
+/// piecewise(a, p, b, q, ..., c) -> a if p, b if q, ..., c otherwise
+///
+/// Returns the value of the first branch whose condition holds. A final value without a condition always applies.
+pub fn piecewise(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	let mut args = args.into_iter();
+	let mut branches = Vec::new();
+
+	while let Some(value) = args.next() {
+		branches.push((value, args.next().unwrap_or_else(|| Expr::boolean(true))));
+	}
+
+	let expr = Expr::Piecewise(branches);
+	expr.clone().simplify(ctx).unwrap_or(expr)
+}
+
+/// factorial(n) -> n!
+///
+/// Returns the factorial of a non-negative integer.
+pub fn factorial(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	numeric("factorial", args, 1, ctx, |args| combinatorics::factorial(&args[0]))
+}
+
+/// factorial2(n) -> n!!
+///
+/// Returns the double factorial of a non-negative integer.
+pub fn factorial2(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	numeric("factorial2", args, 1, ctx, |args| {
+		combinatorics::double_factorial(&args[0])
+	})
+}
+
+/// binomial(n, k) -> n choose k
+///
+/// Returns the binomial coefficient. If `k` is a non-negative integer, `n` may be symbolic.
+pub fn binomial(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	let args = simplify_args(args, 2, ctx);
+
+	match args.as_slice() {
+		[Expr::Num(n), Expr::Num(k)] if let Some(result) = combinatorics::binomial(n, k) => Expr::Num(result),
+
+		// Expand into the falling factorial `n * (n - 1) * ... * (n - k + 1) / k!`
+		[n, Expr::Num(k)] if let Some(denom) = combinatorics::factorial(k) => {
+			let falling = (0..k.to_u32().unwrap()).map(|i| n.clone() - Expr::Num(i.into()));
+			let expr = Expr::Mul(falling.chain([Expr::Num(denom.recip())]).collect());
+
+			expr.simplify(ctx).unwrap_or_else(|_| unevaluated("binomial", args))
+		}
+
+		_ => unevaluated("binomial", args),
 	}
+}
+
+/// multinomial(k1, k2, ...) -> (k1 + k2 + ...)! / (k1! * k2! * ...)
+///
+/// Returns the multinomial coefficient of non-negative integers.
+pub fn multinomial(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	let count = args.len();
+	numeric("multinomial", args, count, ctx, combinatorics::multinomial)
+}
+
+/// fib(n) -> F(n)
+///
+/// Returns the n-th Fibonacci number.
+pub fn fib(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	numeric("fib", args, 1, ctx, |args| combinatorics::fibonacci(&args[0]))
+}
+
+/// lucas(n) -> L(n)
+///
+/// Returns the n-th Lucas number.
+pub fn lucas(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	numeric("lucas", args, 1, ctx, |args| combinatorics::lucas(&args[0]))
+}
+
+/// bernoulli(n) -> B(n)
+/// bernoulli(n, x) -> B_n(x)
+///
+/// Returns the n-th Bernoulli number, using the convention `B(1) = -1/2`, or the n-th Bernoulli polynomial at `x`.
+pub fn bernoulli(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	match args.len() {
+		2 => family("bernoulli", args, 2, ctx, |n, _| Some(Polynomial::bernoulli(n))),
+		_ => numeric("bernoulli", args, 1, ctx, |args| combinatorics::bernoulli(&args[0])),
+	}
+}
+
+/// stirling1(n, k) -> s(n, k)
+///
+/// Returns the signed Stirling number of the first kind.
+pub fn stirling1(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	numeric("stirling1", args, 2, ctx, |args| {
+		combinatorics::stirling1(&args[0], &args[1])
+	})
+}
+
+/// stirling2(n, k) -> S(n, k)
+///
+/// Returns the Stirling number of the second kind.
+pub fn stirling2(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	numeric("stirling2", args, 2, ctx, |args| {
+		combinatorics::stirling2(&args[0], &args[1])
+	})
+}
+
+/// catalan(n) -> C(n)
+///
+/// Returns the n-th Catalan number.
+pub fn catalan(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	numeric("catalan", args, 1, ctx, |args| combinatorics::catalan(&args[0]))
+}
+
+/// partitions(n) -> p(n)
+///
+/// Returns the number of integer partitions of n.
+pub fn partitions(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	numeric("partitions", args, 1, ctx, |args| combinatorics::partitions(&args[0]))
+}
+
+/// exp(x) -> e^x
+///
+/// Returns the exponential function, which is only evaluated exactly at zero.
+pub fn exp(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	numeric("exp", args, 1, ctx, |args| args[0].is_zero().then(Number::one))
+}
+
+/// ln(x) -> ln x
+///
+/// Returns the natural logarithm, which is only evaluated exactly at one.
+pub fn ln(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	numeric("ln", args, 1, ctx, |args| args[0].is_one().then(Number::zero))
+}
+
+/// log(x, b) -> log_b x
+///
+/// Returns the logarithm to a base, which is evaluated exactly if it is rational and written as a quotient of natural
+/// logarithms otherwise.
+pub fn log(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	let args = simplify_args(args, 2, ctx);
 
-	let Ok(Expr::Num(n)) = args.into_iter().next().unwrap().simplify(ctx) else {
-		unimplemented!()
+	let result = match args.as_slice() {
+		[Expr::Num(x), Expr::Num(base)] if let Some(result) = logs::log(x, base) => Some(Expr::Num(result)),
+		[x, base] => {
+			let quotient = ln(vec![x.clone()], ctx) * ln(vec![base.clone()], ctx).pow(Expr::neg_one());
+			quotient.simplify(ctx).ok()
+		}
+		_ => None,
 	};
 
-	Expr::Num(n.round())
+	result.unwrap_or_else(|| unevaluated("log", args))
 }
 
-/// ceil(n) -> n
+/// sqrt(x) -> x^(1/2)
 ///
-/// Returns the smallest integer more than or equal to n.
-pub fn ceil(args: Vec<Expr>, ctx: &mut Context) -> Expr {
-	if args.len() != 1 {
-		panic!("expected one argument")
+/// Returns the square root as a power, which is evaluated exactly for rational squares.
+pub fn sqrt(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	let [arg] = simplify_args(args, 1, ctx).try_into().unwrap();
+	let expr = arg.clone().pow(Expr::Num(Number::from(1) / 2));
+
+	expr.simplify(ctx).unwrap_or_else(|_| unevaluated("sqrt", vec![arg]))
+}
+
+/// sin(x) -> sin x
+///
+/// Returns the sine, which is evaluated exactly at rational multiples of `pi` with known values.
+pub fn sin(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	trigonometric("sin", args, ctx)
+}
+
+/// cos(x) -> cos x
+///
+/// Returns the cosine, which is evaluated exactly at rational multiples of `pi` with known values.
+pub fn cos(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	trigonometric("cos", args, ctx)
+}
+
+/// tan(x) -> tan x
+///
+/// Returns the tangent, which is evaluated exactly at rational multiples of `pi` with known values.
+pub fn tan(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	trigonometric("tan", args, ctx)
+}
+
+/// atan(x) -> atan x
+///
+/// Returns the arctangent, which is only evaluated exactly at zero.
+pub fn atan(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	numeric("atan", args, 1, ctx, |args| args[0].is_zero().then(Number::zero))
+}
+
+/// sum(f, k, a, b) -> f(a) + f(a + 1) + ... + f(b)
+///
+/// Returns the sum over all integers `k` from `a` to `b`, using a closed form for symbolic bounds if one is found.
+pub fn sum(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	iterated("sum", args, ctx, summation::sum)
+}
+
+/// product(f, k, a, b) -> f(a) * f(a + 1) * ... * f(b)
+///
+/// Returns the product over all integers `k` from `a` to `b`, using a closed form for symbolic bounds if one is found.
+pub fn product(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	iterated("product", args, ctx, summation::product)
+}
+
+/// diff(f, x) -> df/dx
+///
+/// Returns the derivative with respect to a symbol.
+pub fn diff(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	let args = simplify_args(args, 2, ctx);
+
+	let result = match args.as_slice() {
+		[expr, var] if let Some(var) = variable(var) => expr.derivative(var).simplify(ctx).ok(),
+		_ => None,
+	};
+
+	result.unwrap_or_else(|| unevaluated("diff", args))
+}
+
+/// integrate(f, x) -> F
+/// integrate(f, x, a, b) -> F(b) - F(a)
+///
+/// Returns an antiderivative, or the definite integral if bounds are given. The call stays unevaluated if no
+/// antiderivative is found.
+pub fn integrate(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	let count = if args.len() == 4 { 4 } else { 2 };
+	let args = simplify_args(args, count, ctx);
+
+	let result = match args.as_slice() {
+		[expr, var] if let Some(var) = variable(var) => expr.integrate(var, ctx).ok(),
+		[expr, var, lo, hi] if let Some(var) = variable(var) => expr.integrate_definite(var, lo, hi, ctx).ok(),
+		_ => None,
+	};
+
+	result.unwrap_or_else(|| unevaluated("integrate", args))
+}
+
+/// limit(f, x, a) -> lim f as x -> a
+/// limit(f, x, a, dir) -> lim f as x -> a from the `left` or `right`
+///
+/// Returns the limit, where `inf` stands for infinity and `undefined` for a limit that does not exist. The call stays
+/// unevaluated if the limit could not be determined.
+pub fn limit(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	let count = if args.len() == 4 { 4 } else { 3 };
+	let args = simplify_args(args, count, ctx);
+
+	let direction = match args.get(3).map(variable) {
+		None => Some(Direction::Both),
+		Some(Some(sym)) if sym.name() == "left" => Some(Direction::Left),
+		Some(Some(sym)) if sym.name() == "right" => Some(Direction::Right),
+		Some(_) => None,
+	};
+
+	let result = match args.as_slice() {
+		[expr, var, point, ..]
+			if let Some(var) = variable(var)
+				&& let Some(direction) = direction =>
+		{
+			let point = Point::from(point.clone());
+			limits::limit(expr.clone(), var, point, direction, ctx).ok().flatten()
+		}
+		_ => None,
+	};
+
+	match result {
+		Some(limit) => limit.into(),
+		None => unevaluated("limit", args),
 	}
+}
 
-	let Ok(Expr::Num(n)) = args.into_iter().next().unwrap().simplify(ctx) else {
-		unimplemented!()
+/// series(f, x, a, n) -> f(a) + f'(a) (x - a) + ...
+///
+/// Returns the Taylor, Laurent or Puiseux series around a point with all terms of degree below `n`, dropping the error
+/// term `O((x - a)^n)`.
+pub fn series(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	let args = simplify_args(args, 4, ctx);
+
+	let result = match args.as_slice() {
+		[expr, var, point, Expr::Num(order)] if let Some(var) = variable(var) => {
+			let series = expr.series(var, point, order.clone());
+			series.map(|series| series.to_expr(var, point))
+		}
+		_ => None,
 	};
 
-	Expr::Num(n.ceil())
+	result.unwrap_or_else(|| unevaluated("series", args))
 }
 
-/// floor(n) -> n
+/// replace_all(f, lhs, rhs) -> f with each match of the pattern `lhs` replaced by `rhs`
 ///
-/// Returns the smallest integer less than or equal to n.
-pub fn floor(args: Vec<Expr>, ctx: &mut Context) -> Expr {
-	if args.len() != 1 {
-		panic!("expected one argument")
+/// Replaces subexpressions matching a pattern once, where symbols ending in `_` are wildcards.
+pub fn replace_all(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	let args = simplify_args(args, 3, ctx);
+
+	let result = match args.as_slice() {
+		[expr, lhs, rhs] => expr.replace_all(&[Rule::new(lhs.clone(), rhs.clone())], ctx).ok(),
+		_ => None,
+	};
+
+	result.unwrap_or_else(|| unevaluated("replace_all", args))
+}
+
+/// replace_repeated(f, lhs, rhs) -> f with matches of the pattern `lhs` replaced by `rhs` until nothing changes
+///
+/// Replaces subexpressions matching a pattern until no more matches are left, where symbols ending in `_` are
+/// wildcards.
+pub fn replace_repeated(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	let args = simplify_args(args, 3, ctx);
+
+	let result = match args.as_slice() {
+		[expr, lhs, rhs] => expr.replace_repeated(&[Rule::new(lhs.clone(), rhs.clone())], ctx).ok(),
+		_ => None,
+	};
+
+	result.unwrap_or_else(|| unevaluated("replace_repeated", args))
+}
+
+/// trigsimp(f) -> f with simplified trigonometric functions
+///
+/// Returns the expression with exact values, shifted arguments and the Pythagorean identity applied.
+pub fn trigsimp(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	rewritten("trigsimp", args, ctx, Expr::trigsimp)
+}
+
+/// expand_trig(f) -> f with expanded trigonometric functions
+///
+/// Returns the expression with trigonometric functions of sums and integer multiples expanded.
+pub fn expand_trig(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	rewritten("expand_trig", args, ctx, Expr::expand_trig)
+}
+
+/// trig_to_exp(f) -> f with trigonometric functions written as exponentials
+///
+/// Returns the expression with sines, cosines and tangents rewritten as exponentials, where `i` is the imaginary unit.
+pub fn trig_to_exp(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	rewritten("trig_to_exp", args, ctx, Expr::trig_to_exp)
+}
+
+/// exp_to_trig(f) -> f with imaginary exponentials written as trigonometric functions
+///
+/// Returns the expression with exponentials of imaginary arguments rewritten using Euler's formula, where `i` is the
+/// imaginary unit.
+pub fn exp_to_trig(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	rewritten("exp_to_trig", args, ctx, Expr::exp_to_trig)
+}
+
+/// expand_log(f) -> f with expanded logarithms
+///
+/// Returns the expression with logarithms of products, powers and rational perfect powers expanded where valid.
+pub fn expand_log(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	rewritten("expand_log", args, ctx, Expr::expand_log)
+}
+
+/// combine_log(f) -> f with combined logarithms
+///
+/// Returns the expression with sums of logarithms with numeric coefficients combined into single logarithms.
+pub fn combine_log(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	rewritten("combine_log", args, ctx, Expr::combine_log)
+}
+
+/// solve_inequality(r, x) -> condition on x
+///
+/// Solves a relation or boolean connective of relations between rational functions in `x`, returning the solution as
+/// an equivalent condition on `x`, like `x < -1 or x > 1` for `x^2 > 1`.
+pub fn solve_inequality(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	let args = simplify_args(args, 2, ctx);
+
+	let result = match args.as_slice() {
+		[expr, var] if let Some(var) = variable(var) => {
+			let set = expr.solve_inequality(var, ctx).ok().flatten();
+			set.and_then(|set| set.condition(var))
+		}
+		_ => None,
+	};
+
+	result.unwrap_or_else(|| unevaluated("solve_inequality", args))
+}
+
+/// identity(n) -> I
+///
+/// Returns the identity matrix of size n.
+pub fn identity(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	let args = simplify_args(args, 1, ctx);
+
+	match args[0].clone().into_num().and_then(|num| num.to_u32()) {
+		Some(size) => Expr::Matrix(Matrix::identity(size as usize).map(Expr::Num)),
+		None => unevaluated("identity", args),
+	}
+}
+
+/// transpose(A) -> A^T
+///
+/// Returns the transposed matrix.
+pub fn transpose(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	linear("transpose", args, ctx, |matrix, _| {
+		Some(Expr::Matrix(matrix.transpose()))
+	})
+}
+
+/// trace(A) -> sum of the diagonal
+///
+/// Returns the trace of a square matrix.
+pub fn trace(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	linear("trace", args, ctx, |matrix, ctx| {
+		let diagonal = (0..matrix.rows()).map(|index| matrix[(index, index)].clone());
+		matrix
+			.is_square()
+			.then(|| Expr::Add(diagonal.collect()))
+			.and_then(|expr| expr.simplify(ctx).ok())
+	})
+}
+
+/// det(A) -> |A|
+///
+/// Returns the determinant of a square matrix, which may have symbolic entries.
+pub fn det(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	linear("det", args, ctx, |matrix, ctx| matrix.determinant(ctx).ok().flatten())
+}
+
+/// inverse(A) -> A^-1
+///
+/// Returns the inverse of a square matrix, which may have symbolic entries. The call stays unevaluated for singular
+/// matrices.
+pub fn inverse(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	linear("inverse", args, ctx, |matrix, ctx| {
+		matrix.inverse(ctx).ok().flatten().map(Expr::Matrix)
+	})
+}
+
+/// rank(A) -> r
+///
+/// Returns the rank of a matrix of numbers.
+pub fn rank(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	linear("rank", args, ctx, |matrix, _| {
+		matrix.to_numbers().map(|matrix| Expr::Num(matrix.rank().into()))
+	})
+}
+
+/// rref(A) -> reduced row echelon form of A
+///
+/// Returns the reduced row echelon form of a matrix of numbers.
+pub fn rref(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	linear("rref", args, ctx, |matrix, _| {
+		matrix
+			.to_numbers()
+			.map(|matrix| Expr::Matrix(matrix.rref().0.map(Expr::Num)))
+	})
+}
+
+/// nullspace(A) -> basis of the nullspace
+///
+/// Returns a basis of the nullspace of a matrix of numbers as the columns of a matrix.
+pub fn nullspace(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	linear("nullspace", args, ctx, |matrix, _| {
+		let basis = matrix.to_numbers()?.nullspace();
+		let columns = Matrix::from_fn(matrix.cols(), basis.len(), |row, col| {
+			Expr::Num(basis[col][(row, 0)].clone())
+		});
+
+		Some(Expr::Matrix(columns))
+	})
+}
+
+/// charpoly(A, x) -> det(x I - A)
+///
+/// Returns the characteristic polynomial of a square matrix in `x`, which may have symbolic entries.
+pub fn charpoly(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	let args = simplify_args(args, 2, ctx);
+
+	let result = match args.as_slice() {
+		[Expr::Matrix(matrix), var] if let Some(var) = variable(var) => match matrix.to_numbers() {
+			Some(matrix) => matrix.charpoly().map(|poly| Expr::Poly(var.clone(), poly)),
+			None => matrix.charpoly(ctx).ok().flatten().and_then(|coeffs| {
+				let degree = coeffs.len() - 1;
+				let terms = coeffs
+					.into_iter()
+					.enumerate()
+					.map(|(index, coeff)| coeff * Expr::Poly(var.clone(), Monomial::new(1, degree - index).into()));
+
+				Expr::Add(terms.collect()).simplify(ctx).ok()
+			}),
+		},
+		_ => None,
+	};
+
+	result.unwrap_or_else(|| unevaluated("charpoly", args))
+}
+
+/// eigenvals(A) -> column vector of eigenvalues
+///
+/// Returns the real eigenvalues of a square matrix of numbers in ascending order, repeated by their algebraic
+/// multiplicity.
+pub fn eigenvals(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	linear("eigenvals", args, ctx, |matrix, ctx| {
+		let eigenvalues = matrix.to_numbers()?.eigenvalues(ctx).ok()??;
+		let entries = eigenvalues
+			.into_iter()
+			.flat_map(|(value, multiplicity)| std::iter::repeat_n(value, multiplicity));
+
+		Some(Expr::Matrix(Matrix::column(entries.collect())))
+	})
+}
+
+/// linsolve(eqs, vars) -> condition on vars
+///
+/// Solves a system of linear equations, given as a list or conjunction of equations, for a list of variables. Returns
+/// the solution as an equivalent condition like `x == 2 and y == 1`, where the free variables of infinitely many
+/// solutions stay unconstrained, or `false` if there is no solution.
+pub fn linsolve(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	let args = simplify_args(args, 2, ctx);
+
+	let result = match args.as_slice() {
+		[equations, vars] if let Some(vars) = variables(vars) => {
+			let solution = linsolves::linsolve(&system(equations), &vars, ctx).ok().flatten();
+			solution.map(|solution| solution.condition(&vars))
+		}
+		_ => None,
+	};
+
+	result.unwrap_or_else(|| unevaluated("linsolve", args))
+}
+
+/// groebner(polys, vars) -> column vector of the reduced Gröbner basis
+/// groebner(polys, vars, order) -> basis for the `lex`, `grlex` or `grevlex` order
+///
+/// Returns the reduced Gröbner basis of the ideal generated by a list or conjunction of polynomials or equations, with
+/// the monomials ordered lexicographically by default.
+pub fn groebner(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	let count = if args.len() == 3 { 3 } else { 2 };
+	let args = simplify_args(args, count, ctx);
+
+	let order = match args.get(2).map(variable) {
+		None => Some(MonomialOrder::Lex),
+		Some(Some(sym)) if sym.name() == "lex" => Some(MonomialOrder::Lex),
+		Some(Some(sym)) if sym.name() == "grlex" => Some(MonomialOrder::GrLex),
+		Some(Some(sym)) if sym.name() == "grevlex" => Some(MonomialOrder::GrevLex),
+		Some(_) => None,
+	};
+
+	let result = match args.as_slice() {
+		[polys, vars, ..]
+			if let Some(vars) = variables(vars)
+				&& let Some(order) = order =>
+		{
+			let polys = system(polys)
+				.into_iter()
+				.map(|expr| {
+					let diff = match expr {
+						Expr::Rel(Relation::Eq, lhs, rhs) => *lhs - *rhs,
+						expr => expr,
+					};
+
+					MultiPolynomial::from_expr(&diff.simplify(ctx).ok()?, &vars, order)
+				})
+				.collect::<Option<Vec<_>>>();
+
+			polys.filter(|polys| !polys.is_empty()).map(|polys| {
+				let basis = GroebnerBasis::new(&polys, Algorithm::F4);
+				let entries = basis.polynomials().iter().map(MultiPolynomial::to_expr);
+
+				Expr::Matrix(Matrix::column(entries.collect()))
+			})
+		}
+		_ => None,
+	};
+
+	result.unwrap_or_else(|| unevaluated("groebner", args))
+}
+
+/// solve(eqs, vars) -> condition on vars
+///
+/// Solves a system of polynomial equations with finitely many solutions, given as a list or conjunction of
+/// equations, for a list of variables. Returns the real solutions as an equivalent condition like
+/// `x == 1 and y == 2 or x == 2 and y == 1`, or `false` if there is no real solution.
+pub fn solve(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	let args = simplify_args(args, 2, ctx);
+
+	let result = match args.as_slice() {
+		[equations, vars] if let Some(vars) = variables(vars) => {
+			let solutions = groebners::solve(&system(equations), &vars, ctx).ok().flatten();
+
+			solutions.map(|solutions| {
+				let mut conditions = solutions
+					.into_iter()
+					.map(|values| {
+						let mut equations = vars
+							.iter()
+							.zip(values)
+							.map(|(var, value)| {
+								compare(Relation::Eq, Expr::Poly(var.clone(), Monomial::linear(1).into()), value)
+							})
+							.collect::<Vec<_>>();
+
+						match equations.len() {
+							1 => equations.pop().unwrap(),
+							_ => Expr::And(equations),
+						}
+					})
+					.collect::<Vec<_>>();
+
+				match conditions.len() {
+					1 => conditions.pop().unwrap(),
+					_ => Expr::Or(conditions),
+				}
+			})
+		}
+		_ => None,
+	};
+
+	result.unwrap_or_else(|| unevaluated("solve", args))
+}
+
+/// interpolate(points, x) -> polynomial in x
+///
+/// Returns the polynomial of the lowest degree through the points, given as the rows `[x, y]` of a matrix of numbers.
+pub fn interpolate(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	let args = simplify_args(args, 2, ctx);
+
+	let result = match args.as_slice() {
+		[points, var]
+			if let Some(var) = variable(var)
+				&& let Some(points) = pairs(points) =>
+		{
+			let poly = Polynomial::interpolate(&points);
+			poly.and_then(|poly| Expr::Poly(var.clone(), poly).simplify(ctx).ok())
+		}
+		_ => None,
+	};
+
+	result.unwrap_or_else(|| unevaluated("interpolate", args))
+}
+
+/// interpolate_hermite(points, x) -> polynomial in x
+///
+/// Returns the polynomial of the lowest degree through the points with the given derivatives, given as the rows
+/// `[x, y, y', y'', ...]` of a matrix of numbers.
+pub fn interpolate_hermite(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	let args = simplify_args(args, 2, ctx);
+
+	let result = match args.as_slice() {
+		[points, var]
+			if let Some(var) = variable(var)
+				&& let Some(points) = samples(points) =>
+		{
+			let poly = Polynomial::interpolate_hermite(&points);
+			poly.and_then(|poly| Expr::Poly(var.clone(), poly).simplify(ctx).ok())
+		}
+		_ => None,
+	};
+
+	result.unwrap_or_else(|| unevaluated("interpolate_hermite", args))
+}
+
+/// fit(points, n, x) -> polynomial in x
+///
+/// Returns the least-squares fit of at most degree n to the points, given as the rows `[x, y]` of a matrix of numbers.
+pub fn fit(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	let args = simplify_args(args, 3, ctx);
+
+	let result = match args.as_slice() {
+		[points, Expr::Num(degree), var]
+			if let Some(var) = variable(var)
+				&& let Some(degree) = degree.to_u32()
+				&& let Some(points) = pairs(points) =>
+		{
+			let poly = Polynomial::fit(&points, degree as usize);
+			poly.and_then(|poly| Expr::Poly(var.clone(), poly).simplify(ctx).ok())
+		}
+		_ => None,
+	};
+
+	result.unwrap_or_else(|| unevaluated("fit", args))
+}
+
+/// chebyshevt(n, x) -> T_n(x)
+///
+/// Returns the n-th Chebyshev polynomial of the first kind at `x`.
+pub fn chebyshevt(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	family("chebyshevt", args, 2, ctx, |n, _| Some(Polynomial::chebyshev_t(n)))
+}
+
+/// chebyshevu(n, x) -> U_n(x)
+///
+/// Returns the n-th Chebyshev polynomial of the second kind at `x`.
+pub fn chebyshevu(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	family("chebyshevu", args, 2, ctx, |n, _| Some(Polynomial::chebyshev_u(n)))
+}
+
+/// legendre(n, x) -> P_n(x)
+///
+/// Returns the n-th Legendre polynomial at `x`.
+pub fn legendre(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	family("legendre", args, 2, ctx, |n, _| Some(Polynomial::legendre(n)))
+}
+
+/// hermiteh(n, x) -> H_n(x)
+///
+/// Returns the n-th physicists' Hermite polynomial at `x`.
+pub fn hermiteh(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	family("hermiteh", args, 2, ctx, |n, _| Some(Polynomial::hermite(n)))
+}
+
+/// hermitehe(n, x) -> He_n(x)
+///
+/// Returns the n-th probabilists' Hermite polynomial at `x`.
+pub fn hermitehe(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	family("hermitehe", args, 2, ctx, |n, _| Some(Polynomial::hermite_prob(n)))
+}
+
+/// laguerre(n, x) -> L_n(x)
+///
+/// Returns the n-th Laguerre polynomial at `x`.
+pub fn laguerre(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	family("laguerre", args, 2, ctx, |n, _| Some(Polynomial::laguerre(n)))
+}
+
+/// jacobi(n, a, b, x) -> P_n^(a, b)(x)
+///
+/// Returns the n-th Jacobi polynomial with the numeric parameters `a` and `b` at `x`.
+pub fn jacobi(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	family("jacobi", args, 4, ctx, |n, params| {
+		Some(Polynomial::jacobi(n, &params[0], &params[1]))
+	})
+}
+
+/// cyclotomic(n, x) -> Φ_n(x)
+///
+/// Returns the n-th cyclotomic polynomial at `x` for a positive integer n.
+pub fn cyclotomic(args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	family("cyclotomic", args, 2, ctx, |n, _| Polynomial::cyclotomic(n))
+}
+
+/// Internal helper to create a relation between two expressions.
+fn compare(rel: Relation, lhs: Expr, rhs: Expr) -> Expr {
+	Expr::Rel(rel, lhs.into(), rhs.into())
+}
+
+/// Internal helper to evaluate a minimum or maximum, keeping the argument for which the relation holds with every
+/// later one.
+fn extremum(name: &str, rel: Relation, args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	if args.is_empty() {
+		panic!("expected at least one argument")
+	}
+
+	let count = args.len();
+	let args = simplify_args(args, count, ctx);
+
+	let result = args.iter().cloned().reduce(|acc, arg| {
+		let branches = vec![
+			(acc.clone(), compare(rel, acc, arg.clone())),
+			(arg, Expr::boolean(true)),
+		];
+		Expr::Piecewise(branches)
+	});
+
+	match result.unwrap().simplify(ctx) {
+		Ok(result) => result,
+		Err(_) => unevaluated(name, args),
+	}
+}
+
+/// The signature of an iterated operation like [`summation::sum`].
+type IteratedFn = fn(Expr, &Symbol, Expr, Expr, &mut Context) -> Result<Expr, SimplifyError>;
+
+/// Internal helper to evaluate an iterated operation, keeping the call unevaluated if the variable is not a symbol.
+fn iterated(name: &str, args: Vec<Expr>, ctx: &mut Context, execute: IteratedFn) -> Expr {
+	let Ok([expr, var, lo, hi]) = <[Expr; 4]>::try_from(args) else {
+		panic!("expected 4 argument(s)")
+	};
+
+	let result = variable(&var).and_then(|sym| execute(expr.clone(), sym, lo.clone(), hi.clone(), ctx).ok());
+
+	result.unwrap_or_else(|| unevaluated(name, vec![expr, var, lo, hi]))
+}
+
+/// Internal helper to evaluate a function of a single matrix, keeping the call unevaluated for other arguments or if
+/// the function returns [`None`].
+fn linear(
+	name: &str,
+	args: Vec<Expr>,
+	ctx: &mut Context,
+	execute: impl FnOnce(&Matrix<Expr>, &mut Context) -> Option<Expr>,
+) -> Expr {
+	let args = simplify_args(args, 1, ctx);
+
+	let result = match &args[0] {
+		Expr::Matrix(matrix) => execute(matrix, ctx),
+		_ => None,
+	};
+
+	result.unwrap_or_else(|| unevaluated(name, args))
+}
+
+/// Internal helper to evaluate a function of one argument as a piecewise expression, which is built from the branches
+/// for the argument and simplified. Piecewise arguments are handled branch by branch.
+fn piecewise_of(name: &str, args: Vec<Expr>, ctx: &mut Context, branches: fn(Expr) -> Vec<(Expr, Expr)>) -> Expr {
+	let args = simplify_args(args, 1, ctx);
+
+	let expr = match &args[0] {
+		Expr::Piecewise(outer) => Expr::Piecewise(
+			outer
+				.iter()
+				.map(|(value, condition)| (Expr::Piecewise(branches(value.clone())), condition.clone()))
+				.collect(),
+		),
+		arg => Expr::Piecewise(branches(arg.clone())),
+	};
+
+	expr.simplify(ctx).unwrap_or_else(|_| unevaluated(name, args))
+}
+
+/// Internal helper to rewrite the single argument of a function, keeping the call unevaluated if that fails.
+fn rewritten(name: &str, args: Vec<Expr>, ctx: &mut Context, rewrite: Pass) -> Expr {
+	let args = simplify_args(args, 1, ctx);
+
+	rewrite(&args[0], ctx).unwrap_or_else(|_| unevaluated(name, args))
+}
+
+/// Internal helper to round a number, keeping integers and rounding piecewise arguments branch by branch. Other
+/// calls stay unevaluated.
+fn rounded(name: &str, args: Vec<Expr>, ctx: &mut Context, round: fn(Number) -> Number) -> Expr {
+	let args = simplify_args(args, 1, ctx);
+
+	match &args[0] {
+		Expr::Num(num) => Expr::Num(round(num.clone())),
+		arg if arg.is_integer(ctx) == Some(true) => arg.clone(),
+		Expr::Piecewise(branches) => Expr::Piecewise(
+			branches
+				.iter()
+				.map(|(value, condition)| (rounded(name, vec![value.clone()], ctx, round), condition.clone()))
+				.collect(),
+		),
+		_ => unevaluated(name, args),
+	}
+}
+
+/// Internal helper to simplify the arguments of a function, panicking if the wrong amount is given.
+fn simplify_args(args: Vec<Expr>, count: usize, ctx: &mut Context) -> Vec<Expr> {
+	if args.len() != count {
+		panic!("expected {count} argument(s)")
 	}
 
-	let Ok(Expr::Num(n)) = args.into_iter().next().unwrap().simplify(ctx) else {
-		unimplemented!()
+	args.into_iter()
+		.map(|arg| arg.clone().simplify(ctx).unwrap_or(arg))
+		.collect()
+}
+
+/// Internal helper to evaluate a function on numbers, keeping the call unevaluated for other arguments.
+fn numeric(
+	name: &str,
+	args: Vec<Expr>,
+	count: usize,
+	ctx: &mut Context,
+	execute: impl FnOnce(&[Number]) -> Option<Number>,
+) -> Expr {
+	let args = simplify_args(args, count, ctx);
+
+	let result = args
+		.iter()
+		.map(|arg| match arg {
+			Expr::Num(num) => Some(num.clone()),
+			_ => None,
+		})
+		.collect::<Option<Vec<_>>>()
+		.and_then(|nums| execute(&nums));
+
+	match result {
+		Some(num) => Expr::Num(num),
+		None => unevaluated(name, args),
+	}
+}
+
+/// Internal helper to evaluate a polynomial of a family at the last argument, where the first argument is the degree
+/// and the others are numeric parameters. Keeps the call unevaluated if the polynomial is not defined or the degree is
+/// larger than `MAX_FAMILY_DEGREE`.
+fn family(
+	name: &str,
+	args: Vec<Expr>,
+	count: usize,
+	ctx: &mut Context,
+	build: impl FnOnce(u32, &[Number]) -> Option<Polynomial>,
+) -> Expr {
+	let args = simplify_args(args, count, ctx);
+	let (x, params) = args.split_last().unwrap();
+
+	let result = params
+		.iter()
+		.map(|param| param.clone().into_num())
+		.collect::<Option<Vec<_>>>()
+		.and_then(|params| {
+			let n = params[0].to_u32().filter(|&n| n <= MAX_FAMILY_DEGREE)?;
+			build(n, &params[1..])
+		})
+		.and_then(|poly| {
+			let var = Symbol::new("x").unwrap();
+			Expr::Poly(var.clone(), poly).substitute(&var, x).simplify(ctx).ok()
+		});
+
+	result.unwrap_or_else(|| unevaluated(name, args))
+}
+
+/// Internal helper to evaluate a sine, cosine or tangent, keeping the call unevaluated if it is not simplified.
+fn trigonometric(name: &str, args: Vec<Expr>, ctx: &mut Context) -> Expr {
+	let args = simplify_args(args, 1, ctx);
+
+	match trig::evaluate(name, &args[0], ctx) {
+		Ok(Some(result)) => result.clone().simplify(ctx).unwrap_or(result),
+		_ => unevaluated(name, args),
+	}
+}
+
+/// Internal helper to get the symbol of an expression that is just a symbol like `x`.
+fn variable(expr: &Expr) -> Option<&Symbol> {
+	match expr {
+		Expr::Poly(sym, poly) if *poly == Polynomial::from(Monomial::linear(1)) => Some(sym),
+		_ => None,
+	}
+}
+
+/// Internal helper to get the symbols of a single symbol or a matrix of symbols.
+fn variables(expr: &Expr) -> Option<Vec<Symbol>> {
+	match expr {
+		Expr::Matrix(matrix) => matrix.entries().iter().map(|entry| variable(entry).cloned()).collect(),
+		expr => variable(expr).map(|var| vec![var.clone()]),
+	}
+}
+
+/// Internal helper to get the points `(x, y)` from the rows of a matrix of numbers with two columns.
+fn pairs(expr: &Expr) -> Option<Vec<(Number, Number)>> {
+	let points = samples(expr)?;
+	let pairs = points.into_iter().map(|(x, values)| match values.as_slice() {
+		[y] => Some((x, y.clone())),
+		_ => None,
+	});
+
+	pairs.collect()
+}
+
+/// Internal helper to get the points `(x, [y, y', ...])` from the rows of a matrix of numbers with at least two
+/// columns.
+fn samples(expr: &Expr) -> Option<Vec<(Number, Vec<Number>)>> {
+	let Expr::Matrix(matrix) = expr else {
+		return None;
 	};
 
-	Expr::Num(n.floor())
+	if matrix.cols() < 2 {
+		return None;
+	}
+
+	let matrix = matrix.to_numbers()?;
+	let rows = (0..matrix.rows()).map(|row| (matrix[(row, 0)].clone(), matrix.row(row)[1..].to_vec()));
+
+	Some(rows.collect())
+}
+
+/// Internal helper to get the equations of a list or conjunction of equations, or of a single equation.
+fn system(expr: &Expr) -> Vec<Expr> {
+	match expr {
+		Expr::And(exprs) => exprs.clone(),
+		Expr::Matrix(matrix) => matrix.entries().to_vec(),
+		expr => vec![expr.clone()],
+	}
+}
+
+/// Internal helper to create an unevaluated function call.
+fn unevaluated(name: &str, args: Vec<Expr>) -> Expr {
+	Expr::Fun(Symbol::new(name).unwrap(), args)
 }
@@ -0,0 +1,504 @@
+//! Symbolic sums and products with closed forms.
+//!
+//! Both [`sum`] and [`product`] evaluate finite ranges with numeric bounds exactly, term by term. For other ranges, a
+//! closed form is searched for, falling back to an unevaluated `sum` or `product` call if none is found.
+
+use rug::Integer;
+use rug::ops::Pow;
+
+use crate::combinatorics::bernoulli_numbers;
+use crate::context::Context;
+use crate::error::SimplifyError;
+use crate::expr::{Expr, Symbol};
+use crate::monomial::Monomial;
+use crate::number::Number;
+use crate::polynomial::Polynomial;
+use crate::stdlib::StdLib;
+
+/// The maximum amount of terms of a numeric range that is evaluated term by term.
+const MAX_TERMS: u32 = 10_000;
+
+/// Sums an expression over all integers `var` from `lo` to `hi`, both inclusive.
+///
+/// Closed forms are found for polynomial summands using Faulhaber's formula, for geometric series, and for rational
+/// summands with a rational antidifference using Gosper's algorithm. Sums of such terms are split up.
+///
+/// # Examples
+///
+/// ```
+/// use abacas::context::Context;
+/// use abacas::expr::{Expr, Symbol};
+/// use abacas::summation::sum;
+///
+/// let ctx = &mut Context::new();
+/// let k = Symbol::new("k").unwrap();
+/// let n = Symbol::new("n").unwrap();
+///
+/// let squares = Expr::Poly(k.clone(), "x^2".parse().unwrap());
+/// let upper = Expr::Poly(n.clone(), "x".parse().unwrap());
+///
+/// let result = sum(squares, &k, Expr::one(), upper, ctx).unwrap();
+/// assert_eq!(result, Expr::Poly(n, "1/3x^3 + 1/2x^2 + 1/6x".parse().unwrap()));
+/// ```
+pub fn sum(expr: Expr, var: &Symbol, lo: Expr, hi: Expr, ctx: &mut Context) -> Result<Expr, SimplifyError> {
+	let (expr, lo, hi) = (expr.simplify(ctx)?, lo.simplify(ctx)?, hi.simplify(ctx)?);
+
+	if let Some(range) = numeric_range(&lo, &hi) {
+		let terms = range.map(|k| expr.substitute(var, &Expr::Num(k)));
+		return evaluate(Expr::Add(terms.collect()), ctx);
+	}
+
+	// The antidifference `F(k + 1) - F(k) = expr` telescopes to `F(hi + 1) - F(lo)`
+	match antidifference(&expr, var, ctx)? {
+		Some(anti) => evaluate(
+			anti.substitute(var, &(hi + Expr::one())) - anti.substitute(var, &lo),
+			ctx,
+		),
+		None => Ok(unevaluated("sum", expr, var, lo, hi)),
+	}
+}
+
+/// Multiplies an expression over all integers `var` from `lo` to `hi`, both inclusive.
+///
+/// Closed forms are found for constant factors, powers of constants with a summable exponent, and linear factors,
+/// which result in factorials. Products of such factors are split up.
+///
+/// # Examples
+///
+/// ```
+/// use abacas::context::Context;
+/// use abacas::expr::{Expr, Symbol};
+/// use abacas::summation::product;
+///
+/// let ctx = &mut Context::new();
+/// let k = Symbol::new("k").unwrap();
+/// let n = Expr::Poly(Symbol::new("n").unwrap(), "x".parse().unwrap());
+///
+/// let factors = Expr::Poly(k.clone(), "x".parse().unwrap());
+/// let result = product(factors, &k, Expr::one(), n.clone(), ctx).unwrap();
+/// assert_eq!(result.to_string(), "factorial(n)");
+///
+/// let factors = Expr::Poly(k.clone(), "x + 2".parse().unwrap());
+/// let result = product(factors, &k, Expr::one(), n, ctx).unwrap();
+/// assert_eq!(result.to_string(), "factorial(n + 2) * 0.5");
+/// ```
+pub fn product(expr: Expr, var: &Symbol, lo: Expr, hi: Expr, ctx: &mut Context) -> Result<Expr, SimplifyError> {
+	let (expr, lo, hi) = (expr.simplify(ctx)?, lo.simplify(ctx)?, hi.simplify(ctx)?);
+
+	if let Some(range) = numeric_range(&lo, &hi) {
+		let factors = range.map(|k| expr.substitute(var, &Expr::Num(k)));
+		return evaluate(Expr::Mul(factors.collect()), ctx);
+	}
+
+	// The antiquotient `F(k + 1) / F(k) = expr` telescopes to `F(hi + 1) / F(lo)`
+	match antiquotient(&expr, var, &lo, ctx)? {
+		Some(anti) => evaluate(
+			anti.substitute(var, &(hi + Expr::one())) / anti.substitute(var, &lo),
+			ctx,
+		),
+		None => Ok(unevaluated("product", expr, var, lo, hi)),
+	}
+}
+
+/// Internal helper to find an antidifference `F` with `F(k + 1) - F(k) = expr`, or [`None`] if none is found.
+fn antidifference(expr: &Expr, var: &Symbol, ctx: &mut Context) -> Result<Option<Expr>, SimplifyError> {
+	let k = Expr::Poly(var.clone(), Monomial::linear(1).into());
+
+	if !expr.has_symbol(var) {
+		return Ok(Some(expr.clone() * k));
+	}
+
+	if let Some((numer, denom)) = rational(expr, var) {
+		if denom.is_one() {
+			return Ok(Some(Expr::Poly(var.clone(), faulhaber(&numer))));
+		}
+
+		if let Some((numer, denom)) = gosper(&numer, &denom) {
+			let anti = Expr::Poly(var.clone(), numer) / Expr::Poly(var.clone(), denom);
+			return Ok(Some(anti));
+		}
+	}
+
+	match expr {
+		// Sums are split into their terms
+		Expr::Add(terms) => {
+			let mut antis = Vec::with_capacity(terms.len());
+
+			for term in terms {
+				let Some(anti) = antidifference(term, var, ctx)? else {
+					return Ok(None);
+				};
+
+				antis.push(anti);
+			}
+
+			Ok(Some(Expr::Add(antis)))
+		}
+
+		// Constant factors are extracted from products
+		Expr::Mul(factors) if factors.iter().any(|factor| !factor.has_symbol(var)) => {
+			let (mut constant, rest): (Vec<_>, Vec<_>) =
+				factors.iter().cloned().partition(|expr| !expr.has_symbol(var));
+
+			let Some(anti) = antidifference(&Expr::Mul(rest).simplify(ctx)?, var, ctx)? else {
+				return Ok(None);
+			};
+
+			constant.push(anti);
+			Ok(Some(Expr::Mul(constant)))
+		}
+
+		// Geometric series `base^(a k + b)` with the ratio `q = base^a`
+		Expr::Pow(base, exp)
+			if !base.has_symbol(var)
+				&& let Some((slope, _)) = linear(exp, var) =>
+		{
+			let ratio = base.as_ref().clone().pow(Expr::Num(slope)).simplify(ctx)?;
+
+			if ratio.is_num_and(Number::is_one) {
+				return Ok(Some(expr.clone() * k));
+			}
+
+			Ok(Some(expr.clone() / (ratio - Expr::one())))
+		}
+
+		_ => Ok(None),
+	}
+}
+
+/// Internal helper to find an antiquotient `F` with `F(k + 1) / F(k) = expr` for all `k` from `lo` on, or [`None`] if
+/// none is found. Factors with an integer root from `lo` on have none, since their factorials would have negative
+/// arguments.
+fn antiquotient(expr: &Expr, var: &Symbol, lo: &Expr, ctx: &mut Context) -> Result<Option<Expr>, SimplifyError> {
+	let k = Expr::Poly(var.clone(), Monomial::linear(1).into());
+	let below = |root: Number| matches!(lo, Expr::Num(lo) if root < *lo);
+
+	if !expr.has_symbol(var) {
+		return Ok(Some(expr.clone().pow(k)));
+	}
+
+	match expr {
+		// Products are split into their factors
+		Expr::Mul(factors) => {
+			let mut antis = Vec::with_capacity(factors.len());
+
+			for factor in factors {
+				let Some(anti) = antiquotient(factor, var, lo, ctx)? else {
+					return Ok(None);
+				};
+
+				antis.push(anti);
+			}
+
+			Ok(Some(Expr::Mul(antis)))
+		}
+
+		// Powers of constants multiply to the sum of their exponents
+		Expr::Pow(base, exp) if !base.has_symbol(var) => {
+			let anti = antidifference(exp, var, ctx)?;
+			Ok(anti.map(|anti| base.as_ref().clone().pow(anti)))
+		}
+
+		// Integer powers multiply to the power of the product
+		Expr::Pow(base, exp) if exp.is_num_and(Number::is_integer) => {
+			let anti = antiquotient(base, var, lo, ctx)?;
+			Ok(anti.map(|anti| anti.pow(exp.as_ref().clone())))
+		}
+
+		// Linear factors `a (k + s)` multiply to `a^k (k + s - 1)!` for integers `s`
+		Expr::Poly(_, _) if let Some((slope, intercept)) = linear(expr, var) => {
+			let shift = intercept / &slope;
+
+			if !shift.is_integer() || !below(-shift.clone()) {
+				return Ok(None);
+			}
+
+			let arg = Expr::Poly(var.clone(), Monomial::linear(1) + (shift - 1));
+			let factorial = Expr::Fun(Symbol::new("factorial").unwrap(), vec![arg]);
+
+			Ok(Some(Expr::Num(slope).pow(k) * factorial))
+		}
+
+		// Monomials `a k^m` multiply to `a^k (k - 1)!^m`
+		Expr::Poly(_, poly)
+			if let [mono] = poly.monomials().as_slice()
+				&& mono.degree.is_integer()
+				&& below(Number::zero()) =>
+		{
+			let arg = Expr::Poly(var.clone(), Monomial::linear(1) - 1);
+			let factorial = Expr::Fun(Symbol::new("factorial").unwrap(), vec![arg]);
+
+			Ok(Some(
+				Expr::Num(mono.coeff.clone()).pow(k) * factorial.pow(Expr::Num(mono.degree.clone())),
+			))
+		}
+
+		_ => Ok(None),
+	}
+}
+
+/// Internal helper to evaluate calls to the StdLib in a result, for example factorials of numbers, and simplify it.
+pub(crate) fn evaluate(expr: Expr, ctx: &mut Context) -> Result<Expr, SimplifyError> {
+	StdLib::new().evaluate(expr.simplify(ctx)?, ctx).simplify(ctx)
+}
+
+/// Internal helper to get the antidifference of a polynomial using Faulhaber's formula, which is
+/// `S_m(k) = (binomial(m + 1, 0) B_0 k^(m + 1) + ... + binomial(m + 1, m) B_m k) / (m + 1)` for a monomial `k^m`.
+fn faulhaber(poly: &Polynomial) -> Polynomial {
+	let degree = poly.degree().and_then(Number::to_u32).unwrap_or_default();
+	let bernoulli = &bernoulli_numbers(degree);
+
+	let monomials = poly.monomials().flat_map(|mono| {
+		let m = mono.degree.to_u32().unwrap();
+
+		(0..=m).filter(|&i| !bernoulli[i as usize].is_zero()).map(move |i| {
+			let binomial = Number::from_integer(Integer::binomial_u(m + 1, i).into());
+			let coeff = binomial * &bernoulli[i as usize] * &mono.coeff / (m + 1);
+
+			Monomial::new(coeff, m + 1 - i)
+		})
+	});
+
+	Polynomial::new(monomials)
+}
+
+/// Internal helper implementing Gosper's algorithm for a rational summand `t = numer / denom`.
+/// Returns the rational antidifference as a quotient of polynomials, or [`None`] if it does not exist.
+fn gosper(numer: &Polynomial, denom: &Polynomial) -> Option<(Polynomial, Polynomial)> {
+	let one = Number::one();
+
+	// The ratio of consecutive terms `t(k + 1) / t(k) = a(k) / b(k)`
+	let (mut a, mut b) = reduce(numer.shift(&one)? * denom, denom.shift(&one)? * numer);
+	let mut c = Polynomial::from(1);
+
+	// Rewrite the ratio as `a(k) / b(k) * c(k + 1) / c(k)` with `gcd(a(k), b(k + h)) = 1` for all `h >= 0`.
+	// A common root of `a(k)` and `b(k + h)` bounds `h` by the sum of both root bounds.
+	let bound = (a.root_bound() + &b.root_bound()).floor().to_u32()?;
+
+	for h in 1..=bound {
+		loop {
+			let gcd = a.clone().gcd(b.shift(&h.into())?);
+
+			if gcd.is_constant() {
+				break;
+			}
+
+			a /= &gcd;
+			b /= &gcd.shift(&-Number::from(h))?;
+
+			for i in 1..=h {
+				c *= &gcd.shift(&-Number::from(i))?;
+			}
+		}
+	}
+
+	// Find a polynomial `x` with `a(k) x(k + 1) - b(k - 1) x(k) = c(k)`
+	let b = b.shift(&Number::neg_one())?;
+	let x = solve_recurrence(&a, &b, &c)?;
+
+	// The antidifference is `b(k - 1) x(k) / c(k) * t(k)`
+	Some(reduce(b * &x * numer, c * denom))
+}
+
+/// Internal helper to get the slope and intercept of an expression that is linear in `var`.
+pub(crate) fn linear(expr: &Expr, var: &Symbol) -> Option<(Number, Number)> {
+	let Expr::Poly(sym, poly) = expr else {
+		return None;
+	};
+
+	match poly.monomials().as_slice() {
+		[slope] if sym == var && slope.degree.is_one() => Some((slope.coeff.clone(), Number::zero())),
+		[slope, intercept] if sym == var && slope.degree.is_one() && intercept.degree.is_zero() => {
+			Some((slope.coeff.clone(), intercept.coeff.clone()))
+		}
+		_ => None,
+	}
+}
+
+/// Internal helper to get the integers of a numeric range, or [`None`] if the range is not numeric or too long.
+fn numeric_range(lo: &Expr, hi: &Expr) -> Option<impl Iterator<Item = Number>> {
+	let (Expr::Num(lo), Expr::Num(hi)) = (lo, hi) else {
+		return None;
+	};
+
+	if !lo.is_integer() || !hi.is_integer() {
+		return None;
+	}
+
+	let count = (hi.clone() - lo + 1).max(Number::zero()).to_u32()?;
+	let lo = lo.clone();
+
+	(count <= MAX_TERMS).then(|| (0..count).map(move |offset| lo.clone() + offset))
+}
+
+/// Internal helper to write an expression as a quotient of polynomials in `var` with non-negative integer degrees.
+/// Returns [`None`] if the expression is not a rational function in `var` with numeric coefficients.
+pub(crate) fn rational(expr: &Expr, var: &Symbol) -> Option<(Polynomial, Polynomial)> {
+	let (numer, denom) = match expr {
+		Expr::Num(num) => (Polynomial::from(num.clone()), Polynomial::from(1)),
+
+		Expr::Poly(sym, poly) if sym == var && poly.monomials().all(|mono| mono.degree.is_integer()) => {
+			// Negative degrees are moved into the denominator
+			let lowest = poly.monomials().last()?.degree.clone().min(Number::zero());
+			let denom = Polynomial::from(Monomial::new(1, -lowest));
+
+			(poly.clone() * &denom, denom)
+		}
+
+		Expr::Add(terms) => {
+			terms
+				.iter()
+				.try_fold((Polynomial::ZERO, Polynomial::from(1)), |(numer, denom), term| {
+					let (term_numer, term_denom) = rational(term, var)?;
+					Some((numer * &term_denom + term_numer * &denom, denom * &term_denom))
+				})?
+		}
+
+		Expr::Mul(factors) => {
+			factors
+				.iter()
+				.try_fold((Polynomial::from(1), Polynomial::from(1)), |(numer, denom), factor| {
+					let (factor_numer, factor_denom) = rational(factor, var)?;
+					Some((numer * &factor_numer, denom * &factor_denom))
+				})?
+		}
+
+		Expr::Pow(base, exp) => {
+			let Expr::Num(exp) = exp.as_ref() else {
+				return None;
+			};
+
+			let power = exp.clone().abs().to_u32()?;
+			let (mut numer, mut denom) = rational(base, var)?;
+
+			if exp.is_negative() {
+				if numer.is_zero() {
+					return None;
+				}
+
+				(numer, denom) = (denom, numer);
+			}
+
+			let pow = |poly: &Polynomial| (0..power).fold(Polynomial::from(1), |acc, _| acc * poly);
+			(pow(&numer), pow(&denom))
+		}
+
+		_ => return None,
+	};
+
+	Some(reduce(numer, denom))
+}
+
+/// Internal helper to cancel the common factors of a quotient of polynomials and make the denominator monic.
+fn reduce(numer: Polynomial, denom: Polynomial) -> (Polynomial, Polynomial) {
+	let gcd = numer.clone().gcd(denom.clone());
+	let (mut numer, mut denom) = (numer / &gcd, denom / &gcd);
+
+	if let Some(factor) = denom.monic_mut() {
+		numer /= &factor;
+	}
+
+	(numer, denom)
+}
+
+/// Internal helper to find a polynomial `x` with `a(k) x(k + 1) - b(k) x(k) = c(k)`, or [`None`] if none exists.
+fn solve_recurrence(a: &Polynomial, b: &Polynomial, c: &Polynomial) -> Option<Polynomial> {
+	let degree = |poly: &Polynomial| poly.degree().and_then(Number::to_u32).map_or(-1, i64::from);
+	let (deg_a, deg_b, deg_c) = (degree(a), degree(b), degree(c));
+	let max = deg_a.max(deg_b);
+
+	// Bound the degree of `x`, which depends on whether the leading terms of the left side cancel
+	let mut bound = deg_c - max;
+
+	if deg_a == deg_b && a.leading() == b.leading() {
+		bound += 1;
+
+		let second = |poly: &Polynomial| {
+			poly.get(&(max - 1).into())
+				.map(|mono| mono.coeff.clone())
+				.unwrap_or_default()
+		};
+		let root = (second(b) - &second(a)) / a.leading().unwrap();
+
+		if let Some(root) = root.to_u32() {
+			bound = bound.max(root.into());
+		}
+	}
+
+	let bound = u32::try_from(bound).ok()?;
+
+	// Every coefficient of `x` contributes one column of a linear system
+	let columns: Vec<_> = (0..=bound)
+		.map(|j| {
+			let basis = Polynomial::from(Monomial::new(1, j));
+			Some(a.clone() * &basis.shift(&Number::one())? - b.clone() * &basis)
+		})
+		.collect::<Option<_>>()?;
+
+	let rows = columns.iter().chain([c]).map(degree).max().unwrap_or_default() + 1;
+	let coeff = |poly: &Polynomial, i: i64| poly.get(&i.into()).map(|mono| mono.coeff.clone()).unwrap_or_default();
+
+	let system = (0..rows)
+		.map(|i| columns.iter().chain([c]).map(|poly| coeff(poly, i)).collect())
+		.collect();
+
+	let solution = solve_linear(system)?;
+	let monomials = solution.into_iter().enumerate().filter(|(_, coeff)| !coeff.is_zero());
+
+	Some(Polynomial::new(monomials.map(|(j, coeff)| Monomial::new(coeff, j))))
+}
+
+/// Internal helper to solve a linear system given as an augmented matrix with Gauss-Jordan elimination.
+/// Free variables are set to zero. Returns [`None`] if the system is inconsistent.
+pub(crate) fn solve_linear(mut rows: Vec<Vec<Number>>) -> Option<Vec<Number>> {
+	let columns = rows.first().map_or(0, |row| row.len() - 1);
+	let mut pivots = Vec::new();
+
+	for column in 0..columns {
+		let rank = pivots.len();
+
+		let Some(pivot) = (rank..rows.len()).find(|&row| !rows[row][column].is_zero()) else {
+			continue;
+		};
+
+		rows.swap(rank, pivot);
+
+		let factor = rows[rank][column].clone();
+		rows[rank].iter_mut().for_each(|value| *value /= &factor);
+
+		let pivot_row = rows[rank].clone();
+
+		for (row, values) in rows.iter_mut().enumerate() {
+			let factor = values[column].clone();
+
+			if row == rank || factor.is_zero() {
+				continue;
+			}
+
+			for (value, pivot) in values.iter_mut().zip(&pivot_row) {
+				*value -= &(pivot.clone() * &factor);
+			}
+		}
+
+		pivots.push(column);
+	}
+
+	// A remaining row with a non-zero right side means the system is inconsistent
+	if rows[pivots.len()..].iter().any(|row| !row[columns].is_zero()) {
+		return None;
+	}
+
+	let mut solution = vec![Number::zero(); columns];
+
+	for (row, column) in pivots.into_iter().enumerate() {
+		solution[column] = rows[row][columns].clone();
+	}
+
+	Some(solution)
+}
+
+/// Internal helper to create an unevaluated `sum` or `product` call.
+fn unevaluated(name: &str, expr: Expr, var: &Symbol, lo: Expr, hi: Expr) -> Expr {
+	let var = Expr::Poly(var.clone(), Monomial::linear(1).into());
+	Expr::Fun(Symbol::new(name).unwrap(), vec![expr, var, lo, hi])
+}
@@ -0,0 +1,676 @@
+//! Trigonometric simplification and identities.
+//!
+//! Sines, cosines and tangents are evaluated exactly at rational multiples of `pi` with known values, shifted by
+//! multiples of `pi / 2`, and odd functions pull out the sign of negated arguments. The conversions between
+//! trigonometric functions and exponentials use the symbol `i` for the imaginary unit.
+
+use itertools::Itertools;
+use rug::ops::{Pow, RemRounding};
+
+use crate::combinatorics;
+use crate::context::Context;
+use crate::error::SimplifyError;
+use crate::expr::{Expr, Symbol};
+use crate::monomial::Monomial;
+use crate::number::Number;
+use crate::polynomial::Polynomial;
+
+/// The maximum multiple of an angle that [`Expr::expand_trig`] expands.
+const MAX_MULTIPLE: u32 = 64;
+
+// Operations
+impl Expr {
+	/// Rewrites exponentials with imaginary exponents into sines and cosines using Euler's formula
+	/// `exp(a + ib) = exp(a) (cos(b) + i sin(b))`, reducing powers of `i` afterwards.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::context::Context;
+	/// use abacas::expr::{Expr, Symbol};
+	///
+	/// let ctx = &mut Context::new();
+	/// let arg = Expr::Poly(Symbol::new("i").unwrap(), "x".parse().unwrap())
+	///     * Expr::Poly(Symbol::new("x").unwrap(), "x".parse().unwrap());
+	/// let expr = Expr::Fun(Symbol::new("exp").unwrap(), vec![arg]);
+	///
+	/// assert_eq!(expr.exp_to_trig(ctx).unwrap().to_string(), "cos(x) + sin(x) * i");
+	/// ```
+	pub fn exp_to_trig(&self, ctx: &mut Context) -> Result<Self, SimplifyError> {
+		let expr = self.clone().simplify_builtin(ctx)?;
+
+		let expr = expr.bottom_up(ctx, &mut |expr, ctx| match expr {
+			Self::Fun(name, args) if name.name() == "exp" && args.len() == 1 => match euler(&args[0], ctx)? {
+				Some(result) => Ok(result),
+				None => Ok(Self::Fun(name, args)),
+			},
+			expr => Ok(expr),
+		})?;
+
+		collect(reduce_imaginary(expr, ctx)?, ctx)
+	}
+
+	/// Expands sines, cosines and tangents of sums and integer multiples with the angle addition formulas.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::context::Context;
+	/// use abacas::expr::{Expr, Symbol};
+	///
+	/// let ctx = &mut Context::new();
+	/// let sin = |poly: &str| Expr::Fun(Symbol::new("sin").unwrap(), vec![Expr::Poly(Symbol::new("x").unwrap(), poly.parse().unwrap())]);
+	///
+	/// assert_eq!(sin("2x").expand_trig(ctx).unwrap().to_string(), "cos(x) * sin(x) * 2");
+	/// ```
+	pub fn expand_trig(&self, ctx: &mut Context) -> Result<Self, SimplifyError> {
+		let expr = self.clone().simplify_builtin(ctx)?;
+
+		let expr = expr.bottom_up(ctx, &mut |expr, ctx| match expr {
+			Self::Fun(name, args) if is_trig(&name) && args.len() == 1 => match expand(name.name(), &args[0], ctx)? {
+				Some(result) => Ok(result),
+				None => Ok(Self::Fun(name, args)),
+			},
+			expr => Ok(expr),
+		})?;
+
+		expr.simplify_builtin(ctx)
+	}
+
+	/// Rewrites sines, cosines and tangents as exponentials with imaginary exponents, like
+	/// `sin(x) = -i/2 (exp(ix) - exp(-ix))`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::context::Context;
+	/// use abacas::expr::{Expr, Symbol};
+	///
+	/// let ctx = &mut Context::new();
+	/// let x = Expr::Poly(Symbol::new("x").unwrap(), "x".parse().unwrap());
+	/// let expr = Expr::Fun(Symbol::new("cos").unwrap(), vec![x]);
+	///
+	/// let result = expr.trig_to_exp(ctx).unwrap();
+	/// assert_eq!(result.to_string(), "(exp(-1 * i * x) + exp(i * x)) * 0.5");
+	/// assert_eq!(result.exp_to_trig(ctx).unwrap(), expr);
+	/// ```
+	pub fn trig_to_exp(&self, ctx: &mut Context) -> Result<Self, SimplifyError> {
+		let expr = self.clone().simplify_builtin(ctx)?;
+
+		let expr = expr.bottom_up(ctx, &mut |expr, ctx| match expr {
+			Self::Fun(name, args) if is_trig(&name) && args.len() == 1 => {
+				let i = imaginary(1);
+				let pos = fun("exp", (i.clone() * args[0].clone()).simplify_builtin(ctx)?);
+				let neg = fun("exp", (-i * args[0].clone()).simplify_builtin(ctx)?);
+
+				let result = match name.name() {
+					"sin" => imaginary(Number::from(-1) / 2) * (pos - neg),
+					"cos" => Self::Num(Number::from(1) / 2) * (pos + neg),
+					_ => imaginary(-1) * (pos.clone() - neg.clone()) * (pos + neg).pow(Self::neg_one()),
+				};
+
+				result.simplify_builtin(ctx)
+			}
+			expr => Ok(expr),
+		})?;
+
+		expr.simplify_builtin(ctx)
+	}
+
+	/// Simplifies trigonometric functions by evaluating them exactly where possible, shifting their arguments by
+	/// multiples of `pi / 2`, pulling out signs of negated arguments and reducing sums with the Pythagorean identity
+	/// `sin(x)^2 + cos(x)^2 = 1`. This can be enabled as a pass of [`Expr::simplify`] with [`Context::passes`].
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use abacas::context::Context;
+	/// use abacas::expr::{Expr, Symbol};
+	/// use rug::ops::Pow;
+	///
+	/// let ctx = &mut Context::new();
+	/// let x = || Expr::Poly(Symbol::new("x").unwrap(), "x".parse().unwrap());
+	/// let square = |name| Expr::Fun(Symbol::new(name).unwrap(), vec![x()]).pow(Expr::Num(2.into()));
+	///
+	/// let expr = Expr::Num(3.into()) * square("sin") + Expr::Num(3.into()) * square("cos") + x();
+	/// assert_eq!(expr.trigsimp(ctx).unwrap().to_string(), "x + 3");
+	///
+	/// // The pass is opt-in
+	/// ctx.passes.push(Expr::trigsimp);
+	/// assert_eq!((Expr::one() - square("cos")).simplify(ctx).unwrap().to_string(), "sin(x)^2");
+	/// ```
+	pub fn trigsimp(&self, ctx: &mut Context) -> Result<Self, SimplifyError> {
+		let expr = self.clone().simplify_builtin(ctx)?;
+
+		let expr = expr.bottom_up(ctx, &mut |expr, ctx| match expr {
+			Self::Add(_) => pythagorean(terms(&expr), ctx),
+			Self::Fun(name, args) if is_trig(&name) && args.len() == 1 => match evaluate(name.name(), &args[0], ctx)? {
+				Some(result) => result.simplify_builtin(ctx),
+				None => Ok(Self::Fun(name, args)),
+			},
+			// Signs pulled out of odd functions cancel in even powers
+			Self::Pow(base, exp) if is_negated(&base) && exp.is_num_and(Number::is_integer) => {
+				let Self::Num(exp) = *exp else { unreachable!() };
+				let sign = if (exp.clone() / 2).is_integer() {
+					Self::one()
+				} else {
+					Self::neg_one()
+				};
+
+				(sign * negate(*base).pow(Self::Num(exp))).simplify_builtin(ctx)
+			}
+			expr => Ok(expr),
+		})?;
+
+		expr.simplify_builtin(ctx)
+	}
+}
+
+/// Evaluates a sine, cosine or tangent exactly at rational multiples of `pi` with known values. Otherwise, multiples of
+/// `pi / 2` are removed from the argument, and odd functions pull out the sign of negated arguments. Returns [`None`] if
+/// nothing changed.
+pub(crate) fn evaluate(name: &str, arg: &Expr, ctx: &mut Context) -> Result<Option<Expr>, SimplifyError> {
+	let (turns, rest) = split_pi(arg, ctx)?;
+
+	if rest.is_num_and(Number::is_zero) {
+		match exact(name, &turns) {
+			Some(value) => return Ok(Some(value)),
+			// Poles of the tangent stay unevaluated
+			None if (turns.clone() * 2).is_integer() => return Ok(None),
+			None => {}
+		}
+	}
+
+	// Remove multiples of pi / 2 from the argument
+	let quarters = (turns.clone() * 2).floor();
+	let offset = turns - &(quarters.clone() / 2);
+	let quarter = quarters.clone().rem_euc(&Number::from(4)).to_u32().unwrap();
+
+	let mut inner = match offset.is_zero() {
+		true => rest,
+		false => (rest + pi(offset.clone())).simplify_builtin(ctx)?,
+	};
+
+	// Odd functions pull out the sign, as long as there is no offset which would be shifted again
+	let negated = offset.is_zero() && is_negated(&inner);
+
+	if negated {
+		inner = negate(inner).simplify_builtin(ctx)?;
+	}
+
+	if quarters.is_zero() && !negated {
+		return Ok(None);
+	}
+
+	let (sin, cos) = (fun("sin", inner.clone()), fun("cos", inner.clone()));
+	let sin = if negated { -sin } else { sin };
+
+	let result = match (name, quarter) {
+		("sin", 0) | ("cos", 3) => sin,
+		("sin", 1) | ("cos", 0) => cos,
+		("sin", 2) | ("cos", 1) => -sin,
+		("sin", _) | ("cos", _) => -cos,
+		(_, quarter) if quarter % 2 == 0 => {
+			let tan = fun("tan", inner);
+			if negated { -tan } else { tan }
+		}
+		_ => -(cos * sin.pow(Expr::neg_one())),
+	};
+
+	Ok(Some(result))
+}
+
+/// Internal helper to expand products of sums and collect the terms that only differ in their numeric coefficients,
+/// reducing powers of the imaginary unit in each term.
+pub(crate) fn collect(expr: Expr, ctx: &mut Context) -> Result<Expr, SimplifyError> {
+	expr.bottom_up(ctx, &mut |expr, ctx| match expr {
+		Expr::Add(_) | Expr::Mul(_) => {
+			let mut collected: Vec<(Vec<Expr>, Number)> = Vec::new();
+
+			for factors in distribute(&expr) {
+				let product = Expr::Mul(factors).simplify_builtin(ctx)?;
+				let (coeff, factors) = split_coeff(reduce_imaginary(product, ctx)?);
+
+				match collected.iter_mut().find(|(other, _)| *other == factors) {
+					Some((_, sum)) => *sum += &coeff,
+					None => collected.push((factors, coeff)),
+				}
+			}
+
+			let terms = collected.into_iter().map(|(mut factors, coeff)| {
+				factors.push(Expr::Num(coeff));
+				Expr::Mul(factors)
+			});
+
+			Expr::Add(terms.collect()).simplify_builtin(ctx)
+		}
+		expr => Ok(expr),
+	})
+}
+
+/// Internal helper to expand an expression into a sum of products, returning the factors of each product.
+fn distribute(expr: &Expr) -> Vec<Vec<Expr>> {
+	match expr {
+		Expr::Add(_) | Expr::Poly(_, _) if terms(expr).len() > 1 => terms(expr).iter().flat_map(distribute).collect(),
+		Expr::Mul(factors) => factors.iter().fold(vec![Vec::new()], |products, factor| {
+			let terms = distribute(factor);
+
+			products
+				.iter()
+				.flat_map(|product| {
+					terms.iter().map(|term| {
+						let mut product = product.clone();
+						product.extend(term.iter().cloned());
+						product
+					})
+				})
+				.collect()
+		}),
+		expr => vec![vec![expr.clone()]],
+	}
+}
+
+/// Internal helper to rewrite an exponential with an imaginary part in its exponent using Euler's formula.
+fn euler(arg: &Expr, ctx: &mut Context) -> Result<Option<Expr>, SimplifyError> {
+	let i = Symbol::new("i").unwrap();
+	let (mut real, mut imag) = (Vec::new(), Vec::new());
+
+	for term in terms(arg) {
+		if !term.has_symbol(&i) {
+			real.push(term);
+			continue;
+		}
+
+		let coeff = (term * Expr::Poly(i.clone(), Monomial::new(1, -1).into())).simplify_builtin(ctx)?;
+
+		if coeff.has_symbol(&i) {
+			return Ok(None);
+		}
+
+		imag.push(coeff);
+	}
+
+	if imag.is_empty() {
+		return Ok(None);
+	}
+
+	let imag = Expr::Add(imag).simplify_builtin(ctx)?;
+	let [cos, sin] = ["cos", "sin"].map(|name| fun(name, imag.clone()));
+	let cos = evaluate("cos", &imag, ctx)?.unwrap_or(cos);
+	let sin = evaluate("sin", &imag, ctx)?.unwrap_or(sin);
+
+	let result = match real.is_empty() {
+		true => cos + imaginary(1) * sin,
+		false => fun("exp", Expr::Add(real)) * (cos + imaginary(1) * sin),
+	};
+
+	Ok(Some(result.simplify_builtin(ctx)?))
+}
+
+/// Internal helper to get the exact value of a sine, cosine or tangent at a rational multiple of `pi`.
+fn exact(name: &str, turns: &Number) -> Option<Expr> {
+	// Values of sin(k pi / 12) for k from 0 to 6, where the cosine is a shifted sine
+	fn sin(turns: &Number) -> Option<Expr> {
+		let mut turns = turns.clone().rem_euc(&Number::from(2));
+		let negative = turns >= Number::one();
+
+		if negative {
+			turns -= &Number::one();
+		}
+
+		if turns > Number::from(1) / 2 {
+			turns = Number::one() - &turns;
+		}
+
+		let twelfths = turns * 12;
+		let sqrt = |num: i64| Expr::Num(num.into()).pow(Expr::Num(Number::from(1) / 2));
+		let quarter = |expr: Expr| Expr::Num(Number::from(1) / 4) * expr;
+
+		let value = match twelfths.to_u32()? {
+			0 => Expr::zero(),
+			1 => quarter(sqrt(6) - sqrt(2)),
+			2 => Expr::Num(Number::from(1) / 2),
+			3 => Expr::Num(Number::from(1) / 2) * sqrt(2),
+			4 => Expr::Num(Number::from(1) / 2) * sqrt(3),
+			5 => quarter(sqrt(6) + sqrt(2)),
+			_ => Expr::one(),
+		};
+
+		Some(match (negative, value) {
+			(true, Expr::Num(num)) => Expr::Num(-num),
+			(true, value) => -value,
+			(false, value) => value,
+		})
+	}
+
+	// Values of tan(k pi / 12) for k from 0 to 5
+	fn tan(turns: &Number) -> Option<Expr> {
+		let mut turns = turns.clone().rem_euc(&Number::one());
+		let negative = turns > Number::from(1) / 2;
+
+		if negative {
+			turns = Number::one() - &turns;
+		}
+
+		let twelfths = turns * 12;
+		let sqrt3 = || Expr::Num(3.into()).pow(Expr::Num(Number::from(1) / 2));
+
+		let value = match twelfths.to_u32()? {
+			0 => Expr::zero(),
+			1 => Expr::Num(2.into()) - sqrt3(),
+			2 => Expr::Num(Number::from(1) / 3) * sqrt3(),
+			3 => Expr::one(),
+			4 => sqrt3(),
+			5 => Expr::Num(2.into()) + sqrt3(),
+			_ => return None,
+		};
+
+		Some(if negative { -value } else { value })
+	}
+
+	match name {
+		"sin" => sin(turns),
+		"cos" => sin(&(turns.clone() + &(Number::from(1) / 2))),
+		_ => tan(turns),
+	}
+}
+
+/// Internal helper to expand a sine, cosine or tangent of a sum or an integer multiple.
+fn expand(name: &str, arg: &Expr, ctx: &mut Context) -> Result<Option<Expr>, SimplifyError> {
+	let mut terms = terms(arg);
+
+	// Angle addition formulas, expanding the remaining sum recursively
+	if terms.len() > 1 {
+		let first = terms.remove(0);
+		let rest = Expr::Add(terms).simplify_builtin(ctx)?;
+
+		let expanded = |name: &str, arg: &Expr, ctx: &mut Context| -> Result<Expr, SimplifyError> {
+			match expand(name, arg, ctx)? {
+				Some(result) => Ok(result),
+				None => Ok(evaluate(name, arg, ctx)?.unwrap_or_else(|| fun(name, arg.clone()))),
+			}
+		};
+
+		let result = match name {
+			"sin" => {
+				let (sin_a, cos_a) = (expanded("sin", &first, ctx)?, expanded("cos", &first, ctx)?);
+				let (sin_b, cos_b) = (expanded("sin", &rest, ctx)?, expanded("cos", &rest, ctx)?);
+				sin_a * cos_b + cos_a * sin_b
+			}
+			"cos" => {
+				let (sin_a, cos_a) = (expanded("sin", &first, ctx)?, expanded("cos", &first, ctx)?);
+				let (sin_b, cos_b) = (expanded("sin", &rest, ctx)?, expanded("cos", &rest, ctx)?);
+				cos_a * cos_b - sin_a * sin_b
+			}
+			_ => {
+				let (tan_a, tan_b) = (expanded("tan", &first, ctx)?, expanded("tan", &rest, ctx)?);
+				(tan_a.clone() + tan_b.clone()) * (Expr::one() - tan_a * tan_b).pow(Expr::neg_one())
+			}
+		};
+
+		return Ok(Some(result));
+	}
+
+	let Some((count, angle)) = multiple(&terms[0], ctx)? else {
+		return Ok(None);
+	};
+
+	// Multiple angle formulas from the binomial expansion of (cos(x) + i sin(x))^n, where the terms with odd powers of
+	// the sine make up the sine and the others the cosine
+	let sum = |odd: bool, base: &dyn Fn(u32) -> Expr| {
+		let terms = (0..=count).filter(|k| (k % 2 == 1) == odd).map(|k| {
+			let sign = if (k / 2) % 2 == 0 { 1 } else { -1 };
+			let binomial = combinatorics::binomial(&count.into(), &k.into()).unwrap();
+			Expr::Num(binomial * sign) * base(k)
+		});
+
+		Expr::Add(terms.collect())
+	};
+
+	let result = match name {
+		"tan" => {
+			let tan = |k: u32| fun("tan", angle.clone()).pow(Expr::Num(k.into()));
+			sum(true, &tan) * sum(false, &tan).pow(Expr::neg_one())
+		}
+		_ => {
+			let power = |k: u32| {
+				let sin = fun("sin", angle.clone()).pow(Expr::Num(k.into()));
+				let cos = fun("cos", angle.clone()).pow(Expr::Num((count - k).into()));
+				sin * cos
+			};
+			sum(name == "sin", &power)
+		}
+	};
+
+	Ok(Some(result))
+}
+
+/// Internal helper to create a function call with a single argument.
+fn fun(name: &str, arg: Expr) -> Expr {
+	Expr::Fun(Symbol::new(name).unwrap(), vec![arg])
+}
+
+/// Internal helper to create a multiple of the imaginary unit `i`.
+fn imaginary(coeff: impl Into<Number>) -> Expr {
+	Expr::Poly(Symbol::new("i").unwrap(), Monomial::linear(coeff).into())
+}
+
+/// Internal helper to check if a function is a sine, cosine or tangent.
+fn is_trig(name: &Symbol) -> bool {
+	matches!(name.name(), "sin" | "cos" | "tan")
+}
+
+/// Internal helper to check if an expression is written with a leading minus sign, which for sums means their first
+/// term, so that exactly one of an expression and its negation is negated.
+fn is_negated(expr: &Expr) -> bool {
+	match expr {
+		Expr::Add(terms) => terms.first().is_some_and(is_negated),
+		Expr::Mul(factors) => factors.iter().any(|factor| factor.is_num_and(Number::is_negative)),
+		Expr::Num(num) => num.is_negative(),
+		Expr::Poly(_, poly) => poly.monomials().next().is_some_and(|mono| mono.coeff.is_negative()),
+		Expr::And(_)
+		| Expr::Fun(_, _)
+		| Expr::Matrix(_)
+		| Expr::Not(_)
+		| Expr::Or(_)
+		| Expr::Piecewise(_)
+		| Expr::Pow(_, _)
+		| Expr::Rel(..) => false,
+	}
+}
+
+/// Internal helper to split an integer multiple `n x` with `n >= 2` into its factor and angle.
+fn multiple(term: &Expr, ctx: &mut Context) -> Result<Option<(u32, Expr)>, SimplifyError> {
+	let (coeff, angle) = match term {
+		Expr::Poly(sym, poly) if let Ok(mono) = poly.monomials().exactly_one() => {
+			let angle = Monomial::new(1, mono.degree.clone());
+			(mono.coeff.clone(), Expr::Poly(sym.clone(), angle.into()))
+		}
+		Expr::Mul(factors) if let Some(Expr::Num(coeff)) = factors.iter().find(|factor| factor.is_num()) => {
+			let rest = factors.iter().filter(|factor| !factor.is_num()).cloned().collect();
+			(coeff.clone(), Expr::Mul(rest).simplify_builtin(ctx)?)
+		}
+		_ => return Ok(None),
+	};
+
+	let count = coeff.to_u32();
+	Ok(count
+		.filter(|count| (2..=MAX_MULTIPLE).contains(count))
+		.map(|count| (count, angle)))
+}
+
+/// Internal helper to negate an expression, negating the terms of sums individually.
+fn negate(expr: Expr) -> Expr {
+	match expr {
+		Expr::Add(terms) => Expr::Add(terms.into_iter().map(negate).collect()),
+		expr => -expr,
+	}
+}
+
+/// Internal helper to create a rational multiple of `pi`.
+fn pi(turns: Number) -> Expr {
+	Expr::Poly(Symbol::new("pi").unwrap(), Monomial::linear(turns).into())
+}
+
+/// Internal helper to reduce sums with the Pythagorean identity, replacing pairs of terms `c sin(x)^2 + c cos(x)^2` with
+/// `c` and `c sin(x)^2 - c` with `-c cos(x)^2`, or the other way around.
+fn pythagorean(mut terms: Vec<Expr>, ctx: &mut Context) -> Result<Expr, SimplifyError> {
+	'search: loop {
+		for i in 0..terms.len() {
+			let Some((name, arg, coeff)) = split_square(&terms[i], ctx)? else {
+				continue;
+			};
+
+			for j in (0..terms.len()).filter(|&j| j != i) {
+				let replacement = match split_square(&terms[j], ctx)? {
+					Some((other, other_arg, other_coeff))
+						if other != name && other_arg == arg && other_coeff == coeff =>
+					{
+						coeff.clone()
+					}
+					_ if (terms[j].clone() + coeff.clone())
+						.simplify_builtin(ctx)?
+						.is_num_and(Number::is_zero) =>
+					{
+						let other = if name == "sin" { "cos" } else { "sin" };
+						-coeff.clone() * fun(other, arg.clone()).pow(Expr::Num(2.into()))
+					}
+					_ => continue,
+				};
+
+				terms.remove(i.max(j));
+				terms.remove(i.min(j));
+				terms.push(replacement.simplify_builtin(ctx)?);
+
+				continue 'search;
+			}
+		}
+
+		return Expr::Add(terms).simplify_builtin(ctx);
+	}
+}
+
+/// Internal helper to reduce the powers of the imaginary unit `i` in polynomials, where `i^2 = -1`.
+fn reduce_imaginary(expr: Expr, ctx: &mut Context) -> Result<Expr, SimplifyError> {
+	let i = Symbol::new("i").unwrap();
+
+	expr.bottom_up(ctx, &mut |expr, ctx| match expr {
+		Expr::Poly(sym, poly) if sym == i && poly.monomials().all(|mono| mono.degree.is_integer()) => {
+			let mut reduced = Polynomial::default();
+
+			for mono in poly.monomials() {
+				let quarter = mono.degree.clone().rem_euc(&Number::from(4)).to_u32().unwrap();
+				let coeff = if quarter >= 2 {
+					-mono.coeff.clone()
+				} else {
+					mono.coeff.clone()
+				};
+
+				reduced += Polynomial::from(Monomial::new(coeff, quarter % 2));
+			}
+
+			Expr::Poly(sym, reduced).simplify_builtin(ctx)
+		}
+		expr => Ok(expr),
+	})
+}
+
+/// Internal helper to split an argument into the rational multiple of `pi` it contains and the remaining terms.
+fn split_pi(arg: &Expr, ctx: &mut Context) -> Result<(Number, Expr), SimplifyError> {
+	let mut turns = Number::zero();
+	let mut rest = Vec::new();
+
+	for term in terms(arg) {
+		match &term {
+			Expr::Poly(sym, poly)
+				if sym.name() == "pi"
+					&& let Ok(mono) = poly.monomials().exactly_one()
+					&& mono.degree.is_one() =>
+			{
+				turns += &mono.coeff
+			}
+			_ => rest.push(term),
+		}
+	}
+
+	Ok((turns, Expr::Add(rest).simplify_builtin(ctx)?))
+}
+
+/// Internal helper to split a simplified product into its numeric coefficient and the remaining factors.
+fn split_coeff(expr: Expr) -> (Number, Vec<Expr>) {
+	let factors = match expr {
+		Expr::Mul(factors) => factors,
+		expr => vec![expr],
+	};
+
+	let mut coeff = Number::one();
+	let mut rest = Vec::new();
+
+	for factor in factors {
+		match factor {
+			Expr::Num(num) => coeff *= &num,
+			Expr::Poly(ref sym, ref poly) if let Ok(mono) = poly.monomials().exactly_one() => {
+				coeff *= &mono.coeff;
+				rest.push(Expr::Poly(sym.clone(), Monomial::new(1, mono.degree.clone()).into()));
+			}
+			factor => rest.push(factor),
+		}
+	}
+
+	(coeff, rest)
+}
+
+/// Internal helper to split a term `c sin(x)^2` or `c cos(x)^2` into the function name, its argument and the other
+/// factors.
+fn split_square(term: &Expr, ctx: &mut Context) -> Result<Option<(String, Expr, Expr)>, SimplifyError> {
+	let mut factors = match term {
+		Expr::Mul(factors) => factors.clone(),
+		_ => vec![term.clone()],
+	};
+
+	let position = factors.iter().position(|factor| match factor {
+		Expr::Pow(base, exp) => {
+			let is_square = exp.is_num_and(|exp| exp.to_u32() == Some(2));
+			is_square
+				&& matches!(&**base, Expr::Fun(name, args) if matches!(name.name(), "sin" | "cos") && args.len() == 1)
+		}
+		_ => false,
+	});
+
+	let Some(position) = position else {
+		return Ok(None);
+	};
+
+	let Expr::Pow(base, _) = factors.remove(position) else {
+		unreachable!()
+	};
+
+	let Expr::Fun(name, mut args) = *base else {
+		unreachable!()
+	};
+
+	let coeff = Expr::Mul(factors).simplify_builtin(ctx)?;
+	Ok(Some((name.name().to_owned(), args.remove(0), coeff)))
+}
+
+/// Internal helper to split a sum into its terms, writing polynomials as one term per monomial.
+fn terms(expr: &Expr) -> Vec<Expr> {
+	let items = match expr {
+		Expr::Add(items) => items.clone(),
+		_ => vec![expr.clone()],
+	};
+
+	items
+		.into_iter()
+		.flat_map(|item| match item {
+			Expr::Poly(sym, poly) => poly
+				.monomials()
+				.map(|mono| match &mono.degree {
+					degree if degree.is_zero() => Expr::Num(mono.coeff.clone()),
+					_ => Expr::Poly(sym.clone(), mono.clone().into()),
+				})
+				.collect(),
+			item => vec![item],
+		})
+		.collect()
+}
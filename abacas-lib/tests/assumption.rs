@@ -0,0 +1,138 @@
+use abacas::assumption::Assumption;
+use abacas::context::Context;
+use abacas::expr::{Expr, Symbol};
+use abacas::number::Number;
+use rug::ops::Pow;
+
+const NUM: fn(i64) -> Expr = |num| Expr::Num(num.into());
+const FRAC: fn(i64, i64) -> Expr = |numer, denom| Expr::Num(Number::from(numer) / denom);
+
+const X: fn(&str) -> Expr = |poly| Expr::Poly(Symbol::new("x").unwrap(), poly.parse().unwrap());
+const SYM: fn(&str) -> Expr = |name| Expr::Poly(Symbol::new(name).unwrap(), "x".parse().unwrap());
+const FUN: fn(&str, Expr) -> Expr = |name, arg| Expr::Fun(Symbol::new(name).unwrap(), vec![arg]);
+
+/// Helper to create a context with the given assumptions.
+fn context(assumptions: &[(&str, Assumption)]) -> Context {
+	let mut ctx = Context::new();
+
+	for &(name, assumption) in assumptions {
+		assert!(ctx.assume(Symbol::new(name).unwrap(), assumption));
+	}
+
+	ctx
+}
+
+#[test]
+fn assume() {
+	let ctx = &mut context(&[("x", Assumption::Nonnegative)]);
+	let x = || Symbol::new("x").unwrap();
+
+	assert_eq!(X("x").is_positive(ctx), None);
+	assert_eq!(X("x").is_negative(ctx), Some(false));
+
+	// Assumptions are combined
+	assert!(ctx.assume(x(), Assumption::Nonzero));
+	assert_eq!(X("x").is_positive(ctx), Some(true));
+
+	// Contradicting assumptions are rejected
+	assert!(!ctx.assume(x(), Assumption::Negative));
+	assert_eq!(X("x").is_positive(ctx), Some(true));
+}
+
+#[test]
+fn signs() {
+	let ctx = &context(&[
+		("x", Assumption::Positive),
+		("y", Assumption::Real),
+		("z", Assumption::Negative),
+	]);
+
+	assert_eq!(NUM(-3).is_negative(ctx), Some(true));
+	assert_eq!(NUM(0).is_nonnegative(ctx), Some(true));
+	assert_eq!(NUM(0).is_nonzero(ctx), Some(false));
+	assert_eq!(SYM("pi").is_positive(ctx), Some(true));
+
+	// Sums and products
+	assert_eq!(X("x^2 + 3x + 1").is_positive(ctx), Some(true));
+	assert_eq!(X("x - 1").is_positive(ctx), None);
+	assert_eq!((X("x") * SYM("z")).is_negative(ctx), Some(true));
+	assert_eq!((SYM("z") - X("x")).is_negative(ctx), Some(true));
+	assert_eq!((SYM("y") * SYM("y")).is_nonnegative(ctx), None);
+
+	// Powers
+	assert_eq!(SYM("y").pow(NUM(2)).is_nonnegative(ctx), Some(true));
+	assert_eq!(SYM("y").pow(NUM(-2)).is_positive(ctx), Some(true));
+	assert_eq!(SYM("z").pow(NUM(3)).is_negative(ctx), Some(true));
+	assert_eq!(SYM("z").pow(FRAC(1, 2)).is_real(ctx), None);
+	assert_eq!(SYM("z").pow(FRAC(1, 3)).is_negative(ctx), Some(true));
+	assert_eq!(X("x").pow(SYM("y")).is_positive(ctx), Some(true));
+
+	// Functions
+	assert_eq!(FUN("exp", SYM("y")).is_positive(ctx), Some(true));
+	assert_eq!(FUN("exp", SYM("w")).is_positive(ctx), None);
+	assert_eq!(FUN("sin", SYM("y")).is_real(ctx), Some(true));
+	assert_eq!(FUN("ln", X("x")).is_real(ctx), Some(true));
+	assert_eq!(FUN("ln", SYM("y")).is_real(ctx), None);
+
+	// Nothing is known about symbols without assumptions
+	assert_eq!(SYM("w").is_real(ctx), None);
+	assert_eq!(SYM("w").pow(NUM(2)).is_nonnegative(ctx), None);
+}
+
+#[test]
+fn integers() {
+	let ctx = &context(&[("n", Assumption::Integer), ("x", Assumption::Positive)]);
+	let n = |poly: &str| Expr::Poly(Symbol::new("n").unwrap(), poly.parse().unwrap());
+
+	assert_eq!(n("x^3 - 2x").is_integer(ctx), Some(true));
+	assert_eq!(n("0.5x").is_integer(ctx), None);
+	assert_eq!((n("x") * NUM(4) + NUM(1)).pow(NUM(2)).is_integer(ctx), Some(true));
+	assert_eq!(n("x").pow(NUM(-1)).is_integer(ctx), None);
+	assert_eq!(X("x").is_integer(ctx), None);
+	assert_eq!(FRAC(3, 2).is_integer(ctx), Some(false));
+
+	// Integers are real
+	assert_eq!(n("x^2").is_nonnegative(ctx), Some(true));
+	assert_eq!(FUN("factorial", n("x")).is_positive(ctx), None);
+	assert_eq!(FUN("factorial", n("x^2")).is_positive(ctx), Some(true));
+}
+
+#[test]
+fn simplification() {
+	let ctx = &mut context(&[("x", Assumption::Nonnegative), ("y", Assumption::Positive)]);
+	let sqrt = |expr: Expr| expr.pow(FRAC(1, 2));
+
+	assert_eq!(sqrt(X("x^2")).simplify(ctx).unwrap(), X("x"));
+	assert_eq!(sqrt(X("9x^4")).simplify(ctx).unwrap(), X("3x^2"));
+	assert_eq!(
+		sqrt(X("x") * SYM("y")).simplify(ctx).unwrap().to_string(),
+		"x^0.5 * y^0.5"
+	);
+	assert_eq!(
+		SYM("y")
+			.pow(SYM("a"))
+			.pow(FRAC(1, 2))
+			.simplify(ctx)
+			.unwrap()
+			.to_string(),
+		"y^(0.5a)"
+	);
+
+	// Symbols with unknown signs are kept
+	assert_eq!(
+		sqrt(SYM("z").pow(NUM(2))).simplify(ctx).unwrap().to_string(),
+		"(z^2)^0.5"
+	);
+	assert_eq!(sqrt(X("-x^2")).simplify(ctx).unwrap().to_string(), "(-x^2)^0.5");
+
+	// Even powers of real symbols are nonnegative, but the root is not the symbol itself
+	let ctx = &mut context(&[("r", Assumption::Real), ("n", Assumption::Integer)]);
+	assert_eq!(
+		sqrt(SYM("r").pow(NUM(2))).simplify(ctx).unwrap().to_string(),
+		"(r^2)^0.5"
+	);
+	assert_eq!(
+		sqrt(SYM("n").pow(NUM(2))).simplify(ctx).unwrap().to_string(),
+		"(n^2)^0.5"
+	);
+}
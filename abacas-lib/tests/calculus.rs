@@ -0,0 +1,214 @@
+use std::collections::HashMap;
+
+use abacas::assumption::Assumption;
+use abacas::context::Context;
+use abacas::expr::{Expr, Symbol};
+use abacas::interval::Interval;
+use abacas::number::Number;
+use abacas::polynomial::Polynomial;
+use abacas::stdlib::StdLib;
+
+const NUM: fn(i64) -> Expr = |num| Expr::Num(num.into());
+const FRAC: fn(i64, i64) -> Expr = |numer, denom| Expr::Num(Number::from(numer) / denom);
+
+const X: fn(&str) -> Expr = |poly| Expr::Poly(Symbol::new("x").unwrap(), poly.parse().unwrap());
+const FUN: fn(&str, Expr) -> Expr = |name, arg| Expr::Fun(Symbol::new(name).unwrap(), vec![arg]);
+const POW: fn(Expr, Expr) -> Expr = |base, exp| Expr::Pow(base.into(), exp.into());
+
+/// Helper to check that an expression is integrated, by comparing the derivative of the antiderivative with the
+/// integrand at points between `lo` and `hi` where both can be evaluated.
+fn check(expr: &Expr, lo: i64, hi: i64) {
+	let x = Symbol::new("x").unwrap();
+	let anti = expr.integrate(&x, &mut Context::new()).unwrap();
+
+	assert!(
+		!matches!(&anti, Expr::Fun(name, _) if name.name() == "integrate"),
+		"no antiderivative for {expr}"
+	);
+
+	let diff = (anti.derivative(&x) - expr.clone())
+		.simplify(&mut Context::new())
+		.unwrap();
+	let mut evaluated = 0;
+
+	for i in 0..=20 {
+		let point = Number::from(lo) + &(Number::from(hi - lo) * i / 20) + &(Number::from(1) / 1000);
+		let vars = HashMap::from([(x.clone(), Interval::point(point))]);
+
+		let Some(value) = diff.eval_interval(&vars, 128) else {
+			continue;
+		};
+
+		let tolerance = Number::from(1) / 1_000_000_000;
+		assert!(
+			value.contains_zero() && value.width() < tolerance,
+			"wrong antiderivative {anti} of {expr}"
+		);
+
+		evaluated += 1;
+	}
+
+	assert!(evaluated > 0, "could not evaluate {anti}");
+}
+
+#[test]
+fn derivative() {
+	let ctx = &mut Context::new();
+	let x = Symbol::new("x").unwrap();
+	let diff = |expr: Expr, ctx: &mut Context| expr.derivative(&x).simplify(ctx).unwrap();
+
+	assert_eq!(diff(X("x^3 - 2x + x^-1"), ctx), X("3x^2 - 2 - x^-2"));
+	assert_eq!(diff(FUN("exp", X("2x")), ctx).to_string(), "exp(2x) * 2");
+	assert_eq!(diff(FUN("ln", X("x")), ctx), X("x^-1"));
+	assert_eq!(
+		diff(X("x^2") * FUN("sin", X("x")), ctx).to_string(),
+		"cos(x) * x^2 + sin(x) * 2x"
+	);
+	assert_eq!(diff(POW(NUM(2), X("x")), ctx).to_string(), "ln(2) * 2^x");
+
+	// Other symbols are constant, unknown functions stay unevaluated
+	let y = Expr::Poly(Symbol::new("y").unwrap(), "x^2".parse().unwrap());
+	assert_eq!(diff(y * X("x"), ctx).to_string(), "y^2");
+	assert_eq!(diff(FUN("gamma", X("x^2")), ctx).to_string(), "diff(gamma(x^2), x)");
+	assert_eq!(diff(FUN("gamma", NUM(2)), ctx), NUM(0));
+
+	// The derivative of an unevaluated integral is the integrand
+	let integral = Expr::Fun(Symbol::new("integrate").unwrap(), vec![FUN("gamma", X("x")), X("x")]);
+	assert_eq!(diff(integral, ctx), FUN("gamma", X("x")));
+}
+
+#[test]
+fn rational_functions() {
+	// Random rational functions with linear, repeated and quadratic factors in the denominator
+	for _ in 0..20 {
+		let factors = ["x - 2", "x + 3", "2x - 1", "x^2 + 1", "x^2 + x + 1", "x^2 - 2"];
+		let mut denom = Polynomial::from(1);
+
+		for _ in 0..fastrand::usize(1..4) {
+			let factor: Polynomial = fastrand::choice(factors).unwrap().parse().unwrap();
+			denom *= &factor;
+		}
+
+		let numer: Polynomial = (0..5)
+			.map(|degree| format!("{}x^{degree}", fastrand::i64(-5..=5)))
+			.collect::<Vec<_>>()
+			.join(" + ")
+			.parse()
+			.unwrap();
+
+		let x = Symbol::new("x").unwrap();
+		let expr = Expr::Poly(x.clone(), numer) / Expr::Poly(x, denom);
+
+		check(&expr.simplify(&mut Context::new()).unwrap(), 3, 8);
+	}
+
+	let x = Symbol::new("x").unwrap();
+	let ctx = &mut Context::new();
+
+	assert_eq!(X("x^-1").integrate(&x, ctx).unwrap().to_string(), "ln(x)");
+	assert_eq!(
+		POW(X("x^2 + 4"), NUM(-1)).integrate(&x, ctx).unwrap().to_string(),
+		"atan(0.5x) * 0.5"
+	);
+
+	// Irreducible factors of higher degrees are not supported
+	let expr = POW(X("x^4 + 1"), NUM(-1));
+	assert_eq!(
+		expr.integrate(&x, ctx).unwrap().to_string(),
+		"integrate((x^4 + 1)^-1, x)"
+	);
+}
+
+#[test]
+fn elementary() {
+	let integrands = [
+		// Table integrals of linear arguments
+		FUN("exp", X("2x + 1")),
+		FUN("ln", X("3x")),
+		FUN("sin", X("x")),
+		FUN("cos", X("4x - 1")),
+		FUN("tan", X("x")),
+		FUN("atan", X("2x")),
+		FUN("sqrt", X("x + 1")),
+		POW(NUM(3), X("x")),
+		POW(X("2x + 1"), FRAC(-1, 3)),
+		// Substitution
+		X("x") * FUN("cos", X("x^2")),
+		X("x^3") * POW(X("x^4 + 1"), FRAC(1, 2)),
+		FUN("exp", X("x")) / (NUM(1) + FUN("exp", X("x"))),
+		FUN("cos", X("x")) * FUN("exp", FUN("sin", X("x"))),
+		POW(X("x") * FUN("ln", X("x")), NUM(-1)),
+		// Integration by parts
+		X("x^2") * FUN("exp", X("x")),
+		X("x") * FUN("sin", X("2x")),
+		X("x") * FUN("ln", X("x")),
+		X("x") * FUN("atan", X("x")),
+		FUN("ln", X("x^2 + 1")),
+		POW(FUN("ln", X("x")), NUM(2)),
+	];
+
+	for expr in integrands {
+		check(&expr, 1, 2);
+	}
+}
+
+#[test]
+fn definite() {
+	let x = Symbol::new("x").unwrap();
+	let a = Expr::Poly(Symbol::new("a").unwrap(), "x".parse().unwrap());
+
+	let integral = |expr: Expr, lo: Expr, hi: Expr| expr.integrate_definite(&x, &lo, &hi, &mut Context::new()).unwrap();
+
+	assert_eq!(integral(X("x^2"), NUM(0), NUM(3)), NUM(9));
+	assert_eq!(integral(X("x^2"), NUM(3), NUM(0)), NUM(-9));
+	assert_eq!(integral(POW(X("x^2 + 1"), NUM(-1)), NUM(0), NUM(0)), NUM(0));
+	assert_eq!(integral(X("x^-1"), NUM(1), NUM(2)).to_string(), "ln(2)");
+	assert_eq!(
+		integral(a.clone() * X("x") + X("1"), NUM(0), NUM(2)).to_string(),
+		"2a + 2"
+	);
+	assert_eq!(
+		integral(X("x"), NUM(0), a.clone()),
+		Expr::Poly(Symbol::new("a").unwrap(), "1/2x^2".parse().unwrap())
+	);
+
+	// Integrands with poles between the bounds stay unevaluated
+	let pole = integral(X("x^-2"), NUM(-1), NUM(1));
+	assert_eq!(pole.to_string(), "integrate(x^-2, x, -1, 1)");
+
+	let pole = integral(POW(X("x^2 - 2"), NUM(-1)), NUM(0), NUM(3));
+	assert_eq!(pole.to_string(), "integrate((x^2 - 2)^-1, x, 0, 3)");
+
+	// Symbolic parameters inside the integrand could introduce poles
+	let unknown = integral(POW(X("x") + a, NUM(-1)), NUM(0), NUM(1));
+	assert_eq!(unknown.to_string(), "integrate((a + x)^-1, x, 0, 1)");
+}
+
+#[test]
+fn stdlib() {
+	let stdlib = StdLib::new();
+	let ctx = &mut Context::new();
+
+	let call = |name: &str, args: Vec<Expr>, ctx: &mut Context| {
+		let expr = Expr::Fun(Symbol::new(name).unwrap(), args);
+		stdlib.evaluate(expr, ctx)
+	};
+
+	assert_eq!(call("diff", vec![X("x^2"), X("x")], ctx), X("2x"));
+	assert_eq!(call("integrate", vec![X("2x"), X("x")], ctx), X("x^2"));
+	assert_eq!(call("integrate", vec![X("2x"), X("x"), NUM(1), NUM(3)], ctx), NUM(8));
+
+	// Definite integrals are evaluated with the StdLib
+	let result = call("integrate", vec![FUN("cos", X("x")), X("x"), NUM(0), NUM(0)], ctx);
+	assert_eq!(result, NUM(0));
+
+	// Assumptions of the context apply to the integrand
+	let ctx = &mut Context::new();
+	ctx.assume(Symbol::new("x").unwrap(), Assumption::Positive);
+	let result = call("integrate", vec![FUN("sqrt", X("x^2")), X("x")], ctx);
+	assert_eq!(result.to_string(), "0.5x^2");
+
+	// The variable has to be a symbol
+	let result = call("diff", vec![X("x^2"), NUM(2)], ctx);
+	assert_eq!(result.to_string(), "diff(x^2, 2)");
+}
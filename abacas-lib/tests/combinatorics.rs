@@ -0,0 +1,84 @@
+use abacas::combinatorics::{
+	bernoulli, binomial, catalan, factorial, fibonacci, lucas, multinomial, partitions, stirling1, stirling2,
+};
+use abacas::context::Context;
+use abacas::expr::{Expr, Symbol};
+use abacas::number::Number;
+use abacas::stdlib::StdLib;
+
+/// Helper to construct a number without type inference required.
+fn n(value: i64) -> Number {
+	value.into()
+}
+
+#[test]
+fn identities() {
+	for m in 0..20 {
+		// Pascal's rule
+		let lhs = binomial(&n(m + 1), &n(5)).unwrap();
+		let rhs = binomial(&n(m), &n(4)).unwrap() + &binomial(&n(m), &n(5)).unwrap();
+		assert_eq!(lhs, rhs);
+
+		// Lucas numbers from Fibonacci numbers
+		let lhs = lucas(&n(m + 1)).unwrap();
+		let rhs = fibonacci(&n(m)).unwrap() + &fibonacci(&n(m + 2)).unwrap();
+		assert_eq!(lhs, rhs);
+
+		// Stirling numbers of the first kind sum to zero and the second kind to the Bell numbers
+		let first = (0..=m)
+			.map(|k| stirling1(&n(m + 2), &n(k)).unwrap())
+			.fold(n(0), |a, b| a + &b);
+		assert_eq!(first + &stirling1(&n(m + 2), &n(m + 1)).unwrap() + &n(1), n(0));
+
+		// Catalan numbers as a difference of binomials
+		let lhs = catalan(&n(m)).unwrap();
+		let rhs = binomial(&n(2 * m), &n(m)).unwrap() - &binomial(&n(2 * m), &n(m + 1)).unwrap();
+		assert_eq!(lhs, rhs);
+	}
+
+	let bell: Vec<_> = (0..8)
+		.map(|m| {
+			(0..=m)
+				.map(|k| stirling2(&n(m), &n(k)).unwrap())
+				.fold(n(0), |a, b| a + &b)
+		})
+		.collect();
+	assert_eq!(bell, [1, 1, 2, 5, 15, 52, 203, 877].map(n));
+}
+
+#[test]
+fn domains() {
+	assert_eq!(factorial(&n(-3)), None);
+	assert_eq!(factorial(&(n(1) / 2)), None);
+	assert_eq!(binomial(&n(5), &n(-1)), None);
+	assert_eq!(multinomial(&[n(1), n(-1)]), None);
+	assert_eq!(partitions(&n(-1)), None);
+	assert_eq!(bernoulli(&n(0)), Some(n(1)));
+
+	// Results that are too large to build are not evaluated
+	assert_eq!(factorial(&n(4000000000)), None);
+	assert_eq!(fibonacci(&n(4000000000)), None);
+	assert_eq!(partitions(&n(4000000000)), None);
+	assert_eq!(bernoulli(&n(100000)), None);
+	assert_eq!(stirling2(&n(100000), &n(2)), None);
+	assert_eq!(binomial(&(n(1) / 2), &n(4000000000)), None);
+}
+
+#[test]
+fn stdlib() {
+	let ctx = &mut Context::new();
+	let stdlib = StdLib::new();
+
+	let fun = |name: &str, args: Vec<Expr>| Expr::Fun(Symbol::new(name).unwrap(), args);
+	let var = |name: &str| Expr::Poly(Symbol::new(name).unwrap(), "x".parse().unwrap());
+
+	let expr = fun("factorial", vec![Expr::Num(n(5))]) + fun("fib", vec![Expr::Num(n(10))]);
+	let expr = stdlib.evaluate(expr, ctx).simplify(ctx).unwrap();
+	assert_eq!(expr.to_string(), "175");
+
+	let expr = stdlib.evaluate(fun("binomial", vec![var("n"), Expr::Num(n(2))]), ctx);
+	assert_eq!(expr.to_string(), "0.5n^2 - 0.5n");
+
+	let expr = stdlib.evaluate(fun("catalan", vec![var("n")]), ctx);
+	assert_eq!(expr.to_string(), "catalan(n)");
+}
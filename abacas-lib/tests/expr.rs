@@ -1,14 +1,20 @@
 use abacas::context::Context;
 use abacas::expr::{Expr, Symbol};
+use abacas::polynomial::Polynomial;
 
 const ADD: fn(Vec<Expr>, ctx: &mut Context) -> Expr = |exprs, ctx| Expr::Add(exprs).simplify(ctx).unwrap();
 const MUL: fn(Vec<Expr>, ctx: &mut Context) -> Expr = |exprs, ctx| Expr::Mul(exprs).simplify(ctx).unwrap();
 
 const COS: fn(Expr) -> Expr = |arg| Expr::Fun(Symbol::new("cos").unwrap(), vec![arg]);
+const FAC: fn(Expr) -> Expr = |arg| Expr::Fun(Symbol::new("factorial").unwrap(), vec![arg]);
 const NUM: fn(i8) -> Expr = |num| Expr::Num(num.into());
 
 const X: fn(&str) -> Expr = |poly| Expr::Poly(Symbol::new("x").unwrap(), poly.parse().unwrap());
-const Y: fn(&str) -> Expr = |poly| Expr::Poly(Symbol::new("y").unwrap(), poly.parse().unwrap());
+const Y: fn(&str) -> Expr = |poly| {
+	let y = Symbol::new("y").unwrap();
+	Expr::Poly(y.clone(), Polynomial::parse_with(poly, &y).unwrap())
+};
+const INV: fn(Expr) -> Expr = |expr| Expr::Pow(expr.into(), NUM(-1).into());
 
 #[test]
 fn add() {
@@ -16,10 +22,10 @@ fn add() {
 	let expr = ADD(vec![], ctx);
 	assert_eq!(expr.to_string(), "0");
 
-	let expr = ADD(vec![NUM(2), X("x"), X("x + 2"), Y("x"), Y("-x + 2")], ctx);
+	let expr = ADD(vec![NUM(2), X("x"), X("x + 2"), Y("y"), Y("-y + 2")], ctx);
 	assert_eq!(expr.to_string(), "2x + 6");
 
-	let expr = ADD(vec![NUM(2), X("x"), X("x + 2"), Y("x"), Y("-2x + 1")], ctx);
+	let expr = ADD(vec![NUM(2), X("x"), X("x + 2"), Y("y"), Y("-2y + 1")], ctx);
 	assert_eq!(expr.to_string(), "5 + 2x - y");
 
 	let expr = ADD(vec![NUM(2), COS(NUM(0)), NUM(-3), COS(NUM(0))], ctx);
@@ -32,12 +38,56 @@ fn mul() {
 	let expr = MUL(vec![], ctx);
 	assert_eq!(expr.to_string(), "1");
 
-	let expr = MUL(vec![NUM(2), X("x"), X("x + 2"), Y("x"), Y("2x^-1")], ctx);
+	let expr = MUL(vec![NUM(2), X("x"), X("x + 2"), Y("y"), Y("2y^-1")], ctx);
 	assert_eq!(expr.to_string(), "4x^2 + 8x");
 
-	let expr = MUL(vec![NUM(2), X("x"), X("x + 2"), Y("x"), Y("x^-1 + 2")], ctx);
+	let expr = MUL(vec![NUM(2), X("x"), X("x + 2"), Y("y"), Y("y^-1 + 2")], ctx);
 	assert_eq!(expr.to_string(), "4 * (x^2 + 2x) * (y + 0.5)");
 
 	let expr = MUL(vec![NUM(2), COS(NUM(0)), NUM(-3), COS(NUM(0))], ctx);
 	assert_eq!(expr.to_string(), "-6 * cos(0)^2");
 }
+
+#[test]
+fn pow() {
+	let ctx = &mut Context::new();
+	let pow = |base: Expr, exp: Expr, ctx: &mut Context| Expr::Pow(base.into(), exp.into()).simplify(ctx).unwrap();
+
+	assert_eq!(pow(NUM(2), NUM(10), ctx).to_string(), "1024");
+
+	// Huge powers are kept unevaluated
+	let exp = Expr::Num(4_000_000_000u32.into());
+	assert_eq!(pow(NUM(2), exp, ctx).to_string(), "2^4000000000");
+
+	// Powers of polynomials are expanded up to a degree limit
+	assert_eq!(pow(X("x + 1"), NUM(2), ctx).to_string(), "x^2 + 2x + 1");
+
+	let exp = Expr::Num(100_000.into());
+	assert_eq!(pow(X("x + 1"), exp, ctx).to_string(), "(x + 1)^100000");
+
+	let exp = Expr::Num(4_000_000_000u32.into());
+	assert_eq!(pow(X("2x"), exp, ctx).to_string(), "(2x)^4000000000");
+
+	let exp = Expr::Num(4_000_000_000u32.into());
+	assert_eq!(pow(X("-x"), exp, ctx).to_string(), "x^4000000000");
+}
+
+#[test]
+fn factorials() {
+	let ctx = &mut Context::new();
+	let expr = MUL(vec![FAC(X("x")), INV(FAC(X("x - 1")))], ctx);
+	assert_eq!(expr.to_string(), "x");
+
+	let expr = MUL(vec![FAC(X("x - 2")), INV(FAC(X("x + 1")))], ctx);
+	assert_eq!(expr.to_string(), "(x^3 - x)^-1");
+
+	let expr = MUL(vec![NUM(3), FAC(X("x + 2")), INV(FAC(X("x")))], ctx);
+	assert_eq!(expr.to_string(), "3x^2 + 9x + 6");
+
+	let expr = MUL(vec![FAC(X("x")), INV(FAC(Y("y")))], ctx);
+	assert_eq!(expr.to_string(), "factorial(x) * factorial(y)^-1");
+
+	// Too many factors in between are not expanded
+	let expr = MUL(vec![FAC(X("x + 4000000000")), INV(FAC(X("x")))], ctx);
+	assert_eq!(expr.to_string(), "factorial(x + 4000000000) * factorial(x)^-1");
+}
@@ -0,0 +1,243 @@
+use abacas::context::Context;
+use abacas::expr::{Expr, Symbol};
+use abacas::groebner::{Algorithm, GroebnerBasis, solve};
+use abacas::matrix::Matrix;
+use abacas::multivariate::{MonomialOrder, MultiPolynomial};
+use abacas::relation::Relation;
+use abacas::stdlib::StdLib;
+use rug::ops::Pow;
+
+const NUM: fn(i64) -> Expr = |num| Expr::Num(num.into());
+
+const SYM: fn(&str) -> Expr = |name| Expr::Poly(Symbol::new(name).unwrap(), "x".parse().unwrap());
+const FUN: fn(&str, Vec<Expr>) -> Expr = |name, args| Expr::Fun(Symbol::new(name).unwrap(), args);
+const EQ: fn(Expr, Expr) -> Expr = |lhs, rhs| Expr::Rel(Relation::Eq, lhs.into(), rhs.into());
+
+/// Helper to create the variables with the given names.
+fn vars(names: &[&str]) -> Vec<Symbol> {
+	names.iter().map(|&name| Symbol::new(name).unwrap()).collect()
+}
+
+/// Helper to convert expressions into polynomials in the given variables.
+fn polys(exprs: Vec<Expr>, vars: &[Symbol], order: MonomialOrder) -> Vec<MultiPolynomial> {
+	let exprs = exprs
+		.into_iter()
+		.map(|expr| expr.simplify(&mut Context::new()).unwrap());
+	exprs
+		.map(|expr| MultiPolynomial::from_expr(&expr, vars, order).unwrap())
+		.collect()
+}
+
+/// Helper to format the polynomials of a basis.
+fn basis(basis: &GroebnerBasis) -> Vec<String> {
+	basis.polynomials().iter().map(ToString::to_string).collect()
+}
+
+#[test]
+fn bases() {
+	let vars = vars(&["x", "y", "z"]);
+	let (x, y, z) = (SYM("x"), SYM("y"), SYM("z"));
+
+	// The cyclic system `x + y + z = x y + y z + z x = x y z - 1 = 0`
+	let cyclic = || {
+		vec![
+			x.clone() + y.clone() + z.clone(),
+			x.clone() * y.clone() + y.clone() * z.clone() + z.clone() * x.clone(),
+			x.clone() * y.clone() * z.clone() - NUM(1),
+		]
+	};
+
+	let lex = ["z^3 - 1", "y^2 + y * z + z^2", "x + y + z"];
+	let graded = ["x + y + z", "y^2 + y * z + z^2", "z^3 - 1"];
+
+	for (order, expected) in [
+		(MonomialOrder::Lex, lex),
+		(MonomialOrder::GrLex, graded),
+		(MonomialOrder::GrevLex, graded),
+	] {
+		let polys = polys(cyclic(), &vars, order);
+		let buchberger = GroebnerBasis::new(&polys, Algorithm::Buchberger);
+
+		assert_eq!(basis(&buchberger), expected);
+		assert_eq!(buchberger, GroebnerBasis::new(&polys, Algorithm::F4));
+		assert!(buchberger.is_zero_dimensional());
+	}
+
+	// Orders that compare the total degree first can give smaller bases
+	let exprs = || vec![x.clone().pow(NUM(2)) - y.clone(), x.clone().pow(NUM(3)) - z.clone()];
+
+	let lex = GroebnerBasis::new(&polys(exprs(), &vars, MonomialOrder::Lex), Algorithm::F4);
+	assert_eq!(basis(&lex), ["y^3 - z^2", "x * z - y^2", "x * y - z", "x^2 - y"]);
+	assert!(!lex.is_zero_dimensional());
+
+	let grevlex = GroebnerBasis::new(&polys(exprs(), &vars, MonomialOrder::GrevLex), Algorithm::F4);
+	assert_eq!(basis(&grevlex), ["y^2 - x * z", "x * y - z", "x^2 - y"]);
+
+	// Inconsistent systems have the basis `1`
+	let exprs = vec![x.clone() * y.clone() - NUM(1), x.clone()];
+	let inconsistent = GroebnerBasis::new(&polys(exprs, &vars, MonomialOrder::GrevLex), Algorithm::Buchberger);
+	assert_eq!(basis(&inconsistent), ["1"]);
+	assert_eq!(inconsistent.triangular(), Some(Vec::new()));
+}
+
+#[test]
+fn membership() {
+	let vars = vars(&["x", "y"]);
+	let (x, y) = (SYM("x"), SYM("y"));
+
+	let exprs = vec![x.clone().pow(NUM(2)) - y.clone(), x.clone() * y.clone() - NUM(1)];
+	let ideal = GroebnerBasis::new(&polys(exprs, &vars, MonomialOrder::GrevLex), Algorithm::F4);
+
+	let members = vec![
+		x.clone().pow(NUM(3)) - NUM(1),
+		y.clone().pow(NUM(3)) - NUM(1),
+		(x.clone() + y.clone()) * (x.clone().pow(NUM(2)) - y.clone()),
+	];
+	assert!(
+		polys(members, &vars, MonomialOrder::GrevLex)
+			.iter()
+			.all(|poly| ideal.contains(poly))
+	);
+
+	let others = vec![x.clone() - NUM(1), x.clone().pow(NUM(2)) + y.clone(), y.clone()];
+	assert!(
+		polys(others, &vars, MonomialOrder::GrevLex)
+			.iter()
+			.all(|poly| !ideal.contains(poly))
+	);
+
+	// Reducing gives the unique normal form modulo the ideal
+	let poly = &polys(vec![x.clone().pow(NUM(4)) + y.clone()], &vars, MonomialOrder::GrevLex)[0];
+	assert_eq!(ideal.reduce(poly).to_string(), "x + y");
+}
+
+#[test]
+fn elimination() {
+	let vars = vars(&["t", "x", "y"]);
+	let (t, x, y) = (SYM("t"), SYM("x"), SYM("y"));
+
+	// Implicitizing the twisted cubic `x = t^2, y = t^3` eliminates `t`
+	let exprs = vec![x.clone() - t.clone().pow(NUM(2)), y.clone() - t.clone().pow(NUM(3))];
+	let ideal = GroebnerBasis::new(&polys(exprs, &vars, MonomialOrder::Elimination(1)), Algorithm::F4);
+
+	let eliminated = ideal.eliminate(1).unwrap();
+	let eliminated = eliminated.iter().map(ToString::to_string).collect::<Vec<_>>();
+	assert_eq!(eliminated, ["x^3 - y^2"]);
+
+	// Eliminating more variables than the order allows is not possible
+	assert_eq!(ideal.eliminate(2), None);
+
+	let exprs = vec![x.clone() + t.clone(), y.clone() - t.clone()];
+	let ideal = GroebnerBasis::new(&polys(exprs, &vars, MonomialOrder::Lex), Algorithm::Buchberger);
+	let eliminated = ideal.eliminate(1).unwrap();
+	assert_eq!(
+		eliminated.iter().map(ToString::to_string).collect::<Vec<_>>(),
+		["x + y"]
+	);
+}
+
+#[test]
+fn solving() {
+	let ctx = &mut Context::new();
+	let (x, y, z) = (SYM("x"), SYM("y"), SYM("z"));
+
+	let mut solve = |equations: Vec<Expr>, names: &[&str]| {
+		let solutions = solve(&equations, &vars(names), ctx).unwrap()?;
+		let solutions = solutions
+			.iter()
+			.map(|values| values.iter().map(ToString::to_string).collect::<Vec<_>>().join(", "));
+
+		Some(solutions.collect::<Vec<_>>())
+	};
+
+	// Rational and quadratic solutions
+	let equations = vec![
+		EQ(x.clone().pow(NUM(2)) + y.clone().pow(NUM(2)), NUM(25)),
+		EQ(x.clone() - y.clone(), NUM(1)),
+	];
+	assert_eq!(solve(equations, &["x", "y"]).unwrap(), ["-3, -4", "4, 3"]);
+
+	let equations = vec![x.clone().pow(NUM(2)) - NUM(2), y.clone().pow(NUM(2)) - NUM(3)];
+	assert_eq!(
+		solve(equations, &["x", "y"]).unwrap(),
+		[
+			"-1 * 2^0.5, -1 * 3^0.5",
+			"2^0.5, -1 * 3^0.5",
+			"-1 * 2^0.5, 3^0.5",
+			"2^0.5, 3^0.5"
+		]
+	);
+
+	// Other algebraic values are real roots of the minimal polynomials of the variables
+	let equations = vec![EQ(x.clone().pow(NUM(3)), NUM(2)), EQ(y.clone(), x.clone().pow(NUM(2)))];
+	assert_eq!(
+		solve(equations, &["x", "y"]).unwrap(),
+		["root(x^3 - 2, 1), root(x^3 - 4, 1)"]
+	);
+
+	// Repeated solutions are only given once, and complex ones are left out
+	let equations = vec![
+		(x.clone() - NUM(1)).pow(NUM(2)),
+		y.clone().pow(NUM(2)) - x.clone(),
+		z.clone().pow(NUM(2)) + NUM(1),
+	];
+	assert_eq!(solve(equations, &["x", "y", "z"]).unwrap(), Vec::<String>::new());
+
+	let equations = vec![(x.clone() - NUM(1)).pow(NUM(2)), y.clone().pow(NUM(2)) - x.clone()];
+	assert_eq!(solve(equations, &["x", "y"]).unwrap(), ["1, -1", "1, 1"]);
+
+	// Systems with infinitely many solutions or non-polynomial equations
+	assert_eq!(solve(vec![x.clone() * y.clone() - NUM(1)], &["x", "y"]), None);
+	assert_eq!(solve(vec![FUN("sin", vec![x.clone()])], &["x"]), None);
+}
+
+#[test]
+fn stdlib() {
+	let stdlib = StdLib::new();
+	let ctx = &mut Context::new();
+	let (x, y) = (SYM("x"), SYM("y"));
+
+	let mut call = |name: &str, args: Vec<Expr>| stdlib.evaluate(FUN(name, args), ctx).to_string();
+	let list = |entries: Vec<Expr>| Expr::Matrix(Matrix::column(entries));
+
+	let polys = || {
+		list(vec![
+			x.clone().pow(NUM(2)) + y.clone().pow(NUM(2)) - NUM(1),
+			x.clone() - y.clone(),
+		])
+	};
+	let vars = || list(vec![x.clone(), y.clone()]);
+
+	assert_eq!(call("groebner", vec![polys(), vars()]), "[[y^2 - 0.5], [x - y]]");
+	assert_eq!(
+		call("groebner", vec![polys(), vars(), SYM("grevlex")]),
+		"[[x - y], [y^2 - 0.5]]"
+	);
+	assert_eq!(
+		call("groebner", vec![polys(), vars(), SYM("other")]),
+		"groebner([[-1 + x^2 + y^2], [x - y]], [[x], [y]], other)"
+	);
+
+	// Solutions are a disjunction of conditions
+	let equations = Expr::And(vec![
+		EQ(x.clone() * y.clone(), NUM(2)),
+		EQ(x.clone() + y.clone(), NUM(3)),
+	]);
+	assert_eq!(
+		call("solve", vec![equations, vars()]),
+		"x == 2 and y == 1 or x == 1 and y == 2"
+	);
+
+	assert_eq!(
+		call("solve", vec![EQ(x.clone().pow(NUM(2)), NUM(4)), x.clone()]),
+		"x == -2 or x == 2"
+	);
+	assert_eq!(
+		call("solve", vec![EQ(x.clone().pow(NUM(2)), NUM(-4)), x.clone()]),
+		"false"
+	);
+	assert_eq!(
+		call("solve", vec![EQ(x.clone() * y.clone(), NUM(1)), vars()]),
+		"solve(x * y == 1, [[x], [y]])"
+	);
+}
@@ -0,0 +1,131 @@
+use abacas::context::Context;
+use abacas::expr::{Expr, Symbol};
+use abacas::matrix::Matrix;
+use abacas::monomial::Monomial;
+use abacas::number::Number;
+use abacas::polynomial::Polynomial;
+use abacas::stdlib::StdLib;
+
+const NUM: fn(i64) -> Expr = |num| Expr::Num(num.into());
+
+const SYM: fn(&str) -> Expr = |name| Expr::Poly(Symbol::new(name).unwrap(), "x".parse().unwrap());
+const FUN: fn(&str, Vec<Expr>) -> Expr = |name, args| Expr::Fun(Symbol::new(name).unwrap(), args);
+
+/// Helper to create points from pairs of integers.
+fn points(pairs: &[(i64, i64)]) -> Vec<(Number, Number)> {
+	pairs.iter().map(|&(x, y)| (x.into(), y.into())).collect()
+}
+
+#[test]
+fn interpolate() {
+	let poly = |pairs: &[(i64, i64)]| Polynomial::interpolate(&points(pairs)).map(|poly| poly.to_string());
+
+	assert_eq!(poly(&[(1, 1), (2, 8), (3, 27), (4, 64)]).unwrap(), "x^3");
+	assert_eq!(poly(&[(0, 1), (2, 2)]).unwrap(), "0.5x + 1");
+	assert_eq!(poly(&[(5, 3)]).unwrap(), "3");
+	assert_eq!(poly(&[]).unwrap(), "0");
+
+	// Extra points on the same polynomial do not raise the degree
+	assert_eq!(poly(&[(-2, 4), (-1, 1), (0, 0), (1, 1), (2, 4)]).unwrap(), "x^2");
+
+	// Fractional points stay exact
+	let points = [
+		(Number::from(1) / 2, Number::from(1) / 4),
+		(Number::from(1) / 3, Number::from(1) / 9),
+	];
+	let result = Polynomial::interpolate(&points).unwrap();
+	let expected = [
+		Monomial::new(Number::from(5) / 6, 1),
+		Monomial::new(Number::from(-1) / 6, 0),
+	];
+	assert_eq!(result, Polynomial::new(expected));
+
+	// Newton and Lagrange agree
+	let pairs = [(-3, 7), (-1, -2), (0, 4), (2, 1), (5, -6)];
+	assert_eq!(
+		Polynomial::interpolate(&self::points(&pairs)),
+		Polynomial::interpolate_lagrange(&self::points(&pairs))
+	);
+
+	// Points must have different `x`
+	assert_eq!(poly(&[(1, 2), (3, 4), (1, 5)]), None);
+	assert_eq!(Polynomial::interpolate_lagrange(&self::points(&[(1, 2), (1, 2)])), None);
+}
+
+#[test]
+fn hermite() {
+	let hermite = |points: &[(i64, &[i64])]| {
+		let points = points
+			.iter()
+			.map(|&(x, values)| (x.into(), values.iter().map(|&value| value.into()).collect()))
+			.collect::<Vec<_>>();
+
+		Polynomial::interpolate_hermite(&points).map(|poly| poly.to_string())
+	};
+
+	// Values and derivatives at a single point give the Taylor polynomial
+	assert_eq!(hermite(&[(0, &[1, 1, 1, 1])]).unwrap(), "1/6x^3 + 0.5x^2 + x + 1");
+	assert_eq!(hermite(&[(1, &[0, 0, 2])]).unwrap(), "x^2 - 2x + 1");
+
+	// Points may have different amounts of derivatives
+	assert_eq!(hermite(&[(-1, &[1, -2]), (1, &[1, 2])]).unwrap(), "x^2");
+	assert_eq!(hermite(&[(0, &[0, 0]), (1, &[1]), (2, &[8, 12])]).unwrap(), "x^3");
+
+	assert_eq!(hermite(&[(0, &[1]), (0, &[1])]), None);
+	assert_eq!(hermite(&[(0, &[])]), None);
+}
+
+#[test]
+fn fit() {
+	let fit = |pairs: &[(i64, i64)], degree| Polynomial::fit(&points(pairs), degree).map(|poly| poly.to_string());
+
+	// The constant fit is the mean, and the linear fit is the regression line
+	assert_eq!(fit(&[(0, 1), (1, 2), (2, 6)], 0).unwrap(), "3");
+	assert_eq!(fit(&[(0, 1), (1, 2), (2, 6)], 1).unwrap(), "2.5x + 0.5");
+	assert_eq!(fit(&[(-1, 0), (1, 1), (3, 5)], 1).unwrap(), "1.25x + 0.75");
+
+	// Points on a polynomial of at most the degree are fitted exactly
+	assert_eq!(fit(&[(-2, 4), (-1, 1), (0, 0), (1, 1), (2, 4)], 2).unwrap(), "x^2");
+	assert_eq!(fit(&[(-2, 4), (-1, 1), (0, 0), (1, 1), (2, 4)], 3).unwrap(), "x^2");
+
+	// The fit needs at least as many different `x` as coefficients
+	assert_eq!(fit(&[(1, 1), (1, 2), (2, 3)], 2), None);
+	assert_eq!(fit(&[(1, 1), (1, 2), (2, 3)], 1).unwrap(), "1.5x");
+}
+
+#[test]
+fn stdlib() {
+	let stdlib = StdLib::new();
+	let ctx = &mut Context::new();
+
+	let mut call = |name: &str, args: Vec<Expr>| stdlib.evaluate(FUN(name, args), ctx).to_string();
+	let rows = |rows: &[&[i64]]| {
+		let rows = rows.iter().map(|row| row.iter().map(|&num| NUM(num)).collect());
+		Expr::Matrix(Matrix::from_rows(rows.collect()).unwrap())
+	};
+
+	let points = rows(&[&[0, 1], &[1, 0], &[2, 3]]);
+	assert_eq!(call("interpolate", vec![points.clone(), SYM("t")]), "2t^2 - 3t + 1");
+	assert_eq!(call("fit", vec![points.clone(), NUM(1), SYM("x")]), "x + 1/3");
+	assert_eq!(call("fit", vec![points.clone(), NUM(0), SYM("x")]), "4/3");
+
+	let points = rows(&[&[-1, 1, -2], &[1, 1, 2]]);
+	assert_eq!(call("interpolate_hermite", vec![points, SYM("x")]), "x^2");
+
+	// Points need numbers, two columns and different `x`
+	let points = rows(&[&[0, 1, 2]]);
+	assert_eq!(
+		call("interpolate", vec![points.clone(), SYM("x")]),
+		"interpolate([[0, 1, 2]], x)"
+	);
+	assert_eq!(call("fit", vec![points, NUM(-1), SYM("x")]), "fit([[0, 1, 2]], -1, x)");
+
+	let points = Expr::Matrix(Matrix::from_rows(vec![vec![NUM(0), SYM("a")]]).unwrap());
+	assert_eq!(call("interpolate", vec![points, SYM("x")]), "interpolate([[0, a]], x)");
+
+	let points = rows(&[&[0, 1], &[0, 2]]);
+	assert_eq!(
+		call("interpolate", vec![points, SYM("x")]),
+		"interpolate([[0, 1], [0, 2]], x)"
+	);
+}
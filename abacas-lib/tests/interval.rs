@@ -0,0 +1,218 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use abacas::context::Context;
+use abacas::expr::{Expr, Symbol};
+use abacas::interval::Interval;
+use abacas::monomial::Monomial;
+use abacas::number::Number;
+use abacas::polynomial::Polynomial;
+use abacas::stdlib::StdLib;
+
+/// Helper to construct a random fraction between -10 and 10.
+fn random_fraction() -> Number {
+	Number::from(fastrand::i32(-10_000..=10_000)) / 1000
+}
+
+/// Helper to construct a random interval between -10 and 10.
+fn random_interval() -> Interval {
+	let (a, b) = (random_fraction(), random_fraction());
+	Interval::new(a.clone().min(b.clone()), a.max(b))
+}
+
+/// Helper to construct a random number inside of an interval.
+fn random_inside(interval: &Interval) -> Number {
+	interval.lo().clone() + &(interval.width() * fastrand::u8(..) / 255)
+}
+
+/// Helper to approximate a number as a float.
+fn to_f64(num: &Number) -> f64 {
+	let (numer, denom) = num.clone().ratio();
+	numer.to_string().parse::<f64>().unwrap() / denom.to_string().parse::<f64>().unwrap()
+}
+
+#[test]
+fn arithmetic() {
+	for _ in 0..1000 {
+		let (a, b) = (random_interval(), random_interval());
+		let (x, y) = (random_inside(&a), random_inside(&b));
+
+		assert!((a.clone() + b.clone()).contains(&(x.clone() + &y)));
+		assert!((a.clone() - b.clone()).contains(&(x.clone() - &y)));
+		assert!((a.clone() * b.clone()).contains(&(x.clone() * &y)));
+
+		if let Some(quotient) = a.checked_div(&b) {
+			assert!(quotient.contains(&(x.clone() / &y)));
+		}
+
+		assert!(a.pow(&2.into(), 64).unwrap().contains(&x.clone().square()));
+		assert!(a.abs().contains(&x.abs()));
+	}
+
+	// Powers with too large endpoints are not enclosed
+	let interval = Interval::new(1.into(), 3.into());
+	assert_eq!(interval.pow(&4000000000u32.into(), 64), None);
+	assert_eq!(interval.pow(&(Number::from(1u64 << 40) / 3), 64), None);
+	assert_eq!(
+		Interval::new(0.into(), 1.into()).pow(&4000000000u32.into(), 64),
+		Some(Interval::new(0.into(), 1.into()))
+	);
+}
+
+#[test]
+fn elementary() {
+	type Function = (fn(&Interval, u32) -> Option<Interval>, fn(f64) -> f64);
+
+	let functions: [Function; 7] = [
+		(Interval::atan, f64::atan),
+		(Interval::cos, f64::cos),
+		(Interval::exp, f64::exp),
+		(Interval::ln, f64::ln),
+		(Interval::sin, f64::sin),
+		(Interval::sqrt, f64::sqrt),
+		(Interval::tan, f64::tan),
+	];
+
+	for _ in 0..200 {
+		let interval = random_interval();
+		let x = random_inside(&interval);
+		let float = to_f64(&x);
+
+		for (function, float_function) in functions {
+			let Some(low) = function(&interval, 64) else {
+				continue;
+			};
+
+			// Higher precision never widens the enclosure of a single point
+			let point = function(&Interval::point(x.clone()), 64).unwrap();
+			let high = function(&Interval::point(x.clone()), 256).unwrap();
+
+			assert!(low.intersect(&point).is_some_and(|both| both == point));
+			assert!(point.intersect(&high).is_some_and(|both| both == high));
+
+			let expected = float_function(float);
+			let actual = to_f64(&high.midpoint());
+			assert!((expected - actual).abs() <= 1e-9 * expected.abs().max(1.0));
+		}
+	}
+
+	// Domains of the functions
+	let negative = Interval::new((-2).into(), (-1).into());
+	assert_eq!(negative.ln(64), None);
+	assert_eq!(negative.sqrt(64), None);
+	assert_eq!(Interval::new(1.into(), 2.into()).tan(64), None);
+}
+
+#[test]
+fn eval_interval() {
+	let x = Symbol::new("x").unwrap();
+	let poly: Polynomial = "x^3 - 4x^2 + x - 7".parse().unwrap();
+	let expr = Expr::Poly(x.clone(), poly.clone()) * Expr::Fun(Symbol::new("exp").unwrap(), vec![Expr::zero()]);
+
+	for _ in 0..200 {
+		let interval = random_interval();
+		let value = poly.eval(&random_inside(&interval)).unwrap();
+		let vars = HashMap::from([(x.clone(), interval)]);
+
+		assert!(expr.eval_interval(&vars, 64).unwrap().contains(&value));
+	}
+
+	// Unknown symbols and functions cannot be evaluated
+	let y = Expr::Poly(Symbol::new("y").unwrap(), "x".parse().unwrap());
+	assert_eq!(y.eval_interval(&HashMap::new(), 64), None);
+
+	let gamma = Expr::Fun(Symbol::new("gamma").unwrap(), vec![Expr::one()]);
+	assert_eq!(gamma.eval_interval(&HashMap::new(), 64), None);
+
+	// Signs of constant expressions
+	let e = Expr::Poly(Symbol::new("e").unwrap(), "x".parse().unwrap());
+	let pi = Expr::Poly(Symbol::new("pi").unwrap(), "x".parse().unwrap());
+
+	assert_eq!(
+		(e.clone() - pi.clone()).eval_sign(&HashMap::new()),
+		Some(Ordering::Less)
+	);
+	assert_eq!((pi.clone() - pi).eval_sign(&HashMap::new()), None);
+	assert_eq!(
+		(e.clone() - e)
+			.simplify(&mut Context::new())
+			.unwrap()
+			.eval_sign(&HashMap::new()),
+		Some(Ordering::Equal)
+	);
+}
+
+#[test]
+fn isolate_roots() {
+	let width = Number::from(1) / 1_000_000;
+
+	for _ in 0..50 {
+		// Build a polynomial from random roots, some of them repeated
+		let roots: Vec<_> = (0..fastrand::usize(1..6)).map(|_| random_fraction()).collect();
+		let poly = roots.iter().fold(Polynomial::from(fastrand::i32(1..10)), |poly, root| {
+			let factor = Polynomial::from(Monomial::linear(1)) - root.clone();
+			let poly = poly * &factor;
+
+			if fastrand::bool() { poly * &factor } else { poly }
+		});
+
+		let mut expected = roots.clone();
+		expected.sort();
+		expected.dedup();
+
+		let isolated = poly.isolate_roots(&width).unwrap();
+		assert_eq!(isolated.len(), expected.len());
+
+		for (interval, root) in isolated.iter().zip(&expected) {
+			assert!(interval.contains(root));
+			assert!(interval.width() <= width);
+		}
+	}
+
+	// Negative degrees do not introduce a root at zero
+	let poly: Polynomial = "x - 4x^-1".parse().unwrap();
+	let roots = poly.isolate_roots(&width).unwrap();
+
+	assert_eq!(roots.len(), 2);
+	assert!(roots[0].contains(&(-2).into()));
+	assert!(roots[1].contains(&2.into()));
+
+	// Polynomials without real roots
+	let poly: Polynomial = "x^4 + 1".parse().unwrap();
+	assert_eq!(poly.isolate_roots(&width), Some(Vec::new()));
+
+	assert_eq!(Polynomial::ZERO.isolate_roots(&width), None);
+	assert_eq!("x^0.5".parse::<Polynomial>().unwrap().isolate_roots(&width), None);
+}
+
+#[test]
+fn stdlib() {
+	let stdlib = StdLib::new();
+	let mut ctx = Context::new();
+
+	let call = |name: &str, arg: Expr, ctx: &mut Context| {
+		let expr = Expr::Fun(Symbol::new(name).unwrap(), vec![arg]);
+		stdlib.evaluate(expr, ctx)
+	};
+
+	assert_eq!(call("exp", Expr::zero(), &mut ctx), Expr::one());
+	assert_eq!(call("ln", Expr::one(), &mut ctx), Expr::zero());
+	assert_eq!(call("sin", Expr::zero(), &mut ctx), Expr::zero());
+	assert_eq!(call("cos", Expr::zero(), &mut ctx), Expr::one());
+	assert_eq!(
+		call("sqrt", Expr::Num(Number::from(9) / 4), &mut ctx),
+		Expr::Num(Number::from(3) / 2)
+	);
+
+	let two = Expr::Num(2.into());
+	let half = Expr::Num(Number::from(1) / 2);
+
+	assert_eq!(
+		call("sqrt", two.clone(), &mut ctx),
+		Expr::Pow(two.clone().into(), half.into())
+	);
+	assert_eq!(
+		call("exp", two.clone(), &mut ctx),
+		Expr::Fun(Symbol::new("exp").unwrap(), vec![two])
+	);
+}
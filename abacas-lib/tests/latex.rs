@@ -0,0 +1,147 @@
+use abacas::context::Context;
+use abacas::expr::{Expr, Symbol};
+use abacas::latex::Latex;
+use abacas::matrix::Matrix;
+use abacas::number::Number;
+use abacas::polynomial::Polynomial;
+use abacas::relation::Relation;
+use rug::ops::Pow;
+
+const NUM: fn(i64) -> Expr = |num| Expr::Num(num.into());
+const FRAC: fn(i64, i64) -> Expr = |numer, denom| Expr::Num(Number::from(numer) / denom);
+
+const SYM: fn(&str) -> Expr = |name| Expr::Poly(Symbol::new(name).unwrap(), "x".parse().unwrap());
+const FUN: fn(&str, Vec<Expr>) -> Expr = |name, args| Expr::Fun(Symbol::new(name).unwrap(), args);
+
+/// Helper to simplify an expression and write it as LaTeX.
+fn latex(expr: Expr) -> String {
+	expr.simplify(&mut Context::new()).unwrap().to_latex()
+}
+
+#[test]
+fn numbers() {
+	assert_eq!(Number::from(0).to_latex(), "0");
+	assert_eq!(Number::from(-42).to_latex(), "-42");
+	assert_eq!((Number::from(3) / 4).to_latex(), r"\frac{3}{4}");
+
+	// Fractions are typeset, while the plain output writes them inline
+	let num = Number::from(1) / 3;
+	assert_eq!(num.to_string(), "1/3");
+	assert_eq!(Latex(&num).to_string(), r"\frac{1}{3}");
+
+	let big = Number::from(10).pow(30) + 1;
+	assert_eq!(big.to_latex(), "1000000000000000000000000000001");
+}
+
+#[test]
+fn polynomials() {
+	let poly = |src: &str| src.parse::<Polynomial>().unwrap().to_latex();
+
+	assert_eq!(poly("0"), "0");
+	assert_eq!(poly("-x^2 + x - 1"), "-x^{2} + x - 1");
+	assert_eq!(poly("0.25x^10 - 3x"), r"\frac{1}{4}x^{10} - 3x");
+	assert_eq!(poly("x^-1 + x^1/3"), r"\sqrt[3]{x} + x^{-1}");
+	assert_eq!(poly("-2x^1.5"), r"-2x^{\frac{3}{2}}");
+
+	// Other variables use Greek letters or upright names
+	let theta = Expr::Poly(Symbol::new("theta").unwrap(), "2x^2".parse().unwrap());
+	assert_eq!(theta.to_latex(), r"2\theta^{2}");
+	assert_eq!(SYM("rate").to_latex(), r"\mathrm{rate}");
+}
+
+#[test]
+fn products() {
+	let (x, y) = (SYM("x"), SYM("y"));
+
+	let expr = NUM(4) * (x.clone().pow(NUM(2)) + NUM(2) * x.clone()) * (y.clone() + FRAC(1, 2));
+	assert_eq!(
+		latex(expr),
+		r"4 \cdot \left(x^{2} + 2x\right) \cdot \left(y + \frac{1}{2}\right)"
+	);
+
+	// Negative powers and denominators of coefficients form fractions
+	assert_eq!(latex(x.clone() / y.clone()), r"\frac{x}{y}");
+	assert_eq!(
+		latex(NUM(-3) * x.clone() / (NUM(2) * y.clone())),
+		r"-\frac{3 \cdot x}{2 \cdot y}"
+	);
+	assert_eq!(latex(NUM(1) / (x.clone() + NUM(1))), r"\frac{1}{x + 1}");
+	assert_eq!(
+		latex(FUN("sin", vec![x.clone()]) * y.clone().pow(NUM(-2))),
+		r"\frac{\sin\left(x\right)}{y^{2}}"
+	);
+}
+
+#[test]
+fn sums() {
+	let (x, y) = (SYM("x"), SYM("y"));
+
+	// Minus signs are extracted from numbers, polynomials and products
+	assert_eq!(latex(y.clone() - NUM(3)), "y - 3");
+	assert_eq!(
+		latex(FUN("f", vec![y.clone()]) - x.clone()),
+		r"\operatorname{f}\left(y\right) - x"
+	);
+	let product = Expr::Mul(vec![NUM(-1), x.clone() + NUM(1), FUN("g", vec![x.clone()])]);
+	assert_eq!(
+		Expr::Add(vec![y.clone(), product]).to_latex(),
+		r"y - \left(x + 1\right) \cdot \operatorname{g}\left(x\right)"
+	);
+	assert_eq!(
+		latex(FUN("cos", vec![x.clone()]) - FRAC(1, 2) * FUN("sin", vec![x.clone()])),
+		r"\cos\left(x\right) - \frac{\sin\left(x\right)}{2}"
+	);
+}
+
+#[test]
+fn powers() {
+	let (x, y) = (SYM("x"), SYM("y"));
+
+	assert_eq!(latex((x.clone() + NUM(1)).pow(FRAC(1, 2))), r"\sqrt{x + 1}");
+	assert_eq!(latex(FUN("sqrt", vec![y.clone()])), r"\sqrt{y}");
+	assert_eq!(latex(y.clone().pow(FRAC(1, 3)) * x.clone()), r"x \cdot \sqrt[3]{y}");
+	assert_eq!(latex(NUM(2).pow(x.clone())), "2^{x}");
+	assert_eq!(latex(x.clone().pow(y.clone() + NUM(1))), "x^{y + 1}");
+	assert_eq!(latex(NUM(-2).pow(x.clone())), r"\left(-2\right)^{x}");
+	assert_eq!(
+		latex(FUN("factorial", vec![x.clone() + NUM(1)])),
+		r"\left(x + 1\right)!"
+	);
+}
+
+#[test]
+fn structures() {
+	let (x, y) = (SYM("x"), SYM("y"));
+
+	let matrix = Matrix::from_rows(vec![vec![NUM(1), FRAC(1, 2)], vec![x.clone(), y.clone()]]).unwrap();
+	assert_eq!(
+		Expr::Matrix(matrix).to_latex(),
+		r"\begin{pmatrix} 1 & \frac{1}{2} \\ x & y \end{pmatrix}"
+	);
+
+	let rel = |rel, lhs: &Expr, rhs| Expr::Rel(rel, lhs.clone().into(), rhs);
+	let expr = Expr::Or(vec![
+		Expr::And(vec![
+			rel(Relation::Ge, &x, NUM(0).into()),
+			rel(Relation::Ne, &y, NUM(1).into()),
+		]),
+		Expr::Not(rel(Relation::Lt, &x, FRAC(-1, 2).into()).into()),
+	]);
+	assert_eq!(expr.to_latex(), r"x \geq 0 \land y \neq 1 \lor \lnot x < -\frac{1}{2}");
+
+	let expr = Expr::Piecewise(vec![
+		(x.clone(), rel(Relation::Gt, &x, NUM(0).into())),
+		(NUM(0), Expr::And(vec![])),
+	]);
+	assert_eq!(
+		expr.to_latex(),
+		r"\begin{cases} x & x > 0 \\ 0 & \text{true} \end{cases}"
+	);
+
+	assert_eq!(FUN("atan", vec![x.clone()]).to_latex(), r"\arctan\left(x\right)");
+	assert_eq!(FUN("abs", vec![x.clone()]).to_latex(), r"\left|x\right|");
+	assert_eq!(
+		FUN("binomial", vec![x.clone(), NUM(2)]).to_latex(),
+		r"\operatorname{binomial}\left(x, 2\right)"
+	);
+}
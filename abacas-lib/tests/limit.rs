@@ -0,0 +1,161 @@
+use abacas::context::Context;
+use abacas::expr::{Expr, Symbol};
+use abacas::limit::{Direction, Limit, Point, limit};
+use abacas::number::Number;
+use abacas::stdlib::StdLib;
+
+const NUM: fn(i64) -> Expr = |num| Expr::Num(num.into());
+const FRAC: fn(i64, i64) -> Expr = |numer, denom| Expr::Num(Number::from(numer) / denom);
+
+const X: fn(&str) -> Expr = |poly| Expr::Poly(Symbol::new("x").unwrap(), poly.parse().unwrap());
+const SYM: fn(&str) -> Expr = |name| Expr::Poly(Symbol::new(name).unwrap(), "x".parse().unwrap());
+const FUN: fn(&str, Expr) -> Expr = |name, arg| Expr::Fun(Symbol::new(name).unwrap(), vec![arg]);
+const POW: fn(Expr, Expr) -> Expr = |base, exp| Expr::Pow(base.into(), exp.into());
+
+/// Helper to take a limit with respect to `x`, panicking if it could not be determined.
+fn lim(expr: Expr, point: Point, direction: Direction) -> Limit {
+	let x = Symbol::new("x").unwrap();
+	let result = limit(expr.clone(), &x, point, direction, &mut Context::new()).unwrap();
+
+	result.unwrap_or_else(|| panic!("no limit for {expr}"))
+}
+
+/// Helper to take a two-sided limit at a finite point.
+fn at(expr: Expr, point: Expr) -> Limit {
+	lim(expr, Point::Finite(point), Direction::Both)
+}
+
+/// Helper to take a limit at positive infinity.
+fn at_infinity(expr: Expr) -> Limit {
+	lim(expr, Point::Infinity, Direction::Both)
+}
+
+#[test]
+fn rational_functions() {
+	// Degree comparison at infinity
+	assert_eq!(at_infinity(X("3x^2 + 1") / X("2x^2 - x")), Limit::Finite(FRAC(3, 2)));
+	assert_eq!(at_infinity(X("x^3") / X("1 - x")), Limit::NegInfinity);
+	assert_eq!(at_infinity(X("x + 5") / X("x^2")), Limit::Finite(NUM(0)));
+
+	let result = lim(X("x^3 + x") / X("x^2 + 1"), Point::NegInfinity, Direction::Both);
+	assert_eq!(result, Limit::NegInfinity);
+
+	// Removable singularities and poles
+	assert_eq!(at(X("x^2 - 1") / X("x - 1"), NUM(1)), Limit::Finite(NUM(2)));
+	assert_eq!(at(X("x^-2"), NUM(0)), Limit::Infinity);
+	assert_eq!(at(X("x + 1") / X("x^2 + x - 2"), NUM(1)), Limit::DoesNotExist);
+
+	// Continuous points are plugged in
+	assert_eq!(at(X("x^2 + 3"), NUM(2)), Limit::Finite(NUM(7)));
+}
+
+#[test]
+fn one_sided() {
+	let zero = || Point::Finite(NUM(0));
+
+	assert_eq!(lim(X("x^-1"), zero(), Direction::Right), Limit::Infinity);
+	assert_eq!(lim(X("x^-1"), zero(), Direction::Left), Limit::NegInfinity);
+	assert_eq!(lim(X("x^-1"), zero(), Direction::Both), Limit::DoesNotExist);
+
+	assert_eq!(lim(FUN("ln", X("x")), zero(), Direction::Right), Limit::NegInfinity);
+	assert_eq!(
+		lim(FUN("exp", X("-x^-1")), zero(), Direction::Right),
+		Limit::Finite(NUM(0))
+	);
+	assert_eq!(lim(FUN("exp", X("-x^-1")), zero(), Direction::Left), Limit::Infinity);
+
+	let result = lim(FUN("atan", X("x^-1")), zero(), Direction::Left);
+	assert_eq!(result.to_string(), "-0.5pi");
+}
+
+#[test]
+fn growth_orders() {
+	// Exponentials dominate powers, which dominate logarithms
+	assert_eq!(at_infinity(X("x^3") / FUN("exp", X("x"))), Limit::Finite(NUM(0)));
+	assert_eq!(at_infinity(POW(NUM(2), X("x")) / X("x^10")), Limit::Infinity);
+	assert_eq!(at_infinity(FUN("ln", X("x")) / X("x")), Limit::Finite(NUM(0)));
+	assert_eq!(
+		at_infinity(POW(FUN("ln", X("x")), NUM(3)) / FUN("sqrt", X("x"))),
+		Limit::Finite(NUM(0))
+	);
+
+	let result = lim(X("x^2") * FUN("exp", X("-x^2")), Point::NegInfinity, Direction::Both);
+	assert_eq!(result, Limit::Finite(NUM(0)));
+
+	// Differences of infinite terms
+	assert_eq!(at_infinity(FUN("exp", X("x")) - X("x^5")), Limit::Infinity);
+	assert_eq!(at_infinity(FUN("ln", X("x")) - X("x")), Limit::NegInfinity);
+
+	// Powers of `x` with equal growth approach the ratio of their coefficients
+	assert_eq!(at_infinity(X("x") / X("x + x^0.5")), Limit::Finite(NUM(1)));
+}
+
+#[test]
+fn indeterminate_forms() {
+	let x = || X("x");
+
+	// 0 / 0
+	assert_eq!(at(FUN("sin", x()) / x(), NUM(0)), Limit::Finite(NUM(1)));
+	assert_eq!(at((FUN("exp", x()) - NUM(1)) / x(), NUM(0)), Limit::Finite(NUM(1)));
+	assert_eq!(
+		at((NUM(1) - FUN("cos", x())) / X("x^2"), NUM(0)),
+		Limit::Finite(FRAC(1, 2))
+	);
+
+	// Functions of such forms
+	let result = at(FUN("exp", FUN("sin", x()) / x()), NUM(0));
+	assert_eq!(result, Limit::Finite(FUN("exp", NUM(1))));
+
+	// 0 * inf
+	let result = lim(x() * FUN("ln", x()), Point::Finite(NUM(0)), Direction::Right);
+	assert_eq!(result, Limit::Finite(NUM(0)));
+	assert_eq!(at_infinity(FUN("sin", x()) / x()), Limit::Finite(NUM(0)));
+
+	// 1^inf, 0^0 and inf^0
+	let result = at_infinity(POW(X("1 + x^-1"), x()));
+	assert_eq!(result, Limit::Finite(FUN("exp", NUM(1))));
+
+	let result = lim(POW(x(), x()), Point::Finite(NUM(0)), Direction::Right);
+	assert_eq!(result, Limit::Finite(NUM(1)));
+	assert_eq!(at_infinity(POW(x(), X("x^-1"))), Limit::Finite(NUM(1)));
+}
+
+#[test]
+fn nonexistent() {
+	assert_eq!(at_infinity(FUN("sin", X("x"))), Limit::DoesNotExist);
+	assert_eq!(at(FUN("cos", X("x^-1")), NUM(0)), Limit::DoesNotExist);
+
+	// Unbounded oscillation is not determined
+	let x = Symbol::new("x").unwrap();
+	let expr = X("x") * FUN("sin", X("x"));
+	let result = limit(expr, &x, Point::Infinity, Direction::Both, &mut Context::new()).unwrap();
+	assert_eq!(result, None);
+}
+
+#[test]
+fn stdlib() {
+	let stdlib = StdLib::new();
+	let ctx = &mut Context::new();
+
+	let call = |args: Vec<Expr>, ctx: &mut Context| {
+		let expr = Expr::Fun(Symbol::new("limit").unwrap(), args);
+		stdlib.evaluate(expr, ctx)
+	};
+
+	let sinc = FUN("sin", X("x")) / X("x");
+	assert_eq!(call(vec![sinc, X("x"), NUM(0)], ctx), NUM(1));
+	assert_eq!(call(vec![X("x^-1"), X("x"), NUM(0)], ctx), SYM("undefined"));
+	assert_eq!(
+		call(vec![X("x^-1"), X("x"), NUM(0), SYM("left")], ctx),
+		Limit::NegInfinity.into()
+	);
+	assert_eq!(call(vec![FUN("exp", X("x")), X("x"), SYM("inf")], ctx), SYM("inf"));
+
+	// Functions are evaluated at the limit
+	let result = call(vec![FUN("exp", X("x^-1")), X("x"), Limit::NegInfinity.into()], ctx);
+	assert_eq!(result, NUM(1));
+
+	// Unknown directions keep the call unevaluated
+	let result = call(vec![X("x"), X("x"), NUM(0), SYM("up")], ctx);
+	assert_eq!(result.to_string(), "limit(x, x, 0, up)");
+}
@@ -0,0 +1,145 @@
+use abacas::context::Context;
+use abacas::expr::{Expr, Symbol};
+use abacas::linsolve::{Solution, linsolve};
+use abacas::matrix::Matrix;
+use abacas::number::Number;
+use abacas::relation::Relation;
+use abacas::stdlib::StdLib;
+
+const NUM: fn(i64) -> Expr = |num| Expr::Num(num.into());
+const FRAC: fn(i64, i64) -> Expr = |numer, denom| Expr::Num(Number::from(numer) / denom);
+
+const SYM: fn(&str) -> Expr = |name| Expr::Poly(Symbol::new(name).unwrap(), "x".parse().unwrap());
+const FUN: fn(&str, Vec<Expr>) -> Expr = |name, args| Expr::Fun(Symbol::new(name).unwrap(), args);
+const EQ: fn(Expr, Expr) -> Expr = |lhs, rhs| Expr::Rel(Relation::Eq, lhs.into(), rhs.into());
+
+/// Helper to create the variables with the given names.
+fn vars(names: &[&str]) -> Vec<Symbol> {
+	names.iter().map(|&name| Symbol::new(name).unwrap()).collect()
+}
+
+#[test]
+fn numeric() {
+	let ctx = &mut Context::new();
+	let (x, y, z) = (SYM("x"), SYM("y"), SYM("z"));
+
+	let equations = [
+		EQ(NUM(2) * x.clone() + y.clone() - z.clone(), NUM(8)),
+		EQ(NUM(-3) * x.clone() - y.clone() + NUM(2) * z.clone(), NUM(-11)),
+		EQ(NUM(-2) * x.clone() + y.clone() + NUM(2) * z.clone(), NUM(-3)),
+	];
+	let result = linsolve(&equations, &vars(&["x", "y", "z"]), ctx).unwrap();
+	assert_eq!(result, Some(Solution::Unique(vec![NUM(2), NUM(3), NUM(-1)])));
+
+	// Fractions stay exact
+	let equations = [
+		EQ(NUM(3) * x.clone() + NUM(2) * y.clone(), NUM(1)),
+		EQ(x.clone() - y.clone(), NUM(1)),
+	];
+	let result = linsolve(&equations, &vars(&["x", "y"]), ctx).unwrap();
+	assert_eq!(result, Some(Solution::Unique(vec![FRAC(3, 5), FRAC(-2, 5)])));
+
+	// Redundant equations leave free variables
+	let equations = [
+		EQ(x.clone() + y.clone() + z.clone(), NUM(6)),
+		EQ(NUM(2) * x.clone() + NUM(2) * y.clone() + NUM(2) * z.clone(), NUM(12)),
+		EQ(x.clone() - z.clone(), NUM(0)),
+	];
+	let result = linsolve(&equations, &vars(&["x", "y", "z"]), ctx).unwrap().unwrap();
+	assert_eq!(
+		result.condition(&vars(&["x", "y", "z"])).to_string(),
+		"x == z and y == -2z + 6"
+	);
+	assert!(matches!(result, Solution::Parametric { free, .. } if free == vars(&["z"])));
+
+	// Contradictions, also after simplifying an equation on its own
+	let equations = [EQ(x.clone() + y.clone(), NUM(1)), EQ(x.clone() + y.clone(), NUM(2))];
+	let result = linsolve(&equations, &vars(&["x", "y"]), ctx).unwrap();
+	assert_eq!(result, Some(Solution::Inconsistent));
+
+	let equations = [EQ(x.clone(), NUM(1)), EQ(NUM(1), NUM(2))];
+	let result = linsolve(&equations, &vars(&["x"]), ctx).unwrap();
+	assert_eq!(result, Some(Solution::Inconsistent));
+}
+
+#[test]
+fn symbolic() {
+	let ctx = &mut Context::new();
+	let (x, y) = (SYM("x"), SYM("y"));
+	let (a, b) = (SYM("a"), SYM("b"));
+
+	// Symbolic right-hand sides with numeric coefficients are expanded
+	let equations = [
+		EQ(x.clone() + NUM(2) * y.clone(), a.clone()),
+		EQ(NUM(3) * x.clone() + NUM(4) * y.clone(), b.clone()),
+	];
+	let result = linsolve(&equations, &vars(&["x", "y"]), ctx).unwrap().unwrap();
+	assert_eq!(
+		result.condition(&vars(&["x", "y"])).to_string(),
+		"x == -2a + b and y == 1.5a - 0.5b"
+	);
+
+	// Symbolic coefficients give quotients of determinants
+	let equations = [
+		EQ(a.clone() * x.clone() + y.clone(), NUM(1)),
+		EQ(x.clone() + b.clone() * y.clone(), NUM(1)),
+	];
+	let result = linsolve(&equations, &vars(&["x", "y"]), ctx).unwrap().unwrap();
+	assert_eq!(
+		result.condition(&vars(&["x", "y"])).to_string(),
+		"x == (b - 1) * (a * b - 1)^-1 and y == (a - 1) * (a * b - 1)^-1"
+	);
+
+	// Coefficients are generic, so they are not zero unless they expand to zero
+	let equations = [
+		EQ(a.clone() * x.clone() + y.clone(), NUM(1)),
+		EQ(a.clone() * x.clone() + y.clone(), b.clone()),
+	];
+	let result = linsolve(&equations, &vars(&["x", "y"]), ctx).unwrap();
+	assert_eq!(result, Some(Solution::Inconsistent));
+
+	let equations = [EQ(a.clone() * x.clone() - a.clone() * y.clone(), NUM(0))];
+	let result = linsolve(&equations, &vars(&["x", "y"]), ctx).unwrap().unwrap();
+	assert_eq!(result.condition(&vars(&["x", "y"])).to_string(), "x == y");
+
+	// Equations that are not linear in the variables
+	let equations = [EQ(x.clone() * y.clone(), NUM(1))];
+	assert_eq!(linsolve(&equations, &vars(&["x", "y"]), ctx).unwrap(), None);
+
+	let equations = [EQ(FUN("sin", vec![x.clone()]), NUM(0))];
+	assert_eq!(linsolve(&equations, &vars(&["x"]), ctx).unwrap(), None);
+}
+
+#[test]
+fn stdlib() {
+	let stdlib = StdLib::new();
+	let ctx = &mut Context::new();
+	let (x, y, z) = (SYM("x"), SYM("y"), SYM("z"));
+
+	let mut call = |args: Vec<Expr>| stdlib.evaluate(FUN("linsolve", args), ctx).to_string();
+	let list = |entries: Vec<Expr>| Expr::Matrix(Matrix::column(entries));
+
+	let equations = list(vec![
+		EQ(x.clone() + y.clone(), NUM(3)),
+		EQ(x.clone() - y.clone(), NUM(1)),
+	]);
+	assert_eq!(
+		call(vec![equations, list(vec![x.clone(), y.clone()])]),
+		"x == 2 and y == 1"
+	);
+
+	// Conjunctions of equations, and single equations and variables
+	let equations = Expr::And(vec![
+		EQ(x.clone() + y.clone() + z.clone(), NUM(1)),
+		EQ(x.clone(), z.clone()),
+	]);
+	let result = call(vec![equations, list(vec![x.clone(), y.clone(), z.clone()])]);
+	assert_eq!(result, "x == z and y == -2z + 1");
+
+	assert_eq!(call(vec![EQ(NUM(2) * x.clone(), y.clone()), x.clone()]), "x == 0.5y");
+	assert_eq!(call(vec![EQ(x.clone(), x.clone() + NUM(1)), x.clone()]), "false");
+
+	// Nonlinear systems stay unevaluated
+	let equations = list(vec![EQ(x.clone() * x.clone(), NUM(1))]);
+	assert_eq!(call(vec![equations, x.clone()]), "linsolve([[x^2 == 1]], x)");
+}
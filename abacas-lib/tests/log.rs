@@ -0,0 +1,129 @@
+use abacas::assumption::Assumption;
+use abacas::context::Context;
+use abacas::expr::{Expr, Symbol};
+use abacas::log;
+use abacas::number::Number;
+use abacas::stdlib::StdLib;
+use rug::ops::Pow;
+
+const NUM: fn(i64) -> Expr = |num| Expr::Num(num.into());
+const FRAC: fn(i64, i64) -> Expr = |numer, denom| Expr::Num(Number::from(numer) / denom);
+
+const X: fn(&str) -> Expr = |poly| Expr::Poly(Symbol::new("x").unwrap(), poly.parse().unwrap());
+const SYM: fn(&str) -> Expr = |name| Expr::Poly(Symbol::new(name).unwrap(), "x".parse().unwrap());
+const FUN: fn(&str, Vec<Expr>) -> Expr = |name, args| Expr::Fun(Symbol::new(name).unwrap(), args);
+
+/// Helper to create the natural logarithm of an expression.
+fn ln(arg: Expr) -> Expr {
+	FUN("ln", vec![arg])
+}
+
+#[test]
+fn exact_logs() {
+	let exact = |x: i64, base: i64| log::log(&x.into(), &base.into());
+
+	assert_eq!(exact(1024, 2), Some(10.into()));
+	assert_eq!(exact(8, 4), Some(Number::from(3) / 2));
+	assert_eq!(exact(64, 16), Some(Number::from(3) / 2));
+	assert_eq!(
+		log::log(&(Number::from(4) / 9), &(Number::from(27) / 8)),
+		Some(Number::from(-2) / 3)
+	);
+
+	assert_eq!(exact(12, 2), None);
+	assert_eq!(exact(-8, 2), None);
+	assert_eq!(exact(8, -2), None);
+	assert_eq!(exact(0, 2), None);
+
+	// Exponents with many prime factors are combined from prime roots
+	let power = Number::from(6).pow(1155u32);
+	assert_eq!(log::log(&power, &36.into()), Some(Number::from(1155) / 2));
+	assert_eq!(log::log(&(power + 1), &6.into()), None);
+}
+
+#[test]
+fn expand_log() {
+	let ctx = &mut Context::new();
+	let expand = |expr: Expr, ctx: &mut Context| expr.expand_log(ctx).unwrap().to_string();
+
+	assert_eq!(expand(ln(NUM(32)), ctx), "ln(2) * 5");
+	assert_eq!(expand(ln(FRAC(1, 9)), ctx), "ln(3) * -2");
+	assert_eq!(expand(ln(NUM(1)), ctx), "0");
+	assert_eq!(expand(ln(NUM(6)), ctx), "ln(6)");
+
+	// Positive factors and odd powers are split off
+	assert_eq!(expand(ln(X("3x^5")), ctx), "ln(3) + ln(x) * 5");
+	assert_eq!(expand(ln(SYM("y").pow(FRAC(1, 2))), ctx), "ln(y) * 0.5");
+
+	// Exponentials of real arguments are positive
+	assert_eq!(expand(ln(FUN("exp", vec![SYM("y")]) * X("x")), ctx), "ln(exp(y) * x)");
+	ctx.assume(Symbol::new("y").unwrap(), Assumption::Real);
+	assert_eq!(expand(ln(FUN("exp", vec![SYM("y")]) * X("x")), ctx), "ln(x) + y");
+
+	// Products of factors with unknown signs and even powers stay unchanged
+	assert_eq!(expand(ln(X("x") * SYM("z")), ctx), "ln(x * z)");
+	assert_eq!(expand(ln(SYM("z").pow(NUM(2))), ctx), "ln(z^2)");
+	assert_eq!(expand(ln(X("-2x")), ctx), "ln(-2x)");
+
+	// Assumed positive symbols are expanded
+	ctx.assume(Symbol::new("z").unwrap(), Assumption::Positive);
+	assert_eq!(expand(ln(X("x") * SYM("z").pow(NUM(2))), ctx), "ln(x) + ln(z) * 2");
+}
+
+#[test]
+fn combine_log() {
+	let ctx = &mut Context::new();
+	let combine = |expr: Expr, ctx: &mut Context| expr.combine_log(ctx).unwrap().to_string();
+
+	assert_eq!(combine(ln(NUM(2)) + ln(NUM(3)), ctx), "ln(6)");
+	assert_eq!(combine(ln(X("x")) * NUM(3) + SYM("y"), ctx), "ln(x^3) + y");
+	assert_eq!(combine(ln(SYM("y")) - ln(X("x")) + NUM(1), ctx), "ln(x^-1 * y) + 1");
+
+	// Combined logarithms cancel with exponentials
+	let expr = FUN("exp", vec![ln(X("x")) * NUM(2) + ln(SYM("y"))]);
+	assert_eq!(combine(expr, ctx), "x^2 * y");
+
+	// Expanding and combining are inverse for positive factors
+	let expr = ln(X("8x^3"));
+	assert_eq!(expr.expand_log(ctx).unwrap().combine_log(ctx).unwrap(), expr);
+}
+
+#[test]
+fn simplification() {
+	let ctx = &mut Context::new();
+	let exp = |arg| FUN("exp", vec![arg]);
+
+	assert_eq!(exp(ln(X("x + 1"))).simplify(ctx).unwrap(), X("x + 1"));
+	assert_eq!(ln(exp(SYM("y"))).simplify(ctx).unwrap(), SYM("y"));
+
+	// Powers of the same base are merged
+	assert_eq!((exp(X("x")) / exp(X("x"))).simplify(ctx).unwrap(), NUM(1));
+	assert_eq!(
+		(NUM(2).pow(X("x")) * NUM(2).pow(SYM("y")))
+			.simplify(ctx)
+			.unwrap()
+			.to_string(),
+		"2^(x + y)"
+	);
+	assert_eq!(
+		(NUM(3).pow(FRAC(1, 2)) * NUM(3).pow(FRAC(1, 2))).simplify(ctx).unwrap(),
+		NUM(3)
+	);
+	assert_eq!((ln(X("x")).pow(NUM(2)) / ln(X("x"))).simplify(ctx).unwrap(), ln(X("x")));
+}
+
+#[test]
+fn stdlib() {
+	let stdlib = StdLib::new();
+	let ctx = &mut Context::new();
+
+	let mut call = |name: &str, args: Vec<Expr>| stdlib.evaluate(FUN(name, args), ctx).to_string();
+
+	assert_eq!(call("log", vec![NUM(81), NUM(3)]), "4");
+	assert_eq!(call("log", vec![NUM(2), NUM(8)]), "1/3");
+	assert_eq!(call("log", vec![X("x"), NUM(10)]), "ln(x) * ln(10)^-1");
+	assert_eq!(call("log", vec![X("x"), NUM(1)]), "log(x, 1)");
+
+	assert_eq!(call("expand_log", vec![ln(X("9x"))]), "ln(x) + ln(3) * 2");
+	assert_eq!(call("combine_log", vec![ln(X("x")) + ln(SYM("y"))]), "ln(x * y)");
+}
@@ -0,0 +1,123 @@
+use abacas::context::Context;
+use abacas::expr::{Expr, Symbol};
+use abacas::mathml::MathMl;
+use abacas::matrix::Matrix;
+use abacas::number::Number;
+use abacas::relation::Relation;
+use rug::ops::Pow;
+
+const NUM: fn(i64) -> Expr = |num| Expr::Num(num.into());
+const FRAC: fn(i64, i64) -> Expr = |numer, denom| Expr::Num(Number::from(numer) / denom);
+
+const SYM: fn(&str) -> Expr = |name| Expr::Poly(Symbol::new(name).unwrap(), "x".parse().unwrap());
+const FUN: fn(&str, Vec<Expr>) -> Expr = |name, args| Expr::Fun(Symbol::new(name).unwrap(), args);
+
+/// Helper to simplify an expression and write it as MathML, without the surrounding `<math>` element.
+fn mathml(expr: Expr) -> String {
+	let mathml = MathMl(&expr.simplify(&mut Context::new()).unwrap()).to_string();
+	let inner = mathml
+		.strip_prefix(r#"<math xmlns="http://www.w3.org/1998/Math/MathML">"#)
+		.unwrap();
+	inner.strip_suffix("</math>").unwrap().to_owned()
+}
+
+#[test]
+fn polynomials() {
+	let poly = |src: &str| mathml(Expr::Poly(Symbol::new("x").unwrap(), src.parse().unwrap()));
+
+	assert_eq!(poly("0"), "<mn>0</mn>");
+	assert_eq!(poly("3x"), "<mrow><mn>3</mn><mi>x</mi></mrow>");
+	assert_eq!(
+		poly("-x^3 + 0.5x - 1"),
+		concat!(
+			"<mrow><mrow><mo>-</mo><msup><mi>x</mi><mn>3</mn></msup></mrow><mo>+</mo>",
+			"<mrow><mfrac><mn>1</mn><mn>2</mn></mfrac><mi>x</mi></mrow><mo>-</mo><mn>1</mn></mrow>"
+		)
+	);
+	assert_eq!(
+		poly("x^-1 + x^0.25"),
+		concat!(
+			"<mrow><mroot><mi>x</mi><mn>4</mn></mroot><mo>+</mo>",
+			"<msup><mi>x</mi><mrow><mo>-</mo><mn>1</mn></mrow></msup></mrow>"
+		)
+	);
+}
+
+#[test]
+fn operations() {
+	let (x, y) = (SYM("x"), SYM("y"));
+
+	assert_eq!(
+		mathml(NUM(-3) * x.clone() / (NUM(2) * y.clone())),
+		concat!(
+			"<mrow><mo>-</mo><mfrac><mrow><mn>3</mn><mo>⋅</mo><mi>x</mi></mrow>",
+			"<mrow><mn>2</mn><mo>⋅</mo><mi>y</mi></mrow></mfrac></mrow>"
+		)
+	);
+	assert_eq!(
+		mathml(y.clone() - FRAC(1, 2) * FUN("sin", vec![x.clone()])),
+		concat!(
+			"<mrow><mrow><mo>-</mo><mfrac><mrow><mi>sin</mi><mo>&#x2061;</mo><mrow><mo>(</mo><mi>x</mi><mo>)</mo>",
+			"</mrow></mrow><mn>2</mn></mfrac></mrow><mo>+</mo><mi>y</mi></mrow>"
+		)
+	);
+	assert_eq!(
+		mathml((x.clone() + NUM(1)).pow(FRAC(1, 2))),
+		"<msqrt><mrow><mi>x</mi><mo>+</mo><mn>1</mn></mrow></msqrt>"
+	);
+	assert_eq!(
+		mathml(NUM(-2).pow(x.clone())),
+		"<msup><mrow><mo>(</mo><mrow><mo>-</mo><mn>2</mn></mrow><mo>)</mo></mrow><mi>x</mi></msup>"
+	);
+	assert_eq!(
+		mathml(FUN("abs", vec![x.clone()]) + FUN("factorial", vec![x.clone() + NUM(1)])),
+		concat!(
+			"<mrow><mrow><mo>|</mo><mi>x</mi><mo>|</mo></mrow><mo>+</mo>",
+			"<mrow><mrow><mo>(</mo><mrow><mi>x</mi><mo>+</mo><mn>1</mn></mrow><mo>)</mo></mrow><mo>!</mo></mrow></mrow>"
+		)
+	);
+}
+
+#[test]
+fn structures() {
+	let (x, y) = (SYM("x"), SYM("y"));
+
+	let matrix = Matrix::from_rows(vec![vec![NUM(1), FRAC(1, 2)], vec![x.clone(), y.clone()]]).unwrap();
+	assert_eq!(
+		mathml(Expr::Matrix(matrix)),
+		concat!(
+			"<mrow><mo>(</mo><mtable><mtr><mtd><mn>1</mn></mtd><mtd><mfrac><mn>1</mn><mn>2</mn></mfrac></mtd></mtr>",
+			"<mtr><mtd><mi>x</mi></mtd><mtd><mi>y</mi></mtd></mtr></mtable><mo>)</mo></mrow>"
+		)
+	);
+
+	// Comparisons are escaped
+	let rel = |rel, lhs: &Expr, rhs| Expr::Rel(rel, lhs.clone().into(), rhs);
+	let expr = Expr::Or(vec![
+		Expr::And(vec![
+			rel(Relation::Ge, &x, NUM(0).into()),
+			rel(Relation::Ne, &y, NUM(1).into()),
+		]),
+		Expr::Not(rel(Relation::Lt, &x, FRAC(-1, 2).into()).into()),
+	]);
+	assert_eq!(
+		expr.to_mathml(),
+		concat!(
+			r#"<math xmlns="http://www.w3.org/1998/Math/MathML"><mrow><mrow><mrow><mi>x</mi><mo>≥</mo><mn>0</mn></mrow>"#,
+			"<mo>∧</mo><mrow><mi>y</mi><mo>≠</mo><mn>1</mn></mrow></mrow><mo>∨</mo><mrow><mo>¬</mo><mrow><mi>x</mi>",
+			"<mo>&lt;</mo><mrow><mo>-</mo><mfrac><mn>1</mn><mn>2</mn></mfrac></mrow></mrow></mrow></mrow></math>"
+		)
+	);
+
+	let expr = Expr::Piecewise(vec![
+		(x.clone(), rel(Relation::Gt, &x, NUM(0).into())),
+		(NUM(0), Expr::And(vec![])),
+	]);
+	assert_eq!(
+		mathml(expr),
+		concat!(
+			"<mrow><mo>{</mo><mtable><mtr><mtd><mi>x</mi></mtd><mtd><mrow><mi>x</mi><mo>&gt;</mo><mn>0</mn></mrow></mtd>",
+			"</mtr><mtr><mtd><mn>0</mn></mtd><mtd><mtext>true</mtext></mtd></mtr></mtable></mrow>"
+		)
+	);
+}
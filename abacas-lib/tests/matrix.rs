@@ -0,0 +1,179 @@
+use abacas::context::Context;
+use abacas::error::SimplifyError;
+use abacas::expr::{Expr, Symbol};
+use abacas::matrix::Matrix;
+use abacas::number::Number;
+use rug::ops::Pow;
+
+const NUM: fn(i64) -> Expr = |num| Expr::Num(num.into());
+
+const X: fn(&str) -> Expr = |poly| Expr::Poly(Symbol::new("x").unwrap(), poly.parse().unwrap());
+const SYM: fn(&str) -> Expr = |name| Expr::Poly(Symbol::new(name).unwrap(), "x".parse().unwrap());
+
+/// Helper to create a matrix of numbers from its rows.
+fn matrix(rows: &[&[i64]]) -> Matrix<Number> {
+	let rows = rows
+		.iter()
+		.map(|row| row.iter().map(|&num| Number::from(num)).collect());
+	Matrix::from_rows(rows.collect()).unwrap()
+}
+
+/// Helper to create a matrix expression from its rows.
+fn expr(rows: Vec<Vec<Expr>>) -> Expr {
+	Expr::Matrix(Matrix::from_rows(rows).unwrap())
+}
+
+#[test]
+fn determinant() {
+	assert_eq!(matrix(&[&[1, 2], &[3, 4]]).determinant(), Some((-2).into()));
+	assert_eq!(matrix(&[&[0, 1], &[1, 0]]).determinant(), Some((-1).into()));
+	assert_eq!(matrix(&[&[1, 2], &[2, 4]]).determinant(), Some(0.into()));
+	assert_eq!(matrix(&[&[1, 2, 3]]).determinant(), None);
+
+	let vandermonde = matrix(&[&[1, 1, 1, 1], &[1, 2, 4, 8], &[1, 3, 9, 27], &[1, 4, 16, 64]]);
+	assert_eq!(vandermonde.determinant(), Some(12.into()));
+
+	// Symbolic determinants are expanded
+	let ctx = &mut Context::new();
+	let rows = vec![
+		vec![SYM("a"), SYM("b"), NUM(0)],
+		vec![SYM("c"), SYM("d"), NUM(0)],
+		vec![NUM(0), NUM(0), NUM(2)],
+	];
+	let result = Matrix::from_rows(rows).unwrap().determinant(ctx).unwrap().unwrap();
+	assert_eq!(result.to_string(), "-2 * b * c + 2 * a * d");
+
+	let rows = vec![
+		vec![X("x"), NUM(1), NUM(0)],
+		vec![NUM(1), X("x"), NUM(1)],
+		vec![NUM(0), NUM(1), X("x")],
+	];
+	let result = Matrix::from_rows(rows).unwrap().determinant(ctx).unwrap().unwrap();
+	assert_eq!(result.to_string(), "x^3 - 2x");
+}
+
+#[test]
+fn elimination() {
+	let a = matrix(&[&[1, 2, 1], &[2, 4, 0], &[3, 6, 1]]);
+
+	let (reduced, pivots) = a.rref();
+	assert_eq!(reduced.to_string(), "[[1, 2, 0], [0, 0, 1], [0, 0, 0]]");
+	assert_eq!(pivots, [0, 2]);
+	assert_eq!(a.rank(), 2);
+
+	let basis = a.nullspace();
+	assert_eq!(basis.len(), 1);
+	assert_eq!(basis[0].to_string(), "[[-2], [1], [0]]");
+	assert_eq!(a.clone() * &basis[0], Matrix::zero(3, 1));
+
+	assert_eq!(a.inverse(), None);
+	assert_eq!(Matrix::<Number>::identity(3).nullspace(), []);
+
+	let b = matrix(&[&[2, 1, 1], &[1, 3, 2], &[1, 0, 0]]);
+	let inverse = b.inverse().unwrap();
+	assert_eq!(inverse.to_string(), "[[0, 0, 1], [-2, 1, 3], [3, -1, -5]]");
+	assert_eq!(b * &inverse, Matrix::identity(3));
+
+	// The empty matrix is its own inverse
+	let empty = Matrix::<Number>::identity(0);
+	assert_eq!(empty.inverse(), Some(empty));
+}
+
+#[test]
+fn decompositions() {
+	let a = matrix(&[&[0, 2, 1], &[1, 1, 0], &[2, 0, 3]]);
+
+	let (p, l, u) = a.lu().unwrap();
+	assert_eq!(p.clone() * &a, l.clone() * &u);
+	assert!((0..3).all(|row| l[(row, row)] == 1 && (row + 1..3).all(|col| l[(row, col)] == 0)));
+	assert!((0..3).all(|row| (0..row).all(|col| u[(row, col)] == 0)));
+
+	// Singular matrices can be decomposed too
+	let singular = matrix(&[&[1, 2], &[2, 4]]);
+	let (p, l, u) = singular.lu().unwrap();
+	assert_eq!(u.to_string(), "[[1, 2], [0, 0]]");
+	assert_eq!(p * &singular, l * &u);
+
+	let (q, r) = a.qr();
+	assert_eq!(q.clone() * &r, a);
+
+	let gram = q.transpose() * &q;
+	assert!((0..3).all(|row| (0..3).all(|col| row == col || gram[(row, col)] == 0)));
+}
+
+#[test]
+fn eigenvalues() {
+	let ctx = &mut Context::new();
+	let mut eigenvalues = |a: Matrix<Number>| {
+		let eigenvalues = a.eigenvalues(ctx).unwrap().unwrap();
+		eigenvalues
+			.into_iter()
+			.map(|(value, multiplicity)| format!("{value} ({multiplicity})"))
+			.collect::<Vec<_>>()
+	};
+
+	let a = matrix(&[&[2, 1], &[1, 2]]);
+	assert_eq!(a.charpoly().unwrap().to_string(), "x^2 - 4x + 3");
+	assert_eq!(eigenvalues(a), ["1 (1)", "3 (1)"]);
+
+	let a = matrix(&[&[3, 1, 0], &[0, 3, 0], &[0, 0, -1]]);
+	assert_eq!(a.charpoly().unwrap().to_string(), "x^3 - 5x^2 + 3x + 9");
+	assert_eq!(eigenvalues(a), ["-1 (1)", "3 (2)"]);
+
+	// Irrational eigenvalues are exact, and complex ones are left out
+	assert_eq!(
+		eigenvalues(matrix(&[&[0, 1], &[2, 0]])),
+		["-1 * 2^0.5 (1)", "2^0.5 (1)"]
+	);
+	assert_eq!(eigenvalues(matrix(&[&[0, -1], &[1, 0]])), Vec::<String>::new());
+	assert_eq!(
+		eigenvalues(matrix(&[&[0, 0, 2], &[1, 0, 0], &[0, 1, 0]])),
+		["root(x^3 - 2, 1) (1)"]
+	);
+}
+
+#[test]
+fn simplify() {
+	let ctx = &mut Context::new();
+	let mut simplify = |expr: Expr| expr.simplify(ctx).map(|expr| expr.to_string());
+
+	let a = || expr(vec![vec![NUM(1), NUM(2)], vec![NUM(3), NUM(4)]]);
+	let b = || expr(vec![vec![NUM(0), NUM(1)], vec![NUM(1), NUM(0)]]);
+
+	// Products keep the order of the matrices
+	assert_eq!(simplify(a() * b()).unwrap(), "[[2, 1], [4, 3]]");
+	assert_eq!(simplify(b() * a()).unwrap(), "[[3, 4], [1, 2]]");
+	assert_eq!(simplify(a() * X("x") + b()).unwrap(), "[[x, 2x + 1], [3x + 1, 4x]]");
+	assert_eq!(simplify(a().pow(NUM(3))).unwrap(), "[[37, 54], [81, 118]]");
+	assert_eq!(simplify(a() / a()).unwrap(), "[[1, 0], [0, 1]]");
+	assert_eq!(simplify(a().pow(NUM(0))).unwrap(), "[[1, 0], [0, 1]]");
+
+	let row = expr(vec![vec![NUM(1), NUM(2)]]);
+	let column = Expr::Matrix(Matrix::column(vec![SYM("a"), SYM("b")]));
+	assert_eq!(simplify(row.clone() * column.clone()).unwrap(), "[[a + 2b]]");
+
+	// Sizes must fit, and singular matrices cannot be inverted
+	assert!(matches!(
+		simplify(row.clone() + column.clone()),
+		Err(SimplifyError::DimensionMismatch)
+	));
+	assert!(simplify(column * row.clone() * NUM(2) * a() * a()).is_ok());
+	assert!(matches!(
+		simplify(a() * row.clone()),
+		Err(SimplifyError::DimensionMismatch)
+	));
+	assert!(matches!(simplify(a() + NUM(1)), Err(SimplifyError::DimensionMismatch)));
+	assert!(matches!(
+		simplify(row.pow(NUM(2))),
+		Err(SimplifyError::DimensionMismatch)
+	));
+
+	let singular = expr(vec![vec![NUM(1), NUM(2)], vec![NUM(2), NUM(4)]]);
+	assert!(matches!(
+		simplify(singular.pow(NUM(-1))),
+		Err(SimplifyError::DivisionByZero)
+	));
+
+	let empty = Expr::Matrix(Matrix::identity(0).map(Expr::Num));
+	assert_eq!(simplify(empty.pow(NUM(-1))).unwrap(), "[]");
+}
@@ -0,0 +1,114 @@
+use abacas::error::ParseError;
+use abacas::number::Number;
+
+/// Helper to construct a number without type inference required.
+fn n(input: &str) -> Number {
+	input.parse().unwrap()
+}
+
+/// Helper to construct a random fraction.
+fn random_fraction() -> Number {
+	Number::from(fastrand::i32(..)) / fastrand::i32(1..)
+}
+
+#[test]
+fn continued_fraction() {
+	for _ in 0..5000 {
+		let number = random_fraction();
+		let terms = number.continued_fraction();
+
+		assert!(terms.iter().all(Number::is_integer));
+		assert!(terms.iter().skip(1).all(Number::is_positive));
+		assert_eq!(Number::from_continued_fraction(&terms), Some(number.clone()));
+		assert_eq!(number.convergents().last(), Some(&number));
+	}
+
+	assert_eq!(Number::from_continued_fraction(&[]), None);
+}
+
+#[test]
+fn limit_denominator() {
+	let pi = n("3.141592653589793");
+
+	assert_eq!(pi.limit_denominator(&n("100")), n("311") / &n("99"));
+	assert_eq!(pi.limit_denominator(&n("30000")), n("94053") / &n("29938"));
+
+	for _ in 0..5000 {
+		let number = random_fraction();
+		let max = Number::from(fastrand::u16(1..));
+		let approx = number.limit_denominator(&max);
+
+		// No fraction with a smaller denominator may be closer than the result
+		let error = (approx.clone() - &number).abs();
+		let denom = approx.clone().denom();
+
+		assert!(denom <= max);
+		assert!(
+			number
+				.convergents()
+				.iter()
+				.all(|c| c.clone().denom() > max || (c.clone() - &number).abs() >= error)
+		);
+	}
+}
+
+#[test]
+fn parse_approx() {
+	assert_eq!(Number::from_str_approx("0.2").unwrap(), n("1") / &n("5"));
+	assert_eq!(Number::from_str_approx("0.3").unwrap(), n("1") / &n("3"));
+	assert_eq!(Number::from_str_approx("0.0").unwrap(), n("0"));
+	assert_eq!(Number::from_str_approx("3.14").unwrap(), n("22") / &n("7"));
+	assert_eq!(Number::from_str_approx("-2.718").unwrap(), n("-106") / &n("39"));
+	assert_eq!(Number::from_str_approx(".5").unwrap(), n("1") / &n("2"));
+
+	assert!(Number::from_str_approx("").is_err());
+	assert!(Number::from_str_approx("1.2.3").is_err());
+	assert!(Number::from_str_approx("abc").is_err());
+}
+
+#[test]
+fn parse_decimal() {
+	assert_eq!(n("1.25"), n("5") / &n("4"));
+	assert_eq!(n("-0.05"), n("-1") / &n("20"));
+	assert_eq!(n("+3.0"), n("3"));
+	assert_eq!(n("2/4"), n("0.5"));
+	assert_eq!(n(".5"), n("1/2"));
+	assert_eq!(n("7."), n("7"));
+}
+
+#[test]
+fn parse_literals() {
+	assert_eq!(n("-1_234.5_6"), n("-123456/100"));
+	assert_eq!(n("1.5E+2"), n("150"));
+	assert_eq!(n("25e-2"), n("1/4"));
+	assert_eq!(n("1.2(3)"), n("37/30"));
+	assert_eq!(n(".(9)"), n("1"));
+	assert_eq!(n("0.(3)e1"), n("10/3"));
+	assert_eq!(n("0xff/0b11"), n("85"));
+	assert_eq!(n("-0o1_7"), n("-15"));
+	assert_eq!(n("1.5/0.25"), n("6"));
+
+	// Repeating decimals and fractions are exact, even in approximation mode
+	assert_eq!(Number::from_str_approx("0.(6)").unwrap(), n("2/3"));
+	assert_eq!(Number::from_str_approx("314/100").unwrap(), n("157/50"));
+	assert_eq!(Number::from_str_approx("1.0e3").unwrap(), n("1000"));
+}
+
+#[test]
+fn parse_errors() {
+	let error = |input: &str| input.parse::<Number>().unwrap_err();
+
+	assert!(matches!(error(""), ParseError::UnexpectedEnd(0)));
+	assert!(matches!(error("."), ParseError::UnexpectedEnd(1)));
+	assert!(matches!(error("1.-5"), ParseError::UnexpectedChar('-', 2)));
+	assert!(matches!(error("1__0"), ParseError::UnexpectedChar('_', 1)));
+	assert!(matches!(error("_1"), ParseError::UnexpectedChar('_', 0)));
+	assert!(matches!(error("0x"), ParseError::UnexpectedEnd(2)));
+	assert!(matches!(error("0b102"), ParseError::UnexpectedChar('2', 4)));
+	assert!(matches!(error("0.(12"), ParseError::UnexpectedEnd(5)));
+	assert!(matches!(error("1e"), ParseError::UnexpectedEnd(2)));
+	assert!(matches!(error("1e999999999"), ParseError::InvalidString(_)));
+	assert!(matches!(error("3/0"), ParseError::ZeroDenominator(2)));
+	assert!(matches!(error("3/-1"), ParseError::UnexpectedChar('-', 2)));
+	assert!(matches!(error("1 2"), ParseError::UnexpectedChar(' ', 1)));
+}
@@ -0,0 +1,158 @@
+use abacas::context::Context;
+use abacas::expr::{Expr, Symbol};
+use abacas::number::Number;
+use abacas::pattern::{Bindings, Rule, RuleSet};
+use abacas::stdlib::StdLib;
+use rug::ops::Pow;
+
+const NUM: fn(i64) -> Expr = |num| Expr::Num(num.into());
+
+const X: fn(&str) -> Expr = |poly| Expr::Poly(Symbol::new("x").unwrap(), poly.parse().unwrap());
+const SYM: fn(&str) -> Expr = |name| Expr::Poly(Symbol::new(name).unwrap(), "x".parse().unwrap());
+const FUN: fn(&str, Vec<Expr>) -> Expr = |name, args| Expr::Fun(Symbol::new(name).unwrap(), args);
+const POW: fn(Expr, Expr) -> Expr = |base, exp| Expr::Pow(base.into(), exp.into());
+
+/// Helper to create the rule `sin(a_)^2 + cos(a_)^2 -> 1`.
+fn pythagorean() -> Rule {
+	let square = |name, arg| FUN(name, vec![arg]).pow(NUM(2));
+	Rule::new(square("sin", SYM("a_")) + square("cos", SYM("a_")), NUM(1))
+}
+
+#[test]
+fn matching() {
+	let ctx = &mut Context::new();
+	let sym = |name| Symbol::new(name).unwrap();
+
+	// Terms of sums and products match in any order
+	let pattern = SYM("a_") * X("x^2") + SYM("b_");
+	let bindings = X("5 - 2x^2").matches(&pattern, ctx).unwrap().unwrap();
+	assert_eq!(bindings[&sym("a_")], NUM(-2));
+	assert_eq!(bindings[&sym("b_")], NUM(5));
+
+	// Repeated wildcards have to match the same expression
+	let pattern = FUN("f", vec![SYM("a_"), SYM("a_")]);
+	assert!(FUN("f", vec![X("x"), X("x")]).matches(&pattern, ctx).unwrap().is_some());
+	assert!(
+		FUN("f", vec![X("x"), SYM("y")])
+			.matches(&pattern, ctx)
+			.unwrap()
+			.is_none()
+	);
+
+	// Sequence wildcards match any amount of arguments or terms
+	let pattern = FUN("f", vec![SYM("a__"), NUM(3)]);
+	let bindings = FUN("f", vec![NUM(1), NUM(2), NUM(3)])
+		.matches(&pattern, ctx)
+		.unwrap()
+		.unwrap();
+	assert_eq!(bindings[&sym("a__")], NUM(3));
+
+	let pattern = FUN("exp", vec![SYM("a_")]) * SYM("b__");
+	let bindings = (FUN("exp", vec![X("x")]) * SYM("y") * NUM(2))
+		.matches(&pattern, ctx)
+		.unwrap()
+		.unwrap();
+	assert_eq!(bindings[&sym("b__")].to_string(), "2y");
+
+	// Powers of polynomials are matched as powers
+	let pattern = POW(SYM("a_"), SYM("n_"));
+	let bindings = X("x^3").matches(&pattern, ctx).unwrap().unwrap();
+	assert_eq!(bindings[&sym("n_")], NUM(3));
+}
+
+#[test]
+fn replace_all() {
+	let ctx = &mut Context::new();
+	let square = |name, arg| FUN(name, vec![arg]).pow(NUM(2));
+
+	// Sums match parts of larger sums
+	let expr = square("sin", X("2x")) + square("cos", X("2x")) + SYM("y");
+	assert_eq!(expr.replace_all(&[pythagorean()], ctx).unwrap().to_string(), "y + 1");
+
+	// Different arguments do not match
+	let expr = square("sin", X("x")) + square("cos", SYM("y"));
+	assert_eq!(
+		expr.replace_all(&[pythagorean()], ctx).unwrap().to_string(),
+		"cos(y)^2 + sin(x)^2"
+	);
+
+	// Sequences are spliced into function arguments
+	let rules = [Rule::new(
+		FUN("f", vec![SYM("a_"), SYM("b__")]),
+		FUN("g", vec![SYM("b__"), SYM("a_")]),
+	)];
+	let expr = FUN("f", vec![NUM(1), NUM(2), NUM(3)]);
+	assert_eq!(expr.replace_all(&rules, ctx).unwrap().to_string(), "g(2, 3, 1)");
+	assert_eq!(
+		FUN("f", vec![NUM(1)]).replace_all(&rules, ctx).unwrap().to_string(),
+		"g(1)"
+	);
+
+	// Replaced parts are not rewritten again
+	let rule = Rule::new(FUN("f", vec![SYM("a_")]), FUN("f", vec![FUN("f", vec![SYM("a_")])]));
+	let result = FUN("f", vec![X("x")]).replace_all(&[rule], ctx).unwrap();
+	assert_eq!(result.to_string(), "f(f(x))");
+}
+
+#[test]
+fn replace_repeated() {
+	let ctx = &mut Context::new();
+	let fact = |arg| FUN("fact", vec![arg]);
+	let is_positive = |bindings: &Bindings| bindings[&Symbol::new("n_").unwrap()].is_num_and(Number::is_positive);
+
+	// fact(n_) -> n_ fact(n_ - 1) for positive n_, and fact(0) -> 1
+	let rules = [
+		Rule::new(fact(SYM("n_")), SYM("n_") * fact(SYM("n_") - NUM(1))).when(is_positive),
+		Rule::new(fact(NUM(0)), NUM(1)),
+	];
+	assert_eq!(fact(NUM(5)).replace_repeated(&rules, ctx).unwrap(), NUM(120));
+	assert_eq!(fact(SYM("y")).replace_repeated(&rules, ctx).unwrap(), fact(SYM("y")));
+
+	// f(a_ b_) -> f(a_) + f(b_)
+	let rule = Rule::new(
+		FUN("f", vec![SYM("a_") * SYM("b_")]),
+		FUN("f", vec![SYM("a_")]) + FUN("f", vec![SYM("b_")]),
+	);
+	let result = FUN("f", vec![X("x") * SYM("y")])
+		.replace_repeated(&[rule], ctx)
+		.unwrap();
+	assert_eq!(result.to_string(), "f(x) + f(y)");
+}
+
+#[test]
+fn context_rules() {
+	let ctx = &mut Context::new();
+	let expr = || (FUN("sin", vec![X("x")]).pow(NUM(2)) + FUN("cos", vec![X("x")]).pow(NUM(2))) * NUM(3);
+
+	assert_eq!(expr().simplify(ctx).unwrap().to_string(), "(cos(x)^2 + sin(x)^2) * 3");
+
+	// Registered rule sets are applied when simplifying
+	ctx.rules.push(RuleSet::new("trig", [pythagorean()]));
+	assert_eq!(expr().simplify(ctx).unwrap(), NUM(3));
+}
+
+#[test]
+fn stdlib() {
+	let stdlib = StdLib::new();
+	let ctx = &mut Context::new();
+
+	let call = |name: &str, args: Vec<Expr>, ctx: &mut Context| {
+		let expr = Expr::Fun(Symbol::new(name).unwrap(), args);
+		stdlib.evaluate(expr, ctx)
+	};
+
+	let exp = |arg| FUN("exp", vec![arg]);
+	let lhs = exp(SYM("a_")) * exp(SYM("b_"));
+	let rhs = exp(SYM("a_") + SYM("b_"));
+
+	let result = call(
+		"replace_all",
+		vec![exp(X("x")) * exp(SYM("y")), lhs.clone(), rhs.clone()],
+		ctx,
+	);
+	assert_eq!(result.to_string(), "exp(x + y)");
+
+	let expr = exp(X("x")) * exp(SYM("y")) * exp(SYM("z"));
+	let result = call("replace_repeated", vec![expr, lhs, rhs], ctx);
+	assert_eq!(result.to_string(), "exp(x + y + z)");
+}
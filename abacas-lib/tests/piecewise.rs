@@ -0,0 +1,123 @@
+use abacas::assumption::Assumption;
+use abacas::context::Context;
+use abacas::expr::{Expr, Symbol};
+use abacas::number::Number;
+use abacas::relation::Relation;
+use abacas::stdlib::StdLib;
+
+const NUM: fn(i64) -> Expr = |num| Expr::Num(num.into());
+const FRAC: fn(i64, i64) -> Expr = |numer, denom| Expr::Num(Number::from(numer) / denom);
+
+const X: fn(&str) -> Expr = |poly| Expr::Poly(Symbol::new("x").unwrap(), poly.parse().unwrap());
+const SYM: fn(&str) -> Expr = |name| Expr::Poly(Symbol::new(name).unwrap(), "x".parse().unwrap());
+const FUN: fn(&str, Vec<Expr>) -> Expr = |name, args| Expr::Fun(Symbol::new(name).unwrap(), args);
+const REL: fn(Relation, Expr, Expr) -> Expr = |rel, lhs, rhs| Expr::Rel(rel, lhs.into(), rhs.into());
+
+/// Helper to create the absolute value of `x` as a piecewise expression.
+fn abs() -> Expr {
+	Expr::Piecewise(vec![
+		(X("x"), REL(Relation::Ge, X("x"), NUM(0))),
+		(X("-x"), Expr::boolean(true)),
+	])
+}
+
+#[test]
+fn simplify() {
+	let ctx = &mut Context::new();
+	assert!(ctx.assume(Symbol::new("y").unwrap(), Assumption::Negative));
+
+	let mut simplify = |expr: Expr| expr.simplify(ctx).unwrap().to_string();
+
+	assert_eq!(simplify(abs()), "piecewise(x, x >= 0, -x, true)");
+	assert_eq!(simplify(abs().substitute(&Symbol::new("x").unwrap(), &NUM(-4))), "4");
+
+	// Branches are decided under assumptions
+	assert_eq!(simplify(abs().substitute(&Symbol::new("x").unwrap(), &SYM("y"))), "-y");
+
+	// Unreachable branches are dropped, and equal neighbours merged
+	let expr = Expr::Piecewise(vec![
+		(NUM(1), Expr::boolean(false)),
+		(NUM(2), REL(Relation::Lt, X("x"), NUM(0))),
+		(NUM(2), REL(Relation::Gt, X("x"), NUM(5))),
+		(NUM(3), Expr::boolean(true)),
+		(NUM(4), REL(Relation::Eq, X("x"), NUM(1))),
+	]);
+	assert_eq!(simplify(expr), "piecewise(2, x < 0 or x > 5, 3, true)");
+
+	// Without a true condition, the expression stays undefined where no branch applies
+	let expr = Expr::Piecewise(vec![(NUM(1), REL(Relation::Gt, X("x"), NUM(0)))]);
+	assert_eq!(simplify(expr), "piecewise(1, x > 0)");
+}
+
+#[test]
+fn stdlib() {
+	let stdlib = StdLib::new();
+	let ctx = &mut Context::new();
+	assert!(ctx.assume(Symbol::new("n").unwrap(), Assumption::Integer));
+	assert!(ctx.assume(Symbol::new("p").unwrap(), Assumption::Positive));
+
+	let mut call = |name: &str, args: Vec<Expr>| stdlib.evaluate(FUN(name, args), ctx).to_string();
+
+	assert_eq!(call("abs", vec![FRAC(-3, 2)]), "1.5");
+	assert_eq!(call("abs", vec![X("x")]), "piecewise(x, x >= 0, -x, true)");
+	assert_eq!(call("abs", vec![SYM("p")]), "p");
+	assert_eq!(call("sign", vec![NUM(0)]), "0");
+	assert_eq!(call("sign", vec![SYM("p")]), "1");
+	assert_eq!(call("sign", vec![X("x")]), "piecewise(1, x > 0, 0, x == 0, -1, true)");
+
+	assert_eq!(call("min", vec![NUM(3), NUM(-1), NUM(2)]), "-1");
+	assert_eq!(call("max", vec![NUM(3), NUM(-1), NUM(2)]), "3");
+	assert_eq!(call("max", vec![X("x"), NUM(0)]), "piecewise(x, x >= 0, 0, true)");
+	assert_eq!(call("min", vec![SYM("p"), NUM(0)]), "0");
+
+	assert_eq!(call("floor", vec![FRAC(-7, 2)]), "-4");
+	assert_eq!(call("ceil", vec![FRAC(-7, 2)]), "-3");
+	assert_eq!(call("round", vec![FRAC(5, 2)]), "3");
+	assert_eq!(call("floor", vec![SYM("n")]), "n");
+	assert_eq!(call("floor", vec![X("x")]), "floor(x)");
+	assert_eq!(
+		call("ceil", vec![FUN("max", vec![X("x"), FRAC(1, 2)])]),
+		"piecewise(ceil(x), x >= 0.5, 1, true)"
+	);
+
+	// Piecewise expressions can be written as calls
+	let args = vec![NUM(1), REL(Relation::Lt, X("x"), NUM(0)), NUM(2)];
+	assert_eq!(call("piecewise", args), "piecewise(1, x < 0, 2, true)");
+}
+
+#[test]
+fn calculus() {
+	let x = Symbol::new("x").unwrap();
+	let ctx = &mut Context::new();
+
+	let result = abs().derivative(&x).simplify(ctx).unwrap();
+	assert_eq!(result.to_string(), "piecewise(1, x >= 0, -1, true)");
+
+	let result = abs().integrate(&x, ctx).unwrap();
+	assert_eq!(result.to_string(), "piecewise(0.5x^2, x >= 0, -0.5x^2, true)");
+
+	// Definite integrals are split where the branches apply
+	assert_eq!(
+		abs().integrate_definite(&x, &NUM(-1), &NUM(2), ctx).unwrap(),
+		FRAC(5, 2)
+	);
+	assert_eq!(
+		abs().integrate_definite(&x, &NUM(2), &NUM(-1), ctx).unwrap(),
+		FRAC(-5, 2)
+	);
+
+	let expr = Expr::Piecewise(vec![
+		(NUM(1), REL(Relation::Lt, X("x"), NUM(0))),
+		(X("x^2"), REL(Relation::Le, X("x"), NUM(1))),
+		(NUM(2), Expr::boolean(true)),
+	]);
+	assert_eq!(
+		expr.integrate_definite(&x, &NUM(-2), &NUM(3), ctx).unwrap(),
+		FRAC(19, 3)
+	);
+
+	// The expression is undefined where no branch applies
+	let expr = Expr::Piecewise(vec![(NUM(1), REL(Relation::Gt, X("x"), NUM(0)))]);
+	let result = expr.integrate_definite(&x, &NUM(-1), &NUM(1), ctx).unwrap();
+	assert_eq!(result.to_string(), "integrate(piecewise(1, x > 0), x, -1, 1)");
+}
@@ -1,3 +1,4 @@
+use abacas::expr::Symbol;
 use abacas::monomial::Monomial;
 use abacas::number::Number;
 use abacas::polynomial::Polynomial;
@@ -19,6 +20,28 @@ fn p(input: &str) -> Polynomial {
 	input.parse().unwrap()
 }
 
+#[test]
+fn compose() {
+	assert_eq!(p("x^2 + 2x").compose(&p("x - 1")).unwrap(), p("x^2 - 1"));
+	assert_eq!(p("3").compose(&p("x^2 + 5")).unwrap(), p("3"));
+	assert_eq!(p("x^2 + 1").compose(&Polynomial::ZERO).unwrap(), p("1"));
+	assert_eq!(
+		p("x^2").compose(&p("x^2")).unwrap().compose(&p("x + 1")).unwrap(),
+		p("x^4 + 4x^3 + 6x^2 + 4x + 1")
+	);
+
+	// Only non-negative integer degrees can be composed
+	assert_eq!(p("x^-1").compose(&p("x + 1")), None);
+	assert_eq!(p("x^0.5").compose(&p("x + 1")), None);
+
+	// Degrees too large to write out every coefficient are rejected
+	let huge = p("x^4000000000 + 1");
+	assert_eq!(huge.compose(&p("x + 1")), None);
+	assert_eq!(huge.decompose(), None);
+	assert_eq!(huge.shift(&1.into()), None);
+	assert_eq!(huge.mobius_transform(&1.into(), &0.into(), &0.into(), &1.into()), None);
+}
+
 #[test]
 fn construction() {
 	let poly = Polynomial::new([A(), D(), F(), D(), A()]);
@@ -28,6 +51,33 @@ fn construction() {
 	assert_eq!(zero.to_string(), "0");
 }
 
+#[test]
+fn decompose() {
+	let (outer, inner) = p("x^6 + 3x^4 + 3x^2 + 5").decompose().unwrap();
+	assert_eq!((outer, inner), (p("x^3 + 3x^2 + 3x + 5"), p("x^2")));
+
+	// The inner polynomial of the smallest degree is found first
+	let poly = p("x^2 + x")
+		.compose(&p("x^3 - x"))
+		.unwrap()
+		.compose(&p("x^2 + 1"))
+		.unwrap();
+	let (outer, inner) = poly.decompose().unwrap();
+	assert_eq!(inner, p("x^2"));
+	assert_eq!(outer.compose(&inner).unwrap(), poly);
+
+	let poly = p("2x^2 - x").compose(&p("0.5x^3 + x^2 - 7")).unwrap();
+	let (outer, inner) = poly.decompose().unwrap();
+	assert_eq!(inner, p("x^3 + 2x^2"));
+	assert_eq!(outer, p("0.5x^2 - 14.5x + 105"));
+
+	// Prime degrees and polynomials without a decomposition
+	assert_eq!(p("x^5 + x^2").decompose(), None);
+	assert_eq!(p("x^4 + x^3 + x").decompose(), None);
+	assert_eq!(p("x^2 + 1").decompose(), None);
+	assert_eq!(Polynomial::ZERO.decompose(), None);
+}
+
 #[test]
 fn gcd() {
 	let a = p("x - 1") * &p("x + 6");
@@ -95,6 +145,20 @@ fn parse() {
 
 	let same = p(expected.to_string().as_str());
 	assert_eq!(same, expected);
+
+	// Spacing around signs does not matter, but signs of degrees and exponents belong to their term
+	assert_eq!(p("4x^2+5x^3-1"), p("5x^3 + 4x^2 - 1"));
+	assert_eq!(p("  x^-1 -x^-2+ 3 "), p("-x^-2 + x^-1 + 3"));
+	assert_eq!(p("1.5e-1x - -2"), p("0.15x + 2"));
+	assert_eq!(p("2 * x^ 3 - 0x"), p("2x^3"));
+	assert_eq!(p("0x1F + 1/2x"), p("0.5x + 31"));
+
+	// Errors point to the invalid term
+	let error = |src: &str| src.parse::<Polynomial>().unwrap_err().to_string();
+	assert_eq!(error("x^2 + 3y - 1"), "invalid term '3y' at index 6");
+	assert_eq!(error("x+x^"), "invalid term 'x^' at index 2");
+	assert_eq!(error("x^2 -"), "unexpected end of input at index 5");
+	assert_eq!(error(""), "unexpected end of input at index 0");
 }
 
 #[test]
@@ -118,6 +182,85 @@ fn polydiv() {
 	assert_eq!(div_self, Some((Polynomial::from(1), Polynomial::ZERO)));
 }
 
+#[test]
+fn rational_roots() {
+	let poly = p("12x + 8") * &p("x - 2/7") * &p("x - 2/7") * &p("x^2 - 2") * &p("99991x + 5");
+	let roots = poly.rational_roots().unwrap();
+
+	assert_eq!(
+		roots,
+		[Number::from(-2) / 3, Number::from(-5) / 99991, Number::from(2) / 7]
+	);
+
+	assert_eq!(p("x^2 + 1").rational_roots(), Some(Vec::new()));
+	assert_eq!(p("x^2 - 2x^-1").rational_roots(), Some(Vec::new()));
+	assert_eq!(Polynomial::ZERO.rational_roots(), None);
+}
+
+#[test]
+fn symbols() {
+	let (t, long) = (Symbol::new("t").unwrap(), Symbol::new("theta").unwrap());
+
+	let poly = Polynomial::parse_with("t^3 - 2t+1/2", &t).unwrap();
+	assert_eq!(poly, p("x^3 - 2x + 0.5"));
+	assert_eq!(poly.display_with(&t).to_string(), "t^3 - 2t + 0.5");
+	assert_eq!(poly.display_with(&long).to_string(), "theta^3 - 2theta + 0.5");
+	assert_eq!(
+		Polynomial::parse_with("-theta^2 + 3 theta", &long).unwrap(),
+		p("-x^2 + 3x")
+	);
+
+	// Other variables are invalid, and `x` is not special
+	assert!(Polynomial::parse_with("t + x", &t).is_err());
+	assert!(Polynomial::parse_with("x", &long).is_err());
+
+	let mono = Monomial::parse_with("-2/3 * t^-1", &t).unwrap();
+	assert_eq!(mono, Monomial::new(Number::from(-2) / 3, -1));
+	assert_eq!(Monomial::new(4, 1).display_with(&long).to_string(), "4theta");
+
+	// Symbols like `e1` are separated from the coefficient, which would otherwise read as `2e1 = 20`
+	let e1 = Symbol::new("e1").unwrap();
+	let poly = p("2x^2 - x");
+	assert_eq!(poly.display_with(&e1).to_string(), "2 * e1^2 - e1");
+	assert_eq!(Polynomial::parse_with("2 * e1^2 - e1", &e1).unwrap(), poly);
+}
+
+#[test]
+fn transforms() {
+	let poly = p("x^3 - 6x^2 + 11x - 6");
+
+	// Shifting and scaling move the roots `1`, `2` and `3`
+	assert_eq!(poly.shift(&2.into()).unwrap(), p("x^3 - x"));
+	assert_eq!(poly.shift(&(-1).into()).unwrap(), p("x^3 - 9x^2 + 26x - 24"));
+	assert_eq!(poly.shift(&0.into()).unwrap(), poly);
+	assert_eq!(
+		poly.scale(&(Number::from(1) / 2)).unwrap() * 8,
+		p("x^3 - 12x^2 + 44x - 48")
+	);
+	assert_eq!(poly.scale(&0.into()).unwrap(), p("-6"));
+
+	// Reversing inverts the roots
+	assert_eq!(poly.reverse(), p("-6x^3 + 11x^2 - 6x + 1"));
+	assert_eq!(p("x^4 + x^2").reverse(), p("x^2 + 1"));
+	assert_eq!(Polynomial::ZERO.reverse(), Polynomial::ZERO);
+
+	// Möbius transforms generalize the other transforms
+	let (zero, one) = (Number::zero(), Number::one());
+	let two = Number::from(2);
+	assert_eq!(poly.mobius_transform(&one, &two, &zero, &one), poly.shift(&two));
+	assert_eq!(poly.mobius_transform(&two, &zero, &zero, &one), poly.scale(&two));
+	assert_eq!(poly.mobius_transform(&zero, &one, &one, &zero).unwrap(), poly.reverse());
+	assert_eq!(
+		poly.mobius_transform(&one, &zero, &one, &one).unwrap(),
+		p("-2x^2 - 7x - 6")
+	);
+
+	// Negative and fractional degrees are not supported
+	assert_eq!(p("x^-2 + 1").shift(&1.into()), None);
+	assert_eq!(p("x^-2 + 1").scale(&0.into()), None);
+	assert_eq!(p("x^0.5").mobius_transform(&one, &zero, &zero, &one), None);
+}
+
 #[test]
 fn zeros() {
 	let from = Polynomial::from(0) + 0 - 0;
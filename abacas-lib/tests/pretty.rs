@@ -0,0 +1,145 @@
+use abacas::context::Context;
+use abacas::expr::{Expr, Symbol};
+use abacas::matrix::Matrix;
+use abacas::number::Number;
+use abacas::pretty::Pretty;
+use abacas::relation::Relation;
+use rug::ops::Pow;
+
+const NUM: fn(i64) -> Expr = |num| Expr::Num(num.into());
+const FRAC: fn(i64, i64) -> Expr = |numer, denom| Expr::Num(Number::from(numer) / denom);
+
+const SYM: fn(&str) -> Expr = |name| Expr::Poly(Symbol::new(name).unwrap(), "x".parse().unwrap());
+const FUN: fn(&str, Vec<Expr>) -> Expr = |name, args| Expr::Fun(Symbol::new(name).unwrap(), args);
+
+/// Helper to simplify an expression and pretty print it with Unicode and ASCII characters.
+fn pretty(expr: Expr) -> (String, String) {
+	let expr = expr.simplify(&mut Context::new()).unwrap();
+	(Pretty(&expr).to_string(), format!("{:#}", Pretty(&expr)))
+}
+
+/// Helper to join lines of a block.
+fn lines(lines: &[&str]) -> String {
+	lines.join("\n")
+}
+
+#[test]
+fn polynomials() {
+	let poly = |src: &str| pretty(Expr::Poly(Symbol::new("x").unwrap(), src.parse().unwrap()));
+
+	assert_eq!(poly("0"), ("0".into(), "0".into()));
+	assert_eq!(
+		poly("-x^3 + 2x - 1"),
+		("-x³ + 2x - 1".into(), lines(&["  3", "-x  + 2x - 1"]))
+	);
+
+	// Fractions are stacked and exponents without superscripts are raised
+	assert_eq!(
+		poly("0.5x^2 - 3x + 1/3"),
+		(
+			lines(&["1          1", "─x² - 3x + ─", "2          3"]),
+			lines(&["1 2        1", "-x  - 3x + -", "2          3"])
+		)
+	);
+	assert_eq!(
+		poly("x^-1 + x^0.5 - x^1.5").0,
+		lines(&["  3/2    _", "-x    + √x + x⁻¹"])
+	);
+}
+
+#[test]
+fn fractions() {
+	let (x, y) = (SYM("x"), SYM("y"));
+
+	assert_eq!(
+		pretty(NUM(-3) * x.clone() / (NUM(2) * y.clone())),
+		(lines(&[" 3⋅x", "-───", " 2⋅y"]), lines(&[" 3*x", "----", " 2*y"]))
+	);
+	assert_eq!(
+		pretty(y.clone() - FRAC(1, 2) * FUN("sin", vec![x.clone()])).0,
+		lines(&[" sin(x)", "-────── + y", "   2"])
+	);
+
+	// Parentheses grow with their contents
+	assert_eq!(
+		pretty((x.clone() / (y.clone() + NUM(1))).pow(NUM(2))),
+		(
+			lines(&["   x²", "────────", "(y + 1)²"]),
+			lines(&["    2", "   x", "--------", "       2", "(y + 1)"])
+		)
+	);
+	assert_eq!(
+		pretty(FUN("f", vec![x.clone() / y.clone()])).0,
+		lines(&[" ⎛x⎞", "f⎜─⎟", " ⎝y⎠"])
+	);
+}
+
+#[test]
+fn roots() {
+	let (x, y) = (SYM("x"), SYM("y"));
+
+	assert_eq!(
+		pretty((x.clone() + NUM(1)).pow(FRAC(1, 2)) + y.clone().pow(FRAC(1, 3))),
+		(
+			lines(&[" _____    _", "√x + 1 + ∛y"]),
+			lines(&["  _____    1/3", "\\/x + 1 + y"])
+		)
+	);
+	assert_eq!(
+		pretty(FUN("sqrt", vec![NUM(1) / (x.clone() + NUM(1))])).0,
+		lines(&[" _____", "│  1", "│─────", "√x + 1"])
+	);
+	assert_eq!(pretty(x.clone().pow(FRAC(1, 5))).0, lines(&[" 1/5", "x"]));
+}
+
+#[test]
+fn structures() {
+	let (x, y) = (SYM("x"), SYM("y"));
+
+	let matrix = Matrix::from_rows(vec![vec![NUM(1), FRAC(1, 2)], vec![x.clone().pow(NUM(2)), NUM(-10)]]).unwrap();
+	assert_eq!(
+		pretty(Expr::Matrix(matrix)),
+		(
+			lines(&["⎡     1 ⎤", "⎢1    ─ ⎥", "⎢     2 ⎥", "⎣x²  -10⎦"]),
+			lines(&["[     1 ]", "[1    - ]", "[     2 ]", "[ 2     ]", "[x   -10]"])
+		)
+	);
+
+	let rel = |rel, lhs: &Expr, rhs| Expr::Rel(rel, lhs.clone().into(), rhs);
+	let expr = Expr::Or(vec![
+		Expr::And(vec![
+			rel(Relation::Ge, &x, NUM(0).into()),
+			rel(Relation::Ne, &y, NUM(1).into()),
+		]),
+		Expr::Not(rel(Relation::Lt, &x, FRAC(-1, 2).into()).into()),
+	]);
+	assert_eq!(
+		Pretty(&expr).to_string(),
+		lines(&[
+			"                      1",
+			"x ≥ 0 ∧ y ≠ 1 ∨ ¬x < -─",
+			"                      2"
+		])
+	);
+	assert_eq!(
+		format!("{:#}", Pretty(&expr)),
+		lines(&[
+			"                              1",
+			"x >= 0 and y != 1 or not x < --",
+			"                              2"
+		])
+	);
+
+	let expr = Expr::Piecewise(vec![
+		(x.clone() / NUM(2), rel(Relation::Gt, &x, NUM(0).into())),
+		(NUM(0), Expr::And(vec![])),
+	]);
+	assert_eq!(expr.to_pretty(), lines(&["⎧x", "⎨─  for x > 0", "⎪2", "⎩0  otherwise"]));
+
+	assert_eq!(pretty(NUM(-2).pow(x.clone())).0, lines(&["    x", "(-2)"]));
+	assert_eq!(
+		pretty(FUN("abs", vec![x.clone() / y.clone()])).0,
+		lines(&["|x|", "|─|", "|y|"])
+	);
+	assert_eq!(pretty(FUN("factorial", vec![x.clone() + NUM(1)])).0, "(x + 1)!");
+}
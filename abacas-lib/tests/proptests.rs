@@ -48,3 +48,86 @@ fn polydiv() {
 		assert_eq!(quotient * &b + remainder, a);
 	}
 }
+
+#[test]
+fn interpolate() {
+	for _ in 0..200 {
+		let a = random_poly(10);
+		let points = (0..=10)
+			.map(|_| {
+				let x = Number::from(fastrand::i16(..)) / fastrand::u16(1..);
+				(x.clone(), a.eval(&x).unwrap())
+			})
+			.collect::<Vec<_>>();
+
+		// Random points may coincide, in which case there is no interpolating polynomial
+		match Polynomial::interpolate(&points) {
+			Some(poly) => assert_eq!(poly, a),
+			None => assert!((1..points.len()).any(|index| points[..index].iter().any(|p| p.0 == points[index].0))),
+		}
+	}
+}
+
+#[test]
+fn hermite() {
+	for _ in 0..200 {
+		let a = random_poly(11);
+		let derivative = a.derivative();
+
+		// Values and first derivatives at six points determine a polynomial of degree eleven
+		let denom = fastrand::u16(1..);
+		let points = (0..6)
+			.map(|x| {
+				let x = Number::from(x) / denom;
+				(x.clone(), vec![a.eval(&x).unwrap(), derivative.eval(&x).unwrap()])
+			})
+			.collect::<Vec<_>>();
+
+		assert_eq!(Polynomial::interpolate_hermite(&points).unwrap(), a);
+	}
+}
+
+#[test]
+fn fit() {
+	for _ in 0..200 {
+		let a = random_poly(5);
+		let points = (0..20)
+			.map(|x| (Number::from(x), a.eval(&Number::from(x)).unwrap()))
+			.collect::<Vec<_>>();
+
+		// Exact data is fitted exactly by every degree that is high enough
+		assert_eq!(Polynomial::fit(&points, 5).unwrap(), a);
+		assert_eq!(Polynomial::fit(&points, 8).unwrap(), a);
+	}
+}
+
+#[test]
+fn compose() {
+	for _ in 0..200 {
+		let a = random_poly(4);
+		let b = random_poly(3);
+		let by = Number::from(fastrand::i16(..)) / fastrand::u16(1..);
+
+		// The Taylor shift agrees with composing with `x + a`
+		let linear = Polynomial::from(Monomial::linear(1)) + by.clone();
+		assert_eq!(a.shift(&by), a.compose(&linear));
+
+		// Decompositions compose back to the polynomial
+		let composed = a.compose(&b).unwrap();
+		let (outer, inner) = composed.decompose().unwrap();
+		assert_eq!(outer.compose(&inner).unwrap(), composed);
+	}
+}
+
+#[test]
+fn roundtrip() {
+	for _ in 0..500 {
+		let poly = random_poly(20) - random_poly(20);
+
+		// Negative and fractional degrees are read back as well
+		let degree = Number::from(fastrand::i8(-5..=5)) / fastrand::u8(1..=4);
+		let poly = poly + Polynomial::from(Monomial::new(Number::from(fastrand::u16(1..)) / 7, degree));
+
+		assert_eq!(poly.to_string().parse::<Polynomial>().unwrap(), poly);
+	}
+}
@@ -0,0 +1,115 @@
+use abacas::assumption::Assumption;
+use abacas::context::Context;
+use abacas::expr::{Expr, Symbol};
+use abacas::relation::Relation;
+use abacas::set::Set;
+
+const NUM: fn(i64) -> Expr = |num| Expr::Num(num.into());
+
+const X: fn(&str) -> Expr = |poly| Expr::Poly(Symbol::new("x").unwrap(), poly.parse().unwrap());
+const SYM: fn(&str) -> Expr = |name| Expr::Poly(Symbol::new(name).unwrap(), "x".parse().unwrap());
+const REL: fn(Relation, Expr, Expr) -> Expr = |rel, lhs, rhs| Expr::Rel(rel, lhs.into(), rhs.into());
+
+/// Helper to solve an inequality in `x` and display the solution set.
+fn solve(expr: Expr) -> Option<String> {
+	let ctx = &mut Context::new();
+	let set = expr.solve_inequality(&Symbol::new("x").unwrap(), ctx).unwrap();
+
+	set.map(|set| set.to_string())
+}
+
+#[test]
+fn simplify() {
+	let ctx = &mut Context::new();
+	assert!(ctx.assume(Symbol::new("y").unwrap(), Assumption::Positive));
+
+	let mut simplify = |expr: Expr| expr.simplify(ctx).unwrap().to_string();
+
+	// Relations between numbers and expressions with known signs are decided
+	assert_eq!(simplify(REL(Relation::Lt, NUM(1), NUM(2))), "true");
+	assert_eq!(simplify(REL(Relation::Ne, NUM(3), NUM(3))), "false");
+	assert_eq!(simplify(REL(Relation::Gt, SYM("y") + NUM(1), NUM(0))), "true");
+	assert_eq!(simplify(REL(Relation::Le, X("x"), NUM(0))), "x <= 0");
+
+	// Connectives absorb constants, and negations are pushed inwards
+	let rel = || REL(Relation::Lt, X("x"), NUM(1));
+	assert_eq!(simplify(Expr::And(vec![rel(), Expr::boolean(true)])), "x < 1");
+	assert_eq!(simplify(Expr::Or(vec![rel(), Expr::boolean(true)])), "true");
+	assert_eq!(simplify(Expr::Not(Expr::Not(rel().into()).into())), "x < 1");
+	assert_eq!(
+		simplify(Expr::Not(
+			Expr::And(vec![rel(), REL(Relation::Eq, X("x"), NUM(0))]).into()
+		)),
+		"x != 0 or x >= 1"
+	);
+}
+
+#[test]
+fn polynomial() {
+	assert_eq!(
+		solve(REL(Relation::Gt, X("x^2"), NUM(1))).unwrap(),
+		"(-inf, -1) ∪ (1, inf)"
+	);
+	assert_eq!(
+		solve(REL(Relation::Le, X("x^3 - x"), NUM(0))).unwrap(),
+		"(-inf, -1] ∪ [0, 1]"
+	);
+	assert_eq!(solve(REL(Relation::Ge, X("x^2 - 2x + 1"), NUM(0))).unwrap(), "ℝ");
+	assert_eq!(solve(REL(Relation::Le, X("x^2 - 2x + 1"), NUM(0))).unwrap(), "{1}");
+	assert_eq!(solve(REL(Relation::Lt, X("x^2 + 1"), NUM(0))).unwrap(), "∅");
+	assert_eq!(
+		solve(REL(Relation::Ne, X("x^2"), X("x"))).unwrap(),
+		"(-inf, 0) ∪ (0, 1) ∪ (1, inf)"
+	);
+
+	// Irrational roots are written exactly
+	assert_eq!(
+		solve(REL(Relation::Gt, X("x^3"), NUM(2))).unwrap(),
+		"(root(x^3 - 2, 1), inf)"
+	);
+}
+
+#[test]
+fn rational() {
+	assert_eq!(
+		solve(REL(Relation::Ge, X("x + 1") / X("x - 1"), NUM(0))).unwrap(),
+		"(-inf, -1] ∪ (1, inf)"
+	);
+	assert_eq!(
+		solve(REL(Relation::Lt, X("x^-1"), NUM(1))).unwrap(),
+		"(-inf, 0) ∪ (1, inf)"
+	);
+}
+
+#[test]
+fn connectives() {
+	let expr = Expr::And(vec![
+		REL(Relation::Gt, X("x"), NUM(-2)),
+		REL(Relation::Le, X("x^2"), NUM(4)),
+	]);
+	assert_eq!(solve(expr).unwrap(), "(-2, 2]");
+
+	let expr = Expr::Not(REL(Relation::Lt, X("x^2"), NUM(9)).into());
+	assert_eq!(solve(expr).unwrap(), "(-inf, -3] ∪ [3, inf)");
+
+	// Relations that are not between rational functions cannot be solved
+	let expr = REL(
+		Relation::Lt,
+		Expr::Fun(Symbol::new("sin").unwrap(), vec![X("x")]),
+		NUM(0),
+	);
+	assert_eq!(solve(expr), None);
+}
+
+#[test]
+fn condition() {
+	let x = Symbol::new("x").unwrap();
+	let inf = |poly: &str| Expr::Poly(Symbol::new("inf").unwrap(), poly.parse().unwrap());
+
+	let set = Set::interval(NUM(0), inf("x"), true, true);
+	assert_eq!(set.condition(&x).unwrap().to_string(), "x >= 0");
+
+	let set = Set::interval(inf("-x"), inf("x"), false, false);
+	assert_eq!(set.condition(&x), Some(Expr::And(vec![])));
+	assert_eq!(Set::Empty.condition(&x), Some(Expr::boolean(false)));
+}
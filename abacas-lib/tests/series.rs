@@ -0,0 +1,201 @@
+use abacas::context::Context;
+use abacas::expr::{Expr, Symbol};
+use abacas::number::Number;
+use abacas::series::PowerSeries;
+use abacas::stdlib::StdLib;
+
+const NUM: fn(i64) -> Expr = |num| Expr::Num(num.into());
+const FRAC: fn(i64, i64) -> Expr = |numer, denom| Expr::Num(Number::from(numer) / denom);
+
+const X: fn(&str) -> Expr = |poly| Expr::Poly(Symbol::new("x").unwrap(), poly.parse().unwrap());
+const FUN: fn(&str, Expr) -> Expr = |name, arg| Expr::Fun(Symbol::new(name).unwrap(), vec![arg]);
+
+/// Helper to create a series with numeric coefficients from pairs of degrees and coefficients.
+fn series(terms: &[(i64, Expr)], order: i64) -> PowerSeries {
+	let terms = terms
+		.iter()
+		.map(|(degree, coeff)| (Number::from(*degree), coeff.clone()));
+	PowerSeries::new(terms, order)
+}
+
+/// Helper to expand an expression around a point.
+fn expand(expr: Expr, point: Expr, order: i64) -> PowerSeries {
+	let x = Symbol::new("x").unwrap();
+	expr.series(&x, &point, order)
+		.unwrap_or_else(|| panic!("no series for {expr}"))
+}
+
+#[test]
+fn arithmetic() {
+	let a = series(&[(0, NUM(1)), (1, NUM(2)), (3, NUM(1))], 4);
+	let b = series(&[(1, NUM(-2)), (2, NUM(5))], 3);
+
+	// The lower order wins when adding
+	assert_eq!(a.clone() + &b, series(&[(0, NUM(1)), (2, NUM(5))], 3));
+	assert_eq!(a.clone() - &a, series(&[], 4));
+
+	// Error terms are multiplied by the lowest term of the other series
+	let product = a.clone() * &b;
+	assert_eq!(product, series(&[(1, NUM(-2)), (2, NUM(1))], 3));
+
+	let product = a * &series(&[(-1, NUM(1))], 2);
+	assert_eq!(product, series(&[(-1, NUM(1)), (0, NUM(2))], 2));
+
+	// Terms at or above the order are dropped
+	let truncated = series(&[(0, NUM(1)), (2, NUM(1)), (5, NUM(1))], 3);
+	assert_eq!(truncated.terms().len(), 2);
+	assert_eq!(truncated.truncate(1), series(&[(0, NUM(1))], 1));
+}
+
+#[test]
+fn inversion() {
+	// 1 / (1 - x) = 1 + x + x^2 + ...
+	let geometric = series(&[(0, NUM(1)), (1, NUM(-1))], 6).inverse().unwrap();
+	assert_eq!(
+		geometric,
+		series(
+			&[
+				(0, NUM(1)),
+				(1, NUM(1)),
+				(2, NUM(1)),
+				(3, NUM(1)),
+				(4, NUM(1)),
+				(5, NUM(1))
+			],
+			6
+		)
+	);
+
+	// Inverses of series without a constant term are Laurent series
+	let laurent = series(&[(2, NUM(2)), (3, NUM(2))], 5).inverse().unwrap();
+	assert_eq!(
+		laurent,
+		series(&[(-2, FRAC(1, 2)), (-1, FRAC(-1, 2)), (0, FRAC(1, 2))], 1)
+	);
+
+	assert_eq!(series(&[], 3).inverse(), None);
+}
+
+#[test]
+fn composition() {
+	let x = series(&[(1, NUM(1))], 7);
+	let sin = x.sin().unwrap();
+
+	// exp(sin(x)) = 1 + x + x^2 / 2 - x^4 / 8 - x^5 / 15 + ...
+	let result = x.exp().unwrap().compose(&sin).unwrap();
+	let expected = [
+		(0, NUM(1)),
+		(1, NUM(1)),
+		(2, FRAC(1, 2)),
+		(4, FRAC(-1, 8)),
+		(5, FRAC(-1, 15)),
+		(6, FRAC(-1, 240)),
+	];
+	assert_eq!(result, series(&expected, 7));
+
+	// The reversion of sin(x) is asin(x)
+	let asin = sin.reversion().unwrap();
+	let expected = [(1, NUM(1)), (3, FRAC(1, 6)), (5, FRAC(3, 40))];
+	assert_eq!(asin, series(&expected, 7));
+	assert_eq!(sin.compose(&asin).unwrap(), x);
+
+	// Inner series need a positive valuation, reversions a linear term
+	assert_eq!(sin.compose(&series(&[(0, NUM(1))], 3)), None);
+	assert_eq!(series(&[(2, NUM(1))], 4).reversion(), None);
+}
+
+#[test]
+fn functions() {
+	let x = series(&[(1, NUM(1))], 6);
+	let one_plus_x = series(&[(0, NUM(1)), (1, NUM(1))], 6);
+
+	assert_eq!(x.exp().unwrap().ln().unwrap(), x);
+	assert_eq!(
+		x.sin().unwrap().atan().unwrap().tan().unwrap().to_string(),
+		x.sin().unwrap().to_string()
+	);
+
+	// sqrt(1 + x)^2 = 1 + x
+	let sqrt = one_plus_x.sqrt().unwrap();
+	assert_eq!(sqrt.clone() * &sqrt, one_plus_x);
+
+	// cos(x)^2 + sin(x)^2 = 1
+	let (sin, cos) = (x.sin().unwrap(), x.cos().unwrap());
+	assert_eq!(sin.clone() * &sin + &(cos.clone() * &cos), series(&[(0, NUM(1))], 6));
+
+	// Derivatives and antiderivatives
+	assert_eq!(sin.derivative(), cos.clone().truncate(5));
+	assert_eq!(cos.integrate().unwrap().truncate(6), sin);
+	assert_eq!(series(&[(-1, NUM(1))], 2).integrate(), None);
+
+	// Logarithms need a constant term
+	assert_eq!(x.ln(), None);
+	assert_eq!(series(&[(-1, NUM(1))], 2).exp(), None);
+}
+
+#[test]
+fn expansions() {
+	let x = || X("x");
+
+	// Taylor series
+	let result = expand(FUN("tan", x()), NUM(0), 6);
+	assert_eq!(result, series(&[(1, NUM(1)), (3, FRAC(1, 3)), (5, FRAC(2, 15))], 6));
+
+	let result = expand(FUN("ln", x()), NUM(1), 4);
+	assert_eq!(result, series(&[(1, NUM(1)), (2, FRAC(-1, 2)), (3, FRAC(1, 3))], 4));
+
+	let result = expand(X("x^3 - 2x"), NUM(2), 5);
+	assert_eq!(
+		result,
+		series(&[(0, NUM(4)), (1, NUM(10)), (2, NUM(6)), (3, NUM(1))], 5)
+	);
+
+	// Coefficients stay exact around other points
+	let result = expand(FUN("exp", x()), NUM(1), 2);
+	assert_eq!(result.coeff(&Number::one()), FUN("exp", NUM(1)));
+
+	// Laurent series, where cancellations are expanded again with more terms
+	let result = expand(FUN("cos", x()) / FUN("sin", x()), NUM(0), 3);
+	assert_eq!(result, series(&[(-1, NUM(1)), (1, FRAC(-1, 3))], 3));
+
+	let result = expand((x() - FUN("sin", x())) / X("x^3"), NUM(0), 2);
+	assert_eq!(result, series(&[(0, FRAC(1, 6))], 2));
+
+	// Puiseux series
+	let result = expand(FUN("sqrt", X("x + x^2")), NUM(0), 2);
+	let expected = [(Number::from(1) / 2, NUM(1)), (Number::from(3) / 2, FRAC(1, 2))];
+	assert_eq!(result, PowerSeries::new(expected, 2));
+
+	// Powers with symbolic exponents are exponentials
+	let result = expand(Expr::Pow(NUM(2).into(), x().into()), NUM(0), 2);
+	assert_eq!(result.coeff(&Number::one()), FUN("ln", NUM(2)));
+
+	// Series of order zero have no terms
+	assert_eq!(expand(FUN("sin", x()), NUM(0), 0), series(&[], 0));
+
+	// Singular points and unknown functions have no series
+	let x_sym = Symbol::new("x").unwrap();
+	assert_eq!(FUN("ln", x()).series(&x_sym, &NUM(0), 3), None);
+	assert_eq!(FUN("f", x()).series(&x_sym, &NUM(0), 3), None);
+}
+
+#[test]
+fn stdlib() {
+	let stdlib = StdLib::new();
+	let ctx = &mut Context::new();
+
+	let call = |args: Vec<Expr>, ctx: &mut Context| {
+		let expr = Expr::Fun(Symbol::new("series").unwrap(), args);
+		stdlib.evaluate(expr, ctx)
+	};
+
+	let result = call(vec![FUN("exp", X("x")), X("x"), NUM(0), NUM(4)], ctx);
+	assert_eq!(result, X("1/6x^3 + 1/2x^2 + x + 1"));
+
+	let result = call(vec![FUN("ln", X("x")), X("x"), NUM(1), NUM(3)], ctx);
+	assert_eq!(result, X("-1/2x^2 + 2x - 3/2"));
+
+	// The order has to be a number
+	let result = call(vec![FUN("exp", X("x")), X("x"), NUM(0), X("x")], ctx);
+	assert_eq!(result.to_string(), "series(exp(x), x, 0, x)");
+}
@@ -0,0 +1,164 @@
+use abacas::assumption::Assumption;
+use abacas::context::Context;
+use abacas::expr::{Expr, Symbol};
+use abacas::number::Number;
+use abacas::relation::Relation;
+use abacas::set::Set;
+use rug::ops::Pow;
+
+const NUM: fn(i64) -> Expr = |num| Expr::Num(num.into());
+
+const X: fn(&str) -> Expr = |poly| Expr::Poly(Symbol::new("x").unwrap(), poly.parse().unwrap());
+const SYM: fn(&str) -> Expr = |name| Expr::Poly(Symbol::new(name).unwrap(), "x".parse().unwrap());
+const INF: fn(&str) -> Expr = |poly| Expr::Poly(Symbol::new("inf").unwrap(), poly.parse().unwrap());
+
+/// Helper to create an interval with numeric endpoints.
+fn interval(lo: i64, hi: i64, lo_closed: bool, hi_closed: bool) -> Set {
+	Set::interval(NUM(lo), NUM(hi), lo_closed, hi_closed)
+}
+
+#[test]
+fn union() {
+	let ctx = &mut Context::new();
+
+	let set = Set::union([interval(0, 2, true, false), interval(1, 3, true, true)], ctx).unwrap();
+	assert_eq!(set.to_string(), "[0, 3]");
+
+	// Touching intervals are merged if the common endpoint is included
+	let set = Set::union([interval(0, 1, false, false), interval(1, 2, true, false)], ctx).unwrap();
+	assert_eq!(set.to_string(), "(0, 2)");
+
+	let set = Set::union([interval(0, 1, false, false), interval(1, 2, false, false)], ctx).unwrap();
+	assert_eq!(set.to_string(), "(0, 1) ∪ (1, 2)");
+
+	// Points inside intervals vanish, and the others are sorted
+	let set = Set::union(
+		[Set::finite([NUM(5), NUM(1), NUM(-3)]), interval(0, 2, true, true)],
+		ctx,
+	)
+	.unwrap();
+	assert_eq!(set.to_string(), "{-3} ∪ [0, 2] ∪ {5}");
+
+	let set = Set::union([interval(0, 1, false, false), Set::finite([NUM(0)])], ctx).unwrap();
+	assert_eq!(set.to_string(), "[0, 1)");
+
+	// Irrational endpoints are compared numerically
+	let sqrt2 = NUM(2).pow(Expr::Num(Number::from(1) / 2));
+	let set = Set::union(
+		[
+			Set::interval(NUM(0), sqrt2, true, true),
+			Set::interval(SYM("pi"), INF("x"), true, false),
+		],
+		ctx,
+	)
+	.unwrap();
+	assert_eq!(set.to_string(), "[0, 2^0.5] ∪ [pi, inf)");
+
+	// Unions with integers are kept
+	let set = Set::union([Set::Integers, Set::Empty, interval(0, 1, true, true)], ctx).unwrap();
+	assert_eq!(set.to_string(), "ℤ ∪ [0, 1]");
+}
+
+#[test]
+fn intersection() {
+	let ctx = &mut Context::new();
+
+	let set = Set::intersection([interval(0, 2, true, true), interval(2, 3, true, true)], ctx).unwrap();
+	assert_eq!(set.to_string(), "{2}");
+
+	let set = Set::intersection([interval(0, 2, true, false), interval(2, 3, true, true)], ctx).unwrap();
+	assert_eq!(set, Set::Empty);
+
+	let set = Set::intersection([Set::reals(), Set::finite([NUM(1), SYM("pi")])], ctx).unwrap();
+	assert_eq!(set.to_string(), "{1, pi}");
+
+	// Finite sets are filtered element by element
+	let set = Set::finite([NUM(0), NUM(3), NUM(6)]);
+	let evens = Set::Image {
+		expr: X("2x"),
+		var: Symbol::new("x").unwrap(),
+		base: Set::Integers.into(),
+	};
+	assert_eq!(Set::intersection([set, evens], ctx).unwrap().to_string(), "{0, 6}");
+
+	let set = Set::intersection([Set::Integers, interval(0, 1, true, true)], ctx).unwrap();
+	assert_eq!(set.to_string(), "ℤ ∩ [0, 1]");
+}
+
+#[test]
+fn complement() {
+	let ctx = &mut Context::new();
+
+	let set = Set::finite([NUM(0), NUM(1)]);
+	assert_eq!(
+		set.complement(&Set::reals(), ctx).unwrap().to_string(),
+		"(-inf, 0) ∪ (0, 1) ∪ (1, inf)"
+	);
+
+	let set = Set::union([interval(0, 1, true, true), interval(2, 3, false, false)], ctx).unwrap();
+	assert_eq!(
+		set.complement(&interval(0, 5, true, true), ctx).unwrap().to_string(),
+		"(1, 2] ∪ [3, 5]"
+	);
+
+	assert_eq!(Set::Empty.complement(&Set::Integers, ctx).unwrap(), Set::Integers);
+	assert_eq!(Set::reals().complement(&Set::reals(), ctx).unwrap(), Set::Empty);
+
+	let set = interval(0, 1, true, true).complement(&Set::Integers, ctx).unwrap();
+	assert_eq!(set.to_string(), "ℤ \\ [0, 1]");
+}
+
+#[test]
+fn contains() {
+	let ctx = &mut Context::new();
+	assert!(ctx.assume(Symbol::new("y").unwrap(), Assumption::Positive));
+
+	let set = interval(0, 1, false, true);
+	assert_eq!(set.contains(&NUM(1), ctx).unwrap(), Some(true));
+	assert_eq!(set.contains(&NUM(0), ctx).unwrap(), Some(false));
+	assert_eq!(set.contains(&X("x"), ctx).unwrap(), None);
+
+	// Assumptions are used for symbolic elements
+	let positive = Set::interval(NUM(0), INF("x"), false, false);
+	assert_eq!(positive.contains(&(SYM("y") + NUM(1)), ctx).unwrap(), Some(true));
+	assert_eq!(Set::reals().contains(&SYM("y"), ctx).unwrap(), Some(true));
+	assert_eq!(Set::reals().contains(&X("x"), ctx).unwrap(), None);
+
+	assert_eq!(Set::Integers.contains(&NUM(-4), ctx).unwrap(), Some(true));
+	assert_eq!(
+		Set::finite([NUM(2), X("x")]).contains(&NUM(2), ctx).unwrap(),
+		Some(true)
+	);
+	assert_eq!(
+		Set::finite([NUM(2), NUM(3)]).contains(&NUM(4), ctx).unwrap(),
+		Some(false)
+	);
+
+	// Condition sets check the condition on top of the base set
+	let set = Set::Condition {
+		var: Symbol::new("x").unwrap(),
+		condition: Expr::Rel(Relation::Gt, X("x^2").into(), NUM(2).into()),
+		base: Set::Integers.into(),
+	};
+	assert_eq!(set.contains(&NUM(2), ctx).unwrap(), Some(true));
+	assert_eq!(set.contains(&NUM(1), ctx).unwrap(), Some(false));
+	assert_eq!(set.to_string(), "{x ∈ ℤ | x^2 > 2}");
+
+	let set = Set::Complement(Set::Integers.into(), Set::finite([NUM(0)]).into());
+	assert_eq!(set.contains(&NUM(0), ctx).unwrap(), Some(false));
+	assert_eq!(set.contains(&NUM(7), ctx).unwrap(), Some(true));
+}
+
+#[test]
+fn display() {
+	let ctx = &mut Context::new();
+
+	assert_eq!(Set::Empty.to_string(), "∅");
+	assert_eq!(Set::reals().to_string(), "ℝ");
+	assert_eq!(Set::finite([X("x"), NUM(1)]).to_string(), "{1, x}");
+	assert_eq!(interval(-1, 1, true, false).to_string(), "[-1, 1)");
+
+	let union = Set::union([Set::Integers, Set::finite([SYM("pi")])], ctx).unwrap();
+	let set = Set::intersection([union, interval(0, 4, true, true)], ctx).unwrap();
+	assert_eq!(set.to_string(), "(ℤ ∪ {pi}) ∩ [0, 4]");
+}
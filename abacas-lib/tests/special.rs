@@ -0,0 +1,161 @@
+use abacas::context::Context;
+use abacas::expr::{Expr, Symbol};
+use abacas::monomial::Monomial;
+use abacas::number::Number;
+use abacas::polynomial::Polynomial;
+use abacas::stdlib::StdLib;
+
+const NUM: fn(i64) -> Expr = |num| Expr::Num(num.into());
+const FRAC: fn(i64, i64) -> Expr = |numer, denom| Expr::Num(Number::from(numer) / denom);
+
+const SYM: fn(&str) -> Expr = |name| Expr::Poly(Symbol::new(name).unwrap(), "x".parse().unwrap());
+const FUN: fn(&str, Vec<Expr>) -> Expr = |name, args| Expr::Fun(Symbol::new(name).unwrap(), args);
+
+/// Helper to parse a polynomial.
+fn poly(src: &str) -> Polynomial {
+	src.parse().unwrap()
+}
+
+#[test]
+fn orthogonal() {
+	assert_eq!(Polynomial::chebyshev_t(5), poly("16x^5 - 20x^3 + 5x"));
+	assert_eq!(Polynomial::chebyshev_u(3), poly("8x^3 - 4x"));
+	assert_eq!(Polynomial::legendre(3), poly("2.5x^3 - 1.5x"));
+	assert_eq!(Polynomial::hermite(3), poly("8x^3 - 12x"));
+	assert_eq!(Polynomial::hermite_prob(5), poly("x^5 - 10x^3 + 15x"));
+	assert_eq!(Polynomial::laguerre(0), poly("1"));
+
+	let laguerre = Polynomial::laguerre(3) * 6;
+	assert_eq!(laguerre, poly("-x^3 + 9x^2 - 18x + 6"));
+
+	// Jacobi polynomials generalize Legendre and Chebyshev polynomials
+	let half = Number::from(-1) / 2;
+	let jacobi = Polynomial::jacobi(4, &half, &half);
+	let (scale, _) = jacobi.clone().monic().unwrap();
+	assert_eq!(jacobi / &scale, Polynomial::chebyshev_t(4).monic().unwrap().1);
+	assert_eq!(Polynomial::jacobi(5, &0.into(), &0.into()), Polynomial::legendre(5));
+	assert_eq!(Polynomial::jacobi(0, &3.into(), &(-7).into()), poly("1"));
+	assert_eq!(
+		Polynomial::jacobi(2, &(-1).into(), &(-1).into()),
+		poly("0.25x^2 - 0.25")
+	);
+
+	for n in 2..12 {
+		let x = Polynomial::from(Monomial::linear(1));
+
+		// `T_n(1) = 1`, `U_n(1) = n + 1` and `T_n = (U_n - U_(n - 2)) / 2`
+		assert_eq!(Polynomial::chebyshev_t(n).eval(&1.into()), Some(1.into()));
+		assert_eq!(Polynomial::chebyshev_u(n).eval(&1.into()), Some((n + 1).into()));
+		assert_eq!(
+			Polynomial::chebyshev_t(n) * 2,
+			Polynomial::chebyshev_u(n) - Polynomial::chebyshev_u(n - 2)
+		);
+
+		// `(1 - x^2) P_n'' - 2x P_n' + n (n + 1) P_n = 0`
+		let legendre = Polynomial::legendre(n);
+		let first = legendre.derivative();
+		let second = first.derivative();
+		let lhs = (Polynomial::from(1) - x.clone() * &x) * &second - x.clone() * &first * 2 + legendre * (n * (n + 1));
+		assert!(lhs.is_zero());
+
+		// `H_n' = 2n H_(n - 1)` and `He_n' = n He_(n - 1)`
+		assert_eq!(
+			Polynomial::hermite(n).derivative(),
+			Polynomial::hermite(n - 1) * (2 * n)
+		);
+		assert_eq!(
+			Polynomial::hermite_prob(n).derivative(),
+			Polynomial::hermite_prob(n - 1) * n
+		);
+
+		// `x L_n'' + (1 - x) L_n' + n L_n = 0`
+		let laguerre = Polynomial::laguerre(n);
+		let first = laguerre.derivative();
+		let lhs = x.clone() * &first.derivative() + (Polynomial::from(1) - x.clone()) * &first + laguerre * n;
+		assert!(lhs.is_zero());
+	}
+}
+
+#[test]
+fn bernoulli() {
+	assert_eq!(Polynomial::bernoulli(0), poly("1"));
+	assert_eq!(Polynomial::bernoulli(2), poly("x^2 - x") + Number::from(1) / 6);
+	assert_eq!(Polynomial::bernoulli(4).eval(&0.into()), Some(Number::from(-1) / 30));
+
+	for n in 1..15 {
+		// `B_n' = n B_(n - 1)` and `B_n(1) = B_n(0)` for `n > 1`
+		let bernoulli = Polynomial::bernoulli(n);
+		assert_eq!(bernoulli.derivative(), Polynomial::bernoulli(n - 1) * n);
+
+		if n > 1 {
+			assert_eq!(bernoulli.eval(&1.into()), bernoulli.eval(&0.into()));
+		}
+	}
+}
+
+#[test]
+fn cyclotomic() {
+	assert_eq!(Polynomial::cyclotomic(2).unwrap(), poly("x + 1"));
+	assert_eq!(
+		Polynomial::cyclotomic(7).unwrap(),
+		poly("x^6 + x^5 + x^4 + x^3 + x^2 + x + 1")
+	);
+	assert_eq!(Polynomial::cyclotomic(8).unwrap(), poly("x^4 + 1"));
+	assert_eq!(Polynomial::cyclotomic(0), None);
+
+	// The first cyclotomic polynomial with a coefficient other than -1, 0 and 1
+	let poly = Polynomial::cyclotomic(105).unwrap();
+	assert!(poly.monomials().any(|mono| mono.coeff == -2));
+	assert_eq!(poly.degree(), Some(&48.into()));
+
+	// `x^n - 1` is the product of `Φ_d` over the divisors `d` of `n`
+	for n in 1u32..40 {
+		let product = (1..=n)
+			.filter(|&d| n.is_multiple_of(d))
+			.fold(Polynomial::from(1), |acc, d| acc * &Polynomial::cyclotomic(d).unwrap());
+
+		assert_eq!(product, Polynomial::from(Monomial::new(1, n)) - 1);
+	}
+}
+
+#[test]
+fn stdlib() {
+	let stdlib = StdLib::new();
+	let ctx = &mut Context::new();
+	let x = SYM("x");
+
+	let mut call = |name: &str, args: Vec<Expr>| stdlib.evaluate(FUN(name, args), ctx).to_string();
+
+	assert_eq!(call("chebyshevt", vec![NUM(3), x.clone()]), "4x^3 - 3x");
+	assert_eq!(call("chebyshevu", vec![NUM(2), x.clone()]), "4x^2 - 1");
+	assert_eq!(call("legendre", vec![NUM(2), x.clone()]), "1.5x^2 - 0.5");
+	assert_eq!(call("hermiteh", vec![NUM(2), x.clone()]), "4x^2 - 2");
+	assert_eq!(call("hermitehe", vec![NUM(2), x.clone()]), "x^2 - 1");
+	assert_eq!(call("laguerre", vec![NUM(1), x.clone()]), "-x + 1");
+	assert_eq!(call("jacobi", vec![NUM(1), NUM(0), NUM(0), x.clone()]), "x");
+	assert_eq!(call("cyclotomic", vec![NUM(4), x.clone()]), "x^2 + 1");
+	assert_eq!(call("bernoulli", vec![NUM(1), x.clone()]), "x - 0.5");
+	assert_eq!(call("bernoulli", vec![NUM(2)]), "1/6");
+
+	// Polynomials are evaluated at any expression
+	assert_eq!(call("chebyshevt", vec![NUM(4), FRAC(1, 2)]), "-0.5");
+	assert_eq!(call("legendre", vec![NUM(3), NUM(1)]), "1");
+	assert_eq!(call("chebyshevt", vec![NUM(2), SYM("y") + NUM(1)]), "2y^2 + 4y + 1");
+
+	// Degrees must be non-negative integers and parameters numbers
+	assert_eq!(call("legendre", vec![FRAC(1, 2), x.clone()]), "legendre(0.5, x)");
+	assert_eq!(call("cyclotomic", vec![NUM(0), x.clone()]), "cyclotomic(0, x)");
+	assert_eq!(
+		call("cyclotomic", vec![NUM(4294967291), x.clone()]),
+		"cyclotomic(4294967291, x)"
+	);
+	assert_eq!(
+		call("chebyshevt", vec![NUM(4000000000), x.clone()]),
+		"chebyshevt(4000000000, x)"
+	);
+	assert_eq!(call("hermiteh", vec![NUM(100000), x.clone()]), "hermiteh(100000, x)");
+	assert_eq!(
+		call("jacobi", vec![NUM(1), SYM("a"), NUM(0), x.clone()]),
+		"jacobi(1, a, 0, x)"
+	);
+}
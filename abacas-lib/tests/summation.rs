@@ -0,0 +1,145 @@
+use abacas::context::Context;
+use abacas::expr::{Expr, Symbol};
+use abacas::number::Number;
+use abacas::stdlib::StdLib;
+use abacas::summation::{product, sum};
+
+const NUM: fn(i64) -> Expr = |num| Expr::Num(num.into());
+const FRAC: fn(i64, i64) -> Expr = |numer, denom| Expr::Num(Number::from(numer) / denom);
+
+const K: fn(&str) -> Expr = |poly| Expr::Poly(Symbol::new("k").unwrap(), poly.parse().unwrap());
+const N: fn(&str) -> Expr = |poly| Expr::Poly(Symbol::new("n").unwrap(), poly.parse().unwrap());
+const INV: fn(Expr) -> Expr = |expr| Expr::Pow(expr.into(), NUM(-1).into());
+const POW: fn(Expr, Expr) -> Expr = |base, exp| Expr::Pow(base.into(), exp.into());
+
+/// Helper to substitute the upper bound `n` with a number and evaluate the result.
+fn at(expr: &Expr, n: i64, ctx: &mut Context) -> Expr {
+	let sym = Symbol::new("n").unwrap();
+	let expr = expr.substitute(&sym, &NUM(n)).simplify(ctx).unwrap();
+
+	StdLib::new().evaluate(expr, ctx).simplify(ctx).unwrap()
+}
+
+#[test]
+fn numeric_ranges() {
+	let ctx = &mut Context::new();
+	let k = Symbol::new("k").unwrap();
+
+	assert_eq!(sum(K("x^2"), &k, NUM(1), NUM(10), ctx).unwrap(), NUM(385));
+	assert_eq!(sum(INV(K("x")), &k, NUM(1), NUM(4), ctx).unwrap(), FRAC(25, 12));
+	assert_eq!(sum(K("x"), &k, NUM(5), NUM(4), ctx).unwrap(), NUM(0));
+
+	assert_eq!(product(K("x"), &k, NUM(1), NUM(5), ctx).unwrap(), NUM(120));
+	assert_eq!(
+		product(K("x + 1") * INV(K("x")), &k, NUM(1), NUM(9), ctx).unwrap(),
+		NUM(10)
+	);
+	assert_eq!(product(K("x"), &k, NUM(1), NUM(0), ctx).unwrap(), NUM(1));
+
+	// Calls to the StdLib in the terms are evaluated
+	let factorial = Expr::Fun(Symbol::new("factorial").unwrap(), vec![K("x")]);
+	assert_eq!(sum(factorial, &k, NUM(1), NUM(4), ctx).unwrap(), NUM(33));
+}
+
+#[test]
+fn closed_sums() {
+	let ctx = &mut Context::new();
+	let k = Symbol::new("k").unwrap();
+
+	// Faulhaber's formula for random polynomials, compared with the numeric sums
+	for _ in 0..20 {
+		let coeffs: Vec<_> = (0..6).map(|_| fastrand::i64(-20..20)).collect();
+		let summand = coeffs
+			.iter()
+			.enumerate()
+			.filter(|(_, coeff)| **coeff != 0)
+			.map(|(degree, coeff)| format!("{coeff}x^{degree}"))
+			.collect::<Vec<_>>()
+			.join(" + ");
+
+		let summand = if summand.is_empty() { K("0") } else { K(&summand) };
+		let lo = fastrand::i64(-5..5);
+		let closed = sum(summand.clone(), &k, NUM(lo), N("x"), ctx).unwrap();
+
+		for n in lo - 1..lo + 8 {
+			let expected = sum(summand.clone(), &k, NUM(lo), NUM(n), ctx).unwrap();
+			assert_eq!(at(&closed, n, ctx), expected);
+		}
+	}
+
+	// Geometric series
+	let closed = sum(POW(NUM(2), K("x")), &k, NUM(0), N("x"), ctx).unwrap();
+	assert_eq!(at(&closed, 10, ctx), NUM(2047));
+
+	let closed = sum(POW(FRAC(1, 3), K("2x + 1")), &k, NUM(1), N("x"), ctx).unwrap();
+	assert_eq!(
+		at(&closed, 3, ctx),
+		sum(POW(FRAC(1, 3), K("2x + 1")), &k, NUM(1), NUM(3), ctx).unwrap()
+	);
+
+	// Constant factors and sums of different kinds of terms
+	let summand = NUM(3) * POW(NUM(-1), K("x")) + K("x");
+	let closed = sum(summand.clone(), &k, NUM(1), N("x"), ctx).unwrap();
+	assert_eq!(at(&closed, 7, ctx), sum(summand, &k, NUM(1), NUM(7), ctx).unwrap());
+
+	// Telescoping rational summands with Gosper's algorithm
+	let closed = sum(INV(K("x^2 + x")), &k, NUM(1), N("x"), ctx).unwrap();
+	assert_eq!(at(&closed, 10, ctx), FRAC(10, 11));
+
+	let summand = K("2x + 1") * INV(K("x^4 + 2x^3 + x^2"));
+	let closed = sum(summand, &k, NUM(1), N("x"), ctx).unwrap();
+	assert_eq!(at(&closed, 4, ctx), FRAC(24, 25));
+
+	// Without a closed form, the sum stays unevaluated
+	let harmonic = sum(INV(K("x")), &k, NUM(1), N("x"), ctx).unwrap();
+	assert_eq!(harmonic.to_string(), "sum(k^-1, k, 1, n)");
+}
+
+#[test]
+fn closed_products() {
+	let ctx = &mut Context::new();
+	let k = Symbol::new("k").unwrap();
+
+	let closed = product(NUM(3), &k, NUM(1), N("x"), ctx).unwrap();
+	assert_eq!(at(&closed, 4, ctx), NUM(81));
+
+	let closed = product(K("2x - 4"), &k, NUM(3), N("x"), ctx).unwrap();
+	assert_eq!(at(&closed, 6, ctx), NUM(2 * 4 * 6 * 8));
+
+	let closed = product(POW(NUM(2), K("x")), &k, NUM(1), N("x"), ctx).unwrap();
+	assert_eq!(at(&closed, 4, ctx), NUM(1024));
+
+	let closed = product(K("x^2"), &k, NUM(1), N("x"), ctx).unwrap();
+	assert_eq!(at(&closed, 5, ctx), NUM(14400));
+
+	let unevaluated = product(K("x^2 + 1"), &k, NUM(1), N("x"), ctx).unwrap();
+	assert_eq!(unevaluated.to_string(), "product(k^2 + 1, k, 1, n)");
+
+	// Factors that vanish inside the range have no factorial closed form
+	let unevaluated = product(K("2x - 4"), &k, NUM(1), N("x"), ctx).unwrap();
+	assert_eq!(unevaluated.to_string(), "product(2k - 4, k, 1, n)");
+
+	let unevaluated = product(K("x"), &k, NUM(0), N("x"), ctx).unwrap();
+	assert_eq!(unevaluated.to_string(), "product(k, k, 0, n)");
+}
+
+#[test]
+fn stdlib() {
+	let stdlib = StdLib::new();
+	let ctx = &mut Context::new();
+
+	let call = |name: &str, args: Vec<Expr>, ctx: &mut Context| {
+		let expr = Expr::Fun(Symbol::new(name).unwrap(), args);
+		stdlib.evaluate(expr, ctx)
+	};
+
+	let result = call("sum", vec![K("x"), K("x"), NUM(1), N("x")], ctx);
+	assert_eq!(result, N("1/2x^2 + 1/2x"));
+
+	let result = call("product", vec![K("x"), K("x"), NUM(1), NUM(6)], ctx);
+	assert_eq!(result, NUM(720));
+
+	// The variable has to be a symbol
+	let result = call("sum", vec![K("x"), NUM(2), NUM(1), NUM(6)], ctx);
+	assert_eq!(result.to_string(), "sum(k, 2, 1, 6)");
+}
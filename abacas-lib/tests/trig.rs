@@ -0,0 +1,143 @@
+use abacas::context::Context;
+use abacas::expr::{Expr, Symbol};
+use abacas::number::Number;
+use abacas::stdlib::StdLib;
+use rug::ops::Pow;
+
+const NUM: fn(i64) -> Expr = |num| Expr::Num(num.into());
+const FRAC: fn(i64, i64) -> Expr = |numer, denom| Expr::Num(Number::from(numer) / denom);
+
+const X: fn(&str) -> Expr = |poly| Expr::Poly(Symbol::new("x").unwrap(), poly.parse().unwrap());
+const SYM: fn(&str) -> Expr = |name| Expr::Poly(Symbol::new(name).unwrap(), "x".parse().unwrap());
+const FUN: fn(&str, Expr) -> Expr = |name, arg| Expr::Fun(Symbol::new(name).unwrap(), vec![arg]);
+const PI: fn(i64, i64) -> Expr = |numer, denom| SYM("pi") * FRAC(numer, denom);
+
+/// Helper to evaluate a function of the StdLib with a single argument.
+fn call(name: &str, arg: Expr) -> Expr {
+	StdLib::new().evaluate(FUN(name, arg), &mut Context::new())
+}
+
+#[test]
+fn exact_values() {
+	assert_eq!(call("cos", PI(1, 3)), FRAC(1, 2));
+	assert_eq!(call("sin", PI(-1, 6)), FRAC(-1, 2));
+	assert_eq!(call("cos", PI(3, 2)), NUM(0));
+	assert_eq!(call("sin", PI(7, 1)), NUM(0));
+	assert_eq!(call("tan", PI(3, 4)), NUM(-1));
+
+	assert_eq!(call("sin", PI(1, 4)).to_string(), "0.5 * 2^0.5");
+	assert_eq!(call("tan", PI(1, 3)).to_string(), "3^0.5");
+	assert_eq!(call("cos", PI(11, 12)).to_string(), "(2^0.5 + 6^0.5) * -0.25");
+
+	// Poles and unknown values stay unevaluated
+	assert_eq!(call("tan", PI(1, 2)).to_string(), "tan(0.5pi)");
+	assert!(matches!(call("sin", PI(1, 5)), Expr::Fun(_, _)));
+}
+
+#[test]
+fn shifts_and_parity() {
+	assert_eq!(call("sin", X("-x")), -FUN("sin", X("x")));
+	assert_eq!(call("cos", X("-2x")), FUN("cos", X("2x")));
+	assert_eq!(call("tan", SYM("y") - X("x")).to_string(), "tan(x - y) * -1");
+
+	// Multiples of pi / 2 are removed from the argument
+	assert_eq!(call("sin", X("x") + PI(1, 2)), FUN("cos", X("x")));
+	assert_eq!(call("cos", X("x") + PI(3, 1)), -FUN("cos", X("x")));
+	assert_eq!(call("tan", X("x") + PI(1, 1)), FUN("tan", X("x")));
+	assert_eq!(call("cos", X("x") + PI(5, 2)), -FUN("sin", X("x")));
+}
+
+#[test]
+fn trigsimp() {
+	let ctx = &mut Context::new();
+	let square = |name, arg| FUN(name, arg).pow(NUM(2));
+
+	let expr = square("sin", X("2x")) + square("cos", X("2x"));
+	assert_eq!(expr.trigsimp(ctx).unwrap(), NUM(1));
+
+	let expr = (square("sin", X("x")) + square("cos", X("x"))) * SYM("y") + NUM(2);
+	assert_eq!(expr.trigsimp(ctx).unwrap().to_string(), "y + 2");
+
+	let expr = square("sin", X("x")) * SYM("y") + square("cos", X("x")) * SYM("y") + NUM(2);
+	assert_eq!(expr.trigsimp(ctx).unwrap().to_string(), "y + 2");
+
+	// One minus a square is the other square
+	let expr = X("x + 1") - square("cos", X("x"));
+	assert_eq!(expr.trigsimp(ctx).unwrap().to_string(), "x + sin(x)^2");
+
+	let expr = square("sin", X("-x")) - NUM(1);
+	assert_eq!(expr.trigsimp(ctx).unwrap().to_string(), "-1 * cos(x)^2");
+
+	// Different arguments are not combined
+	let expr = square("sin", X("x")) + square("cos", SYM("y"));
+	assert_eq!(expr.trigsimp(ctx).unwrap().to_string(), "cos(y)^2 + sin(x)^2");
+}
+
+#[test]
+fn expand_trig() {
+	let ctx = &mut Context::new();
+	let expand = |expr: Expr, ctx: &mut Context| expr.expand_trig(ctx).unwrap().to_string();
+
+	assert_eq!(
+		expand(FUN("sin", X("x") + SYM("y")), ctx),
+		"cos(x) * sin(y) + cos(y) * sin(x)"
+	);
+	assert_eq!(
+		expand(FUN("cos", X("x") - SYM("y")), ctx),
+		"cos(x) * cos(y) + sin(x) * sin(y)"
+	);
+	assert_eq!(expand(FUN("cos", X("3x")), ctx), "cos(x) * -3 * sin(x)^2 + cos(x)^3");
+	assert_eq!(expand(FUN("tan", X("2x")), ctx), "tan(x) * 2 * (-1 * tan(x)^2 + 1)^-1");
+
+	// Expanded identities hold after simplifying
+	let expr = FUN("sin", X("2x")).pow(NUM(2)) + FUN("cos", X("2x")).pow(NUM(2));
+	let result = expr.expand_trig(ctx).unwrap();
+	assert_ne!(result, NUM(1));
+	assert_eq!(expr.trigsimp(ctx).unwrap(), NUM(1));
+
+	// Fractional multiples are kept
+	assert_eq!(expand(FUN("sin", X("0.5x")), ctx), "sin(0.5x)");
+}
+
+#[test]
+fn exponentials() {
+	let ctx = &mut Context::new();
+	let i = || SYM("i");
+
+	for name in ["sin", "cos"] {
+		let expr = FUN(name, X("x"));
+		let result = expr.trig_to_exp(ctx).unwrap();
+
+		assert!(!result.has_symbol(&Symbol::new(name).unwrap()));
+		assert_eq!(result.exp_to_trig(ctx).unwrap(), expr);
+	}
+
+	// Euler's identity
+	assert_eq!(FUN("exp", i() * SYM("pi")).exp_to_trig(ctx).unwrap(), NUM(-1));
+
+	let result = FUN("exp", i() * X("x") + NUM(2)).exp_to_trig(ctx).unwrap();
+	assert_eq!(result.to_string(), "cos(x) * exp(2) + exp(2) * sin(x) * i");
+
+	// Products of exponentials are expanded with i^2 = -1
+	let result = (FUN("exp", i() * X("x")) * FUN("exp", i() * SYM("y")))
+		.exp_to_trig(ctx)
+		.unwrap();
+	assert_eq!(
+		result.to_string(),
+		"cos(x) * cos(y) + cos(x) * sin(y) * i + cos(y) * sin(x) * i + sin(x) * sin(y) * -1"
+	);
+}
+
+#[test]
+fn simplify_pass() {
+	let ctx = &mut Context::new();
+	let expr = || NUM(1) - FUN("sin", X("x")).pow(NUM(2)) + FUN("sin", X("x") + PI(1, 2));
+
+	assert_eq!(
+		expr().simplify(ctx).unwrap().to_string(),
+		"sin(0.5pi + x) + -1 * sin(x)^2 + 1"
+	);
+
+	ctx.passes.push(Expr::trigsimp);
+	assert_eq!(expr().simplify(ctx).unwrap().to_string(), "cos(x) + cos(x)^2");
+}